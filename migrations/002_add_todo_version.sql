@@ -0,0 +1,2 @@
+-- Optimistic concurrency: bumped on every update and exposed as the ETag
+ALTER TABLE todos ADD COLUMN IF NOT EXISTS version BIGINT NOT NULL DEFAULT 1;
@@ -0,0 +1,2 @@
+-- Serves keyset pagination over (created_at, id) in either direction
+CREATE INDEX IF NOT EXISTS idx_todos_created_at_id ON todos(created_at DESC, id DESC);
@@ -0,0 +1,5 @@
+-- Soft delete: trashed todos keep their row until restored or purged
+ALTER TABLE todos ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+-- Serves the trash listing and the retention purge, which only read trashed rows
+CREATE INDEX IF NOT EXISTS idx_todos_deleted_at ON todos(deleted_at DESC, id DESC) WHERE deleted_at IS NOT NULL;
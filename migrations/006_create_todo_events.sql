@@ -0,0 +1,28 @@
+-- Audit log: one row per todo mutation, written in the same transaction.
+-- No foreign key, so the history survives the todo being purged.
+CREATE TABLE IF NOT EXISTS todo_events (
+    id BIGSERIAL PRIMARY KEY,
+    todo_id UUID NOT NULL,
+    kind VARCHAR(16) NOT NULL,
+    version BIGINT NOT NULL,
+    before JSONB,
+    after JSONB,
+    changes JSONB NOT NULL DEFAULT '[]',
+    actor TEXT,
+    request_id TEXT,
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_todo_events_todo_id ON todo_events(todo_id, id);
+
+-- Events are immutable once written
+CREATE OR REPLACE FUNCTION reject_todo_event_change() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'todo_events is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS todo_events_append_only ON todo_events;
+CREATE TRIGGER todo_events_append_only
+    BEFORE UPDATE OR DELETE ON todo_events
+    FOR EACH ROW EXECUTE FUNCTION reject_todo_event_change();
@@ -0,0 +1,16 @@
+-- Transactional outbox: domain events queued in the same transaction as the
+-- change, removed once a relay has published them.
+CREATE TABLE IF NOT EXISTS outbox (
+    id BIGSERIAL PRIMARY KEY,
+    event_id UUID NOT NULL UNIQUE,
+    todo_id UUID NOT NULL,
+    event_type VARCHAR(32) NOT NULL,
+    payload JSONB NOT NULL,
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    available_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_error TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_outbox_available_at ON outbox(available_at, id);
+CREATE INDEX IF NOT EXISTS idx_outbox_todo_id ON outbox(todo_id, id);
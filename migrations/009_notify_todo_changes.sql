@@ -0,0 +1,52 @@
+-- Live change stream: every committed change to a todo is announced on the
+-- `todo_changes` channel, which each server instance LISTENs to. Postgres
+-- delivers notifications in commit order, so all instances see the same
+-- sequence.
+CREATE SEQUENCE IF NOT EXISTS todo_change_seq;
+
+CREATE OR REPLACE FUNCTION notify_todo_change() RETURNS trigger AS $$
+DECLARE
+    change_kind TEXT;
+    todo todos;
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        change_kind := 'created';
+        todo := NEW;
+    ELSIF TG_OP = 'DELETE' THEN
+        -- Purging a trashed todo was announced when it was deleted
+        IF OLD.deleted_at IS NOT NULL THEN
+            RETURN NULL;
+        END IF;
+        change_kind := 'deleted';
+        todo := OLD;
+    ELSIF NEW IS NOT DISTINCT FROM OLD OR (OLD.deleted_at IS NOT NULL AND NEW.deleted_at IS NOT NULL) THEN
+        RETURN NULL;
+    ELSIF NEW.deleted_at IS NOT NULL THEN
+        change_kind := 'deleted';
+        todo := OLD;
+    ELSE
+        -- Includes restores from the trash
+        change_kind := 'updated';
+        todo := NEW;
+    END IF;
+
+    PERFORM pg_notify('todo_changes', json_build_object(
+        'seq', nextval('todo_change_seq'),
+        'kind', change_kind,
+        'todo', json_build_object(
+            'id', todo.id,
+            'title', todo.title,
+            'done', todo.done,
+            'created_at', todo.created_at,
+            'updated_at', todo.updated_at,
+            'version', todo.version
+        )
+    )::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS todos_notify_change ON todos;
+CREATE TRIGGER todos_notify_change
+    AFTER INSERT OR UPDATE OR DELETE ON todos
+    FOR EACH ROW EXECUTE FUNCTION notify_todo_change();
@@ -0,0 +1,79 @@
+CREATE TABLE IF NOT EXISTS users (
+    id UUID PRIMARY KEY,
+    -- Lowercased by the application
+    email VARCHAR(254) NOT NULL UNIQUE,
+    -- Argon2id PHC string
+    password_hash TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Only SHA-256 hashes of refresh tokens are kept. A refresh replaces the
+-- presented token with a new one of the same family.
+CREATE TABLE IF NOT EXISTS refresh_tokens (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    family_id UUID NOT NULL,
+    token_hash CHAR(64) NOT NULL UNIQUE,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    replaced_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_family_id ON refresh_tokens(family_id);
+
+-- Todos and webhooks created before accounts existed keep a NULL owner and
+-- are visible to no user. Audit events and outbox messages carry the owner
+-- of their todo so they can be scoped without the todo, which may be purged.
+ALTER TABLE todos ADD COLUMN IF NOT EXISTS owner_id UUID;
+ALTER TABLE todo_events ADD COLUMN IF NOT EXISTS owner_id UUID;
+ALTER TABLE outbox ADD COLUMN IF NOT EXISTS owner_id UUID;
+ALTER TABLE webhooks ADD COLUMN IF NOT EXISTS owner_id UUID;
+
+CREATE INDEX IF NOT EXISTS idx_todos_owner_created_at_id ON todos(owner_id, created_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS idx_webhooks_owner_id ON webhooks(owner_id);
+
+-- Change notifications now name the owner, so streams can be scoped.
+CREATE OR REPLACE FUNCTION notify_todo_change() RETURNS trigger AS $$
+DECLARE
+    change_kind TEXT;
+    todo todos;
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        change_kind := 'created';
+        todo := NEW;
+    ELSIF TG_OP = 'DELETE' THEN
+        -- Purging a trashed todo was announced when it was deleted
+        IF OLD.deleted_at IS NOT NULL THEN
+            RETURN NULL;
+        END IF;
+        change_kind := 'deleted';
+        todo := OLD;
+    ELSIF NEW IS NOT DISTINCT FROM OLD OR (OLD.deleted_at IS NOT NULL AND NEW.deleted_at IS NOT NULL) THEN
+        RETURN NULL;
+    ELSIF NEW.deleted_at IS NOT NULL THEN
+        change_kind := 'deleted';
+        todo := OLD;
+    ELSE
+        -- Includes restores from the trash
+        change_kind := 'updated';
+        todo := NEW;
+    END IF;
+
+    PERFORM pg_notify('todo_changes', json_build_object(
+        'seq', nextval('todo_change_seq'),
+        'kind', change_kind,
+        'todo', json_build_object(
+            'id', todo.id,
+            'title', todo.title,
+            'done', todo.done,
+            'created_at', todo.created_at,
+            'updated_at', todo.updated_at,
+            'version', todo.version,
+            'owner_id', todo.owner_id
+        )
+    )::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,12 @@
+-- Access to a todo given to users other than its owner. The owner's own
+-- role follows from todos.owner_id and is never stored here.
+CREATE TABLE IF NOT EXISTS todo_shares (
+    todo_id UUID NOT NULL REFERENCES todos(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role VARCHAR(16) NOT NULL CHECK (role IN ('viewer', 'editor')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (todo_id, user_id)
+);
+
+-- Lists look up every todo shared with the signed-in user.
+CREATE INDEX IF NOT EXISTS idx_todo_shares_user_id ON todo_shares(user_id, todo_id);
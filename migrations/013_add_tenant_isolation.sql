@@ -0,0 +1,138 @@
+-- Multi-tenancy: every todo, audit event, outbox message, webhook, API
+-- key, account and refresh token belongs to one tenant. Existing rows move
+-- to the default tenant.
+ALTER TABLE todos ADD COLUMN IF NOT EXISTS tenant_id TEXT NOT NULL DEFAULT 'default';
+ALTER TABLE todo_events ADD COLUMN IF NOT EXISTS tenant_id TEXT NOT NULL DEFAULT 'default';
+ALTER TABLE outbox ADD COLUMN IF NOT EXISTS tenant_id TEXT NOT NULL DEFAULT 'default';
+ALTER TABLE webhooks ADD COLUMN IF NOT EXISTS tenant_id TEXT NOT NULL DEFAULT 'default';
+ALTER TABLE api_keys ADD COLUMN IF NOT EXISTS tenant_id TEXT NOT NULL DEFAULT 'default';
+ALTER TABLE users ADD COLUMN IF NOT EXISTS tenant_id TEXT NOT NULL DEFAULT 'default';
+ALTER TABLE refresh_tokens ADD COLUMN IF NOT EXISTS tenant_id TEXT NOT NULL DEFAULT 'default';
+
+-- An email is registered once per tenant; the same person signing up in
+-- two tenants gets two accounts.
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_email_key;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_users_tenant_email ON users(tenant_id, email);
+
+CREATE INDEX IF NOT EXISTS idx_todos_tenant_created_at_id ON todos(tenant_id, created_at DESC, id DESC);
+
+-- Row-level security keeps tenants apart even where a query forgets to.
+-- The repository names the tenant of each request in `app.tenant_id` when
+-- it checks a connection out of the pool; background tasks set
+-- `app.all_tenants` instead. A connection that set neither sees no rows.
+-- FORCE applies the policies to the tables' owner too; only superusers and
+-- roles with BYPASSRLS escape them, so the server must connect as neither.
+-- Later migrations that rewrite these tables must SET LOCAL
+-- app.all_tenants = 'on' first.
+ALTER TABLE todos ENABLE ROW LEVEL SECURITY;
+ALTER TABLE todos FORCE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON todos;
+CREATE POLICY tenant_isolation ON todos
+    USING (current_setting('app.all_tenants', true) = 'on' OR tenant_id = current_setting('app.tenant_id', true))
+    WITH CHECK (current_setting('app.all_tenants', true) = 'on' OR tenant_id = current_setting('app.tenant_id', true));
+
+ALTER TABLE todo_events ENABLE ROW LEVEL SECURITY;
+ALTER TABLE todo_events FORCE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON todo_events;
+CREATE POLICY tenant_isolation ON todo_events
+    USING (current_setting('app.all_tenants', true) = 'on' OR tenant_id = current_setting('app.tenant_id', true))
+    WITH CHECK (current_setting('app.all_tenants', true) = 'on' OR tenant_id = current_setting('app.tenant_id', true));
+
+-- Shares follow their todo, whose own policy the subquery is subject to.
+ALTER TABLE todo_shares ENABLE ROW LEVEL SECURITY;
+ALTER TABLE todo_shares FORCE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON todo_shares;
+CREATE POLICY tenant_isolation ON todo_shares
+    USING (EXISTS (SELECT 1 FROM todos WHERE todos.id = todo_shares.todo_id))
+    WITH CHECK (EXISTS (SELECT 1 FROM todos WHERE todos.id = todo_shares.todo_id));
+
+ALTER TABLE outbox ENABLE ROW LEVEL SECURITY;
+ALTER TABLE outbox FORCE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON outbox;
+CREATE POLICY tenant_isolation ON outbox
+    USING (current_setting('app.all_tenants', true) = 'on' OR tenant_id = current_setting('app.tenant_id', true))
+    WITH CHECK (current_setting('app.all_tenants', true) = 'on' OR tenant_id = current_setting('app.tenant_id', true));
+
+ALTER TABLE webhooks ENABLE ROW LEVEL SECURITY;
+ALTER TABLE webhooks FORCE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON webhooks;
+CREATE POLICY tenant_isolation ON webhooks
+    USING (current_setting('app.all_tenants', true) = 'on' OR tenant_id = current_setting('app.tenant_id', true))
+    WITH CHECK (current_setting('app.all_tenants', true) = 'on' OR tenant_id = current_setting('app.tenant_id', true));
+
+-- Deliveries follow their webhook, as shares follow their todo.
+ALTER TABLE webhook_deliveries ENABLE ROW LEVEL SECURITY;
+ALTER TABLE webhook_deliveries FORCE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON webhook_deliveries;
+CREATE POLICY tenant_isolation ON webhook_deliveries
+    USING (EXISTS (SELECT 1 FROM webhooks WHERE webhooks.id = webhook_deliveries.webhook_id))
+    WITH CHECK (EXISTS (SELECT 1 FROM webhooks WHERE webhooks.id = webhook_deliveries.webhook_id));
+
+-- An API key only authenticates requests in the tenant it was created in.
+ALTER TABLE api_keys ENABLE ROW LEVEL SECURITY;
+ALTER TABLE api_keys FORCE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON api_keys;
+CREATE POLICY tenant_isolation ON api_keys
+    USING (current_setting('app.all_tenants', true) = 'on' OR tenant_id = current_setting('app.tenant_id', true))
+    WITH CHECK (current_setting('app.all_tenants', true) = 'on' OR tenant_id = current_setting('app.tenant_id', true));
+
+-- Accounts only sign in to the tenant they registered in, and a refresh
+-- token only renews its session there.
+ALTER TABLE users ENABLE ROW LEVEL SECURITY;
+ALTER TABLE users FORCE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON users;
+CREATE POLICY tenant_isolation ON users
+    USING (current_setting('app.all_tenants', true) = 'on' OR tenant_id = current_setting('app.tenant_id', true))
+    WITH CHECK (current_setting('app.all_tenants', true) = 'on' OR tenant_id = current_setting('app.tenant_id', true));
+
+ALTER TABLE refresh_tokens ENABLE ROW LEVEL SECURITY;
+ALTER TABLE refresh_tokens FORCE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON refresh_tokens;
+CREATE POLICY tenant_isolation ON refresh_tokens
+    USING (current_setting('app.all_tenants', true) = 'on' OR tenant_id = current_setting('app.tenant_id', true))
+    WITH CHECK (current_setting('app.all_tenants', true) = 'on' OR tenant_id = current_setting('app.tenant_id', true));
+
+-- Change notifications now name the tenant, so streams can be scoped.
+CREATE OR REPLACE FUNCTION notify_todo_change() RETURNS trigger AS $$
+DECLARE
+    change_kind TEXT;
+    todo todos;
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        change_kind := 'created';
+        todo := NEW;
+    ELSIF TG_OP = 'DELETE' THEN
+        -- Purging a trashed todo was announced when it was deleted
+        IF OLD.deleted_at IS NOT NULL THEN
+            RETURN NULL;
+        END IF;
+        change_kind := 'deleted';
+        todo := OLD;
+    ELSIF NEW IS NOT DISTINCT FROM OLD OR (OLD.deleted_at IS NOT NULL AND NEW.deleted_at IS NOT NULL) THEN
+        RETURN NULL;
+    ELSIF NEW.deleted_at IS NOT NULL THEN
+        change_kind := 'deleted';
+        todo := OLD;
+    ELSE
+        -- Includes restores from the trash
+        change_kind := 'updated';
+        todo := NEW;
+    END IF;
+
+    PERFORM pg_notify('todo_changes', json_build_object(
+        'seq', nextval('todo_change_seq'),
+        'kind', change_kind,
+        'todo', json_build_object(
+            'id', todo.id,
+            'title', todo.title,
+            'done', todo.done,
+            'created_at', todo.created_at,
+            'updated_at', todo.updated_at,
+            'version', todo.version,
+            'owner_id', todo.owner_id,
+            'tenant_id', todo.tenant_id
+        )
+    )::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
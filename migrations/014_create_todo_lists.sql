@@ -0,0 +1,76 @@
+-- Named groups of todos. A todo belongs to at most one list. The API
+-- trashes a list's todos before deleting the list; any left behind, such as
+-- todos already in the trash, lose their list.
+CREATE TABLE IF NOT EXISTS todo_lists (
+    id UUID PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    description TEXT,
+    -- '#rrggbb'
+    color VARCHAR(7),
+    archived BOOLEAN NOT NULL DEFAULT FALSE,
+    owner_id UUID,
+    tenant_id TEXT NOT NULL DEFAULT 'default',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_todo_lists_owner_archived ON todo_lists(owner_id, archived, created_at, id);
+
+-- A nullable column with no default, so existing rows are not rewritten.
+ALTER TABLE todos ADD COLUMN IF NOT EXISTS list_id UUID REFERENCES todo_lists(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_todos_list_created_at_id ON todos(list_id, created_at DESC, id DESC);
+
+-- Lists are kept apart by tenant exactly like todos.
+ALTER TABLE todo_lists ENABLE ROW LEVEL SECURITY;
+ALTER TABLE todo_lists FORCE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON todo_lists;
+CREATE POLICY tenant_isolation ON todo_lists
+    USING (current_setting('app.all_tenants', true) = 'on' OR tenant_id = current_setting('app.tenant_id', true))
+    WITH CHECK (current_setting('app.all_tenants', true) = 'on' OR tenant_id = current_setting('app.tenant_id', true));
+
+-- Change notifications now name the list.
+CREATE OR REPLACE FUNCTION notify_todo_change() RETURNS trigger AS $$
+DECLARE
+    change_kind TEXT;
+    todo todos;
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        change_kind := 'created';
+        todo := NEW;
+    ELSIF TG_OP = 'DELETE' THEN
+        -- Purging a trashed todo was announced when it was deleted
+        IF OLD.deleted_at IS NOT NULL THEN
+            RETURN NULL;
+        END IF;
+        change_kind := 'deleted';
+        todo := OLD;
+    ELSIF NEW IS NOT DISTINCT FROM OLD OR (OLD.deleted_at IS NOT NULL AND NEW.deleted_at IS NOT NULL) THEN
+        RETURN NULL;
+    ELSIF NEW.deleted_at IS NOT NULL THEN
+        change_kind := 'deleted';
+        todo := OLD;
+    ELSE
+        -- Includes restores from the trash
+        change_kind := 'updated';
+        todo := NEW;
+    END IF;
+
+    PERFORM pg_notify('todo_changes', json_build_object(
+        'seq', nextval('todo_change_seq'),
+        'kind', change_kind,
+        'todo', json_build_object(
+            'id', todo.id,
+            'title', todo.title,
+            'done', todo.done,
+            'created_at', todo.created_at,
+            'updated_at', todo.updated_at,
+            'version', todo.version,
+            'list_id', todo.list_id,
+            'owner_id', todo.owner_id,
+            'tenant_id', todo.tenant_id
+        )
+    )::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
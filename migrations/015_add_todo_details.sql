@@ -0,0 +1,56 @@
+-- Details of a todo beyond its title. `completed_at` is kept by the API:
+-- set when `done` becomes true and cleared when it becomes false again.
+ALTER TABLE todos ADD COLUMN IF NOT EXISTS description TEXT;
+ALTER TABLE todos ADD COLUMN IF NOT EXISTS due_at TIMESTAMPTZ;
+ALTER TABLE todos ADD COLUMN IF NOT EXISTS priority TEXT NOT NULL DEFAULT 'medium'
+    CHECK (priority IN ('low', 'medium', 'high'));
+ALTER TABLE todos ADD COLUMN IF NOT EXISTS completed_at TIMESTAMPTZ;
+
+-- Todos that are already done were completed at the latest when they were
+-- last updated. The backfill is not a change anyone should be notified of.
+SET LOCAL app.all_tenants = 'on';
+ALTER TABLE todos DISABLE TRIGGER todos_notify_change;
+UPDATE todos SET completed_at = updated_at WHERE done AND completed_at IS NULL;
+ALTER TABLE todos ENABLE TRIGGER todos_notify_change;
+
+-- For the overdue and due-today filters.
+CREATE INDEX IF NOT EXISTS idx_todos_due_at ON todos(due_at) WHERE due_at IS NOT NULL AND deleted_at IS NULL;
+
+-- A description alone can outgrow the 8000 bytes a notification may carry,
+-- so notifications now name the todo and the listener loads it.
+CREATE OR REPLACE FUNCTION notify_todo_change() RETURNS trigger AS $$
+DECLARE
+    change_kind TEXT;
+    todo todos;
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        change_kind := 'created';
+        todo := NEW;
+    ELSIF TG_OP = 'DELETE' THEN
+        -- Purging a trashed todo was announced when it was deleted
+        IF OLD.deleted_at IS NOT NULL THEN
+            RETURN NULL;
+        END IF;
+        change_kind := 'deleted';
+        todo := OLD;
+    ELSIF NEW IS NOT DISTINCT FROM OLD OR (OLD.deleted_at IS NOT NULL AND NEW.deleted_at IS NOT NULL) THEN
+        RETURN NULL;
+    ELSIF NEW.deleted_at IS NOT NULL THEN
+        change_kind := 'deleted';
+        todo := OLD;
+    ELSE
+        -- Includes restores from the trash
+        change_kind := 'updated';
+        todo := NEW;
+    END IF;
+
+    PERFORM pg_notify('todo_changes', json_build_object(
+        'seq', nextval('todo_change_seq'),
+        'kind', change_kind,
+        'id', todo.id,
+        'owner_id', todo.owner_id,
+        'tenant_id', todo.tenant_id
+    )::text);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
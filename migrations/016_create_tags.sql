@@ -0,0 +1,41 @@
+-- Labels that users attach to todos, many to many. Tag names are unique per
+-- owner within a tenant; todos created before accounts existed have no
+-- owner, and their tags share one namespace.
+CREATE TABLE IF NOT EXISTS tags (
+    id UUID PRIMARY KEY,
+    name VARCHAR(50) NOT NULL,
+    owner_id UUID,
+    tenant_id TEXT NOT NULL DEFAULT 'default',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tags_owner_name
+    ON tags(tenant_id, COALESCE(owner_id, '00000000-0000-0000-0000-000000000000'), name);
+
+-- Deleting a tag detaches it from every todo; purging a todo drops its tags.
+CREATE TABLE IF NOT EXISTS todo_tags (
+    todo_id UUID NOT NULL REFERENCES todos(id) ON DELETE CASCADE,
+    tag_id UUID NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (todo_id, tag_id)
+);
+
+-- For the tag filters of todo listings.
+CREATE INDEX IF NOT EXISTS idx_todo_tags_tag_id ON todo_tags(tag_id, todo_id);
+
+-- Tags are kept apart by tenant like lists; attachments follow their todo,
+-- as shares do.
+ALTER TABLE tags ENABLE ROW LEVEL SECURITY;
+ALTER TABLE tags FORCE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON tags;
+CREATE POLICY tenant_isolation ON tags
+    USING (current_setting('app.all_tenants', true) = 'on' OR tenant_id = current_setting('app.tenant_id', true))
+    WITH CHECK (current_setting('app.all_tenants', true) = 'on' OR tenant_id = current_setting('app.tenant_id', true));
+
+ALTER TABLE todo_tags ENABLE ROW LEVEL SECURITY;
+ALTER TABLE todo_tags FORCE ROW LEVEL SECURITY;
+DROP POLICY IF EXISTS tenant_isolation ON todo_tags;
+CREATE POLICY tenant_isolation ON todo_tags
+    USING (EXISTS (SELECT 1 FROM todos WHERE todos.id = todo_tags.todo_id))
+    WITH CHECK (EXISTS (SELECT 1 FROM todos WHERE todos.id = todo_tags.todo_id));
@@ -0,0 +1,13 @@
+-- SQLite mirror of migrations/001_create_todos_table.sql
+CREATE TABLE IF NOT EXISTS todos (
+    id BLOB PRIMARY KEY NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    done BOOLEAN NOT NULL DEFAULT FALSE,
+    -- RFC 3339 with microseconds, written by SqliteTodoRepository
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- Create index for better performance
+CREATE INDEX IF NOT EXISTS idx_todos_done ON todos(done);
+CREATE INDEX IF NOT EXISTS idx_todos_created_at ON todos(created_at);
@@ -0,0 +1,2 @@
+-- Optimistic concurrency: bumped on every update and exposed as the ETag
+ALTER TABLE todos ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,2 @@
+-- SQLite mirror of migrations/003_add_todos_keyset_index.sql
+CREATE INDEX IF NOT EXISTS idx_todos_created_at_id ON todos(created_at DESC, id DESC);
@@ -0,0 +1,4 @@
+-- SQLite mirror of migrations/005_add_todos_deleted_at.sql
+ALTER TABLE todos ADD COLUMN deleted_at TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_todos_deleted_at ON todos(deleted_at DESC, id DESC) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,26 @@
+-- SQLite mirror of migrations/006_create_todo_events.sql
+CREATE TABLE IF NOT EXISTS todo_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    todo_id BLOB NOT NULL,
+    kind VARCHAR(16) NOT NULL,
+    version INTEGER NOT NULL,
+    -- JSON text
+    before TEXT,
+    after TEXT,
+    changes TEXT NOT NULL DEFAULT '[]',
+    actor TEXT,
+    request_id TEXT,
+    occurred_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_todo_events_todo_id ON todo_events(todo_id, id);
+
+CREATE TRIGGER IF NOT EXISTS todo_events_no_update BEFORE UPDATE ON todo_events
+BEGIN
+    SELECT RAISE(ABORT, 'todo_events is append-only');
+END;
+
+CREATE TRIGGER IF NOT EXISTS todo_events_no_delete BEFORE DELETE ON todo_events
+BEGIN
+    SELECT RAISE(ABORT, 'todo_events is append-only');
+END;
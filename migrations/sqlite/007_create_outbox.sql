@@ -0,0 +1,16 @@
+-- SQLite mirror of migrations/007_create_outbox.sql
+CREATE TABLE IF NOT EXISTS outbox (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    event_id BLOB NOT NULL UNIQUE,
+    todo_id BLOB NOT NULL,
+    event_type VARCHAR(32) NOT NULL,
+    -- JSON text
+    payload TEXT NOT NULL,
+    occurred_at TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    available_at TEXT NOT NULL,
+    last_error TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_outbox_available_at ON outbox(available_at, id);
+CREATE INDEX IF NOT EXISTS idx_outbox_todo_id ON outbox(todo_id, id);
@@ -0,0 +1,32 @@
+-- SQLite mirror of migrations/008_create_webhooks.sql
+CREATE TABLE IF NOT EXISTS webhooks (
+    id BLOB PRIMARY KEY,
+    url TEXT NOT NULL,
+    -- JSON array of event types
+    events TEXT NOT NULL DEFAULT '[]',
+    secret TEXT NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    consecutive_failures INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    webhook_id BLOB NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+    event_id BLOB NOT NULL,
+    event_type VARCHAR(32) NOT NULL,
+    -- JSON text
+    payload TEXT NOT NULL,
+    status VARCHAR(16) NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TEXT,
+    response_status INTEGER,
+    last_error TEXT,
+    created_at TEXT NOT NULL,
+    delivered_at TEXT,
+    UNIQUE (webhook_id, event_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_due
+    ON webhook_deliveries(next_attempt_at, id) WHERE status = 'pending';
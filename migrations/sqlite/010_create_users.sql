@@ -0,0 +1,29 @@
+-- SQLite mirror of migrations/010_create_users.sql
+CREATE TABLE IF NOT EXISTS users (
+    id BLOB PRIMARY KEY NOT NULL,
+    email VARCHAR(254) NOT NULL UNIQUE,
+    password_hash TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS refresh_tokens (
+    id BLOB PRIMARY KEY NOT NULL,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    family_id BLOB NOT NULL,
+    token_hash CHAR(64) NOT NULL UNIQUE,
+    expires_at TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    replaced_at TEXT,
+    revoked_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_family_id ON refresh_tokens(family_id);
+
+ALTER TABLE todos ADD COLUMN owner_id BLOB;
+ALTER TABLE todo_events ADD COLUMN owner_id BLOB;
+ALTER TABLE outbox ADD COLUMN owner_id BLOB;
+ALTER TABLE webhooks ADD COLUMN owner_id BLOB;
+
+CREATE INDEX IF NOT EXISTS idx_todos_owner_created_at_id ON todos(owner_id, created_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS idx_webhooks_owner_id ON webhooks(owner_id);
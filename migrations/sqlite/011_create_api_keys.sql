@@ -0,0 +1,15 @@
+-- SQLite mirror of migrations/011_create_api_keys.sql
+CREATE TABLE IF NOT EXISTS api_keys (
+    id BLOB PRIMARY KEY NOT NULL,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    prefix VARCHAR(32) NOT NULL,
+    key_hash CHAR(64) NOT NULL UNIQUE,
+    scope VARCHAR(16) NOT NULL CHECK (scope IN ('read_only', 'read_write', 'admin')),
+    expires_at TEXT,
+    last_used_at TEXT,
+    revoked_at TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_keys_user_created_at ON api_keys(user_id, created_at, id);
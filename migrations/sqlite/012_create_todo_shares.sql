@@ -0,0 +1,10 @@
+-- SQLite mirror of migrations/012_create_todo_shares.sql
+CREATE TABLE IF NOT EXISTS todo_shares (
+    todo_id BLOB NOT NULL REFERENCES todos(id) ON DELETE CASCADE,
+    user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role VARCHAR(16) NOT NULL CHECK (role IN ('viewer', 'editor')),
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (todo_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_todo_shares_user_id ON todo_shares(user_id, todo_id);
@@ -0,0 +1,18 @@
+-- SQLite mirror of migrations/014_create_todo_lists.sql
+CREATE TABLE IF NOT EXISTS todo_lists (
+    id BLOB PRIMARY KEY NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    description TEXT,
+    color VARCHAR(7),
+    archived BOOLEAN NOT NULL DEFAULT FALSE,
+    owner_id BLOB,
+    tenant_id TEXT NOT NULL DEFAULT 'default',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_todo_lists_owner_archived ON todo_lists(owner_id, archived, created_at, id);
+
+ALTER TABLE todos ADD COLUMN list_id BLOB REFERENCES todo_lists(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_todos_list_created_at_id ON todos(list_id, created_at DESC, id DESC);
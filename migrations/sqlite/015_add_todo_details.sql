@@ -0,0 +1,10 @@
+-- SQLite mirror of migrations/015_add_todo_details.sql
+ALTER TABLE todos ADD COLUMN description TEXT;
+ALTER TABLE todos ADD COLUMN due_at TEXT;
+ALTER TABLE todos ADD COLUMN priority TEXT NOT NULL DEFAULT 'medium'
+    CHECK (priority IN ('low', 'medium', 'high'));
+ALTER TABLE todos ADD COLUMN completed_at TEXT;
+
+UPDATE todos SET completed_at = updated_at WHERE done AND completed_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_todos_due_at ON todos(due_at) WHERE due_at IS NOT NULL AND deleted_at IS NULL;
@@ -0,0 +1,20 @@
+-- SQLite mirror of migrations/016_create_tags.sql
+CREATE TABLE IF NOT EXISTS tags (
+    id BLOB PRIMARY KEY NOT NULL,
+    name VARCHAR(50) NOT NULL,
+    owner_id BLOB,
+    tenant_id TEXT NOT NULL DEFAULT 'default',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tags_owner_name ON tags(tenant_id, IFNULL(owner_id, x''), name);
+
+CREATE TABLE IF NOT EXISTS todo_tags (
+    todo_id BLOB NOT NULL REFERENCES todos(id) ON DELETE CASCADE,
+    tag_id BLOB NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (todo_id, tag_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_todo_tags_tag_id ON todo_tags(tag_id, todo_id);
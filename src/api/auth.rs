@@ -0,0 +1,206 @@
+//! Request authentication. Access tokens are HS256 JWTs naming the user in
+//! `sub`; they are checked without a database round trip and simply expire.
+//! Revocable, long-lived sessions are the refresh tokens' job. Scripts and
+//! services authenticate with API keys instead, which are looked up on every
+//! request and limited to their scope. Tokens and keys both belong to the
+//! tenant they were issued in, which they cannot be used outside of.
+
+use std::time::Duration;
+
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Request},
+    http::{header, request::Parts},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    api::tenant::{named_tenant_required, tenant_for},
+    application::api_keys::AuthenticateApiKeyUseCase,
+    domain::api_keys::ApiKeyScope,
+    error::ApiError,
+    request_context::RequestContext,
+    state::AppState,
+};
+
+#[derive(Serialize, Deserialize)]
+struct Claims {
+    sub: Uuid,
+    email: String,
+    iat: i64,
+    exp: i64,
+    tenant: String,
+}
+
+/// Issues and verifies access tokens.
+#[derive(Clone)]
+pub struct AccessTokens {
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    ttl: Duration,
+}
+
+impl AccessTokens {
+    pub fn new(secret: &[u8], ttl: Duration) -> Self {
+        Self { encoding: EncodingKey::from_secret(secret), decoding: DecodingKey::from_secret(secret), ttl }
+    }
+
+    /// Tokens signed with a random per-process key.
+    pub fn random(ttl: Duration) -> Self {
+        let mut key = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut key);
+        Self::new(&key, ttl)
+    }
+
+    /// Seconds a freshly issued token stays valid.
+    pub fn expires_in(&self) -> u64 {
+        self.ttl.as_secs()
+    }
+
+    /// A token for `user_id`, bound to `tenant`.
+    pub fn issue(&self, user_id: Uuid, email: &str, tenant: &str) -> String {
+        let now = Utc::now().timestamp();
+        let claims = Claims {
+            sub: user_id,
+            email: email.to_string(),
+            iat: now,
+            exp: now + self.ttl.as_secs() as i64,
+            tenant: tenant.to_string(),
+        };
+        jsonwebtoken::encode(&Header::new(Algorithm::HS256), &claims, &self.encoding)
+            .expect("HS256 signing cannot fail")
+    }
+
+    pub fn verify(&self, token: &str) -> Result<AuthUser, ApiError> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.leeway = 0;
+        let claims = jsonwebtoken::decode::<Claims>(token, &self.decoding, &validation)
+            .map_err(|_| ApiError::Unauthorized("The access token is invalid or has expired.".to_string()))?
+            .claims;
+        Ok(AuthUser {
+            id: claims.sub,
+            scope: ApiKeyScope::Admin,
+            tenant: claims.tenant,
+            expires_at: DateTime::from_timestamp(claims.exp, 0),
+            api_key_id: None,
+        })
+    }
+}
+
+/// The user a request is authenticated as, from `Authorization: Bearer` or
+/// `Authorization: ApiKey`. Browsers cannot set headers on WebSocket
+/// handshakes, so those may pass an access token as an `access_token` query
+/// parameter instead.
+#[derive(Clone, Debug)]
+pub struct AuthUser {
+    pub id: Uuid,
+    /// What the request may do; `Admin` for access tokens
+    pub scope: ApiKeyScope,
+    /// The tenant the access token or API key was issued in
+    pub tenant: String,
+    /// When the access token or API key stops working; absent means never
+    pub expires_at: Option<DateTime<Utc>>,
+    /// The API key the request authenticated with, if any
+    pub api_key_id: Option<Uuid>,
+}
+
+impl AuthUser {
+    pub fn require(&self, scope: ApiKeyScope) -> Result<(), ApiError> {
+        if self.scope.includes(scope) {
+            Ok(())
+        } else {
+            Err(ApiError::Forbidden(format!("This requires an API key with the `{}` scope.", scope.as_str())))
+        }
+    }
+}
+
+enum Credentials {
+    Bearer(String),
+    ApiKey(String),
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for AuthUser {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        if let Some(user) = parts.extensions.get::<AuthUser>() {
+            return Ok(user.clone());
+        }
+
+        let credentials = authorization(parts)?.or_else(|| query_token(parts).map(Credentials::Bearer)).ok_or_else(|| {
+            ApiError::Unauthorized(
+                "Send an access token as `Authorization: Bearer <token>` or an API key as `Authorization: ApiKey <key>`."
+                    .to_string(),
+            )
+        })?;
+
+        match credentials {
+            Credentials::Bearer(token) => state.access_tokens.verify(&token),
+            Credentials::ApiKey(key) => {
+                // Keys are looked up in the tenant the request names, so a key
+                // from any other tenant is unknown here.
+                named_tenant_required()?;
+                let api_key = AuthenticateApiKeyUseCase::new(&*state.api_key_repository).execute(&key).await?;
+                Ok(AuthUser {
+                    id: api_key.user_id,
+                    scope: api_key.scope,
+                    tenant: api_key.tenant_id,
+                    expires_at: api_key.expires_at,
+                    api_key_id: Some(api_key.id),
+                })
+            }
+        }
+    }
+}
+
+fn authorization(parts: &Parts) -> Result<Option<Credentials>, ApiError> {
+    let Some(value) = parts.headers.get(header::AUTHORIZATION) else { return Ok(None) };
+    let value = value.to_str().unwrap_or_default();
+    match value.split_once(' ').map(|(scheme, credentials)| (scheme, credentials.trim())) {
+        Some((scheme, token)) if scheme.eq_ignore_ascii_case("bearer") && !token.is_empty() => {
+            Ok(Some(Credentials::Bearer(token.to_string())))
+        }
+        Some((scheme, key)) if scheme.eq_ignore_ascii_case("apikey") && !key.is_empty() => {
+            Ok(Some(Credentials::ApiKey(key.to_string())))
+        }
+        _ => Err(ApiError::Unauthorized("The Authorization header must use the Bearer or ApiKey scheme.".to_string())),
+    }
+}
+
+fn query_token(parts: &Parts) -> Option<String> {
+    let upgrade = parts.headers.get(header::UPGRADE)?.to_str().ok()?;
+    if !upgrade.eq_ignore_ascii_case("websocket") {
+        return None;
+    }
+    url::form_urlencoded::parse(parts.uri.query()?.as_bytes())
+        .find(|(name, _)| name == "access_token")
+        .map(|(_, token)| token.into_owned())
+}
+
+/// Middleware for routes that need a user: rejects anonymous requests with
+/// 401, and writes by read-only API keys and tokens of another tenant with
+/// 403, then runs the rest of the request as the authenticated user in their
+/// tenant, which scopes every repository query to their data.
+pub async fn require_user(user: AuthUser, mut request: Request, next: Next) -> Response {
+    if !request.method().is_safe()
+        && let Err(error) = user.require(ApiKeyScope::ReadWrite)
+    {
+        return error.into_response();
+    }
+
+    let tenant = match tenant_for(&user.tenant, RequestContext::current_tenant_id().as_deref()) {
+        Ok(tenant) => tenant,
+        Err(error) => return error.into_response(),
+    };
+
+    let context = RequestContext { tenant_id: Some(tenant), ..RequestContext::for_user(user.id) };
+    request.extensions_mut().insert(user);
+    context.scope(next.run(request)).await
+}
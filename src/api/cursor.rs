@@ -0,0 +1,116 @@
+//! Opaque, tamper-evident list cursors: a base64url JSON position followed by
+//! an HMAC-SHA256 tag over it, e.g. `eyJk...Q.5mX...`.
+
+use std::sync::Arc;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::{
+    domain::todos::{
+        CursorMeta, CursorPaginatedResponse, KeysetDirection, KeysetPage, KeysetPosition, Todo,
+    },
+    error::ApiError,
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Serialize, Deserialize)]
+struct CursorPayload {
+    direction: KeysetDirection,
+    #[serde(flatten)]
+    position: KeysetPosition,
+}
+
+#[derive(Clone)]
+pub struct CursorCodec {
+    key: Arc<[u8]>,
+}
+
+impl CursorCodec {
+    pub fn new(secret: &[u8]) -> Self {
+        Self { key: secret.into() }
+    }
+
+    /// A codec with a random per-process key.
+    pub fn random() -> Self {
+        let mut key = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut key);
+        Self::new(&key)
+    }
+
+    pub fn encode(&self, direction: KeysetDirection, position: KeysetPosition) -> String {
+        let payload = serde_json::to_vec(&CursorPayload { direction, position })
+            .expect("cursor payload serializes");
+        let payload = URL_SAFE_NO_PAD.encode(payload);
+        let tag = URL_SAFE_NO_PAD.encode(self.mac(&payload).finalize().into_bytes());
+        format!("{payload}.{tag}")
+    }
+
+    pub fn decode(&self, cursor: &str) -> Result<(KeysetDirection, KeysetPosition), ApiError> {
+        let invalid = || ApiError::BadRequest("invalid pagination cursor".to_string());
+
+        let (payload, tag) = cursor.split_once('.').ok_or_else(invalid)?;
+        let tag = URL_SAFE_NO_PAD.decode(tag).map_err(|_| invalid())?;
+        self.mac(payload).verify_slice(&tag).map_err(|_| invalid())?;
+
+        let payload = URL_SAFE_NO_PAD.decode(payload).map_err(|_| invalid())?;
+        let CursorPayload { direction, position } =
+            serde_json::from_slice(&payload).map_err(|_| invalid())?;
+        Ok((direction, position))
+    }
+
+    /// Start position for a `cursor` query parameter; empty means the first page.
+    pub fn start(&self, cursor: &str) -> Result<Option<(KeysetDirection, KeysetPosition)>, ApiError> {
+        match cursor {
+            "" => Ok(None),
+            cursor => self.decode(cursor).map(Some),
+        }
+    }
+
+    /// Wraps a keyset page read from `start` with cursors to its neighbours.
+    /// A neighbour is only linked when it is known to exist, except that the
+    /// page a client came from is always linked back to.
+    pub fn response(
+        &self,
+        start: Option<(KeysetDirection, KeysetPosition)>,
+        limit: u32,
+        page: KeysetPage<Todo>,
+    ) -> CursorPaginatedResponse<Todo> {
+        let (has_next, has_prev) = match start.map(|(direction, _)| direction) {
+            None => (page.has_more, false),
+            Some(KeysetDirection::After) => (page.has_more, true),
+            Some(KeysetDirection::Before) => (true, page.has_more),
+        };
+
+        let next_cursor = page
+            .data
+            .last()
+            .filter(|_| has_next)
+            .map(|todo| self.encode(KeysetDirection::After, todo.into()));
+        let prev_cursor = page
+            .data
+            .first()
+            .filter(|_| has_prev)
+            .map(|todo| self.encode(KeysetDirection::Before, todo.into()));
+
+        CursorPaginatedResponse {
+            data: page.data,
+            pagination: CursorMeta {
+                limit,
+                next_cursor,
+                prev_cursor,
+                total: page.total,
+            },
+        }
+    }
+
+    fn mac(&self, payload: &str) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+        mac.update(payload.as_bytes());
+        mac
+    }
+}
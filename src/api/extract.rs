@@ -0,0 +1,131 @@
+//! Drop-in replacements for axum's `Json`, `Path` and `Query` whose
+//! rejections are `ApiError`s, so malformed requests get the same
+//! problem+json responses as every other error.
+
+use axum::{
+    async_trait,
+    body::Bytes,
+    extract::{rejection::PathRejection, rejection::QueryRejection, FromRequest, FromRequestParts, Request},
+    http::{header, request::Parts, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::error::Category;
+
+use crate::{domain::todos::validation::FieldError, error::ApiError};
+
+/// A JSON request or response body. Syntax errors are 400s, a body over the
+/// size limit is a 413, and a body that is valid JSON but does not fit the
+/// type is a 422 naming the field.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json<T>(pub T);
+
+#[async_trait]
+impl<T: DeserializeOwned, S: Send + Sync> FromRequest<S> for Json<T> {
+    type Rejection = ApiError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        if !is_json(request.headers()) {
+            return Err(ApiError::UnsupportedMediaType("expected `Content-Type: application/json`".to_string()));
+        }
+        let body = Bytes::from_request(request, state).await.map_err(|rejection| match rejection.status() {
+            status if status.is_server_error() => ApiError::Anyhow(anyhow::anyhow!(rejection.body_text())),
+            StatusCode::PAYLOAD_TOO_LARGE => ApiError::PayloadTooLarge(rejection.body_text()),
+            _ => ApiError::BadRequest(rejection.body_text()),
+        })?;
+        deserialize(&body).map(Json)
+    }
+}
+
+impl<T: Serialize> IntoResponse for Json<T> {
+    fn into_response(self) -> Response {
+        axum::Json(self.0).into_response()
+    }
+}
+
+/// `application/json` or any `application/*+json` type.
+fn is_json(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers.get(header::CONTENT_TYPE).and_then(|value| value.to_str().ok()) else {
+        return false;
+    };
+    let media_type = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    media_type == "application/json" || (media_type.starts_with("application/") && media_type.ends_with("+json"))
+}
+
+fn deserialize<T: DeserializeOwned>(body: &[u8]) -> Result<T, ApiError> {
+    let deserializer = &mut serde_json::Deserializer::from_slice(body);
+    let error = match serde_path_to_error::deserialize(deserializer) {
+        Ok(value) => return Ok(value),
+        Err(error) => error,
+    };
+
+    let path = error.path().to_string();
+    let error = error.into_inner();
+    if error.classify() != Category::Data {
+        return Err(ApiError::BadRequest(format!("malformed JSON body: {error}")));
+    }
+
+    let message = error.to_string();
+    let message = message.rsplit_once(" at line ").map_or(message.as_str(), |(message, _)| message);
+    let field = if path == "." { "body".to_string() } else { path };
+    let (field, code) = if let Some(name) = backticked(message, "missing field `") {
+        // serde reports a missing field on the object that lacks it.
+        (if field == "body" { name.to_string() } else { format!("{field}.{name}") }, "required")
+    } else if message.starts_with("unknown field `") {
+        (field, "unknown_field")
+    } else {
+        (field, "invalid_type")
+    };
+    Err(ApiError::Validation(vec![FieldError::new(&field, code, message)]))
+}
+
+/// The name in `` prefix`name` `` at the start of `message`.
+fn backticked<'a>(message: &'a str, prefix: &str) -> Option<&'a str> {
+    message.strip_prefix(prefix)?.split('`').next()
+}
+
+/// Path parameters. A parameter that does not parse, such as an id that is
+/// not a UUID, is a 400.
+#[derive(Debug, Clone, Copy)]
+pub struct Path<T>(pub T);
+
+#[async_trait]
+impl<T: DeserializeOwned + Send, S: Send + Sync> FromRequestParts<S> for Path<T> {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match axum::extract::Path::<T>::from_request_parts(parts, state).await {
+            Ok(axum::extract::Path(value)) => Ok(Path(value)),
+            Err(rejection) => Err(rejection.into()),
+        }
+    }
+}
+
+/// Query parameters. A query string that does not parse is a 400.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Query<T>(pub T);
+
+#[async_trait]
+impl<T: DeserializeOwned, S: Send + Sync> FromRequestParts<S> for Query<T> {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let axum::extract::Query(value) = axum::extract::Query::try_from_uri(&parts.uri)?;
+        Ok(Query(value))
+    }
+}
+
+impl From<PathRejection> for ApiError {
+    fn from(rejection: PathRejection) -> Self {
+        match rejection {
+            PathRejection::FailedToDeserializePathParams(error) => ApiError::BadRequest(error.body_text()),
+            other => ApiError::Anyhow(anyhow::anyhow!(other.body_text())),
+        }
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        ApiError::BadRequest(rejection.body_text())
+    }
+}
@@ -0,0 +1,117 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    api::extract::{Json, Path, Query},
+    state::AppState,
+    api::auth::AuthUser,
+    domain::api_keys::{ApiKey, ApiKeyScope, CreateApiKeyRequest},
+    domain::todos::{PageQuery, PaginatedResponse},
+    application::api_keys::{CreateApiKeyUseCase, ListApiKeysUseCase, GetApiKeyUseCase, RevokeApiKeyUseCase},
+    error::ApiError
+};
+
+/// Creates an API key for the authenticated user. The response is the only
+/// place the key is ever shown.
+#[utoipa::path(
+    post,
+    path = "/api-keys",
+    request_body = CreateApiKeyRequest,
+    responses(
+        (status = 201, body = CreatedApiKey),
+        (status = 403, description = "API key without the admin scope", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Validation failed", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "auth"
+)]
+pub async fn create_api_key(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(payload): Json<CreateApiKeyRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    user.require(ApiKeyScope::Admin)?;
+    let use_case = CreateApiKeyUseCase::new(&*state.api_key_repository);
+    let created = use_case.execute(user.id, payload).await?;
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+/// Lists the authenticated user's API keys, oldest first, including revoked
+/// and expired ones.
+#[utoipa::path(
+    get,
+    path = "/api-keys",
+    params(PageQuery),
+    responses(
+        (status = 200, body = ApiKeyPage),
+        (status = 403, description = "API key without the admin scope", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "auth"
+)]
+pub async fn list_api_keys(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Query(query): Query<PageQuery>,
+) -> Result<Json<PaginatedResponse<ApiKey>>, ApiError> {
+    user.require(ApiKeyScope::Admin)?;
+    let use_case = ListApiKeysUseCase::new(&*state.api_key_repository);
+    let result = use_case.execute(user.id, query).await?;
+    Ok(Json(result))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api-keys/{id}",
+    params(("id" = Uuid, Path, description = "API key ID")),
+    responses(
+        (status = 200, body = ApiKey),
+        (status = 403, description = "API key without the admin scope", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "API key not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "auth"
+)]
+pub async fn get_api_key(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiKey>, ApiError> {
+    user.require(ApiKeyScope::Admin)?;
+    let use_case = GetApiKeyUseCase::new(&*state.api_key_repository);
+    let api_key = use_case.execute(user.id, id).await?;
+    Ok(Json(api_key))
+}
+
+/// Revokes an API key; requests using it are rejected from now on. The key
+/// stays listed with its `revoked_at`.
+#[utoipa::path(
+    delete,
+    path = "/api-keys/{id}",
+    params(("id" = Uuid, Path, description = "API key ID")),
+    responses(
+        (status = 204, description = "API key revoked"),
+        (status = 403, description = "API key without the admin scope", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "API key not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "auth"
+)]
+pub async fn revoke_api_key(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    user.require(ApiKeyScope::Admin)?;
+    let use_case = RevokeApiKeyUseCase::new(&*state.api_key_repository);
+    use_case.execute(user.id, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
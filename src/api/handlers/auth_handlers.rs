@@ -0,0 +1,149 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+};
+
+use crate::{
+    api::extract::Json,
+    state::AppState,
+    api::auth::AuthUser,
+    api::tenant::named_tenant_required,
+    domain::users::{LoginRequest, RefreshRequest, RegisterRequest, Session, TokenResponse, User},
+    application::users::{
+        RegisterUserUseCase, LoginUserUseCase, RefreshSessionUseCase, LogoutUserUseCase, GetUserUseCase
+    },
+    error::ApiError
+};
+
+/// The access token is bound to `tenant`, the one the request was made in.
+fn token_response(state: &AppState, session: Session, tenant: &str) -> Json<TokenResponse> {
+    Json(TokenResponse {
+        access_token: state.access_tokens.issue(session.user.id, &session.user.email, tenant),
+        token_type: "Bearer".to_string(),
+        expires_in: state.access_tokens.expires_in(),
+        refresh_token: session.refresh_token,
+    })
+}
+
+/// Creates an account in the request's tenant, the only one it can sign in
+/// to. Sign in with `POST /auth/login` afterwards.
+#[utoipa::path(
+    post,
+    path = "/auth/register",
+    request_body = RegisterRequest,
+    responses(
+        (status = 201, body = User),
+        (status = 400, description = "No tenant named while tenancy is enabled", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "Email already registered in the tenant", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Validation failed", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "auth"
+)]
+pub async fn register(
+    State(state): State<AppState>,
+    Json(payload): Json<RegisterRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    named_tenant_required()?;
+    let use_case = RegisterUserUseCase::new(&*state.user_repository, &*state.password_hasher);
+    let user = use_case.execute(payload).await?;
+    Ok((StatusCode::CREATED, Json(user)))
+}
+
+/// Exchanges an email and password for an access token and a refresh token.
+/// Only accounts registered in the request's tenant can sign in.
+#[utoipa::path(
+    post,
+    path = "/auth/login",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, body = TokenResponse),
+        (status = 400, description = "No tenant named while tenancy is enabled", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 401, description = "Invalid email or password, or no such account in the tenant", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "auth"
+)]
+pub async fn login(
+    State(state): State<AppState>,
+    Json(payload): Json<LoginRequest>,
+) -> Result<Json<TokenResponse>, ApiError> {
+    let tenant = named_tenant_required()?;
+    let use_case = LoginUserUseCase::new(&*state.user_repository, &*state.password_hasher);
+    let session = use_case.execute(payload, state.config.auth.refresh_token_ttl()).await?;
+    Ok(token_response(&state, session, &tenant))
+}
+
+/// Exchanges a refresh token for new tokens. Each refresh token works once;
+/// presenting it again signs the session out everywhere. A refresh token
+/// only works in the tenant its session was signed in to.
+#[utoipa::path(
+    post,
+    path = "/auth/refresh",
+    request_body = RefreshRequest,
+    responses(
+        (status = 200, body = TokenResponse),
+        (status = 400, description = "No tenant named while tenancy is enabled", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 401, description = "Refresh token invalid, expired, already used or from another tenant", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "auth"
+)]
+pub async fn refresh(
+    State(state): State<AppState>,
+    Json(payload): Json<RefreshRequest>,
+) -> Result<Json<TokenResponse>, ApiError> {
+    let tenant = named_tenant_required()?;
+    let use_case = RefreshSessionUseCase::new(&*state.user_repository);
+    let session = use_case.execute(&payload.refresh_token, state.config.auth.refresh_token_ttl()).await?;
+    Ok(token_response(&state, session, &tenant))
+}
+
+/// Ends the session of a refresh token. Access tokens already issued stay
+/// valid until they expire.
+#[utoipa::path(
+    post,
+    path = "/auth/logout",
+    request_body = RefreshRequest,
+    responses(
+        (status = 204, description = "Signed out"),
+        (status = 400, description = "No tenant named while tenancy is enabled", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "auth"
+)]
+pub async fn logout(
+    State(state): State<AppState>,
+    Json(payload): Json<RefreshRequest>,
+) -> Result<StatusCode, ApiError> {
+    named_tenant_required()?;
+    let use_case = LogoutUserUseCase::new(&*state.user_repository);
+    use_case.execute(&payload.refresh_token).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// The authenticated user.
+#[utoipa::path(
+    get,
+    path = "/auth/me",
+    responses(
+        (status = 200, body = User),
+        (status = 404, description = "The account was deleted", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "auth"
+)]
+pub async fn me(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> Result<Json<User>, ApiError> {
+    let use_case = GetUserUseCase::new(&*state.user_repository);
+    let user = use_case.execute(user.id).await?;
+    Ok(Json(user))
+}
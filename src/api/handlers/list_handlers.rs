@@ -0,0 +1,214 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use uuid::Uuid;
+
+use crate::{
+    api::extract::{Json, Path, Query},
+    state::AppState,
+    api::query::TodoQuery,
+    domain::lists::{CreateTodoListRequest, TodoList, TodoListQuery, UpdateTodoListRequest},
+    domain::todos::{PaginatedResponse, PaginationQuery},
+    application::lists::{
+        CreateTodoListUseCase, GetTodoListUseCase, ListTodoListsUseCase, UpdateTodoListUseCase,
+        ArchiveTodoListUseCase, DeleteTodoListUseCase, ListTodosInListUseCase
+    },
+    error::ApiError
+};
+
+#[utoipa::path(
+    post,
+    path = "/lists",
+    request_body = CreateTodoListRequest,
+    responses(
+        (status = 201, body = TodoList),
+        (status = 422, description = "Validation failed", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "lists"
+)]
+pub async fn create_list(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateTodoListRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let use_case = CreateTodoListUseCase::new(&*state.list_repository);
+    let list = use_case.execute(payload).await?;
+    Ok((StatusCode::CREATED, Json(list)))
+}
+
+/// Lists the active lists, or the archived ones, oldest first.
+#[utoipa::path(
+    get,
+    path = "/lists",
+    params(TodoListQuery),
+    responses(
+        (status = 200, body = TodoListPage),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "lists"
+)]
+pub async fn list_lists(
+    State(state): State<AppState>,
+    Query(query): Query<TodoListQuery>,
+) -> Result<Json<PaginatedResponse<TodoList>>, ApiError> {
+    let use_case = ListTodoListsUseCase::new(&*state.list_repository);
+    let result = use_case.execute(query).await?;
+    Ok(Json(result))
+}
+
+#[utoipa::path(
+    get,
+    path = "/lists/{id}",
+    params(("id" = Uuid, Path, description = "List ID")),
+    responses(
+        (status = 200, body = TodoList),
+        (status = 404, description = "List not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "lists"
+)]
+pub async fn get_list(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<TodoList>, ApiError> {
+    let use_case = GetTodoListUseCase::new(&*state.list_repository);
+    let list = use_case.execute(id).await?
+        .ok_or(ApiError::NotFound)?;
+    Ok(Json(list))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/lists/{id}",
+    params(("id" = Uuid, Path, description = "List ID")),
+    request_body = UpdateTodoListRequest,
+    responses(
+        (status = 200, body = TodoList),
+        (status = 404, description = "List not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Validation failed", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "lists"
+)]
+pub async fn update_list(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateTodoListRequest>,
+) -> Result<Json<TodoList>, ApiError> {
+    let use_case = UpdateTodoListUseCase::new(&*state.list_repository);
+    let list = use_case.execute(id, payload).await?;
+    Ok(Json(list))
+}
+
+/// Deletes the list and moves its todos to the trash. Restored todos come
+/// back without a list.
+#[utoipa::path(
+    delete,
+    path = "/lists/{id}",
+    params(("id" = Uuid, Path, description = "List ID")),
+    responses(
+        (status = 204, description = "List deleted, its todos trashed"),
+        (status = 403, description = "The list holds a todo the user does not own", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "List not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "lists"
+)]
+pub async fn delete_list(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let use_case = DeleteTodoListUseCase::new(&*state.list_repository, &*state.todo_repository);
+    use_case.execute(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Archives the list. Its todos drop out of `GET /todos` and no todos can
+/// be added to it until it is unarchived.
+#[utoipa::path(
+    post,
+    path = "/lists/{id}/archive",
+    params(("id" = Uuid, Path, description = "List ID")),
+    responses(
+        (status = 200, body = TodoList),
+        (status = 404, description = "List not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "lists"
+)]
+pub async fn archive_list(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<TodoList>, ApiError> {
+    let use_case = ArchiveTodoListUseCase::new(&*state.list_repository);
+    let list = use_case.execute(id, true).await?;
+    Ok(Json(list))
+}
+
+/// Brings an archived list, and its todos, back.
+#[utoipa::path(
+    post,
+    path = "/lists/{id}/unarchive",
+    params(("id" = Uuid, Path, description = "List ID")),
+    responses(
+        (status = 200, body = TodoList),
+        (status = 404, description = "List not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "lists"
+)]
+pub async fn unarchive_list(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<TodoList>, ApiError> {
+    let use_case = ArchiveTodoListUseCase::new(&*state.list_repository);
+    let list = use_case.execute(id, false).await?;
+    Ok(Json(list))
+}
+
+/// Lists the list's todos, archived or not, with the filters and paging of
+/// `GET /todos`.
+#[utoipa::path(
+    get,
+    path = "/lists/{id}/todos",
+    params(
+        ("id" = Uuid, Path, description = "List ID"),
+        PaginationQuery,
+        ("tag" = Option<Vec<String>>, Query, description = "Only todos with this tag, by name; repeat for several", style = Form, explode)
+    ),
+    responses(
+        (status = 200, body = TodoListResponse),
+        (status = 400, description = "Invalid or tampered cursor", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "List not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid filter or sort", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "lists"
+)]
+pub async fn list_list_todos(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    TodoQuery(pagination): TodoQuery,
+) -> Result<Response, ApiError> {
+    let use_case = ListTodosInListUseCase::new(&*state.list_repository, &*state.todo_repository);
+
+    if let Some(cursor) = &pagination.cursor {
+        let start = state.cursor_codec.start(cursor)?;
+        let limit = pagination.limit();
+        let page = use_case.execute_keyset(id, pagination, start).await?;
+        return Ok(Json(state.cursor_codec.response(start, limit, page)).into_response());
+    }
+
+    let result = use_case.execute(id, pagination).await?;
+    Ok(Json(result).into_response())
+}
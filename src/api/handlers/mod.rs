@@ -1,7 +1,25 @@
+pub mod api_key_handlers;
+pub mod auth_handlers;
 pub mod health;
+pub mod list_handlers;
+pub mod tag_handlers;
 pub mod todo_handlers;
+pub mod webhook_handlers;
+pub mod ws_handlers;
 
+pub use api_key_handlers::{create_api_key, list_api_keys, get_api_key, revoke_api_key};
+pub use auth_handlers::{register, login, refresh, logout, me};
 pub use health::health;
+pub use list_handlers::{
+    create_list, list_lists, get_list, update_list, delete_list, archive_list, unarchive_list, list_list_todos
+};
+pub use tag_handlers::{create_tag, list_tags, rename_tag, delete_tag, attach_tag, detach_tag};
 pub use todo_handlers::{
-    create_todo, list_todos, get_todo, update_todo, delete_todo, get_todos_by_done
+    create_todo, list_todos, search_todos, get_todo, update_todo, patch_todo, delete_todo, batch_todos,
+    list_trash, restore_todo, purge_todo, get_todo_history, todo_events, get_todos_by_done,
+    list_todo_access, grant_todo_access, revoke_todo_access
+};
+pub use webhook_handlers::{
+    create_webhook, list_webhooks, get_webhook, update_webhook, delete_webhook, list_deliveries
 };
+pub use ws_handlers::todo_socket;
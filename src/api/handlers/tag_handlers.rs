@@ -0,0 +1,169 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    api::extract::{Json, Path, Query},
+    state::AppState,
+    api::preconditions::{etag_header, IfMatch},
+    domain::tags::{CreateTagRequest, RenameTagRequest, Tag, TagQuery},
+    domain::todos::PaginatedResponse,
+    application::tags::{CreateTagUseCase, ListTagsUseCase, RenameTagUseCase, DeleteTagUseCase, TagTodoUseCase},
+    error::ApiError
+};
+
+#[utoipa::path(
+    post,
+    path = "/tags",
+    request_body = CreateTagRequest,
+    responses(
+        (status = 201, body = Tag),
+        (status = 409, description = "The user already has a tag with this name", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Validation failed", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "tags"
+)]
+pub async fn create_tag(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateTagRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let use_case = CreateTagUseCase::new(&*state.tag_repository);
+    let tag = use_case.execute(payload).await?;
+    Ok((StatusCode::CREATED, Json(tag)))
+}
+
+/// Lists the user's tags by name.
+#[utoipa::path(
+    get,
+    path = "/tags",
+    params(TagQuery),
+    responses(
+        (status = 200, body = TagPage),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "tags"
+)]
+pub async fn list_tags(
+    State(state): State<AppState>,
+    Query(query): Query<TagQuery>,
+) -> Result<Json<PaginatedResponse<Tag>>, ApiError> {
+    let use_case = ListTagsUseCase::new(&*state.tag_repository);
+    let result = use_case.execute(query).await?;
+    Ok(Json(result))
+}
+
+/// Renames the tag on every todo it is attached to. The todos keep their
+/// ETags.
+#[utoipa::path(
+    patch,
+    path = "/tags/{id}",
+    params(("id" = Uuid, Path, description = "Tag ID")),
+    request_body = RenameTagRequest,
+    responses(
+        (status = 200, body = Tag),
+        (status = 404, description = "Tag not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "The user already has a tag with this name", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Validation failed", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "tags"
+)]
+pub async fn rename_tag(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<RenameTagRequest>,
+) -> Result<Json<Tag>, ApiError> {
+    let use_case = RenameTagUseCase::new(&*state.tag_repository);
+    let tag = use_case.execute(id, payload).await?;
+    Ok(Json(tag))
+}
+
+/// Deletes the tag and detaches it from every todo.
+#[utoipa::path(
+    delete,
+    path = "/tags/{id}",
+    params(("id" = Uuid, Path, description = "Tag ID")),
+    responses(
+        (status = 204, description = "Tag deleted"),
+        (status = 404, description = "Tag not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "tags"
+)]
+pub async fn delete_tag(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let use_case = DeleteTagUseCase::new(&*state.tag_repository);
+    use_case.execute(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Attaches one of the user's tags to the todo. Attaching a tag the todo
+/// already has changes nothing.
+#[utoipa::path(
+    put,
+    path = "/todos/{id}/tags/{tag_id}",
+    params(
+        ("id" = Uuid, Path, description = "Todo ID"),
+        ("tag_id" = Uuid, Path, description = "Tag ID"),
+        ("If-Match" = Option<String>, Header, description = "Only apply if the todo still has this ETag")
+    ),
+    responses(
+        (status = 200, body = Todo, headers(("ETag" = String))),
+        (status = 403, description = "Only editors and the owner may change the todo", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Todo or tag not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 412, description = "ETag does not match", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 428, description = "If-Match is required", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "tags"
+)]
+pub async fn attach_tag(
+    State(state): State<AppState>,
+    Path((id, tag_id)): Path<(Uuid, Uuid)>,
+    IfMatch(expected_version): IfMatch,
+) -> Result<impl IntoResponse, ApiError> {
+    let use_case = TagTodoUseCase::new(&*state.tag_repository, &*state.todo_repository);
+    let todo = use_case.execute(id, tag_id, true, expected_version).await?;
+    Ok((etag_header(&todo), Json(todo)))
+}
+
+/// Detaches the tag from the todo.
+#[utoipa::path(
+    delete,
+    path = "/todos/{id}/tags/{tag_id}",
+    params(
+        ("id" = Uuid, Path, description = "Todo ID"),
+        ("tag_id" = Uuid, Path, description = "Tag ID"),
+        ("If-Match" = Option<String>, Header, description = "Only apply if the todo still has this ETag")
+    ),
+    responses(
+        (status = 200, body = Todo, headers(("ETag" = String))),
+        (status = 403, description = "Only editors and the owner may change the todo", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Todo or tag not found, or the todo does not have the tag", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 412, description = "ETag does not match", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 428, description = "If-Match is required", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "tags"
+)]
+pub async fn detach_tag(
+    State(state): State<AppState>,
+    Path((id, tag_id)): Path<(Uuid, Uuid)>,
+    IfMatch(expected_version): IfMatch,
+) -> Result<impl IntoResponse, ApiError> {
+    let use_case = TagTodoUseCase::new(&*state.tag_repository, &*state.todo_repository);
+    let todo = use_case.execute(id, tag_id, false, expected_version).await?;
+    Ok((etag_header(&todo), Json(todo)))
+}
@@ -1,119 +1,549 @@
-use axum::{extract::{Path, State, Query}, Json};
+use std::collections::VecDeque;
+use std::convert::Infallible;
+
+use axum::{
+    extract::State,
+    body::Bytes,
+    http::{header, HeaderMap, StatusCode},
+    response::{sse::{Event, KeepAlive, Sse}, IntoResponse, Response},
+};
+use futures_util::stream::{self, Stream};
+use tokio::sync::broadcast::error::RecvError;
 use uuid::Uuid;
 use tokio::time::Instant;
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    api::extract::{Json, Path, Query},
     state::AppState, 
+    api::preconditions::{etag_header, not_modified, IfMatch},
+    api::query::TodoQuery,
     domain::todos::{
-        Todo, CreateTodoRequest, UpdateTodoRequest, PaginationQuery, PaginatedResponse
+        Todo, CreateTodoRequest, ReplaceTodoRequest, TodoPatch, PaginationQuery, PaginatedResponse,
+        SearchTodosQuery, TodoSearchHit, BatchRequest, BatchResponse, PageQuery, TrashedTodo,
+        TodoEvent, TodoChange, TodoChangesQuery, GrantAccessRequest, TodoShare
     }, 
-    domain::todos::traits::{TodoFinder, TodoCreator},
     application::todos::{
-        CreateTodoUseCase, GetTodoUseCase, ListTodosUseCase, UpdateTodoUseCase, DeleteTodoUseCase
+        CreateTodoUseCase, GetTodoUseCase, ListTodosUseCase, SearchTodosUseCase, UpdateTodoUseCase,
+        PatchTodoUseCase, DeleteTodoUseCase, BatchTodosUseCase,
+        ListTrashUseCase, RestoreTodoUseCase, PurgeTodoUseCase, GetTodoHistoryUseCase,
+        ListTodoAccessUseCase, GrantTodoAccessUseCase, RevokeTodoAccessUseCase, TodoAuthorizer,
+        TodoDomainEvents
     },
-    error::ApiError
+    error::ApiError,
+    request_context::RequestContext
 };
 
 #[utoipa::path(
     post,
     path = "/todos",
     request_body = CreateTodoRequest,
-    responses((status = 201, body = Todo)),
+    responses(
+        (status = 201, body = Todo, headers(("ETag" = String, description = "Entity tag of the new todo"))),
+        (status = 409, description = "Conflicts with an existing todo", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Validation failed", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
     tag = "todos"
 )]
 pub async fn create_todo(
     State(state): State<AppState>,
     Json(payload): Json<CreateTodoRequest>,
-) -> Result<Json<Todo>, ApiError> {
-    let use_case = CreateTodoUseCase::new(&*state.todo_repository);
+) -> Result<impl IntoResponse, ApiError> {
+    let use_case = CreateTodoUseCase::new(&*state.todo_repository, &*state.list_repository);
     let todo = use_case.execute(payload).await?;
-    Ok(Json(todo))
+    Ok((etag_header(&todo), Json(todo)))
 }
 
+/// Lists todos matching the filters, by page number or, when `cursor` is
+/// given, by keyset cursor.
 #[utoipa::path(
     get,
     path = "/todos",
-    params(
-        ("page" = Option<u32>, Query, description = "Page number (default: 1)"),
-        ("limit" = Option<u32>, Query, description = "Items per page (default: 10, max: 100)")
+    params(PaginationQuery, ("tag" = Option<Vec<String>>, Query, description = "Only todos with this tag, by name; repeat for several", style = Form, explode)),
+    responses(
+        (status = 200, body = TodoListResponse),
+        (status = 400, description = "Invalid or tampered cursor", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid filter or sort", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
     ),
-    responses((status = 200, body = PaginatedResponse<Todo>)),
     tag = "todos"
 )]
 pub async fn list_todos(
     State(state): State<AppState>,
-    Query(pagination): Query<PaginationQuery>,
-) -> Result<Json<PaginatedResponse<Todo>>, ApiError> {
+    TodoQuery(pagination): TodoQuery,
+) -> Result<Response, ApiError> {
     let use_case = ListTodosUseCase::new(&*state.todo_repository);
+
+    if let Some(cursor) = &pagination.cursor {
+        let start = state.cursor_codec.start(cursor)?;
+        let limit = pagination.limit();
+        let page = use_case.execute_keyset(pagination, start).await?;
+        return Ok(Json(state.cursor_codec.response(start, limit, page)).into_response());
+    }
+
     let result = use_case.execute(pagination).await?;
+    Ok(Json(result).into_response())
+}
+
+/// Finds todos whose titles contain every search term, most relevant first.
+#[utoipa::path(
+    get,
+    path = "/todos/search",
+    params(SearchTodosQuery),
+    responses(
+        (status = 200, body = TodoSearchPage),
+        (status = 422, description = "Blank or overlong query", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "todos"
+)]
+pub async fn search_todos(
+    State(state): State<AppState>,
+    Query(query): Query<SearchTodosQuery>,
+) -> Result<Json<PaginatedResponse<TodoSearchHit>>, ApiError> {
+    let use_case = SearchTodosUseCase::new(&*state.todo_repository);
+    let result = use_case.execute(query).await?;
     Ok(Json(result))
 }
 
 #[utoipa::path(
     get,
     path = "/todos/{id}",
-    params(("id" = Uuid, Path, description = "Todo ID")),
-    responses((status = 200, body = Todo), (status = 404, description = "not found")),
+    params(
+        ("id" = Uuid, Path, description = "Todo ID"),
+        ("If-None-Match" = Option<String>, Header, description = "Answer 304 if the todo still has one of these ETags")
+    ),
+    responses(
+        (status = 200, body = Todo, headers(("ETag" = String))),
+        (status = 304, description = "Not modified", headers(("ETag" = String))),
+        (status = 404, description = "Todo not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
     tag = "todos"
 )]
 pub async fn get_todo(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<Todo>, ApiError> {
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
     let use_case = GetTodoUseCase::new(&*state.todo_repository);
     let todo = use_case.execute(id).await?
         .ok_or(ApiError::NotFound)?;
-    Ok(Json(todo))
+
+    if not_modified(&headers, &todo)? {
+        return Ok((StatusCode::NOT_MODIFIED, etag_header(&todo)).into_response());
+    }
+    Ok((etag_header(&todo), Json(todo)).into_response())
 }
 
+/// Replaces every writable field of the todo.
 #[utoipa::path(
     put,
     path = "/todos/{id}",
-    params(("id" = Uuid, Path, description = "Todo ID")),
-    request_body = UpdateTodoRequest,
-    responses((status = 200, body = Todo), (status = 404, description = "not found")),
+    params(
+        ("id" = Uuid, Path, description = "Todo ID"),
+        ("If-Match" = Option<String>, Header, description = "Only apply if the todo still has this ETag")
+    ),
+    request_body = ReplaceTodoRequest,
+    responses(
+        (status = 200, body = Todo, headers(("ETag" = String))),
+        (status = 403, description = "Only editors and the owner may change the todo", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Todo not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 412, description = "ETag does not match", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 428, description = "If-Match is required", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Validation failed", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
     tag = "todos"
 )]
 pub async fn update_todo(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-    Json(payload): Json<UpdateTodoRequest>,
-) -> Result<Json<Todo>, ApiError> {
-    let use_case = UpdateTodoUseCase::new(&*state.todo_repository);
-    let todo = use_case.execute(id, payload).await?;
-    Ok(Json(todo))
+    IfMatch(expected_version): IfMatch,
+    Json(payload): Json<ReplaceTodoRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let use_case = UpdateTodoUseCase::new(&*state.todo_repository, &*state.list_repository);
+    let todo = use_case.execute(id, payload.into(), expected_version).await?;
+    Ok((etag_header(&todo), Json(todo)))
+}
+
+/// Partially updates the todo with a JSON Merge Patch (RFC 7396) or, when sent
+/// as `application/json-patch+json`, a JSON Patch (RFC 6902).
+#[utoipa::path(
+    patch,
+    path = "/todos/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Todo ID"),
+        ("If-Match" = Option<String>, Header, description = "Only apply if the todo still has this ETag")
+    ),
+    request_body(content = TodoMergePatch, content_type = "application/merge-patch+json"),
+    responses(
+        (status = 200, body = Todo, headers(("ETag" = String))),
+        (status = 400, description = "Malformed patch document", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "Only editors and the owner may change the todo", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Todo not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 412, description = "ETag does not match, or the todo changed while patching", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 415, description = "Unsupported patch media type", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 428, description = "If-Match is required", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Patch could not be applied or result failed validation", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "todos"
+)]
+pub async fn patch_todo(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    IfMatch(expected_version): IfMatch,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<impl IntoResponse, ApiError> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    let patch = TodoPatch::parse(content_type, &body)?;
+
+    let use_case = PatchTodoUseCase::new(&*state.todo_repository, &*state.list_repository);
+    let todo = use_case.execute(id, patch, expected_version).await?;
+    Ok((etag_header(&todo), Json(todo)))
 }
 
+/// Moves the todo to the trash, from where it can be restored until purged.
 #[utoipa::path(
     delete,
     path = "/todos/{id}",
-    params(("id" = Uuid, Path, description = "Todo ID")),
-    responses((status = 204, description = "deleted"), (status = 404, description = "not found")),
+    params(
+        ("id" = Uuid, Path, description = "Todo ID"),
+        ("If-Match" = Option<String>, Header, description = "Only apply if the todo still has this ETag")
+    ),
+    responses(
+        (status = 204, description = "Moved to the trash"),
+        (status = 403, description = "Only the owner may delete the todo", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Todo not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 412, description = "ETag does not match", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 428, description = "If-Match is required", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
     tag = "todos"
 )]
 pub async fn delete_todo(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> Result<axum::http::StatusCode, ApiError> {
+    IfMatch(expected_version): IfMatch,
+) -> Result<StatusCode, ApiError> {
     let use_case = DeleteTodoUseCase::new(&*state.todo_repository);
+    use_case.execute(id, expected_version).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Lists deleted todos, most recently deleted first.
+#[utoipa::path(
+    get,
+    path = "/todos/trash",
+    params(PageQuery),
+    responses(
+        (status = 200, body = TrashPage),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "todos"
+)]
+pub async fn list_trash(
+    State(state): State<AppState>,
+    Query(query): Query<PageQuery>,
+) -> Result<Json<PaginatedResponse<TrashedTodo>>, ApiError> {
+    let use_case = ListTrashUseCase::new(&*state.todo_repository);
+    let result = use_case.execute(query).await?;
+    Ok(Json(result))
+}
+
+/// Takes a deleted todo out of the trash.
+#[utoipa::path(
+    post,
+    path = "/todos/{id}/restore",
+    params(("id" = Uuid, Path, description = "Todo ID")),
+    responses(
+        (status = 200, body = Todo, headers(("ETag" = String))),
+        (status = 403, description = "Only the owner may restore the todo", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Todo not in the trash", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "todos"
+)]
+pub async fn restore_todo(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let use_case = RestoreTodoUseCase::new(&*state.todo_repository);
+    let todo = use_case.execute(id).await?;
+    Ok((etag_header(&todo), Json(todo)))
+}
+
+/// Permanently deletes a todo that is already in the trash.
+#[utoipa::path(
+    delete,
+    path = "/todos/trash/{id}",
+    params(("id" = Uuid, Path, description = "Todo ID")),
+    responses(
+        (status = 204, description = "Purged"),
+        (status = 403, description = "Only the owner may purge the todo", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Todo not in the trash", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "todos"
+)]
+pub async fn purge_todo(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let use_case = PurgeTodoUseCase::new(&*state.todo_repository);
     use_case.execute(id).await?;
-    Ok(axum::http::StatusCode::NO_CONTENT)
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Lists who the todo is shared with besides its owner.
+#[utoipa::path(
+    get,
+    path = "/todos/{id}/access",
+    params(("id" = Uuid, Path, description = "Todo ID")),
+    responses(
+        (status = 200, body = Vec<TodoShare>),
+        (status = 404, description = "Todo not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "todos"
+)]
+pub async fn list_todo_access(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<TodoShare>>, ApiError> {
+    let use_case = ListTodoAccessUseCase::new(&*state.todo_repository);
+    let shares = use_case.execute(id).await?;
+    Ok(Json(shares))
+}
+
+/// Shares the todo with another account, or changes their role.
+#[utoipa::path(
+    post,
+    path = "/todos/{id}/access",
+    params(("id" = Uuid, Path, description = "Todo ID")),
+    request_body = GrantAccessRequest,
+    responses(
+        (status = 200, body = TodoShare),
+        (status = 403, description = "Only the owner may share the todo", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Todo not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Validation failed or no such account", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "todos"
+)]
+pub async fn grant_todo_access(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<GrantAccessRequest>,
+) -> Result<Json<TodoShare>, ApiError> {
+    let use_case = GrantTodoAccessUseCase::new(&*state.todo_repository, &*state.user_repository);
+    let share = use_case.execute(id, payload).await?;
+    Ok(Json(share))
+}
+
+/// Takes a user's access to the todo away. Users may also give up their own.
+#[utoipa::path(
+    delete,
+    path = "/todos/{id}/access/{user_id}",
+    params(
+        ("id" = Uuid, Path, description = "Todo ID"),
+        ("user_id" = Uuid, Path, description = "User to revoke access from")
+    ),
+    responses(
+        (status = 204, description = "Access revoked"),
+        (status = 403, description = "Only the owner may revoke others' access", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Todo not found or not shared with the user", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "todos"
+)]
+pub async fn revoke_todo_access(
+    State(state): State<AppState>,
+    Path((id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    let use_case = RevokeTodoAccessUseCase::new(&*state.todo_repository);
+    use_case.execute(id, user_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Lists everything that happened to a todo, oldest first. The history
+/// outlives the todo, so it is still available after a purge.
+#[utoipa::path(
+    get,
+    path = "/todos/{id}/history",
+    params(("id" = Uuid, Path, description = "Todo ID"), PageQuery),
+    responses(
+        (status = 200, body = TodoHistoryPage),
+        (status = 404, description = "Todo not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "todos"
+)]
+pub async fn get_todo_history(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<PageQuery>,
+) -> Result<Json<PaginatedResponse<TodoEvent>>, ApiError> {
+    let use_case = GetTodoHistoryUseCase::new(&*state.todo_repository);
+    let result = use_case.execute(id, query).await?;
+    Ok(Json(result))
 }
 
+/// Streams changes to todos as Server-Sent Events: `created`, `updated` and
+/// `deleted`, each with the todo as data and a resumable id. A `resync`
+/// event means changes were missed and the client should reload.
+#[utoipa::path(
+    get,
+    path = "/todos/events",
+    params(
+        TodoChangesQuery,
+        ("Last-Event-ID" = Option<i64>, Header, description = "Resume after this event, if it is still buffered")
+    ),
+    responses(
+        (status = 200, description = "Event stream", content_type = "text/event-stream", body = String),
+        (status = 400, description = "Invalid Last-Event-ID", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "todos"
+)]
+pub async fn todo_events(
+    State(state): State<AppState>,
+    Query(query): Query<TodoChangesQuery>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let last_event_id = match headers.get("last-event-id") {
+        Some(value) => Some(
+            value
+                .to_str()
+                .ok()
+                .and_then(|value| value.trim().parse::<i64>().ok())
+                .ok_or_else(|| ApiError::BadRequest("Last-Event-ID must be an event id from this stream".to_string()))?,
+        ),
+        None => None,
+    };
+
+    // The stream is polled outside the request's scope.
+    let context = RequestContext::current();
+    let todos = state.todo_repository.clone();
+    let subscription = state.change_feed.subscribe(last_event_id);
+    let initial = (VecDeque::from(subscription.replay), subscription.missed, subscription.receiver);
+
+    let events = stream::unfold(initial, move |(mut replay, mut missed, mut receiver)| {
+        let query = query.clone();
+        let context = context.clone();
+        let todos = todos.clone();
+        async move {
+            loop {
+                if missed {
+                    missed = false;
+                    return Some((Ok(Event::default().event("resync").data("")), (replay, missed, receiver)));
+                }
+                let change = match replay.pop_front() {
+                    Some(change) => change,
+                    None => match receiver.recv().await {
+                        Ok(change) => change,
+                        // This client fell too far behind.
+                        Err(RecvError::Lagged(_)) => {
+                            missed = true;
+                            continue;
+                        }
+                        Err(RecvError::Closed) => return None,
+                    },
+                };
+                if !query.matches(&change) {
+                    continue;
+                }
+                match context.clone().scope(TodoAuthorizer::new(&*todos).may_watch(&change)).await {
+                    Ok(true) => return Some((Ok(change_event(&change)), (replay, missed, receiver))),
+                    Ok(false) => {}
+                    Err(error) => tracing::error!(code = error.code(), error = %error, "could not check who may see a change"),
+                }
+            }
+        }
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::new().interval(state.config.changes.keep_alive())))
+}
+
+fn change_event(change: &TodoChange) -> Event {
+    let data = serde_json::to_string(&change.todo).expect("a todo serializes to JSON");
+    Event::default().id(change.seq.to_string()).event(change.kind.as_str()).data(data)
+}
+
+/// Applies several creates, updates and deletes in one request. Each
+/// operation gets the status its single-request equivalent would have.
+#[utoipa::path(
+    post,
+    path = "/todos/batch",
+    request_body = BatchRequest,
+    responses(
+        (status = 207, description = "Per-operation results", body = BatchResponse),
+        (status = 422, description = "Empty or oversized batch", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "todos"
+)]
+pub async fn batch_todos(
+    State(state): State<AppState>,
+    Json(request): Json<BatchRequest>,
+) -> Result<(StatusCode, Json<BatchResponse>), ApiError> {
+    let use_case = BatchTodosUseCase::new(&*state.todo_repository, &*state.list_repository);
+    let response = use_case.execute(request, state.config.features.require_if_match).await?;
+    Ok((StatusCode::MULTI_STATUS, Json(response)))
+}
+
+/// Shorthand for `GET /todos?done={done}`, paginated by page number.
 #[utoipa::path(
     get,
     path = "/todos/done/{done}",
-    params(("done" = bool, Path, description = "Filter by done status")),
-    responses((status = 200, body = [Todo])),
+    params(
+        ("done" = bool, Path, description = "Filter by done status"),
+        ("page" = Option<u32>, Query, description = "Page number (default: 1)"),
+        ("limit" = Option<u32>, Query, description = "Items per page (default: 10, max: 100)")
+    ),
+    responses(
+        (status = 200, body = TodoPage),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
     tag = "todos"
 )]
 pub async fn get_todos_by_done(
     State(state): State<AppState>,
     Path(done): Path<bool>,
-) -> Result<Json<Vec<Todo>>, ApiError> {
-    let todos = state.todo_repository.find_by_done(done).await?;
-    Ok(Json(todos))
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<PaginatedResponse<Todo>>, ApiError> {
+    let pagination = PaginationQuery {
+        page: pagination.page,
+        limit: pagination.limit,
+        done: Some(done),
+        ..Default::default()
+    };
+
+    let use_case = ListTodosUseCase::new(&*state.todo_repository);
+    let result = use_case.execute(pagination).await?;
+    Ok(Json(result))
 }
 
 #[derive(Deserialize)]
@@ -158,10 +588,11 @@ pub async fn performance_test(
             let create_request = CreateTodoRequest {
                 title: format!("Performance Test Todo #{}", i),
                 done: Some(i % 2 == 0),
+                ..Default::default()
             };
             
             // Create todo directly using the repository
-            match state.todo_repository.create(create_request).await {
+            match state.todo_repository.create(create_request, TodoDomainEvents::created).await {
                 Ok(_) => success_count += 1,
                 Err(_) => error_count += 1,
             }
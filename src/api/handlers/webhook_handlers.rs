@@ -0,0 +1,156 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    api::extract::{Json, Path, Query},
+    state::AppState,
+    domain::todos::{PageQuery, PaginatedResponse},
+    domain::webhooks::{CreateWebhookRequest, UpdateWebhookRequest, Webhook, WebhookDelivery},
+    application::webhooks::{
+        CreateWebhookUseCase, GetWebhookUseCase, ListWebhooksUseCase, UpdateWebhookUseCase, DeleteWebhookUseCase,
+        ListDeliveriesUseCase
+    },
+    error::ApiError
+};
+
+/// Subscribes `url` to domain events. Deliveries are signed with `secret`,
+/// which is never returned.
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 201, body = Webhook),
+        (status = 422, description = "Validation failed", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "webhooks"
+)]
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateWebhookRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let use_case = CreateWebhookUseCase::new(&*state.webhook_repository);
+    let webhook = use_case.execute(payload, state.config.webhooks.target_policy()).await?;
+    Ok((StatusCode::CREATED, Json(webhook)))
+}
+
+/// Lists webhooks, oldest first.
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    params(PageQuery),
+    responses(
+        (status = 200, body = WebhookPage),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "webhooks"
+)]
+pub async fn list_webhooks(
+    State(state): State<AppState>,
+    Query(query): Query<PageQuery>,
+) -> Result<Json<PaginatedResponse<Webhook>>, ApiError> {
+    let use_case = ListWebhooksUseCase::new(&*state.webhook_repository);
+    let result = use_case.execute(query).await?;
+    Ok(Json(result))
+}
+
+#[utoipa::path(
+    get,
+    path = "/webhooks/{id}",
+    params(("id" = Uuid, Path, description = "Webhook ID")),
+    responses(
+        (status = 200, body = Webhook),
+        (status = 404, description = "Webhook not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "webhooks"
+)]
+pub async fn get_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Webhook>, ApiError> {
+    let use_case = GetWebhookUseCase::new(&*state.webhook_repository);
+    let webhook = use_case.execute(id).await?
+        .ok_or(ApiError::NotFound)?;
+    Ok(Json(webhook))
+}
+
+/// Changes the given fields. Setting `active` to `true` re-enables a
+/// webhook that was disabled after repeated failures.
+#[utoipa::path(
+    patch,
+    path = "/webhooks/{id}",
+    params(("id" = Uuid, Path, description = "Webhook ID")),
+    request_body = UpdateWebhookRequest,
+    responses(
+        (status = 200, body = Webhook),
+        (status = 404, description = "Webhook not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Validation failed", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "webhooks"
+)]
+pub async fn update_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateWebhookRequest>,
+) -> Result<Json<Webhook>, ApiError> {
+    let use_case = UpdateWebhookUseCase::new(&*state.webhook_repository);
+    let webhook = use_case.execute(id, payload, state.config.webhooks.target_policy()).await?;
+    Ok(Json(webhook))
+}
+
+/// Removes the webhook and its delivery log; pending deliveries are dropped.
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{id}",
+    params(("id" = Uuid, Path, description = "Webhook ID")),
+    responses(
+        (status = 204, description = "Webhook deleted"),
+        (status = 404, description = "Webhook not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "webhooks"
+)]
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let use_case = DeleteWebhookUseCase::new(&*state.webhook_repository);
+    use_case.execute(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Lists the webhook's deliveries, newest first, with the outcome of the
+/// last attempt of each.
+#[utoipa::path(
+    get,
+    path = "/webhooks/{id}/deliveries",
+    params(("id" = Uuid, Path, description = "Webhook ID"), PageQuery),
+    responses(
+        (status = 200, body = WebhookDeliveryPage),
+        (status = 404, description = "Webhook not found", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Database unavailable", body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = ProblemDetails, content_type = "application/problem+json")
+    ),
+    tag = "webhooks"
+)]
+pub async fn list_deliveries(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<PageQuery>,
+) -> Result<Json<PaginatedResponse<WebhookDelivery>>, ApiError> {
+    let use_case = ListDeliveriesUseCase::new(&*state.webhook_repository);
+    let result = use_case.execute(id, query).await?;
+    Ok(Json(result))
+}
@@ -0,0 +1,232 @@
+use axum::{
+    extract::{
+        ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::Response,
+};
+use chrono::Utc;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::{interval_at, sleep_until, timeout, Instant};
+
+use crate::{
+    state::AppState,
+    config::WebSocketConfig,
+    api::{auth::AuthUser, ws_protocol::{ClientCommand, ClientMessage, ServerMessage, Subscriptions}},
+    domain::{api_keys::ApiKeyScope, todos::{TodoChange, TodoSubscription}},
+    application::todos::{CreateTodoUseCase, DeleteTodoUseCase, TodoAuthorizer, UpdateTodoUseCase},
+    error::ApiError,
+    request_context::RequestContext,
+};
+
+/// Opens a WebSocket for collaborative editing: clients subscribe to todos
+/// and receive their changes, and create, update or delete todos over the
+/// same socket. See the README for the message protocol.
+#[utoipa::path(
+    get,
+    path = "/ws",
+    responses(
+        (status = 101, description = "Switching to the WebSocket protocol"),
+        (status = 400, description = "Not a WebSocket upgrade request")
+    ),
+    tag = "todos"
+)]
+pub async fn todo_socket(State(state): State<AppState>, user: AuthUser, upgrade: WebSocketUpgrade) -> Response {
+    // The socket outlives the request, so its context has to be carried over.
+    let context = RequestContext::current();
+    upgrade
+        .max_message_size(state.config.websocket.max_message_bytes)
+        .on_upgrade(move |socket| Connection::new(state, context, user).run(socket))
+}
+
+struct Connection {
+    state: AppState,
+    context: RequestContext,
+    user: AuthUser,
+    subscriptions: Subscriptions,
+}
+
+impl Connection {
+    fn new(state: AppState, context: RequestContext, user: AuthUser) -> Self {
+        let subscriptions = Subscriptions::new(state.config.websocket.max_subscriptions);
+        Self { state, context, user, subscriptions }
+    }
+
+    async fn run(mut self, mut socket: WebSocket) {
+        let config = self.state.config.websocket.clone();
+        let mut changes = self.state.change_feed.subscribe(None).receiver;
+        let mut pings = interval_at(Instant::now() + config.ping_interval(), config.ping_interval());
+        let mut last_heard = Instant::now();
+        // The connection lasts no longer than the credentials it was opened with.
+        let expiry = self.user.expires_at.map(|at| Instant::now() + (at - Utc::now()).to_std().unwrap_or_default());
+
+        loop {
+            let outgoing = tokio::select! {
+                incoming = socket.recv() => match incoming {
+                    Some(Ok(message)) => {
+                        last_heard = Instant::now();
+                        match message {
+                            Message::Text(text) => match self.handle(&text).await {
+                                Ok(reply) => Some(reply),
+                                Err(CredentialsRevoked) => {
+                                    close(&mut socket, &config, "API key revoked").await;
+                                    break;
+                                }
+                            },
+                            Message::Binary(_) => Some(ServerMessage::error(
+                                None,
+                                &ApiError::BadRequest("Messages must be JSON text.".to_string()),
+                            )),
+                            // Pings are answered by axum; pongs only prove the client is alive.
+                            Message::Ping(_) | Message::Pong(_) => None,
+                            Message::Close(_) => break,
+                        }
+                        .map(|message| text(&message))
+                    }
+                    _ => break,
+                },
+                change = changes.recv() => match change {
+                    Ok(change) => {
+                        let subscriptions = self.subscriptions.matching(&change);
+                        if !subscriptions.is_empty() && self.may_watch(&change).await {
+                            Some(text(&ServerMessage::Change { subscriptions, change }))
+                        } else {
+                            None
+                        }
+                    }
+                    // A slow client: tell it to reload instead of queueing without bound.
+                    Err(RecvError::Lagged(_)) => Some(text(&ServerMessage::Resync)),
+                    Err(RecvError::Closed) => break,
+                },
+                _ = pings.tick() => {
+                    if last_heard.elapsed() >= config.idle_timeout() {
+                        close(&mut socket, &config, "idle timeout").await;
+                        break;
+                    }
+                    if !self.credentials_usable().await {
+                        close(&mut socket, &config, "API key revoked").await;
+                        break;
+                    }
+                    Some(Message::Ping(Vec::new()))
+                }
+                _ = sleep_until(expiry.unwrap_or_else(Instant::now)), if expiry.is_some() => {
+                    close(&mut socket, &config, "credentials expired").await;
+                    break;
+                }
+            };
+
+            if let Some(message) = outgoing {
+                match timeout(config.send_timeout(), socket.send(message)).await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(_)) => break,
+                    Err(_) => {
+                        tracing::info!("disconnecting a WebSocket client that stopped reading");
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Checked per change rather than per subscription, since shares are
+    /// granted and revoked while the socket is open.
+    async fn may_watch(&self, change: &TodoChange) -> bool {
+        let authorizer = TodoAuthorizer::new(&*self.state.todo_repository);
+        match self.context.clone().scope(authorizer.may_watch(change)).await {
+            Ok(visible) => visible,
+            Err(error) => {
+                tracing::error!(code = error.code(), error = %error, "could not check who may see a change");
+                false
+            }
+        }
+    }
+
+    /// Access tokens cannot be revoked and their expiry is timed by `run`,
+    /// so only API keys are looked up again. A key that cannot be checked
+    /// counts as revoked.
+    async fn credentials_usable(&self) -> bool {
+        let Some(key_id) = self.user.api_key_id else { return true };
+        let lookup = self.state.api_key_repository.find_by_id(self.user.id, key_id);
+        match self.context.clone().scope(lookup).await {
+            Ok(key) => key.is_some_and(|key| key.is_usable(Utc::now())),
+            Err(error) => {
+                tracing::error!(code = error.code(), error = %error, "could not check a WebSocket client's API key");
+                false
+            }
+        }
+    }
+
+    /// The reply to a client message. Writes check the API key again first,
+    /// since it may have been revoked since the handshake.
+    async fn handle(&mut self, text: &str) -> Result<ServerMessage, CredentialsRevoked> {
+        let message: ClientMessage = match serde_json::from_str(text) {
+            Ok(message) => message,
+            Err(error) => {
+                return Ok(ServerMessage::error(None, &ApiError::BadRequest(format!("Invalid message: {error}"))));
+            }
+        };
+        if message.command.writes() && !self.credentials_usable().await {
+            return Err(CredentialsRevoked);
+        }
+
+        let reference = message.reference;
+        let result = self.context.clone().scope(self.execute(message.command, reference.clone())).await;
+        Ok(result.unwrap_or_else(|error| {
+            if error.status().is_server_error() {
+                tracing::error!(code = error.code(), error = %error, "WebSocket command failed");
+            }
+            ServerMessage::error(reference, &error)
+        }))
+    }
+
+    async fn execute(&mut self, command: ClientCommand, reference: Option<String>) -> Result<ServerMessage, ApiError> {
+        let repository = &*self.state.todo_repository;
+        let require_version = self.state.config.features.require_if_match;
+        if command.writes() {
+            self.user.require(ApiKeyScope::ReadWrite)?;
+        }
+
+        match command {
+            ClientCommand::Subscribe { subscription, todo_ids, done } => {
+                self.subscriptions.insert(subscription, TodoSubscription { todo_ids, done })?;
+                Ok(ServerMessage::Ack { reference })
+            }
+            ClientCommand::Unsubscribe { subscription } => {
+                self.subscriptions.remove(&subscription)?;
+                Ok(ServerMessage::Ack { reference })
+            }
+            ClientCommand::Create { todo } => {
+                let todo = CreateTodoUseCase::new(repository, &*self.state.list_repository).execute(todo).await?;
+                Ok(ServerMessage::Result { reference, todo })
+            }
+            ClientCommand::Update { id, changes, version } => {
+                if require_version && version.is_none() {
+                    return Err(ApiError::PreconditionRequired);
+                }
+                let todo = UpdateTodoUseCase::new(repository, &*self.state.list_repository).execute(id, changes, version).await?;
+                Ok(ServerMessage::Result { reference, todo })
+            }
+            ClientCommand::Delete { id, version } => {
+                if require_version && version.is_none() {
+                    return Err(ApiError::PreconditionRequired);
+                }
+                DeleteTodoUseCase::new(repository).execute(id, version).await?;
+                Ok(ServerMessage::Ack { reference })
+            }
+            ClientCommand::Ping => Ok(ServerMessage::Pong { reference }),
+        }
+    }
+}
+
+/// The API key a connection was opened with is no longer usable.
+struct CredentialsRevoked;
+
+/// Closes the socket as a policy violation, giving the client `reason`.
+async fn close(socket: &mut WebSocket, config: &WebSocketConfig, reason: &str) {
+    let frame = CloseFrame { code: close_code::POLICY, reason: reason.to_string().into() };
+    let _ = timeout(config.send_timeout(), socket.send(Message::Close(Some(frame)))).await;
+}
+
+fn text(message: &ServerMessage) -> Message {
+    Message::Text(serde_json::to_string(message).expect("server messages serialize to JSON"))
+}
@@ -1,2 +1,8 @@
+pub mod auth;
+pub mod cursor;
+pub mod extract;
 pub mod handlers;
-
+pub mod preconditions;
+pub mod query;
+pub mod tenant;
+pub mod ws_protocol;
@@ -0,0 +1,94 @@
+//! Conditional requests (RFC 9110 §13) for todo resources. A todo's entity
+//! tag is its `version` as a strong ETag, e.g. `"3"`.
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderMap, HeaderName, HeaderValue},
+};
+
+use crate::{domain::todos::Todo, error::ApiError, state::AppState};
+
+pub fn etag(todo: &Todo) -> HeaderValue {
+    HeaderValue::from_str(&format!("\"{}\"", todo.version))
+        .expect("a quoted integer is a valid header value")
+}
+
+/// `ETag` response header for `todo`, usable as an `IntoResponseParts`.
+pub fn etag_header(todo: &Todo) -> [(HeaderName, HeaderValue); 1] {
+    [(header::ETAG, etag(todo))]
+}
+
+/// The version a mutation is conditional on, taken from `If-Match`.
+///
+/// `None` means unconditional: the header was absent (allowed unless
+/// `features.require_if_match` is set, which yields 428) or was `*`. Weak or
+/// foreign tags can never match strongly and fail with 412.
+pub struct IfMatch(pub Option<i64>);
+
+#[async_trait]
+impl FromRequestParts<AppState> for IfMatch {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let Some(value) = header_str(&parts.headers, header::IF_MATCH)? else {
+            if state.config.features.require_if_match {
+                return Err(ApiError::PreconditionRequired);
+            }
+            return Ok(IfMatch(None));
+        };
+
+        if value.trim() == "*" {
+            return Ok(IfMatch(None));
+        }
+
+        let versions: Vec<i64> = entity_tags(value)
+            .filter(|tag| !tag.starts_with("W/"))
+            .filter_map(parse_version)
+            .collect();
+
+        match versions.as_slice() {
+            [] => Err(ApiError::PreconditionFailed),
+            [version] => Ok(IfMatch(Some(*version))),
+            _ => Err(ApiError::BadRequest(
+                "If-Match with more than one entity tag is not supported".to_string(),
+            )),
+        }
+    }
+}
+
+/// Whether a read should answer 304 Not Modified: `If-None-Match` is `*` or
+/// lists the todo's tag under weak comparison.
+pub fn not_modified(headers: &HeaderMap, todo: &Todo) -> Result<bool, ApiError> {
+    let Some(value) = header_str(headers, header::IF_NONE_MATCH)? else {
+        return Ok(false);
+    };
+
+    if value.trim() == "*" {
+        return Ok(true);
+    }
+
+    Ok(entity_tags(value)
+        .map(|tag| tag.trim_start_matches("W/"))
+        .filter_map(parse_version)
+        .any(|version| version == todo.version))
+}
+
+fn header_str(headers: &HeaderMap, name: HeaderName) -> Result<Option<&str>, ApiError> {
+    headers
+        .get(&name)
+        .map(|value| {
+            value
+                .to_str()
+                .map_err(|_| ApiError::BadRequest(format!("{name} is not valid ASCII")))
+        })
+        .transpose()
+}
+
+fn entity_tags(value: &str) -> impl Iterator<Item = &str> {
+    value.split(',').map(str::trim).filter(|tag| !tag.is_empty())
+}
+
+fn parse_version(tag: &str) -> Option<i64> {
+    tag.strip_prefix('"')?.strip_suffix('"')?.parse().ok()
+}
@@ -0,0 +1,24 @@
+//! Query strings with repeated parameters, which `Query` cannot collect
+//! into a list.
+
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+
+use crate::{api::extract::Query, domain::todos::PaginationQuery, error::ApiError};
+
+/// The query of a todo listing: a `PaginationQuery` together with every
+/// `tag` parameter, as in `?tag=work&tag=urgent`.
+pub struct TodoQuery(pub PaginationQuery);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for TodoQuery {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(mut query) = Query::<PaginationQuery>::from_request_parts(parts, state).await?;
+        query.tag = url::form_urlencoded::parse(parts.uri.query().unwrap_or_default().as_bytes())
+            .filter(|(name, _)| name == "tag")
+            .map(|(_, value)| value.into_owned())
+            .collect();
+        Ok(TodoQuery(query))
+    }
+}
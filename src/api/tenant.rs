@@ -0,0 +1,98 @@
+//! Which organisation a request acts in. With `tenancy.enabled` a request
+//! names its tenant with an `X-Tenant-Id` header or, when
+//! `tenancy.base_domain` is set, the subdomain it was sent to; access tokens
+//! remember the tenant they were issued in. Otherwise every request belongs
+//! to the default tenant.
+
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderName},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    config::TenancyConfig,
+    error::ApiError,
+    request_context::{RequestContext, DEFAULT_TENANT},
+    state::AppState,
+};
+
+pub static TENANT_HEADER: HeaderName = HeaderName::from_static("x-tenant-id");
+
+/// Longest tenant id; tenant ids double as DNS labels.
+const MAX_TENANT_ID_LENGTH: usize = 63;
+
+/// Whether `value` is a valid tenant id: a lowercase DNS label such as
+/// `acme` or `acme-eu`.
+pub fn is_tenant_id(value: &str) -> bool {
+    (1..=MAX_TENANT_ID_LENGTH).contains(&value.len())
+        && !value.starts_with('-')
+        && !value.ends_with('-')
+        && value.bytes().all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit() || byte == b'-')
+}
+
+/// The tenant `request` names, from its header first and its host second.
+fn named_tenant(request: &Request, config: &TenancyConfig) -> Result<Option<String>, ApiError> {
+    if let Some(value) = request.headers().get(&TENANT_HEADER) {
+        let tenant = value.to_str().unwrap_or_default();
+        if !is_tenant_id(tenant) {
+            return Err(ApiError::BadRequest(
+                "X-Tenant-Id must be a lowercase tenant id such as `acme`.".to_string(),
+            ));
+        }
+        return Ok(Some(tenant.to_string()));
+    }
+
+    let Some(base_domain) = &config.base_domain else { return Ok(None) };
+    let host = request
+        .uri()
+        .host()
+        .or_else(|| request.headers().get(header::HOST).and_then(|value| value.to_str().ok()))
+        .unwrap_or_default();
+    let host = host.split(':').next().unwrap_or_default().to_ascii_lowercase();
+    Ok(host
+        .strip_suffix(base_domain.as_str())
+        .and_then(|subdomain| subdomain.strip_suffix('.'))
+        .filter(|subdomain| is_tenant_id(subdomain))
+        .map(str::to_string))
+}
+
+/// Middleware that makes the tenant the request names current. Requests
+/// naming none are left without one here; `require_user` takes it from the
+/// access token, and signing in or using an API key turns them away.
+pub async fn resolve_tenant(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let tenancy = &state.config.tenancy;
+    let tenant = if tenancy.enabled {
+        match named_tenant(&request, tenancy) {
+            Ok(tenant) => tenant,
+            Err(error) => return error.into_response(),
+        }
+    } else {
+        Some(DEFAULT_TENANT.to_string())
+    };
+
+    match tenant {
+        Some(tenant) => RequestContext::for_tenant(&tenant).scope(next.run(request)).await,
+        None => next.run(request).await,
+    }
+}
+
+/// The tenant the request names. Signing in and authenticating with an API
+/// key both need one, since tokens and keys only work in a single tenant.
+pub fn named_tenant_required() -> Result<String, ApiError> {
+    RequestContext::current_tenant_id().ok_or_else(|| {
+        ApiError::BadRequest("Name a tenant with the X-Tenant-Id header or the subdomain of the request.".to_string())
+    })
+}
+
+/// The tenant an authenticated request acts in: the one its credentials
+/// were issued in, which the request may repeat but not change.
+pub fn tenant_for(issued: &str, named: Option<&str>) -> Result<String, ApiError> {
+    match named {
+        Some(named) if named != issued => {
+            Err(ApiError::Forbidden(format!("The access token was issued for the `{issued}` tenant.")))
+        }
+        _ => Ok(issued.to_string()),
+    }
+}
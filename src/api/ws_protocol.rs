@@ -0,0 +1,140 @@
+//! JSON messages of the `/ws` endpoint. Every message is an object with a
+//! `type`; client messages may carry a `ref` that the reply echoes, e.g.
+//! `{"type": "create", "ref": "7", "todo": {"title": "Buy milk"}}` answered by
+//! `{"type": "result", "ref": "7", "todo": {...}}`.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    domain::todos::{CreateTodoRequest, Todo, TodoChange, TodoSubscription, UpdateTodoRequest},
+    error::{ApiError, ProblemDetails},
+};
+
+/// Longest subscription name a client may choose.
+const MAX_SUBSCRIPTION_NAME_LENGTH: usize = 64;
+
+#[derive(Deserialize)]
+pub struct ClientMessage {
+    /// Correlates the reply with this message
+    #[serde(rename = "ref", default)]
+    pub reference: Option<String>,
+    #[serde(flatten)]
+    pub command: ClientCommand,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientCommand {
+    /// Starts, or replaces, the subscription with this name.
+    Subscribe {
+        subscription: String,
+        #[serde(default)]
+        todo_ids: Option<Vec<Uuid>>,
+        #[serde(default)]
+        done: Option<bool>,
+    },
+    Unsubscribe { subscription: String },
+    Create { todo: CreateTodoRequest },
+    /// Sets the given fields; `version` makes it conditional like `If-Match`.
+    Update {
+        id: Uuid,
+        changes: UpdateTodoRequest,
+        #[serde(default)]
+        version: Option<i64>,
+    },
+    Delete {
+        id: Uuid,
+        #[serde(default)]
+        version: Option<i64>,
+    },
+    Ping,
+}
+
+impl ClientCommand {
+    /// Whether the command changes todos, which takes a `read_write` key.
+    pub fn writes(&self) -> bool {
+        matches!(self, ClientCommand::Create { .. } | ClientCommand::Update { .. } | ClientCommand::Delete { .. })
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// A subscribe, unsubscribe or delete succeeded.
+    Ack {
+        #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+        reference: Option<String>,
+    },
+    /// A create or update succeeded.
+    Result {
+        #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+        reference: Option<String>,
+        todo: Todo,
+    },
+    /// A message was malformed or its command failed.
+    Error {
+        #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+        reference: Option<String>,
+        error: ProblemDetails,
+    },
+    Pong {
+        #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+        reference: Option<String>,
+    },
+    /// A todo changed; `subscriptions` names every subscription it matches.
+    Change {
+        subscriptions: Vec<String>,
+        #[serde(flatten)]
+        change: TodoChange,
+    },
+    /// Changes were dropped because the client fell behind; it should reload
+    /// what it shows.
+    Resync,
+}
+
+impl ServerMessage {
+    pub fn error(reference: Option<String>, error: &ApiError) -> Self {
+        ServerMessage::Error { reference, error: error.to_problem() }
+    }
+}
+
+/// The named subscriptions of one connection.
+pub struct Subscriptions {
+    by_name: BTreeMap<String, TodoSubscription>,
+    max: usize,
+}
+
+impl Subscriptions {
+    pub fn new(max: usize) -> Self {
+        Self { by_name: BTreeMap::new(), max }
+    }
+
+    pub fn insert(&mut self, name: String, subscription: TodoSubscription) -> Result<(), ApiError> {
+        if name.is_empty() || name.len() > MAX_SUBSCRIPTION_NAME_LENGTH {
+            return Err(ApiError::BadRequest(format!(
+                "Subscription names must be 1 to {MAX_SUBSCRIPTION_NAME_LENGTH} characters long."
+            )));
+        }
+        if self.by_name.len() >= self.max && !self.by_name.contains_key(&name) {
+            return Err(ApiError::BadRequest(format!("A connection may hold at most {} subscriptions.", self.max)));
+        }
+        self.by_name.insert(name, subscription);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<(), ApiError> {
+        self.by_name.remove(name).map(|_| ()).ok_or(ApiError::NotFound)
+    }
+
+    /// Names of the subscriptions `change` matches, in name order.
+    pub fn matching(&self, change: &TodoChange) -> Vec<String> {
+        self.by_name
+            .iter()
+            .filter(|(_, subscription)| subscription.matches(change))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
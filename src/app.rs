@@ -1,23 +1,86 @@
 use axum::{
-    routing::{get, post},
+    middleware,
+    routing::{delete, get, patch, post, put},
     Router,
 };
 use utoipa_swagger_ui::SwaggerUi;
 use utoipa::OpenApi;
 
-use crate::{api::handlers, doc::ApiDoc, state::AppState};
+use crate::{
+    api::{auth::require_user, handlers, tenant::resolve_tenant}, config::Config, doc::ApiDoc, error::problem_instance, request_context::request_context,
+    state::AppState,
+};
 use crate::api::handlers::todo_handlers;
 
-pub fn build_app(state: AppState) -> Router {
-    Router::new()
-        .route("/health", get(handlers::health))
+pub fn build_app(config: &Config, state: AppState) -> Router {
+    // Everything but health, docs and the sign-in flow needs a user.
+    let mut protected = Router::new()
+        .route("/auth/me", get(handlers::me))
+        .route("/api-keys", post(handlers::create_api_key).get(handlers::list_api_keys))
+        .route("/api-keys/:id", get(handlers::get_api_key).delete(handlers::revoke_api_key))
         .route("/todos", post(handlers::create_todo).get(handlers::list_todos))
-        .route("/todos/:id", get(handlers::get_todo).put(handlers::update_todo).delete(handlers::delete_todo))
+        .route("/todos/search", get(handlers::search_todos))
+        .route("/todos/events", get(handlers::todo_events))
+        .route("/todos/batch", post(handlers::batch_todos))
+        .route("/todos/trash", get(handlers::list_trash))
+        .route("/todos/trash/:id", delete(handlers::purge_todo))
+        .route(
+            "/todos/:id",
+            get(handlers::get_todo)
+                .put(handlers::update_todo)
+                .patch(handlers::patch_todo)
+                .delete(handlers::delete_todo),
+        )
+        .route("/todos/:id/restore", post(handlers::restore_todo))
+        .route("/todos/:id/history", get(handlers::get_todo_history))
+        .route("/todos/:id/access", get(handlers::list_todo_access).post(handlers::grant_todo_access))
+        .route("/todos/:id/access/:user_id", delete(handlers::revoke_todo_access))
+        .route("/todos/:id/tags/:tag_id", put(handlers::attach_tag).delete(handlers::detach_tag))
         .route("/todos/done/:done", get(handlers::get_todos_by_done))
-        .route("/todos/performance-test", post(todo_handlers::performance_test))
-        .merge(
+        .route("/lists", post(handlers::create_list).get(handlers::list_lists))
+        .route(
+            "/lists/:id",
+            get(handlers::get_list)
+                .patch(handlers::update_list)
+                .delete(handlers::delete_list),
+        )
+        .route("/lists/:id/archive", post(handlers::archive_list))
+        .route("/lists/:id/unarchive", post(handlers::unarchive_list))
+        .route("/lists/:id/todos", get(handlers::list_list_todos))
+        .route("/tags", post(handlers::create_tag).get(handlers::list_tags))
+        .route("/tags/:id", patch(handlers::rename_tag).delete(handlers::delete_tag))
+        .route("/webhooks", post(handlers::create_webhook).get(handlers::list_webhooks))
+        .route(
+            "/webhooks/:id",
+            get(handlers::get_webhook)
+                .patch(handlers::update_webhook)
+                .delete(handlers::delete_webhook),
+        )
+        .route("/webhooks/:id/deliveries", get(handlers::list_deliveries))
+        .route("/ws", get(handlers::todo_socket));
+
+    if config.features.performance_test {
+        protected = protected.route("/todos/performance-test", post(todo_handlers::performance_test));
+    }
+
+    let mut router = Router::new()
+        .route("/health", get(handlers::health))
+        .route("/auth/register", post(handlers::register))
+        .route("/auth/login", post(handlers::login))
+        .route("/auth/refresh", post(handlers::refresh))
+        .route("/auth/logout", post(handlers::logout))
+        .merge(protected.route_layer(middleware::from_fn_with_state(state.clone(), require_user)));
+
+    if config.features.swagger_ui {
+        router = router.merge(
             SwaggerUi::new("/docs")
                 .url("/api-docs/openapi.json", ApiDoc::openapi()),
-        )
+        );
+    }
+
+    router
+        .layer(middleware::from_fn_with_state(state.clone(), resolve_tenant))
+        .layer(middleware::from_fn(problem_instance))
+        .layer(middleware::from_fn(request_context))
         .with_state(state)
 }
\ No newline at end of file
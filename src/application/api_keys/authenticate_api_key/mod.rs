@@ -0,0 +1,25 @@
+use chrono::Utc;
+
+use crate::domain::api_keys::{hash_api_key, ApiKey, API_KEY_PREFIX};
+use crate::domain::api_keys::traits::ApiKeyRepository;
+use crate::error::ApiError;
+
+pub struct AuthenticateApiKeyUseCase<'a, T: ApiKeyRepository + ?Sized> {
+    api_key_repository: &'a T,
+}
+
+impl<'a, T: ApiKeyRepository + ?Sized> AuthenticateApiKeyUseCase<'a, T> {
+    pub fn new(api_key_repository: &'a T) -> Self {
+        Self { api_key_repository }
+    }
+
+    /// The key's record, if it is known, unexpired and not revoked.
+    pub async fn execute(&self, key: &str) -> Result<ApiKey, ApiError> {
+        let invalid = || ApiError::Unauthorized("The API key is invalid, expired or revoked.".to_string());
+        if !key.starts_with(API_KEY_PREFIX) {
+            return Err(invalid());
+        }
+
+        self.api_key_repository.authenticate(&hash_api_key(key), Utc::now()).await?.ok_or_else(invalid)
+    }
+}
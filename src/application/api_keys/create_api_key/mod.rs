@@ -0,0 +1,35 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::api_keys::{ApiKeySecret, CreateApiKeyRequest, CreatedApiKey, NewApiKey};
+use crate::domain::api_keys::traits::ApiKeyRepository;
+use crate::error::ApiError;
+
+pub struct CreateApiKeyUseCase<'a, T: ApiKeyRepository + ?Sized> {
+    api_key_repository: &'a T,
+}
+
+impl<'a, T: ApiKeyRepository + ?Sized> CreateApiKeyUseCase<'a, T> {
+    pub fn new(api_key_repository: &'a T) -> Self {
+        Self { api_key_repository }
+    }
+
+    pub async fn execute(&self, user_id: Uuid, request: CreateApiKeyRequest) -> Result<CreatedApiKey, ApiError> {
+        request.validate(Utc::now())?;
+
+        let secret = ApiKeySecret::generate();
+        let api_key = self
+            .api_key_repository
+            .create(NewApiKey {
+                user_id,
+                name: request.name.trim().to_string(),
+                scope: request.scope,
+                expires_at: request.expires_at,
+                prefix: secret.prefix,
+                key_hash: secret.hash,
+            })
+            .await?;
+
+        Ok(CreatedApiKey { key: secret.key, api_key })
+    }
+}
@@ -0,0 +1,19 @@
+use uuid::Uuid;
+
+use crate::domain::api_keys::ApiKey;
+use crate::domain::api_keys::traits::ApiKeyRepository;
+use crate::error::ApiError;
+
+pub struct GetApiKeyUseCase<'a, T: ApiKeyRepository + ?Sized> {
+    api_key_repository: &'a T,
+}
+
+impl<'a, T: ApiKeyRepository + ?Sized> GetApiKeyUseCase<'a, T> {
+    pub fn new(api_key_repository: &'a T) -> Self {
+        Self { api_key_repository }
+    }
+
+    pub async fn execute(&self, user_id: Uuid, id: Uuid) -> Result<ApiKey, ApiError> {
+        self.api_key_repository.find_by_id(user_id, id).await?.ok_or(ApiError::NotFound)
+    }
+}
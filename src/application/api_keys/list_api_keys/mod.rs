@@ -0,0 +1,20 @@
+use uuid::Uuid;
+
+use crate::domain::api_keys::ApiKey;
+use crate::domain::api_keys::traits::ApiKeyRepository;
+use crate::domain::todos::{PageQuery, PaginatedResponse};
+use crate::error::ApiError;
+
+pub struct ListApiKeysUseCase<'a, T: ApiKeyRepository + ?Sized> {
+    api_key_repository: &'a T,
+}
+
+impl<'a, T: ApiKeyRepository + ?Sized> ListApiKeysUseCase<'a, T> {
+    pub fn new(api_key_repository: &'a T) -> Self {
+        Self { api_key_repository }
+    }
+
+    pub async fn execute(&self, user_id: Uuid, query: PageQuery) -> Result<PaginatedResponse<ApiKey>, ApiError> {
+        self.api_key_repository.find_all_paginated(user_id, query.pagination()).await
+    }
+}
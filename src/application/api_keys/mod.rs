@@ -0,0 +1,11 @@
+pub mod create_api_key;
+pub mod list_api_keys;
+pub mod get_api_key;
+pub mod revoke_api_key;
+pub mod authenticate_api_key;
+
+pub use create_api_key::*;
+pub use list_api_keys::*;
+pub use get_api_key::*;
+pub use revoke_api_key::*;
+pub use authenticate_api_key::*;
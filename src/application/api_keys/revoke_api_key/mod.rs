@@ -0,0 +1,20 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::api_keys::ApiKey;
+use crate::domain::api_keys::traits::ApiKeyRepository;
+use crate::error::ApiError;
+
+pub struct RevokeApiKeyUseCase<'a, T: ApiKeyRepository + ?Sized> {
+    api_key_repository: &'a T,
+}
+
+impl<'a, T: ApiKeyRepository + ?Sized> RevokeApiKeyUseCase<'a, T> {
+    pub fn new(api_key_repository: &'a T) -> Self {
+        Self { api_key_repository }
+    }
+
+    pub async fn execute(&self, user_id: Uuid, id: Uuid) -> Result<ApiKey, ApiError> {
+        self.api_key_repository.revoke(user_id, id, Utc::now()).await
+    }
+}
@@ -0,0 +1,23 @@
+use uuid::Uuid;
+
+use crate::domain::lists::TodoList;
+use crate::domain::lists::traits::TodoListArchiver;
+use crate::error::ApiError;
+
+/// Archives a list together with its todos, or restores both. The todos
+/// are untouched: archiving takes them out of `GET /todos`, which only
+/// shows them again when asked for their list, and closes the list to new
+/// todos.
+pub struct ArchiveTodoListUseCase<'a, L: TodoListArchiver + ?Sized> {
+    list_repository: &'a L,
+}
+
+impl<'a, L: TodoListArchiver + ?Sized> ArchiveTodoListUseCase<'a, L> {
+    pub fn new(list_repository: &'a L) -> Self {
+        Self { list_repository }
+    }
+
+    pub async fn execute(&self, id: Uuid, archived: bool) -> Result<TodoList, ApiError> {
+        self.list_repository.set_archived(id, archived).await
+    }
+}
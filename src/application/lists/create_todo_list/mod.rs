@@ -0,0 +1,18 @@
+use crate::domain::lists::{CreateTodoListRequest, TodoList};
+use crate::domain::lists::traits::TodoListStore;
+use crate::error::ApiError;
+
+pub struct CreateTodoListUseCase<'a, L: TodoListStore + ?Sized> {
+    list_repository: &'a L,
+}
+
+impl<'a, L: TodoListStore + ?Sized> CreateTodoListUseCase<'a, L> {
+    pub fn new(list_repository: &'a L) -> Self {
+        Self { list_repository }
+    }
+
+    pub async fn execute(&self, request: CreateTodoListRequest) -> Result<TodoList, ApiError> {
+        request.validate()?;
+        self.list_repository.create(request).await
+    }
+}
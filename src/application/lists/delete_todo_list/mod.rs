@@ -0,0 +1,63 @@
+use uuid::Uuid;
+
+use crate::application::todos::{TodoAuthorizer, TodoDomainEvents};
+use crate::domain::lists::traits::TodoListStore;
+use crate::domain::todos::{BatchMode, BatchOperation, PaginationQuery, TodoRole};
+use crate::domain::todos::traits::{TodoBatchWriter, TodoPaginator, TodoRoles};
+use crate::error::ApiError;
+
+/// Deletes a list together with its todos. The todos go to the trash like
+/// any deleted todo, recording their events, a page at a time in atomic
+/// batches; the list is removed once it is empty. Should a batch fail, the
+/// list stays with the todos not yet trashed, and deleting it again
+/// finishes the job. Todos restored from the trash come back without a
+/// list.
+pub struct DeleteTodoListUseCase<'a, L, T>
+where
+    L: TodoListStore + ?Sized,
+    T: TodoPaginator + TodoBatchWriter + TodoRoles + ?Sized,
+{
+    list_repository: &'a L,
+    todo_repository: &'a T,
+}
+
+impl<'a, L, T> DeleteTodoListUseCase<'a, L, T>
+where
+    L: TodoListStore + ?Sized,
+    T: TodoPaginator + TodoBatchWriter + TodoRoles + ?Sized,
+{
+    pub fn new(list_repository: &'a L, todo_repository: &'a T) -> Self {
+        Self { list_repository, todo_repository }
+    }
+
+    /// Fails with `Forbidden`, before trashing anything more, on a todo in
+    /// the list that the user does not own.
+    pub async fn execute(&self, id: Uuid) -> Result<(), ApiError> {
+        if self.list_repository.find_by_id(id).await?.is_none() {
+            return Err(ApiError::NotFound);
+        }
+
+        let authorizer = TodoAuthorizer::new(self.todo_repository);
+        let in_list = PaginationQuery { list_id: Some(id), limit: PaginationQuery::MAX_LIMIT, ..Default::default() };
+        loop {
+            let page = self.todo_repository.find_all_paginated(in_list.clone()).await?;
+            if page.data.is_empty() {
+                break;
+            }
+
+            let mut operations = Vec::with_capacity(page.data.len());
+            for todo in page.data {
+                authorizer.authorize(todo.id, TodoRole::Owner).await?;
+                operations.push(BatchOperation::Delete { id: todo.id, expected_version: None });
+            }
+            // The operation that failed, not one rolled back with it
+            let results = self.todo_repository.apply_batch(operations, BatchMode::Atomic, TodoDomainEvents::deleted).await?;
+            let mut errors = results.into_iter().filter_map(Result::err);
+            if let Some(error) = errors.find(|error| !matches!(error, ApiError::FailedDependency)) {
+                return Err(error);
+            }
+        }
+
+        self.list_repository.delete(id).await
+    }
+}
@@ -0,0 +1,19 @@
+use uuid::Uuid;
+
+use crate::domain::lists::TodoList;
+use crate::domain::lists::traits::TodoListStore;
+use crate::error::ApiError;
+
+pub struct GetTodoListUseCase<'a, L: TodoListStore + ?Sized> {
+    list_repository: &'a L,
+}
+
+impl<'a, L: TodoListStore + ?Sized> GetTodoListUseCase<'a, L> {
+    pub fn new(list_repository: &'a L) -> Self {
+        Self { list_repository }
+    }
+
+    pub async fn execute(&self, id: Uuid) -> Result<Option<TodoList>, ApiError> {
+        self.list_repository.find_by_id(id).await
+    }
+}
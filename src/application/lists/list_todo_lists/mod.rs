@@ -0,0 +1,18 @@
+use crate::domain::lists::{TodoList, TodoListQuery};
+use crate::domain::lists::traits::TodoListStore;
+use crate::domain::todos::PaginatedResponse;
+use crate::error::ApiError;
+
+pub struct ListTodoListsUseCase<'a, L: TodoListStore + ?Sized> {
+    list_repository: &'a L,
+}
+
+impl<'a, L: TodoListStore + ?Sized> ListTodoListsUseCase<'a, L> {
+    pub fn new(list_repository: &'a L) -> Self {
+        Self { list_repository }
+    }
+
+    pub async fn execute(&self, query: TodoListQuery) -> Result<PaginatedResponse<TodoList>, ApiError> {
+        self.list_repository.find_all_paginated(query.archived, query.pagination()).await
+    }
+}
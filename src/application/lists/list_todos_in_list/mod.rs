@@ -0,0 +1,43 @@
+use uuid::Uuid;
+
+use crate::application::todos::ListTodosUseCase;
+use crate::domain::lists::traits::TodoListStore;
+use crate::domain::todos::{KeysetDirection, KeysetPage, KeysetPosition, PaginatedResponse, PaginationQuery, Todo};
+use crate::domain::todos::traits::TodoPaginator;
+use crate::error::ApiError;
+
+/// Lists the todos of one list, archived or not, with the filters and
+/// paging of `ListTodosUseCase`. An unknown list is `NotFound` rather than
+/// an empty page.
+pub struct ListTodosInListUseCase<'a, L: TodoListStore + ?Sized, T: TodoPaginator + ?Sized> {
+    list_repository: &'a L,
+    todo_repository: &'a T,
+}
+
+impl<'a, L: TodoListStore + ?Sized, T: TodoPaginator + ?Sized> ListTodosInListUseCase<'a, L, T> {
+    pub fn new(list_repository: &'a L, todo_repository: &'a T) -> Self {
+        Self { list_repository, todo_repository }
+    }
+
+    pub async fn execute(&self, id: Uuid, pagination: PaginationQuery) -> Result<PaginatedResponse<Todo>, ApiError> {
+        let pagination = self.in_list(id, pagination).await?;
+        ListTodosUseCase::new(self.todo_repository).execute(pagination).await
+    }
+
+    pub async fn execute_keyset(
+        &self,
+        id: Uuid,
+        pagination: PaginationQuery,
+        start: Option<(KeysetDirection, KeysetPosition)>,
+    ) -> Result<KeysetPage<Todo>, ApiError> {
+        let pagination = self.in_list(id, pagination).await?;
+        ListTodosUseCase::new(self.todo_repository).execute_keyset(pagination, start).await
+    }
+
+    async fn in_list(&self, id: Uuid, pagination: PaginationQuery) -> Result<PaginationQuery, ApiError> {
+        if self.list_repository.find_by_id(id).await?.is_none() {
+            return Err(ApiError::NotFound);
+        }
+        Ok(PaginationQuery { list_id: Some(id), ..pagination })
+    }
+}
@@ -0,0 +1,15 @@
+pub mod create_todo_list;
+pub mod get_todo_list;
+pub mod list_todo_lists;
+pub mod update_todo_list;
+pub mod archive_todo_list;
+pub mod delete_todo_list;
+pub mod list_todos_in_list;
+
+pub use create_todo_list::*;
+pub use get_todo_list::*;
+pub use list_todo_lists::*;
+pub use update_todo_list::*;
+pub use archive_todo_list::*;
+pub use delete_todo_list::*;
+pub use list_todos_in_list::*;
@@ -0,0 +1,20 @@
+use uuid::Uuid;
+
+use crate::domain::lists::{TodoList, UpdateTodoListRequest};
+use crate::domain::lists::traits::TodoListStore;
+use crate::error::ApiError;
+
+pub struct UpdateTodoListUseCase<'a, L: TodoListStore + ?Sized> {
+    list_repository: &'a L,
+}
+
+impl<'a, L: TodoListStore + ?Sized> UpdateTodoListUseCase<'a, L> {
+    pub fn new(list_repository: &'a L) -> Self {
+        Self { list_repository }
+    }
+
+    pub async fn execute(&self, id: Uuid, request: UpdateTodoListRequest) -> Result<TodoList, ApiError> {
+        request.validate()?;
+        self.list_repository.update(id, request).await
+    }
+}
@@ -1,2 +1,6 @@
+pub mod api_keys;
+pub mod lists;
+pub mod tags;
 pub mod todos;
-
+pub mod users;
+pub mod webhooks;
@@ -0,0 +1,18 @@
+use crate::domain::tags::{CreateTagRequest, Tag};
+use crate::domain::tags::traits::TagStore;
+use crate::error::ApiError;
+
+pub struct CreateTagUseCase<'a, G: TagStore + ?Sized> {
+    tag_repository: &'a G,
+}
+
+impl<'a, G: TagStore + ?Sized> CreateTagUseCase<'a, G> {
+    pub fn new(tag_repository: &'a G) -> Self {
+        Self { tag_repository }
+    }
+
+    pub async fn execute(&self, request: CreateTagRequest) -> Result<Tag, ApiError> {
+        request.validate()?;
+        self.tag_repository.create(request).await
+    }
+}
@@ -0,0 +1,19 @@
+use uuid::Uuid;
+
+use crate::application::todos::TodoDomainEvents;
+use crate::domain::tags::traits::TagStore;
+use crate::error::ApiError;
+
+pub struct DeleteTagUseCase<'a, G: TagStore + ?Sized> {
+    tag_repository: &'a G,
+}
+
+impl<'a, G: TagStore + ?Sized> DeleteTagUseCase<'a, G> {
+    pub fn new(tag_repository: &'a G) -> Self {
+        Self { tag_repository }
+    }
+
+    pub async fn execute(&self, id: Uuid) -> Result<(), ApiError> {
+        self.tag_repository.delete(id, TodoDomainEvents::updated).await
+    }
+}
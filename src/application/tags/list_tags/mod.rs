@@ -0,0 +1,18 @@
+use crate::domain::tags::{Tag, TagQuery};
+use crate::domain::tags::traits::TagStore;
+use crate::domain::todos::PaginatedResponse;
+use crate::error::ApiError;
+
+pub struct ListTagsUseCase<'a, G: TagStore + ?Sized> {
+    tag_repository: &'a G,
+}
+
+impl<'a, G: TagStore + ?Sized> ListTagsUseCase<'a, G> {
+    pub fn new(tag_repository: &'a G) -> Self {
+        Self { tag_repository }
+    }
+
+    pub async fn execute(&self, query: TagQuery) -> Result<PaginatedResponse<Tag>, ApiError> {
+        self.tag_repository.find_all_paginated(query.pagination()).await
+    }
+}
@@ -0,0 +1,11 @@
+pub mod create_tag;
+pub mod list_tags;
+pub mod rename_tag;
+pub mod delete_tag;
+pub mod tag_todo;
+
+pub use create_tag::*;
+pub use list_tags::*;
+pub use rename_tag::*;
+pub use delete_tag::*;
+pub use tag_todo::*;
@@ -0,0 +1,21 @@
+use uuid::Uuid;
+
+use crate::application::todos::TodoDomainEvents;
+use crate::domain::tags::{RenameTagRequest, Tag};
+use crate::domain::tags::traits::TagStore;
+use crate::error::ApiError;
+
+pub struct RenameTagUseCase<'a, G: TagStore + ?Sized> {
+    tag_repository: &'a G,
+}
+
+impl<'a, G: TagStore + ?Sized> RenameTagUseCase<'a, G> {
+    pub fn new(tag_repository: &'a G) -> Self {
+        Self { tag_repository }
+    }
+
+    pub async fn execute(&self, id: Uuid, request: RenameTagRequest) -> Result<Tag, ApiError> {
+        request.validate()?;
+        self.tag_repository.rename(id, request, TodoDomainEvents::updated).await
+    }
+}
@@ -0,0 +1,41 @@
+use uuid::Uuid;
+
+use crate::application::todos::{TodoAuthorizer, TodoDomainEvents};
+use crate::domain::tags::traits::TagStore;
+use crate::domain::todos::{Todo, TodoRole};
+use crate::domain::todos::traits::{TodoRoles, TodoTagging};
+use crate::error::ApiError;
+
+/// Attaches one of the user's tags to a todo they may edit, or detaches it.
+/// Someone else's tag is `NotFound`, as it is everywhere else.
+pub struct TagTodoUseCase<'a, G, T>
+where
+    G: TagStore + ?Sized,
+    T: TodoTagging + TodoRoles + ?Sized,
+{
+    tag_repository: &'a G,
+    todo_repository: &'a T,
+}
+
+impl<'a, G, T> TagTodoUseCase<'a, G, T>
+where
+    G: TagStore + ?Sized,
+    T: TodoTagging + TodoRoles + ?Sized,
+{
+    pub fn new(tag_repository: &'a G, todo_repository: &'a T) -> Self {
+        Self { tag_repository, todo_repository }
+    }
+
+    pub async fn execute(&self, id: Uuid, tag_id: Uuid, attached: bool, expected_version: Option<i64>) -> Result<Todo, ApiError> {
+        TodoAuthorizer::new(self.todo_repository).authorize(id, TodoRole::Editor).await?;
+        if self.tag_repository.find_by_id(tag_id).await?.is_none() {
+            return Err(ApiError::NotFound);
+        }
+
+        if attached {
+            self.todo_repository.attach_tag(id, tag_id, expected_version, TodoDomainEvents::updated).await
+        } else {
+            self.todo_repository.detach_tag(id, tag_id, expected_version, TodoDomainEvents::updated).await
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use uuid::Uuid;
+
+use crate::domain::todos::{TodoChange, TodoRole};
+use crate::domain::todos::traits::TodoRoles;
+use crate::error::ApiError;
+use crate::request_context::RequestContext;
+
+/// Decides whether the current user may act on a todo. Use cases consult it
+/// before each write, since repositories only tell todos a user can see from
+/// those they cannot.
+pub struct TodoAuthorizer<'a, T: TodoRoles + ?Sized> {
+    todo_repository: &'a T,
+}
+
+impl<'a, T: TodoRoles + ?Sized> TodoAuthorizer<'a, T> {
+    pub fn new(todo_repository: &'a T) -> Self {
+        Self { todo_repository }
+    }
+
+    /// Passes when the current user's role on `id` includes `required`.
+    /// Users with no role get `NotFound`, exactly like a missing todo, so
+    /// nothing leaks about todos they were never shared; users who can see
+    /// the todo but not change it get `Forbidden`. Work outside a request,
+    /// which sees every todo, is always allowed.
+    pub async fn authorize(&self, id: Uuid, required: TodoRole) -> Result<(), ApiError> {
+        let Some(user_id) = RequestContext::current_user_id() else { return Ok(()) };
+        match self.todo_repository.role_of(id, user_id).await? {
+            Some(role) if role.includes(required) => Ok(()),
+            Some(_) => Err(ApiError::Forbidden(format!("This requires the `{}` role on the todo.", required.as_str()))),
+            None => Err(ApiError::NotFound),
+        }
+    }
+
+    /// Whether the current user may see `change` on a live feed: the todo
+    /// must be in their tenant and theirs or shared with them, with any
+    /// role. Tasks across all tenants see every change.
+    pub async fn may_watch(&self, change: &TodoChange) -> Result<bool, ApiError> {
+        if !RequestContext::tenant_scope()?.includes(&change.todo.tenant_id) {
+            return Ok(false);
+        }
+        let Some(user_id) = RequestContext::current_user_id() else { return Ok(true) };
+        if change.todo.owner_id == Some(user_id) {
+            return Ok(true);
+        }
+        Ok(self.todo_repository.role_of(change.todo.id, user_id).await?.is_some())
+    }
+}
@@ -0,0 +1,81 @@
+use crate::application::todos::{ListPlacement, TodoAuthorizer, TodoDomainEvents};
+use crate::domain::lists::traits::TodoListStore;
+use crate::domain::todos::{BatchMode, BatchOperation, BatchOutcome, BatchRequest, BatchResponse};
+use crate::domain::todos::traits::{TodoBatchWriter, TodoFinder, TodoRoles};
+use crate::error::ApiError;
+
+pub struct BatchTodosUseCase<'a, T: TodoFinder + TodoBatchWriter + TodoRoles + ?Sized, L: TodoListStore + ?Sized> {
+    todo_repository: &'a T,
+    list_repository: &'a L,
+}
+
+impl<'a, T: TodoFinder + TodoBatchWriter + TodoRoles + ?Sized, L: TodoListStore + ?Sized> BatchTodosUseCase<'a, T, L> {
+    pub fn new(todo_repository: &'a T, list_repository: &'a L) -> Self {
+        Self { todo_repository, list_repository }
+    }
+
+    /// Invalid operations fail with `Validation` without reaching the
+    /// repository; in atomic mode they stop the whole batch. With
+    /// `require_version`, updates and deletes lacking `expected_version`
+    /// fail the same way with `PreconditionRequired`, and operations on
+    /// todos the user may not change with `NotFound` or `Forbidden`. Creates
+    /// into a list are checked like a single create, and moves to another
+    /// list against the todo as it was before the batch.
+    pub async fn execute(&self, request: BatchRequest, require_version: bool) -> Result<BatchResponse, ApiError> {
+        request.validate()?;
+        let mode = request.mode;
+        let authorizer = TodoAuthorizer::new(self.todo_repository);
+
+        let mut results: Vec<Option<Result<BatchOutcome, ApiError>>> = Vec::with_capacity(request.operations.len());
+        let mut positions = Vec::new();
+        let mut valid = Vec::new();
+        for (index, operation) in request.operations.into_iter().enumerate() {
+            let unversioned = matches!(
+                operation,
+                BatchOperation::Update { expected_version: None, .. } | BatchOperation::Delete { expected_version: None, .. }
+            );
+            let checked = match operation.validate() {
+                Ok(()) if require_version && unversioned => Err(ApiError::PreconditionRequired),
+                Ok(()) => match operation.required_role() {
+                    Some((id, role)) => match authorizer.authorize(id, role).await {
+                        Ok(()) => self.check_placement(&operation).await,
+                        denied => denied,
+                    },
+                    None => self.check_placement(&operation).await,
+                },
+                checked => checked,
+            };
+            match checked {
+                Ok(()) => {
+                    positions.push(index);
+                    valid.push(operation);
+                    results.push(None);
+                }
+                Err(error) => results.push(Some(Err(error))),
+            }
+        }
+
+        let doomed = mode == BatchMode::Atomic && valid.len() < results.len();
+        if !valid.is_empty() && !doomed {
+            let applied = self.todo_repository.apply_batch(valid, mode, TodoDomainEvents::written).await?;
+            for (index, result) in positions.into_iter().zip(applied) {
+                results[index] = Some(result);
+            }
+        }
+
+        Ok(BatchResponse::new(mode, mode.settle(results)))
+    }
+
+    async fn check_placement(&self, operation: &BatchOperation) -> Result<(), ApiError> {
+        let placement = ListPlacement::new(self.list_repository);
+        match operation {
+            BatchOperation::Create { list_id: Some(list_id), .. } => placement.check(*list_id).await,
+            BatchOperation::Update { id, list_id: Some(target), .. } => {
+                let current = self.todo_repository.find_by_id(*id).await?
+                    .ok_or(ApiError::NotFound)?;
+                placement.check_move(self.todo_repository, &current, *target).await
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -1,18 +1,25 @@
-
+use crate::application::todos::{ListPlacement, TodoDomainEvents};
+use crate::domain::lists::traits::TodoListStore;
 use crate::domain::todos::{Todo, CreateTodoRequest};
 use crate::domain::todos::traits::TodoCreator;
 use crate::error::ApiError;
 
-pub struct CreateTodoUseCase<'a, T: TodoCreator> {
+pub struct CreateTodoUseCase<'a, T: TodoCreator + ?Sized, L: TodoListStore + ?Sized> {
     todo_repository: &'a T,
+    list_repository: &'a L,
 }
 
-impl<'a, T: TodoCreator> CreateTodoUseCase<'a, T> {
-    pub fn new(todo_repository: &'a T) -> Self {
-        Self { todo_repository }
+impl<'a, T: TodoCreator + ?Sized, L: TodoListStore + ?Sized> CreateTodoUseCase<'a, T, L> {
+    pub fn new(todo_repository: &'a T, list_repository: &'a L) -> Self {
+        Self { todo_repository, list_repository }
     }
 
+    /// A `list_id` must name one of the user's lists that is not archived.
     pub async fn execute(&self, request: CreateTodoRequest) -> Result<Todo, ApiError> {
-        self.todo_repository.create(request).await
+        request.validate()?;
+        if let Some(list_id) = request.list_id {
+            ListPlacement::new(self.list_repository).check(list_id).await?;
+        }
+        self.todo_repository.create(request, TodoDomainEvents::created).await
     }
 }
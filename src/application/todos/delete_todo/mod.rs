@@ -1,18 +1,21 @@
 use uuid::Uuid;
 
-use crate::domain::todos::traits::TodoDeleter;
+use crate::application::todos::{TodoAuthorizer, TodoDomainEvents};
+use crate::domain::todos::TodoRole;
+use crate::domain::todos::traits::{TodoDeleter, TodoRoles};
 use crate::error::ApiError;
 
-pub struct DeleteTodoUseCase<'a, T: TodoDeleter> {
+pub struct DeleteTodoUseCase<'a, T: TodoDeleter + TodoRoles + ?Sized> {
     todo_repository: &'a T,
 }
 
-impl<'a, T: TodoDeleter> DeleteTodoUseCase<'a, T> {
+impl<'a, T: TodoDeleter + TodoRoles + ?Sized> DeleteTodoUseCase<'a, T> {
     pub fn new(todo_repository: &'a T) -> Self {
         Self { todo_repository }
     }
 
-    pub async fn execute(&self, id: Uuid) -> Result<(), ApiError> {
-        self.todo_repository.delete(id).await
+    pub async fn execute(&self, id: Uuid, expected_version: Option<i64>) -> Result<(), ApiError> {
+        TodoAuthorizer::new(self.todo_repository).authorize(id, TodoRole::Owner).await?;
+        self.todo_repository.delete(id, expected_version, TodoDomainEvents::deleted).await
     }
 }
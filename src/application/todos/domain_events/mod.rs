@@ -0,0 +1,49 @@
+use crate::domain::todos::{DomainEvent, NewTodoEvent, TodoEventKind};
+
+/// The domain events each kind of write emits. Use cases pass the matching
+/// function to the repository, which calls it on the change it records; a
+/// write whose change does not fit the function emits nothing.
+pub struct TodoDomainEvents;
+
+impl TodoDomainEvents {
+    pub fn created(change: &NewTodoEvent) -> Vec<DomainEvent> {
+        match &change.after {
+            Some(todo) => vec![DomainEvent::TodoCreated { todo: todo.clone() }],
+            None => Vec::new(),
+        }
+    }
+
+    /// `TodoUpdated`, followed by `TodoCompleted` when the write set `done`.
+    pub fn updated(change: &NewTodoEvent) -> Vec<DomainEvent> {
+        let Some(todo) = &change.after else { return Vec::new() };
+        let mut events = vec![DomainEvent::TodoUpdated { todo: todo.clone(), changes: change.changes.clone() }];
+        if todo.done && !change.before.as_ref().is_some_and(|before| before.done) {
+            events.push(DomainEvent::TodoCompleted { todo: todo.clone() });
+        }
+        events
+    }
+
+    pub fn deleted(change: &NewTodoEvent) -> Vec<DomainEvent> {
+        vec![DomainEvent::TodoDeleted { todo_id: change.todo_id, version: change.version }]
+    }
+
+    /// A restored todo is announced as updated, with its full state, so
+    /// consumers that dropped it can upsert it again.
+    pub fn restored(change: &NewTodoEvent) -> Vec<DomainEvent> {
+        match &change.after {
+            Some(todo) => vec![DomainEvent::TodoUpdated { todo: todo.clone(), changes: Vec::new() }],
+            None => Vec::new(),
+        }
+    }
+
+    /// For batches, which mix creates, updates and deletes.
+    pub fn written(change: &NewTodoEvent) -> Vec<DomainEvent> {
+        match change.kind {
+            TodoEventKind::Created => Self::created(change),
+            TodoEventKind::Updated => Self::updated(change),
+            TodoEventKind::Deleted => Self::deleted(change),
+            TodoEventKind::Restored => Self::restored(change),
+            TodoEventKind::Purged => Vec::new(),
+        }
+    }
+}
@@ -4,11 +4,11 @@ use crate::domain::todos::Todo;
 use crate::domain::todos::traits::TodoFinder;
 use crate::error::ApiError;
 
-pub struct GetTodoUseCase<'a, T: TodoFinder> {
+pub struct GetTodoUseCase<'a, T: TodoFinder + ?Sized> {
     todo_repository: &'a T,
 }
 
-impl<'a, T: TodoFinder> GetTodoUseCase<'a, T> {
+impl<'a, T: TodoFinder + ?Sized> GetTodoUseCase<'a, T> {
     pub fn new(todo_repository: &'a T) -> Self {
         Self { todo_repository }
     }
@@ -0,0 +1,25 @@
+use uuid::Uuid;
+
+use crate::domain::todos::{PageQuery, PaginatedResponse, TodoEvent};
+use crate::domain::todos::traits::{TodoFinder, TodoHistory};
+use crate::error::ApiError;
+
+pub struct GetTodoHistoryUseCase<'a, T: TodoHistory + TodoFinder + ?Sized> {
+    todo_repository: &'a T,
+}
+
+impl<'a, T: TodoHistory + TodoFinder + ?Sized> GetTodoHistoryUseCase<'a, T> {
+    pub fn new(todo_repository: &'a T) -> Self {
+        Self { todo_repository }
+    }
+
+    /// A todo with no recorded events is only found if it is live: it
+    /// predates the audit log.
+    pub async fn execute(&self, id: Uuid, query: PageQuery) -> Result<PaginatedResponse<TodoEvent>, ApiError> {
+        let history = self.todo_repository.find_history(id, query.pagination()).await?;
+        if history.pagination.total == 0 && self.todo_repository.find_by_id(id).await?.is_none() {
+            return Err(ApiError::NotFound);
+        }
+        Ok(history)
+    }
+}
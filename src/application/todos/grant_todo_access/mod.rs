@@ -0,0 +1,41 @@
+use uuid::Uuid;
+
+use crate::application::todos::TodoAuthorizer;
+use crate::domain::todos::validation::FieldError;
+use crate::domain::todos::{GrantAccessRequest, TodoRole, TodoShare};
+use crate::domain::todos::traits::{TodoRoles, TodoSharing};
+use crate::domain::users::traits::UserStore;
+use crate::domain::users::validation::normalize_email;
+use crate::error::ApiError;
+
+pub struct GrantTodoAccessUseCase<'a, T: TodoRoles + TodoSharing + ?Sized, U: UserStore + ?Sized> {
+    todo_repository: &'a T,
+    user_repository: &'a U,
+}
+
+impl<'a, T: TodoRoles + TodoSharing + ?Sized, U: UserStore + ?Sized> GrantTodoAccessUseCase<'a, T, U> {
+    pub fn new(todo_repository: &'a T, user_repository: &'a U) -> Self {
+        Self { todo_repository, user_repository }
+    }
+
+    /// Shares the todo with the account registered under `request.email`,
+    /// or changes the role of someone it is already shared with.
+    pub async fn execute(&self, id: Uuid, request: GrantAccessRequest) -> Result<TodoShare, ApiError> {
+        request.validate()?;
+        TodoAuthorizer::new(self.todo_repository).authorize(id, TodoRole::Owner).await?;
+
+        let user = self
+            .user_repository
+            .find_credentials(&normalize_email(&request.email))
+            .await?
+            .ok_or_else(|| {
+                ApiError::Validation(vec![FieldError::new("email", "unknown", "no account is registered with this email")])
+            })?
+            .user;
+        if self.todo_repository.role_of(id, user.id).await? == Some(TodoRole::Owner) {
+            return Err(ApiError::Validation(vec![FieldError::new("email", "owner", "the owner already has access")]));
+        }
+
+        self.todo_repository.grant(id, user.id, request.role).await
+    }
+}
@@ -0,0 +1,22 @@
+use uuid::Uuid;
+
+use crate::application::todos::TodoAuthorizer;
+use crate::domain::todos::{TodoRole, TodoShare};
+use crate::domain::todos::traits::{TodoRoles, TodoSharing};
+use crate::error::ApiError;
+
+pub struct ListTodoAccessUseCase<'a, T: TodoRoles + TodoSharing + ?Sized> {
+    todo_repository: &'a T,
+}
+
+impl<'a, T: TodoRoles + TodoSharing + ?Sized> ListTodoAccessUseCase<'a, T> {
+    pub fn new(todo_repository: &'a T) -> Self {
+        Self { todo_repository }
+    }
+
+    /// Everyone who can see a todo may see who else can.
+    pub async fn execute(&self, id: Uuid) -> Result<Vec<TodoShare>, ApiError> {
+        TodoAuthorizer::new(self.todo_repository).authorize(id, TodoRole::Viewer).await?;
+        self.todo_repository.find_shares(id).await
+    }
+}
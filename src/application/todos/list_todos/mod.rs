@@ -1,18 +1,39 @@
 
-use crate::domain::todos::{Todo, PaginationQuery, PaginatedResponse};
+use crate::domain::todos::{
+    Todo, PaginationQuery, PaginatedResponse, KeysetQuery, KeysetPage, KeysetDirection, KeysetPosition
+};
 use crate::domain::todos::traits::TodoPaginator;
 use crate::error::ApiError;
 
-pub struct ListTodosUseCase<'a, T: TodoPaginator> {
+pub struct ListTodosUseCase<'a, T: TodoPaginator + ?Sized> {
     todo_repository: &'a T,
 }
 
-impl<'a, T: TodoPaginator> ListTodosUseCase<'a, T> {
+impl<'a, T: TodoPaginator + ?Sized> ListTodosUseCase<'a, T> {
     pub fn new(todo_repository: &'a T) -> Self {
         Self { todo_repository }
     }
 
     pub async fn execute(&self, pagination: PaginationQuery) -> Result<PaginatedResponse<Todo>, ApiError> {
+        pagination.validate()?;
         self.todo_repository.find_all_paginated(pagination).await
     }
+
+    /// Reads the keyset page past `start` (the first page when `None`) with
+    /// the filters and limit of `pagination`.
+    pub async fn execute_keyset(
+        &self,
+        pagination: PaginationQuery,
+        start: Option<(KeysetDirection, KeysetPosition)>,
+    ) -> Result<KeysetPage<Todo>, ApiError> {
+        pagination.validate()?;
+        self.todo_repository
+            .find_keyset(KeysetQuery {
+                start,
+                limit: pagination.limit(),
+                include_total: pagination.include_total,
+                filter: pagination.filter(),
+            })
+            .await
+    }
 }
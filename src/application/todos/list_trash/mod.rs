@@ -0,0 +1,17 @@
+use crate::domain::todos::{PaginatedResponse, PageQuery, TrashedTodo};
+use crate::domain::todos::traits::TodoTrash;
+use crate::error::ApiError;
+
+pub struct ListTrashUseCase<'a, T: TodoTrash + ?Sized> {
+    todo_repository: &'a T,
+}
+
+impl<'a, T: TodoTrash + ?Sized> ListTrashUseCase<'a, T> {
+    pub fn new(todo_repository: &'a T) -> Self {
+        Self { todo_repository }
+    }
+
+    pub async fn execute(&self, query: PageQuery) -> Result<PaginatedResponse<TrashedTodo>, ApiError> {
+        self.todo_repository.find_trashed(query.pagination()).await
+    }
+}
@@ -1,11 +1,41 @@
+pub mod authorization;
+pub mod batch_todos;
 pub mod create_todo;
 pub mod get_todo;
+pub mod get_todo_history;
 pub mod list_todos;
+pub mod search_todos;
 pub mod update_todo;
+pub mod patch_todo;
+pub mod placement;
 pub mod delete_todo;
+pub mod domain_events;
+pub mod list_trash;
+pub mod restore_todo;
+pub mod purge_todo;
+pub mod purge_expired_trash;
+pub mod relay_outbox;
+pub mod list_todo_access;
+pub mod grant_todo_access;
+pub mod revoke_todo_access;
 
+pub use authorization::*;
+pub use batch_todos::*;
 pub use create_todo::*;
 pub use get_todo::*;
+pub use get_todo_history::*;
 pub use list_todos::*;
+pub use search_todos::*;
 pub use update_todo::*;
+pub use patch_todo::*;
+pub use placement::*;
 pub use delete_todo::*;
+pub use domain_events::*;
+pub use list_trash::*;
+pub use restore_todo::*;
+pub use purge_todo::*;
+pub use purge_expired_trash::*;
+pub use relay_outbox::*;
+pub use list_todo_access::*;
+pub use grant_todo_access::*;
+pub use revoke_todo_access::*;
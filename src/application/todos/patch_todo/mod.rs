@@ -0,0 +1,38 @@
+use uuid::Uuid;
+
+use crate::application::todos::{ListPlacement, TodoAuthorizer, TodoDomainEvents};
+use crate::domain::lists::traits::TodoListStore;
+use crate::domain::todos::{Todo, TodoPatch, TodoRole, UpdateTodoRequest};
+use crate::domain::todos::traits::{TodoFinder, TodoRoles, TodoUpdater};
+use crate::error::ApiError;
+
+pub struct PatchTodoUseCase<'a, T: TodoFinder + TodoUpdater + TodoRoles + ?Sized, L: TodoListStore + ?Sized> {
+    todo_repository: &'a T,
+    list_repository: &'a L,
+}
+
+impl<'a, T: TodoFinder + TodoUpdater + TodoRoles + ?Sized, L: TodoListStore + ?Sized> PatchTodoUseCase<'a, T, L> {
+    pub fn new(todo_repository: &'a T, list_repository: &'a L) -> Self {
+        Self { todo_repository, list_repository }
+    }
+
+    /// The patch is applied to the version that was read, so the write is
+    /// always conditional on it: a concurrent change in between yields
+    /// `PreconditionFailed` rather than being silently overwritten.
+    pub async fn execute(&self, id: Uuid, patch: TodoPatch, expected_version: Option<i64>) -> Result<Todo, ApiError> {
+        TodoAuthorizer::new(self.todo_repository).authorize(id, TodoRole::Editor).await?;
+        let current = self.todo_repository.find_by_id(id).await?
+            .ok_or(ApiError::NotFound)?;
+
+        if expected_version.is_some_and(|version| version != current.version) {
+            return Err(ApiError::PreconditionFailed);
+        }
+
+        let request: UpdateTodoRequest = patch.apply(&current)?.into();
+        request.validate()?;
+        if let Some(target) = request.list_id {
+            ListPlacement::new(self.list_repository).check_move(self.todo_repository, &current, target).await?;
+        }
+        self.todo_repository.update(id, request, Some(current.version), TodoDomainEvents::updated).await
+    }
+}
@@ -0,0 +1,52 @@
+use uuid::Uuid;
+
+use crate::application::todos::TodoAuthorizer;
+use crate::domain::lists::traits::TodoListStore;
+use crate::domain::todos::{Todo, TodoRole};
+use crate::domain::todos::traits::TodoRoles;
+use crate::domain::todos::validation::FieldError;
+use crate::error::ApiError;
+
+/// Decides whether a todo may be put in a list, both when it is created
+/// there and when a later write moves it.
+pub struct ListPlacement<'a, L: TodoListStore + ?Sized> {
+    list_repository: &'a L,
+}
+
+impl<'a, L: TodoListStore + ?Sized> ListPlacement<'a, L> {
+    pub fn new(list_repository: &'a L) -> Self {
+        Self { list_repository }
+    }
+
+    /// Passes when `list_id` names one of the user's lists that is not
+    /// archived.
+    pub async fn check(&self, list_id: Uuid) -> Result<(), ApiError> {
+        let error = match self.list_repository.find_by_id(list_id).await? {
+            None => FieldError::new("list_id", "unknown_list", "list_id must name one of your lists"),
+            Some(list) if list.archived => {
+                FieldError::new("list_id", "archived_list", "the list is archived; restore it to add todos")
+            }
+            Some(_) => return Ok(()),
+        };
+        Err(ApiError::Validation(vec![error]))
+    }
+
+    /// Checks moving `todo` to `target` (`None` takes it out of its list).
+    /// Leaving it where it is always passes; moving it takes the `Owner`
+    /// role, since the lists are the owner's.
+    pub async fn check_move<T: TodoRoles + ?Sized>(
+        &self,
+        todo_repository: &T,
+        todo: &Todo,
+        target: Option<Uuid>,
+    ) -> Result<(), ApiError> {
+        if todo.list_id == target {
+            return Ok(());
+        }
+        TodoAuthorizer::new(todo_repository).authorize(todo.id, TodoRole::Owner).await?;
+        match target {
+            Some(list_id) => self.check(list_id).await,
+            None => Ok(()),
+        }
+    }
+}
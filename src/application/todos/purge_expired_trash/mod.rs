@@ -0,0 +1,20 @@
+use chrono::{Duration, Utc};
+
+use crate::domain::todos::traits::TodoTrash;
+use crate::error::ApiError;
+
+pub struct PurgeExpiredTrashUseCase<'a, T: TodoTrash + ?Sized> {
+    todo_repository: &'a T,
+}
+
+impl<'a, T: TodoTrash + ?Sized> PurgeExpiredTrashUseCase<'a, T> {
+    pub fn new(todo_repository: &'a T) -> Self {
+        Self { todo_repository }
+    }
+
+    /// Purges todos that have been in the trash longer than `retention`,
+    /// returning how many were removed.
+    pub async fn execute(&self, retention: Duration) -> Result<u64, ApiError> {
+        self.todo_repository.purge_trashed_before(Utc::now() - retention).await
+    }
+}
@@ -0,0 +1,21 @@
+use uuid::Uuid;
+
+use crate::application::todos::TodoAuthorizer;
+use crate::domain::todos::TodoRole;
+use crate::domain::todos::traits::{TodoRoles, TodoTrash};
+use crate::error::ApiError;
+
+pub struct PurgeTodoUseCase<'a, T: TodoTrash + TodoRoles + ?Sized> {
+    todo_repository: &'a T,
+}
+
+impl<'a, T: TodoTrash + TodoRoles + ?Sized> PurgeTodoUseCase<'a, T> {
+    pub fn new(todo_repository: &'a T) -> Self {
+        Self { todo_repository }
+    }
+
+    pub async fn execute(&self, id: Uuid) -> Result<(), ApiError> {
+        TodoAuthorizer::new(self.todo_repository).authorize(id, TodoRole::Owner).await?;
+        self.todo_repository.purge(id).await
+    }
+}
@@ -0,0 +1,61 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::todos::{RelayReport, RetryPolicy};
+use crate::domain::todos::traits::{EventPublisher, TodoOutbox};
+use crate::error::ApiError;
+
+pub struct RelayOutboxUseCase<'a, T: TodoOutbox + ?Sized, P: EventPublisher + ?Sized> {
+    todo_repository: &'a T,
+    publisher: &'a P,
+}
+
+impl<'a, T: TodoOutbox + ?Sized, P: EventPublisher + ?Sized> RelayOutboxUseCase<'a, T, P> {
+    pub fn new(todo_repository: &'a T, publisher: &'a P) -> Self {
+        Self { todo_repository, publisher }
+    }
+
+    /// Publishes up to `batch_size` due messages in outbox order. A message
+    /// is removed only after the publisher accepted it; a failure schedules
+    /// a retry per `retry` and holds back the rest of that todo's messages.
+    pub async fn execute(&self, batch_size: u32, retry: RetryPolicy) -> Result<RelayReport, ApiError> {
+        let now = Utc::now();
+        let messages = self.todo_repository.pending_messages(batch_size, now).await?;
+        let mut report = RelayReport { fetched: messages.len(), ..RelayReport::default() };
+        let mut blocked: HashSet<Uuid> = HashSet::new();
+
+        for message in messages {
+            if blocked.contains(&message.todo_id) {
+                continue;
+            }
+            match self.publisher.publish(&message).await {
+                Ok(()) => {
+                    self.todo_repository.mark_published(message.id).await?;
+                    report.published += 1;
+                }
+                Err(error) => {
+                    let attempts = message.attempts + 1;
+                    let delay = retry.delay(attempts);
+                    tracing::warn!(
+                        event_id = %message.event_id,
+                        attempts,
+                        retry_in_ms = delay.as_millis() as u64,
+                        error = format!("{error:#}"),
+                        "publishing outbox message failed"
+                    );
+                    let retry_at = chrono::Duration::from_std(delay)
+                        .ok()
+                        .and_then(|delay| now.checked_add_signed(delay))
+                        .unwrap_or(DateTime::<Utc>::MAX_UTC);
+                    self.todo_repository.mark_failed(message.id, &format!("{error:#}"), retry_at).await?;
+                    blocked.insert(message.todo_id);
+                    report.failed += 1;
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
@@ -0,0 +1,21 @@
+use uuid::Uuid;
+
+use crate::application::todos::{TodoAuthorizer, TodoDomainEvents};
+use crate::domain::todos::{Todo, TodoRole};
+use crate::domain::todos::traits::{TodoRoles, TodoTrash};
+use crate::error::ApiError;
+
+pub struct RestoreTodoUseCase<'a, T: TodoTrash + TodoRoles + ?Sized> {
+    todo_repository: &'a T,
+}
+
+impl<'a, T: TodoTrash + TodoRoles + ?Sized> RestoreTodoUseCase<'a, T> {
+    pub fn new(todo_repository: &'a T) -> Self {
+        Self { todo_repository }
+    }
+
+    pub async fn execute(&self, id: Uuid) -> Result<Todo, ApiError> {
+        TodoAuthorizer::new(self.todo_repository).authorize(id, TodoRole::Owner).await?;
+        self.todo_repository.restore(id, TodoDomainEvents::restored).await
+    }
+}
@@ -0,0 +1,25 @@
+use uuid::Uuid;
+
+use crate::application::todos::TodoAuthorizer;
+use crate::domain::todos::TodoRole;
+use crate::domain::todos::traits::{TodoRoles, TodoSharing};
+use crate::error::ApiError;
+use crate::request_context::RequestContext;
+
+pub struct RevokeTodoAccessUseCase<'a, T: TodoRoles + TodoSharing + ?Sized> {
+    todo_repository: &'a T,
+}
+
+impl<'a, T: TodoRoles + TodoSharing + ?Sized> RevokeTodoAccessUseCase<'a, T> {
+    pub fn new(todo_repository: &'a T) -> Self {
+        Self { todo_repository }
+    }
+
+    /// The owner may take anyone's access away; everyone else may only give
+    /// up their own.
+    pub async fn execute(&self, id: Uuid, user_id: Uuid) -> Result<(), ApiError> {
+        let required = if RequestContext::current_user_id() == Some(user_id) { TodoRole::Viewer } else { TodoRole::Owner };
+        TodoAuthorizer::new(self.todo_repository).authorize(id, required).await?;
+        self.todo_repository.revoke(id, user_id).await
+    }
+}
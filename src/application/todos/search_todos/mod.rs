@@ -0,0 +1,18 @@
+use crate::domain::todos::{PaginatedResponse, SearchTodosQuery, TodoSearch, TodoSearchHit};
+use crate::domain::todos::traits::TodoSearcher;
+use crate::error::ApiError;
+
+pub struct SearchTodosUseCase<'a, T: TodoSearcher + ?Sized> {
+    todo_repository: &'a T,
+}
+
+impl<'a, T: TodoSearcher + ?Sized> SearchTodosUseCase<'a, T> {
+    pub fn new(todo_repository: &'a T) -> Self {
+        Self { todo_repository }
+    }
+
+    pub async fn execute(&self, query: SearchTodosQuery) -> Result<PaginatedResponse<TodoSearchHit>, ApiError> {
+        query.validate()?;
+        self.todo_repository.search(TodoSearch::parse(&query)).await
+    }
+}
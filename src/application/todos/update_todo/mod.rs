@@ -1,19 +1,30 @@
 use uuid::Uuid;
 
-use crate::domain::todos::{Todo, UpdateTodoRequest};
-use crate::domain::todos::traits::TodoUpdater;
+use crate::application::todos::{ListPlacement, TodoAuthorizer, TodoDomainEvents};
+use crate::domain::lists::traits::TodoListStore;
+use crate::domain::todos::{Todo, TodoRole, UpdateTodoRequest};
+use crate::domain::todos::traits::{TodoFinder, TodoRoles, TodoUpdater};
 use crate::error::ApiError;
 
-pub struct UpdateTodoUseCase<'a, T: TodoUpdater> {
+pub struct UpdateTodoUseCase<'a, T: TodoFinder + TodoUpdater + TodoRoles + ?Sized, L: TodoListStore + ?Sized> {
     todo_repository: &'a T,
+    list_repository: &'a L,
 }
 
-impl<'a, T: TodoUpdater> UpdateTodoUseCase<'a, T> {
-    pub fn new(todo_repository: &'a T) -> Self {
-        Self { todo_repository }
+impl<'a, T: TodoFinder + TodoUpdater + TodoRoles + ?Sized, L: TodoListStore + ?Sized> UpdateTodoUseCase<'a, T, L> {
+    pub fn new(todo_repository: &'a T, list_repository: &'a L) -> Self {
+        Self { todo_repository, list_repository }
     }
 
-    pub async fn execute(&self, id: Uuid, request: UpdateTodoRequest) -> Result<Todo, ApiError> {
-        self.todo_repository.update(id, request).await
+    /// Moving the todo to another list is checked like creating it there.
+    pub async fn execute(&self, id: Uuid, request: UpdateTodoRequest, expected_version: Option<i64>) -> Result<Todo, ApiError> {
+        request.validate()?;
+        TodoAuthorizer::new(self.todo_repository).authorize(id, TodoRole::Editor).await?;
+        if let Some(target) = request.list_id {
+            let current = self.todo_repository.find_by_id(id).await?
+                .ok_or(ApiError::NotFound)?;
+            ListPlacement::new(self.list_repository).check_move(self.todo_repository, &current, target).await?;
+        }
+        self.todo_repository.update(id, request, expected_version, TodoDomainEvents::updated).await
     }
 }
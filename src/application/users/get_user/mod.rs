@@ -0,0 +1,19 @@
+use uuid::Uuid;
+
+use crate::domain::users::User;
+use crate::domain::users::traits::UserStore;
+use crate::error::ApiError;
+
+pub struct GetUserUseCase<'a, T: UserStore + ?Sized> {
+    user_repository: &'a T,
+}
+
+impl<'a, T: UserStore + ?Sized> GetUserUseCase<'a, T> {
+    pub fn new(user_repository: &'a T) -> Self {
+        Self { user_repository }
+    }
+
+    pub async fn execute(&self, id: Uuid) -> Result<User, ApiError> {
+        self.user_repository.find_user(id).await?.ok_or(ApiError::NotFound)
+    }
+}
@@ -0,0 +1,42 @@
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::domain::users::validation::normalize_email;
+use crate::domain::users::{LoginRequest, NewRefreshToken, RefreshSecret, Session};
+use crate::domain::users::traits::{PasswordHasher, RefreshTokens, UserStore};
+use crate::error::ApiError;
+
+pub struct LoginUserUseCase<'a, T: UserStore + RefreshTokens + ?Sized, H: PasswordHasher + ?Sized> {
+    user_repository: &'a T,
+    password_hasher: &'a H,
+}
+
+impl<'a, T: UserStore + RefreshTokens + ?Sized, H: PasswordHasher + ?Sized> LoginUserUseCase<'a, T, H> {
+    pub fn new(user_repository: &'a T, password_hasher: &'a H) -> Self {
+        Self { user_repository, password_hasher }
+    }
+
+    /// Checks the credentials and starts a new refresh token family valid
+    /// for `refresh_ttl`. Unknown emails and wrong passwords fail alike.
+    pub async fn execute(&self, request: LoginRequest, refresh_ttl: Duration) -> Result<Session, ApiError> {
+        let credentials = self.user_repository.find_credentials(&normalize_email(&request.email)).await?;
+        let hash = credentials.as_ref().map(|credentials| credentials.password_hash.as_str());
+        let verified = self.password_hasher.verify(&request.password, hash).await?;
+
+        let Some(credentials) = credentials.filter(|_| verified) else {
+            return Err(ApiError::Unauthorized("Invalid email or password.".to_string()));
+        };
+
+        let secret = RefreshSecret::generate();
+        self.user_repository
+            .store_refresh_token(NewRefreshToken {
+                user_id: credentials.user.id,
+                family_id: Uuid::new_v4(),
+                token_hash: secret.hash,
+                expires_at: Utc::now() + refresh_ttl,
+            })
+            .await?;
+
+        Ok(Session { user: credentials.user, refresh_token: secret.secret })
+    }
+}
@@ -0,0 +1,21 @@
+use chrono::Utc;
+
+use crate::domain::users::hash_refresh_token;
+use crate::domain::users::traits::RefreshTokens;
+use crate::error::ApiError;
+
+pub struct LogoutUserUseCase<'a, T: RefreshTokens + ?Sized> {
+    user_repository: &'a T,
+}
+
+impl<'a, T: RefreshTokens + ?Sized> LogoutUserUseCase<'a, T> {
+    pub fn new(user_repository: &'a T) -> Self {
+        Self { user_repository }
+    }
+
+    /// Ends the session the refresh token belongs to. Unknown tokens are
+    /// ignored, so logging out twice succeeds.
+    pub async fn execute(&self, refresh_token: &str) -> Result<(), ApiError> {
+        self.user_repository.revoke_refresh_family(&hash_refresh_token(refresh_token), Utc::now()).await
+    }
+}
@@ -0,0 +1,11 @@
+pub mod register_user;
+pub mod login_user;
+pub mod refresh_session;
+pub mod logout_user;
+pub mod get_user;
+
+pub use register_user::*;
+pub use login_user::*;
+pub use refresh_session::*;
+pub use logout_user::*;
+pub use get_user::*;
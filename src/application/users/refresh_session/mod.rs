@@ -0,0 +1,46 @@
+use chrono::{Duration, Utc};
+
+use crate::domain::users::{hash_refresh_token, RefreshRotation, RefreshSecret, Session};
+use crate::domain::users::traits::{RefreshTokens, UserStore};
+use crate::error::ApiError;
+
+pub struct RefreshSessionUseCase<'a, T: UserStore + RefreshTokens + ?Sized> {
+    user_repository: &'a T,
+}
+
+impl<'a, T: UserStore + RefreshTokens + ?Sized> RefreshSessionUseCase<'a, T> {
+    pub fn new(user_repository: &'a T) -> Self {
+        Self { user_repository }
+    }
+
+    /// Exchanges a refresh token for the next one of its family. Presenting
+    /// a token that was already exchanged revokes the family.
+    pub async fn execute(&self, refresh_token: &str, refresh_ttl: Duration) -> Result<Session, ApiError> {
+        let now = Utc::now();
+        let next = RefreshSecret::generate();
+        let rotation = self
+            .user_repository
+            .rotate_refresh_token(&hash_refresh_token(refresh_token), &next.hash, now + refresh_ttl, now)
+            .await?;
+
+        let user_id = match rotation {
+            RefreshRotation::Rotated { user_id } => user_id,
+            RefreshRotation::Reused => {
+                tracing::warn!("a replaced refresh token was presented again; its session is revoked");
+                return Err(ApiError::Unauthorized(
+                    "The refresh token was already used; sign in again.".to_string(),
+                ));
+            }
+            RefreshRotation::Invalid => {
+                return Err(ApiError::Unauthorized("The refresh token is invalid or has expired.".to_string()));
+            }
+        };
+
+        let user = self
+            .user_repository
+            .find_user(user_id)
+            .await?
+            .ok_or_else(|| ApiError::Unauthorized("The account no longer exists.".to_string()))?;
+        Ok(Session { user, refresh_token: next.secret })
+    }
+}
@@ -0,0 +1,22 @@
+use crate::domain::users::validation::normalize_email;
+use crate::domain::users::{RegisterRequest, User};
+use crate::domain::users::traits::{PasswordHasher, UserStore};
+use crate::error::ApiError;
+
+pub struct RegisterUserUseCase<'a, T: UserStore + ?Sized, H: PasswordHasher + ?Sized> {
+    user_repository: &'a T,
+    password_hasher: &'a H,
+}
+
+impl<'a, T: UserStore + ?Sized, H: PasswordHasher + ?Sized> RegisterUserUseCase<'a, T, H> {
+    pub fn new(user_repository: &'a T, password_hasher: &'a H) -> Self {
+        Self { user_repository, password_hasher }
+    }
+
+    /// Creates the account; a taken email is a conflict.
+    pub async fn execute(&self, request: RegisterRequest) -> Result<User, ApiError> {
+        request.validate()?;
+        let password_hash = self.password_hasher.hash(&request.password).await?;
+        self.user_repository.create_user(&normalize_email(&request.email), &password_hash).await
+    }
+}
@@ -0,0 +1,19 @@
+use crate::domain::webhooks::{CreateWebhookRequest, TargetPolicy, Webhook};
+use crate::domain::webhooks::traits::WebhookStore;
+use crate::error::ApiError;
+
+pub struct CreateWebhookUseCase<'a, T: WebhookStore + ?Sized> {
+    webhook_repository: &'a T,
+}
+
+impl<'a, T: WebhookStore + ?Sized> CreateWebhookUseCase<'a, T> {
+    pub fn new(webhook_repository: &'a T) -> Self {
+        Self { webhook_repository }
+    }
+
+    /// Subscribes `request.url`, which must be one `targets` permits.
+    pub async fn execute(&self, request: CreateWebhookRequest, targets: TargetPolicy) -> Result<Webhook, ApiError> {
+        request.validate(targets)?;
+        self.webhook_repository.create(request).await
+    }
+}
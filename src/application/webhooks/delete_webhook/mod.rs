@@ -0,0 +1,18 @@
+use uuid::Uuid;
+
+use crate::domain::webhooks::traits::WebhookStore;
+use crate::error::ApiError;
+
+pub struct DeleteWebhookUseCase<'a, T: WebhookStore + ?Sized> {
+    webhook_repository: &'a T,
+}
+
+impl<'a, T: WebhookStore + ?Sized> DeleteWebhookUseCase<'a, T> {
+    pub fn new(webhook_repository: &'a T) -> Self {
+        Self { webhook_repository }
+    }
+
+    pub async fn execute(&self, id: Uuid) -> Result<(), ApiError> {
+        self.webhook_repository.delete(id).await
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::hash_map::{Entry, HashMap};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::webhooks::{
+    sign, DeliveryOutcome, DeliveryPolicy, DispatchReport, Webhook, WebhookDelivery, WebhookRequest,
+    EVENT_ID_HEADER, EVENT_TYPE_HEADER, SIGNATURE_HEADER,
+};
+use crate::domain::webhooks::traits::{WebhookDeliveries, WebhookSender, WebhookStore};
+use crate::error::ApiError;
+
+pub struct DeliverWebhooksUseCase<'a, T: WebhookStore + WebhookDeliveries + ?Sized, S: WebhookSender + ?Sized> {
+    webhook_repository: &'a T,
+    sender: &'a S,
+}
+
+impl<'a, T: WebhookStore + WebhookDeliveries + ?Sized, S: WebhookSender + ?Sized> DeliverWebhooksUseCase<'a, T, S> {
+    pub fn new(webhook_repository: &'a T, sender: &'a S) -> Self {
+        Self { webhook_repository, sender }
+    }
+
+    /// Attempts up to `batch_size` due deliveries. A 2xx response settles a
+    /// delivery; anything else is retried per `policy.retry` until
+    /// `policy.max_attempts` is reached.
+    pub async fn execute(&self, batch_size: u32, policy: DeliveryPolicy) -> Result<DispatchReport, ApiError> {
+        let now = Utc::now();
+        let deliveries = self.webhook_repository.due_deliveries(batch_size, now).await?;
+        let mut report = DispatchReport { fetched: deliveries.len(), ..DispatchReport::default() };
+        let mut webhooks: HashMap<Uuid, Option<Webhook>> = HashMap::new();
+
+        for delivery in deliveries {
+            let webhook = match webhooks.entry(delivery.webhook_id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(self.webhook_repository.find_by_id(delivery.webhook_id).await?),
+            };
+            // Deleted since the deliveries were fetched.
+            let Some(webhook) = webhook else { continue };
+
+            let outcome = self.attempt(webhook, &delivery, policy).await?;
+            match &outcome {
+                DeliveryOutcome::Succeeded { .. } => report.succeeded += 1,
+                DeliveryOutcome::Failed { response_status, error, retry_at } => {
+                    tracing::warn!(
+                        webhook_id = %webhook.id,
+                        delivery_id = delivery.id,
+                        attempts = delivery.attempts + 1,
+                        response_status,
+                        retry_at = retry_at.map(|at| at.to_rfc3339()),
+                        error,
+                        "webhook delivery failed"
+                    );
+                    report.failed += 1;
+                }
+            }
+            self.webhook_repository.record_attempt(delivery.id, outcome, policy.disable_after).await?;
+        }
+
+        Ok(report)
+    }
+
+    async fn attempt(
+        &self,
+        webhook: &Webhook,
+        delivery: &WebhookDelivery,
+        policy: DeliveryPolicy,
+    ) -> Result<DeliveryOutcome, ApiError> {
+        let body = serde_json::to_vec(&delivery.payload).map_err(anyhow::Error::from)?;
+        let now = Utc::now();
+        let request = WebhookRequest {
+            url: webhook.url.clone(),
+            headers: vec![
+                ("content-type", "application/json".to_string()),
+                (SIGNATURE_HEADER, sign(&webhook.secret, now.timestamp(), &body)),
+                (EVENT_ID_HEADER, delivery.event_id.to_string()),
+                (EVENT_TYPE_HEADER, delivery.event_type.clone()),
+            ],
+            body,
+        };
+
+        let (response_status, error) = match self.sender.send(request).await {
+            Ok(status) if (200..300).contains(&status) => {
+                return Ok(DeliveryOutcome::Succeeded { response_status: status });
+            }
+            Ok(status) => (Some(status), format!("receiver answered {status}")),
+            Err(error) => (None, format!("{error:#}")),
+        };
+
+        let attempts = delivery.attempts + 1;
+        let retry_at = (attempts < policy.max_attempts).then(|| {
+            chrono::Duration::from_std(policy.retry.delay(attempts))
+                .ok()
+                .and_then(|delay| now.checked_add_signed(delay))
+                .unwrap_or(DateTime::<Utc>::MAX_UTC)
+        });
+
+        Ok(DeliveryOutcome::Failed { response_status, error, retry_at })
+    }
+}
@@ -0,0 +1,19 @@
+use uuid::Uuid;
+
+use crate::domain::webhooks::Webhook;
+use crate::domain::webhooks::traits::WebhookStore;
+use crate::error::ApiError;
+
+pub struct GetWebhookUseCase<'a, T: WebhookStore + ?Sized> {
+    webhook_repository: &'a T,
+}
+
+impl<'a, T: WebhookStore + ?Sized> GetWebhookUseCase<'a, T> {
+    pub fn new(webhook_repository: &'a T) -> Self {
+        Self { webhook_repository }
+    }
+
+    pub async fn execute(&self, id: Uuid) -> Result<Option<Webhook>, ApiError> {
+        self.webhook_repository.find_by_id(id).await
+    }
+}
@@ -0,0 +1,23 @@
+use uuid::Uuid;
+
+use crate::domain::todos::{PageQuery, PaginatedResponse};
+use crate::domain::webhooks::WebhookDelivery;
+use crate::domain::webhooks::traits::{WebhookDeliveries, WebhookStore};
+use crate::error::ApiError;
+
+pub struct ListDeliveriesUseCase<'a, T: WebhookStore + WebhookDeliveries + ?Sized> {
+    webhook_repository: &'a T,
+}
+
+impl<'a, T: WebhookStore + WebhookDeliveries + ?Sized> ListDeliveriesUseCase<'a, T> {
+    pub fn new(webhook_repository: &'a T) -> Self {
+        Self { webhook_repository }
+    }
+
+    pub async fn execute(&self, webhook_id: Uuid, query: PageQuery) -> Result<PaginatedResponse<WebhookDelivery>, ApiError> {
+        if self.webhook_repository.find_by_id(webhook_id).await?.is_none() {
+            return Err(ApiError::NotFound);
+        }
+        self.webhook_repository.find_deliveries(webhook_id, query.pagination()).await
+    }
+}
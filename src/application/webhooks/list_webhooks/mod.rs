@@ -0,0 +1,18 @@
+use crate::domain::todos::{PageQuery, PaginatedResponse};
+use crate::domain::webhooks::Webhook;
+use crate::domain::webhooks::traits::WebhookStore;
+use crate::error::ApiError;
+
+pub struct ListWebhooksUseCase<'a, T: WebhookStore + ?Sized> {
+    webhook_repository: &'a T,
+}
+
+impl<'a, T: WebhookStore + ?Sized> ListWebhooksUseCase<'a, T> {
+    pub fn new(webhook_repository: &'a T) -> Self {
+        Self { webhook_repository }
+    }
+
+    pub async fn execute(&self, query: PageQuery) -> Result<PaginatedResponse<Webhook>, ApiError> {
+        self.webhook_repository.find_all_paginated(query.pagination()).await
+    }
+}
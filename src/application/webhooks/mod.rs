@@ -0,0 +1,15 @@
+pub mod create_webhook;
+pub mod get_webhook;
+pub mod list_webhooks;
+pub mod update_webhook;
+pub mod delete_webhook;
+pub mod list_deliveries;
+pub mod deliver_webhooks;
+
+pub use create_webhook::*;
+pub use get_webhook::*;
+pub use list_webhooks::*;
+pub use update_webhook::*;
+pub use delete_webhook::*;
+pub use list_deliveries::*;
+pub use deliver_webhooks::*;
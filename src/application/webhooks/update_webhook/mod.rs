@@ -0,0 +1,21 @@
+use uuid::Uuid;
+
+use crate::domain::webhooks::{TargetPolicy, UpdateWebhookRequest, Webhook};
+use crate::domain::webhooks::traits::WebhookStore;
+use crate::error::ApiError;
+
+pub struct UpdateWebhookUseCase<'a, T: WebhookStore + ?Sized> {
+    webhook_repository: &'a T,
+}
+
+impl<'a, T: WebhookStore + ?Sized> UpdateWebhookUseCase<'a, T> {
+    pub fn new(webhook_repository: &'a T) -> Self {
+        Self { webhook_repository }
+    }
+
+    /// Updates the webhook; a new `url` must be one `targets` permits.
+    pub async fn execute(&self, id: Uuid, request: UpdateWebhookRequest, targets: TargetPolicy) -> Result<Webhook, ApiError> {
+        request.validate(targets)?;
+        self.webhook_repository.update(id, request).await
+    }
+}
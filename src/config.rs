@@ -0,0 +1,733 @@
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use clap::Parser;
+use serde::Deserialize;
+use tracing_subscriber::EnvFilter;
+
+use crate::domain::todos::RetryPolicy;
+use crate::domain::webhooks::{DeliveryPolicy, TargetPolicy};
+
+/// Prefix for environment overrides, e.g. `APP__SERVER__BIND_ADDRESS`.
+pub const ENV_PREFIX: &str = "APP__";
+
+/// Shortest accepted signing secret.
+pub const MIN_SECRET_LENGTH: usize = 32;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ConfigError {
+    #[error("failed to read config file {path}: {source}")]
+    Read { path: PathBuf, source: std::io::Error },
+    #[error("failed to parse config file {path}: {source}")]
+    Parse { path: PathBuf, source: toml::de::Error },
+    #[error("unknown configuration key `{0}`")]
+    UnknownKey(String),
+    #[error("invalid value for `{key}`: {value:?}")]
+    InvalidValue { key: String, value: String },
+    #[error("invalid configuration:\n  - {}", .0.join("\n  - "))]
+    Invalid(Vec<String>),
+}
+
+#[derive(Parser, Debug, Default)]
+#[command(name = "axum-api", about = "Todo API server")]
+pub struct CliArgs {
+    /// Path to a TOML config file
+    #[arg(long, short = 'c')]
+    pub config: Option<PathBuf>,
+    #[arg(long)]
+    pub bind_address: Option<String>,
+    #[arg(long)]
+    pub database_url: Option<String>,
+    #[arg(long)]
+    pub max_connections: Option<u32>,
+    #[arg(long)]
+    pub min_connections: Option<u32>,
+    #[arg(long)]
+    pub acquire_timeout_secs: Option<u64>,
+    #[arg(long)]
+    pub run_migrations: Option<bool>,
+    #[arg(long)]
+    pub log_level: Option<String>,
+    #[arg(long)]
+    pub swagger_ui: Option<bool>,
+    #[arg(long)]
+    pub performance_test: Option<bool>,
+    #[arg(long)]
+    pub require_if_match: Option<bool>,
+    #[arg(long)]
+    pub trash_retention_days: Option<u32>,
+}
+
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub database: DatabaseConfig,
+    pub log: LogConfig,
+    pub pagination: PaginationConfig,
+    pub trash: TrashConfig,
+    pub outbox: OutboxConfig,
+    pub webhooks: WebhooksConfig,
+    pub changes: ChangesConfig,
+    pub websocket: WebSocketConfig,
+    pub auth: AuthConfig,
+    pub tenancy: TenancyConfig,
+    pub features: FeatureToggles,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub bind_address: String,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    pub url: Option<String>,
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub acquire_timeout_secs: u64,
+    pub run_migrations: bool,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogConfig {
+    /// An `EnvFilter` directive such as `info` or `info,sqlx=warn`
+    pub level: String,
+}
+
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct PaginationConfig {
+    /// HMAC key for list cursors. When unset a random key is generated at
+    /// startup, so cursors do not survive restarts or cross instances.
+    pub cursor_secret: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct TrashConfig {
+    /// Days a deleted todo stays restorable before it is purged; 0 keeps it forever
+    pub retention_days: u32,
+    /// How often the background purge runs
+    pub purge_interval_secs: u64,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutboxConfig {
+    /// Where the relay delivers domain events
+    pub publisher: PublisherKind,
+    /// Output of the `file` publisher, one JSON event per line
+    pub file_path: PathBuf,
+    /// How often the relay looks for new messages
+    pub poll_interval_ms: u64,
+    /// Messages published per relay pass
+    pub batch_size: u32,
+    /// Delay before the first retry; doubles with every further failure
+    pub retry_base_ms: u64,
+    /// Upper bound for the retry delay
+    pub retry_max_secs: u64,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebhooksConfig {
+    /// Fan domain events out to webhook subscriptions and run the dispatcher
+    pub enabled: bool,
+    /// How often the dispatcher looks for due deliveries
+    pub poll_interval_ms: u64,
+    /// Deliveries attempted per dispatcher pass
+    pub batch_size: u32,
+    /// How long a receiver has to answer
+    pub timeout_secs: u64,
+    /// Attempts per delivery, the first included
+    pub max_attempts: i32,
+    /// Delay before the first retry; doubles with every further failure
+    pub retry_base_ms: u64,
+    /// Upper bound for the retry delay
+    pub retry_max_secs: u64,
+    /// Failed attempts in a row after which a webhook is disabled
+    pub disable_after_failures: i32,
+    /// Let webhooks target loopback, private and link-local addresses, e.g.
+    /// a receiver on the same host during development
+    pub allow_private_targets: bool,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChangesConfig {
+    /// Recent changes kept for clients resuming with `Last-Event-ID`
+    pub replay_buffer: usize,
+    /// How often the SQLite and in-memory backends check for changes
+    pub poll_interval_ms: u64,
+    /// Interval of the comment lines that keep idle streams open
+    pub keep_alive_secs: u64,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebSocketConfig {
+    /// How often the server pings each client
+    pub ping_interval_secs: u64,
+    /// Silence after which a client is disconnected
+    pub idle_timeout_secs: u64,
+    /// How long a single send may wait on a slow client before it is disconnected
+    pub send_timeout_secs: u64,
+    /// Largest message accepted from a client
+    pub max_message_bytes: usize,
+    /// Subscriptions a single connection may hold
+    pub max_subscriptions: usize,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthConfig {
+    /// HMAC key for access tokens. When unset a random key is generated at
+    /// startup, so every session ends with a restart.
+    pub jwt_secret: Option<String>,
+    /// Lifetime of an access token
+    pub access_token_ttl_secs: u64,
+    /// Lifetime of a refresh token; every refresh issues a new one
+    pub refresh_token_ttl_days: u32,
+}
+
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct TenancyConfig {
+    /// Serve several organisations from one database. Each request names its
+    /// tenant, and Postgres row-level security keeps their todos, webhooks,
+    /// API keys and accounts apart. When disabled every request belongs to
+    /// the `default` tenant.
+    pub enabled: bool,
+    /// When set, `<tenant>.<base_domain>` hosts name their tenant, e.g.
+    /// `acme.todos.example.com` for `todos.example.com`
+    pub base_domain: Option<String>,
+}
+
+/// Built-in event publishers.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PublisherKind {
+    /// The relay does not run; messages stay in the outbox.
+    None,
+    /// JSON lines on standard output
+    Stdout,
+    /// JSON lines appended to `outbox.file_path`
+    File,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeatureToggles {
+    pub swagger_ui: bool,
+    pub performance_test: bool,
+    /// Reject PUT, PATCH and DELETE without `If-Match` (428)
+    pub require_if_match: bool,
+}
+
+/// Storage backend, selected from the scheme of `database.url`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DatabaseBackend {
+    Postgres,
+    /// Requires the `sqlite` cargo feature.
+    Sqlite,
+    Memory,
+}
+
+impl DatabaseConfig {
+    pub fn backend(&self) -> Option<DatabaseBackend> {
+        let url = self.url.as_deref()?;
+        let (scheme, _) = url.split_once(':')?;
+        match scheme {
+            "postgres" | "postgresql" => Some(DatabaseBackend::Postgres),
+            "sqlite" => Some(DatabaseBackend::Sqlite),
+            "memory" => Some(DatabaseBackend::Memory),
+            _ => None,
+        }
+    }
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self { bind_address: "127.0.0.1:3000".to_string() }
+    }
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            max_connections: 10,
+            min_connections: 0,
+            acquire_timeout_secs: 30,
+            run_migrations: true,
+        }
+    }
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self { level: "info".to_string() }
+    }
+}
+
+impl Default for TrashConfig {
+    fn default() -> Self {
+        Self { retention_days: 30, purge_interval_secs: 3600 }
+    }
+}
+
+impl TrashConfig {
+    /// `None` when purging is disabled.
+    pub fn retention(&self) -> Option<chrono::Duration> {
+        (self.retention_days > 0).then(|| chrono::Duration::days(self.retention_days.into()))
+    }
+
+    pub fn purge_interval(&self) -> Duration {
+        Duration::from_secs(self.purge_interval_secs)
+    }
+}
+
+impl Default for OutboxConfig {
+    fn default() -> Self {
+        Self {
+            publisher: PublisherKind::Stdout,
+            file_path: PathBuf::from("events.jsonl"),
+            poll_interval_ms: 1000,
+            batch_size: 100,
+            retry_base_ms: 500,
+            retry_max_secs: 300,
+        }
+    }
+}
+
+impl OutboxConfig {
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms)
+    }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            base: Duration::from_millis(self.retry_base_ms),
+            max: Duration::from_secs(self.retry_max_secs),
+        }
+    }
+}
+
+impl Default for WebhooksConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            poll_interval_ms: 1000,
+            batch_size: 50,
+            timeout_secs: 10,
+            max_attempts: 8,
+            retry_base_ms: 1000,
+            retry_max_secs: 3600,
+            disable_after_failures: 20,
+            allow_private_targets: false,
+        }
+    }
+}
+
+impl Default for ChangesConfig {
+    fn default() -> Self {
+        Self { replay_buffer: 1000, poll_interval_ms: 500, keep_alive_secs: 15 }
+    }
+}
+
+impl ChangesConfig {
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms)
+    }
+
+    pub fn keep_alive(&self) -> Duration {
+        Duration::from_secs(self.keep_alive_secs)
+    }
+}
+
+impl Default for WebSocketConfig {
+    fn default() -> Self {
+        Self {
+            ping_interval_secs: 30,
+            idle_timeout_secs: 90,
+            send_timeout_secs: 10,
+            max_message_bytes: 64 * 1024,
+            max_subscriptions: 100,
+        }
+    }
+}
+
+impl WebSocketConfig {
+    pub fn ping_interval(&self) -> Duration {
+        Duration::from_secs(self.ping_interval_secs)
+    }
+
+    pub fn idle_timeout(&self) -> Duration {
+        Duration::from_secs(self.idle_timeout_secs)
+    }
+
+    pub fn send_timeout(&self) -> Duration {
+        Duration::from_secs(self.send_timeout_secs)
+    }
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self { jwt_secret: None, access_token_ttl_secs: 900, refresh_token_ttl_days: 30 }
+    }
+}
+
+impl AuthConfig {
+    pub fn access_token_ttl(&self) -> Duration {
+        Duration::from_secs(self.access_token_ttl_secs)
+    }
+
+    pub fn refresh_token_ttl(&self) -> chrono::Duration {
+        chrono::Duration::days(self.refresh_token_ttl_days.into())
+    }
+}
+
+impl WebhooksConfig {
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms)
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
+
+    pub fn delivery_policy(&self) -> DeliveryPolicy {
+        DeliveryPolicy {
+            retry: RetryPolicy {
+                base: Duration::from_millis(self.retry_base_ms),
+                max: Duration::from_secs(self.retry_max_secs),
+            },
+            max_attempts: self.max_attempts,
+            disable_after: self.disable_after_failures,
+        }
+    }
+
+    pub fn target_policy(&self) -> TargetPolicy {
+        TargetPolicy { allow_private: self.allow_private_targets }
+    }
+}
+
+impl std::str::FromStr for PublisherKind {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "none" => Ok(PublisherKind::None),
+            "stdout" => Ok(PublisherKind::Stdout),
+            "file" => Ok(PublisherKind::File),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Default for FeatureToggles {
+    fn default() -> Self {
+        Self { swagger_ui: true, performance_test: true, require_if_match: false }
+    }
+}
+
+impl Config {
+    /// Loads defaults, then the TOML file, then `APP__*` environment
+    /// variables, then CLI flags, and validates the result.
+    pub fn load(cli: &CliArgs) -> Result<Self, ConfigError> {
+        let env: Vec<(String, String)> = std::env::vars().collect();
+        let path = cli
+            .config
+            .clone()
+            .or_else(|| env_value(&env, "APP_CONFIG").map(PathBuf::from));
+
+        let mut config = match path {
+            Some(path) => Self::from_file(&path)?,
+            None => Self::default(),
+        };
+        config.apply_env(env)?;
+        config.apply_cli(cli);
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|source| ConfigError::Read { path: path.to_path_buf(), source })?;
+        toml::from_str(&contents)
+            .map_err(|source| ConfigError::Parse { path: path.to_path_buf(), source })
+    }
+
+    /// Applies `APP__SECTION__KEY` overrides. A plain `DATABASE_URL` is also
+    /// honoured, with lower precedence than `APP__DATABASE__URL`.
+    pub fn apply_env<I>(&mut self, vars: I) -> Result<(), ConfigError>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let vars: Vec<(String, String)> = vars.into_iter().collect();
+
+        if let Some(url) = env_value(&vars, "DATABASE_URL") {
+            self.database.url = Some(url.to_string());
+        }
+
+        for (name, value) in &vars {
+            if let Some(key) = name.strip_prefix(ENV_PREFIX) {
+                let key = key.to_ascii_lowercase().replace("__", ".");
+                self.set(&key, value)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn apply_cli(&mut self, cli: &CliArgs) {
+        if let Some(value) = &cli.bind_address {
+            self.server.bind_address = value.clone();
+        }
+        if let Some(value) = &cli.database_url {
+            self.database.url = Some(value.clone());
+        }
+        if let Some(value) = cli.max_connections {
+            self.database.max_connections = value;
+        }
+        if let Some(value) = cli.min_connections {
+            self.database.min_connections = value;
+        }
+        if let Some(value) = cli.acquire_timeout_secs {
+            self.database.acquire_timeout_secs = value;
+        }
+        if let Some(value) = cli.run_migrations {
+            self.database.run_migrations = value;
+        }
+        if let Some(value) = &cli.log_level {
+            self.log.level = value.clone();
+        }
+        if let Some(value) = cli.swagger_ui {
+            self.features.swagger_ui = value;
+        }
+        if let Some(value) = cli.performance_test {
+            self.features.performance_test = value;
+        }
+        if let Some(value) = cli.require_if_match {
+            self.features.require_if_match = value;
+        }
+        if let Some(value) = cli.trash_retention_days {
+            self.trash.retention_days = value;
+        }
+    }
+
+    /// Sets a single value by its dotted key, e.g. `database.max_connections`.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), ConfigError> {
+        match key {
+            "server.bind_address" => self.server.bind_address = value.to_string(),
+            "database.url" => self.database.url = Some(value.to_string()),
+            "database.max_connections" => self.database.max_connections = parse(key, value)?,
+            "database.min_connections" => self.database.min_connections = parse(key, value)?,
+            "database.acquire_timeout_secs" => {
+                self.database.acquire_timeout_secs = parse(key, value)?
+            }
+            "database.run_migrations" => self.database.run_migrations = parse(key, value)?,
+            "log.level" => self.log.level = value.to_string(),
+            "pagination.cursor_secret" => self.pagination.cursor_secret = Some(value.to_string()),
+            "trash.retention_days" => self.trash.retention_days = parse(key, value)?,
+            "trash.purge_interval_secs" => self.trash.purge_interval_secs = parse(key, value)?,
+            "outbox.publisher" => self.outbox.publisher = parse(key, value)?,
+            "outbox.file_path" => self.outbox.file_path = PathBuf::from(value),
+            "outbox.poll_interval_ms" => self.outbox.poll_interval_ms = parse(key, value)?,
+            "outbox.batch_size" => self.outbox.batch_size = parse(key, value)?,
+            "outbox.retry_base_ms" => self.outbox.retry_base_ms = parse(key, value)?,
+            "outbox.retry_max_secs" => self.outbox.retry_max_secs = parse(key, value)?,
+            "webhooks.enabled" => self.webhooks.enabled = parse(key, value)?,
+            "webhooks.poll_interval_ms" => self.webhooks.poll_interval_ms = parse(key, value)?,
+            "webhooks.batch_size" => self.webhooks.batch_size = parse(key, value)?,
+            "webhooks.timeout_secs" => self.webhooks.timeout_secs = parse(key, value)?,
+            "webhooks.max_attempts" => self.webhooks.max_attempts = parse(key, value)?,
+            "webhooks.retry_base_ms" => self.webhooks.retry_base_ms = parse(key, value)?,
+            "webhooks.retry_max_secs" => self.webhooks.retry_max_secs = parse(key, value)?,
+            "webhooks.disable_after_failures" => self.webhooks.disable_after_failures = parse(key, value)?,
+            "webhooks.allow_private_targets" => self.webhooks.allow_private_targets = parse(key, value)?,
+            "changes.replay_buffer" => self.changes.replay_buffer = parse(key, value)?,
+            "changes.poll_interval_ms" => self.changes.poll_interval_ms = parse(key, value)?,
+            "changes.keep_alive_secs" => self.changes.keep_alive_secs = parse(key, value)?,
+            "websocket.ping_interval_secs" => self.websocket.ping_interval_secs = parse(key, value)?,
+            "websocket.idle_timeout_secs" => self.websocket.idle_timeout_secs = parse(key, value)?,
+            "websocket.send_timeout_secs" => self.websocket.send_timeout_secs = parse(key, value)?,
+            "websocket.max_message_bytes" => self.websocket.max_message_bytes = parse(key, value)?,
+            "websocket.max_subscriptions" => self.websocket.max_subscriptions = parse(key, value)?,
+            "auth.jwt_secret" => self.auth.jwt_secret = Some(value.to_string()),
+            "auth.access_token_ttl_secs" => self.auth.access_token_ttl_secs = parse(key, value)?,
+            "auth.refresh_token_ttl_days" => self.auth.refresh_token_ttl_days = parse(key, value)?,
+            "tenancy.enabled" => self.tenancy.enabled = parse(key, value)?,
+            "tenancy.base_domain" => self.tenancy.base_domain = Some(value.to_string()),
+            "features.swagger_ui" => self.features.swagger_ui = parse(key, value)?,
+            "features.performance_test" => self.features.performance_test = parse(key, value)?,
+            "features.require_if_match" => self.features.require_if_match = parse(key, value)?,
+            _ => return Err(ConfigError::UnknownKey(key.to_string())),
+        }
+        Ok(())
+    }
+
+    /// Collects every problem rather than stopping at the first one.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut errors = Vec::new();
+
+        if self.server.bind_address.parse::<SocketAddr>().is_err() {
+            errors.push(format!(
+                "server.bind_address `{}` is not a valid socket address",
+                self.server.bind_address
+            ));
+        }
+
+        match self.database.url.as_deref() {
+            None | Some("") => errors.push(
+                "database.url is required (set DATABASE_URL, APP__DATABASE__URL or --database-url)"
+                    .to_string(),
+            ),
+            Some(_) => match self.database.backend() {
+                None => errors.push(
+                    "database.url must use the postgres://, sqlite: or memory:// scheme".to_string(),
+                ),
+                Some(DatabaseBackend::Sqlite) if !cfg!(feature = "sqlite") => errors.push(
+                    "database.url uses sqlite: but this binary was built without the `sqlite` feature"
+                        .to_string(),
+                ),
+                Some(_) => {}
+            },
+        }
+
+        if self.database.max_connections == 0 {
+            errors.push("database.max_connections must be at least 1".to_string());
+        }
+        if self.database.min_connections > self.database.max_connections {
+            errors.push(
+                "database.min_connections must not exceed database.max_connections".to_string(),
+            );
+        }
+        if self.database.acquire_timeout_secs == 0 {
+            errors.push("database.acquire_timeout_secs must be greater than 0".to_string());
+        }
+
+        if EnvFilter::try_new(&self.log.level).is_err() {
+            errors.push(format!("log.level `{}` is not a valid filter directive", self.log.level));
+        }
+
+        if self.pagination.cursor_secret.as_ref().is_some_and(|secret| secret.len() < MIN_SECRET_LENGTH) {
+            errors.push(format!(
+                "pagination.cursor_secret must be at least {MIN_SECRET_LENGTH} bytes"
+            ));
+        }
+
+        if self.trash.purge_interval_secs == 0 {
+            errors.push("trash.purge_interval_secs must be greater than 0".to_string());
+        }
+
+        if self.outbox.poll_interval_ms == 0 {
+            errors.push("outbox.poll_interval_ms must be greater than 0".to_string());
+        }
+        if !(1..=1000).contains(&self.outbox.batch_size) {
+            errors.push("outbox.batch_size must be between 1 and 1000".to_string());
+        }
+        if self.outbox.retry_base_ms == 0 {
+            errors.push("outbox.retry_base_ms must be greater than 0".to_string());
+        }
+        if self.outbox.retry_max_secs.saturating_mul(1000) < self.outbox.retry_base_ms {
+            errors.push("outbox.retry_max_secs must not be shorter than outbox.retry_base_ms".to_string());
+        }
+
+        if self.webhooks.poll_interval_ms == 0 {
+            errors.push("webhooks.poll_interval_ms must be greater than 0".to_string());
+        }
+        if !(1..=1000).contains(&self.webhooks.batch_size) {
+            errors.push("webhooks.batch_size must be between 1 and 1000".to_string());
+        }
+        if self.webhooks.timeout_secs == 0 {
+            errors.push("webhooks.timeout_secs must be greater than 0".to_string());
+        }
+        if self.webhooks.max_attempts < 1 {
+            errors.push("webhooks.max_attempts must be at least 1".to_string());
+        }
+        if self.webhooks.retry_base_ms == 0 {
+            errors.push("webhooks.retry_base_ms must be greater than 0".to_string());
+        }
+        if self.webhooks.retry_max_secs.saturating_mul(1000) < self.webhooks.retry_base_ms {
+            errors.push("webhooks.retry_max_secs must not be shorter than webhooks.retry_base_ms".to_string());
+        }
+        if self.webhooks.disable_after_failures < 1 {
+            errors.push("webhooks.disable_after_failures must be at least 1".to_string());
+        }
+
+        if self.changes.replay_buffer > 100_000 {
+            errors.push("changes.replay_buffer must be at most 100000".to_string());
+        }
+        if self.changes.poll_interval_ms == 0 {
+            errors.push("changes.poll_interval_ms must be greater than 0".to_string());
+        }
+        if self.changes.keep_alive_secs == 0 {
+            errors.push("changes.keep_alive_secs must be greater than 0".to_string());
+        }
+
+        if self.websocket.ping_interval_secs == 0 {
+            errors.push("websocket.ping_interval_secs must be greater than 0".to_string());
+        }
+        if self.websocket.idle_timeout_secs <= self.websocket.ping_interval_secs {
+            errors.push("websocket.idle_timeout_secs must be longer than websocket.ping_interval_secs".to_string());
+        }
+        if self.websocket.send_timeout_secs == 0 {
+            errors.push("websocket.send_timeout_secs must be greater than 0".to_string());
+        }
+        if self.websocket.max_message_bytes < 1024 {
+            errors.push("websocket.max_message_bytes must be at least 1024".to_string());
+        }
+        if self.websocket.max_subscriptions < 1 {
+            errors.push("websocket.max_subscriptions must be at least 1".to_string());
+        }
+
+        if self.auth.jwt_secret.as_ref().is_some_and(|secret| secret.len() < MIN_SECRET_LENGTH) {
+            errors.push(format!("auth.jwt_secret must be at least {MIN_SECRET_LENGTH} bytes"));
+        }
+        if self.auth.access_token_ttl_secs == 0 {
+            errors.push("auth.access_token_ttl_secs must be greater than 0".to_string());
+        }
+        if self.auth.refresh_token_ttl_days < 1 {
+            errors.push("auth.refresh_token_ttl_days must be at least 1".to_string());
+        }
+
+        if self.tenancy.enabled && self.database.backend() == Some(DatabaseBackend::Sqlite) {
+            errors.push("tenancy.enabled needs the postgres:// or memory:// backend; SQLite has no row-level security".to_string());
+        }
+        if let Some(domain) = &self.tenancy.base_domain
+            && (domain.is_empty() || domain.starts_with('.') || domain.contains(['/', ':']))
+        {
+            errors.push(format!("tenancy.base_domain `{domain}` must be a bare domain such as `todos.example.com`"));
+        }
+
+        if errors.is_empty() { Ok(()) } else { Err(ConfigError::Invalid(errors)) }
+    }
+
+    pub fn socket_addr(&self) -> SocketAddr {
+        self.server
+            .bind_address
+            .parse()
+            .expect("bind address is checked by Config::validate")
+    }
+
+    pub fn acquire_timeout(&self) -> Duration {
+        Duration::from_secs(self.database.acquire_timeout_secs)
+    }
+}
+
+fn env_value<'a>(vars: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    vars.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+}
+
+fn parse<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, ConfigError> {
+    value.parse().map_err(|_| ConfigError::InvalidValue {
+        key: key.to_string(),
+        value: value.to_string(),
+    })
+}
@@ -1,26 +1,230 @@
-use utoipa::OpenApi;
+use utoipa::openapi::{
+    path::PathItemType,
+    security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme},
+    Content, Ref, RefOr, ResponseBuilder,
+};
+use serde::Serialize;
+
+use crate::domain::todos::{TodoCursorPage, TodoPage};
+use utoipa::{Modify, OpenApi, ToSchema};
 
 #[derive(OpenApi)]
 #[openapi(
            paths(
                crate::api::handlers::health::health,
+               crate::api::handlers::auth_handlers::register,
+               crate::api::handlers::auth_handlers::login,
+               crate::api::handlers::auth_handlers::refresh,
+               crate::api::handlers::auth_handlers::logout,
+               crate::api::handlers::auth_handlers::me,
+               crate::api::handlers::api_key_handlers::create_api_key,
+               crate::api::handlers::api_key_handlers::list_api_keys,
+               crate::api::handlers::api_key_handlers::get_api_key,
+               crate::api::handlers::api_key_handlers::revoke_api_key,
                crate::api::handlers::todo_handlers::create_todo,
                crate::api::handlers::todo_handlers::list_todos,
+               crate::api::handlers::todo_handlers::search_todos,
                crate::api::handlers::todo_handlers::get_todo,
                crate::api::handlers::todo_handlers::update_todo,
+               crate::api::handlers::todo_handlers::patch_todo,
                crate::api::handlers::todo_handlers::delete_todo,
-               crate::api::handlers::todo_handlers::get_todos_by_done
+               crate::api::handlers::todo_handlers::batch_todos,
+               crate::api::handlers::todo_handlers::list_trash,
+               crate::api::handlers::todo_handlers::restore_todo,
+               crate::api::handlers::todo_handlers::purge_todo,
+               crate::api::handlers::todo_handlers::get_todo_history,
+               crate::api::handlers::todo_handlers::list_todo_access,
+               crate::api::handlers::todo_handlers::grant_todo_access,
+               crate::api::handlers::todo_handlers::revoke_todo_access,
+               crate::api::handlers::todo_handlers::todo_events,
+               crate::api::handlers::todo_handlers::get_todos_by_done,
+               crate::api::handlers::list_handlers::create_list,
+               crate::api::handlers::list_handlers::list_lists,
+               crate::api::handlers::list_handlers::get_list,
+               crate::api::handlers::list_handlers::update_list,
+               crate::api::handlers::list_handlers::delete_list,
+               crate::api::handlers::list_handlers::archive_list,
+               crate::api::handlers::list_handlers::unarchive_list,
+               crate::api::handlers::list_handlers::list_list_todos,
+               crate::api::handlers::tag_handlers::create_tag,
+               crate::api::handlers::tag_handlers::list_tags,
+               crate::api::handlers::tag_handlers::rename_tag,
+               crate::api::handlers::tag_handlers::delete_tag,
+               crate::api::handlers::tag_handlers::attach_tag,
+               crate::api::handlers::tag_handlers::detach_tag,
+               crate::api::handlers::webhook_handlers::create_webhook,
+               crate::api::handlers::webhook_handlers::list_webhooks,
+               crate::api::handlers::webhook_handlers::get_webhook,
+               crate::api::handlers::webhook_handlers::update_webhook,
+               crate::api::handlers::webhook_handlers::delete_webhook,
+               crate::api::handlers::webhook_handlers::list_deliveries,
+               crate::api::handlers::ws_handlers::todo_socket
            ),
     components(
         schemas(
             crate::domain::todos::Todo,
             crate::domain::todos::CreateTodoRequest,
-            crate::domain::todos::UpdateTodoRequest,
+            crate::domain::todos::ReplaceTodoRequest,
             crate::domain::todos::PaginationQuery,
-            crate::domain::todos::PaginatedResponse<crate::domain::todos::Todo>,
-            crate::domain::todos::PaginationMeta
+            crate::domain::todos::TodoPage,
+            crate::domain::todos::PaginationMeta,
+            crate::domain::todos::TodoCursorPage,
+            crate::domain::todos::TodoSearchHit,
+            crate::domain::todos::TodoSearchPage,
+            crate::domain::todos::CursorMeta,
+            crate::domain::todos::BatchRequest,
+            crate::domain::todos::BatchMode,
+            crate::domain::todos::BatchOperation,
+            crate::domain::todos::BatchItemResult,
+            crate::domain::todos::BatchResponse,
+            crate::domain::todos::TrashedTodo,
+            crate::domain::todos::TrashPage,
+            crate::domain::todos::TodoEvent,
+            crate::domain::todos::TodoEventKind,
+            crate::domain::todos::FieldChange,
+            crate::domain::todos::TodoHistoryPage,
+            crate::domain::todos::TodoRole,
+            crate::domain::todos::TodoPriority,
+            crate::domain::todos::TodoShare,
+            crate::domain::todos::GrantAccessRequest,
+            crate::domain::lists::TodoList,
+            crate::domain::lists::CreateTodoListRequest,
+            crate::domain::lists::UpdateTodoListRequest,
+            crate::domain::todos::TodoListPage,
+            crate::domain::todos::TagMatch,
+            crate::domain::tags::Tag,
+            crate::domain::tags::TagRef,
+            crate::domain::tags::CreateTagRequest,
+            crate::domain::tags::RenameTagRequest,
+            crate::domain::todos::TagPage,
+            crate::domain::users::User,
+            crate::domain::users::RegisterRequest,
+            crate::domain::users::LoginRequest,
+            crate::domain::users::RefreshRequest,
+            crate::domain::users::TokenResponse,
+            crate::domain::api_keys::ApiKey,
+            crate::domain::api_keys::ApiKeyScope,
+            crate::domain::api_keys::CreateApiKeyRequest,
+            crate::domain::api_keys::CreatedApiKey,
+            crate::domain::todos::ApiKeyPage,
+            crate::domain::webhooks::Webhook,
+            crate::domain::webhooks::CreateWebhookRequest,
+            crate::domain::webhooks::UpdateWebhookRequest,
+            crate::domain::webhooks::WebhookDelivery,
+            crate::domain::webhooks::DeliveryStatus,
+            crate::domain::todos::WebhookPage,
+            crate::domain::todos::WebhookDeliveryPage,
+            TodoListResponse,
+            crate::domain::todos::validation::FieldError,
+            crate::error::ProblemDetails,
+            TodoMergePatch,
+            JsonPatchOperation
         )
     ),
-    tags((name = "todos", description = "Todo operations"))
+    modifiers(&JsonPatchContentType, &Authentication),
+    tags(
+        (name = "auth", description = "Accounts and sessions"),
+        (name = "todos", description = "Todo operations"),
+        (name = "lists", description = "Lists grouping todos"),
+        (name = "tags", description = "Labels attached to todos"),
+        (name = "webhooks", description = "Subscriptions to todo events")
+    )
 )]
-pub struct ApiDoc;
\ No newline at end of file
+pub struct ApiDoc;
+
+/// `GET /todos` returns the page-number shape unless `cursor` is given.
+#[derive(Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum TodoListResponse {
+    Page(TodoPage),
+    Cursor(TodoCursorPage),
+}
+
+/// RFC 7396 merge patch for a todo: present fields are replaced, absent
+/// fields are left untouched. `null` is rejected for non-nullable fields.
+#[derive(ToSchema)]
+pub struct TodoMergePatch {
+    pub title: Option<String>,
+    pub done: Option<bool>,
+}
+
+/// One RFC 6902 operation; a JSON Patch document is an array of these.
+#[derive(ToSchema)]
+pub struct JsonPatchOperation {
+    /// `add`, `remove`, `replace`, `move`, `copy` or `test`
+    pub op: String,
+    /// JSON Pointer to the target field, e.g. `/title`
+    pub path: String,
+    pub value: Option<serde_json::Value>,
+    /// Source pointer for `move` and `copy`
+    pub from: Option<String>,
+}
+
+/// `#[utoipa::path]` accepts a single request content type, so the JSON Patch
+/// alternative for `PATCH /todos/{id}` is added here.
+struct JsonPatchContentType;
+
+impl Modify for JsonPatchContentType {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let body = openapi
+            .paths
+            .paths
+            .get_mut("/todos/{id}")
+            .and_then(|item| item.operations.get_mut(&PathItemType::Patch))
+            .and_then(|operation| operation.request_body.as_mut());
+
+        if let Some(body) = body {
+            let schema = utoipa::openapi::schema::ArrayBuilder::new()
+                .items(Ref::from_schema_name("JsonPatchOperation"))
+                .build();
+            body.content.insert("application/json-patch+json".to_string(), Content::new(schema));
+        }
+    }
+}
+
+/// Paths served without credentials; every other path requires an access
+/// token or an API key.
+pub const PUBLIC_PATHS: [&str; 5] = ["/health", "/auth/register", "/auth/login", "/auth/refresh", "/auth/logout"];
+
+/// Declares the bearer and API key schemes, and their 401 and 403 responses,
+/// on every protected operation; the route layer that enforces them is
+/// invisible to utoipa.
+struct Authentication;
+
+impl Modify for Authentication {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            let scheme = HttpBuilder::new().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build();
+            components.add_security_scheme("bearer", SecurityScheme::Http(scheme));
+            let api_key = ApiKeyValue::with_description("Authorization", "`ApiKey <key>`, for keys from `POST /api-keys`");
+            components.add_security_scheme("api_key", SecurityScheme::ApiKey(ApiKey::Header(api_key)));
+        }
+
+        let problem = || Content::new(Ref::from_schema_name("ProblemDetails"));
+        let unauthorized = ResponseBuilder::new()
+            .description("Missing or invalid access token or API key")
+            .content("application/problem+json", problem())
+            .build();
+        let forbidden = ResponseBuilder::new()
+            .description("The API key's scope does not allow this request")
+            .content("application/problem+json", problem())
+            .build();
+
+        for (path, item) in openapi.paths.paths.iter_mut() {
+            if PUBLIC_PATHS.contains(&path.as_str()) {
+                continue;
+            }
+            for (method, operation) in item.operations.iter_mut() {
+                operation.security = Some(vec![
+                    SecurityRequirement::new("bearer", Vec::<String>::new()),
+                    SecurityRequirement::new("api_key", Vec::<String>::new()),
+                ]);
+                let responses = &mut operation.responses.responses;
+                responses.insert("401".to_string(), RefOr::T(unauthorized.clone()));
+                if !matches!(method, PathItemType::Get | PathItemType::Head) {
+                    responses.entry("403".to_string()).or_insert_with(|| RefOr::T(forbidden.clone()));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+use crate::domain::api_keys::ApiKeyScope;
+
+/// A long-lived credential for scripts and services, sent as
+/// `Authorization: ApiKey <key>`. Only a hash of the key is stored.
+#[derive(Serialize, Clone, Debug, ToSchema, FromRow)]
+pub struct ApiKey {
+    pub id: Uuid,
+    /// The user the key acts as
+    pub user_id: Uuid,
+    pub name: String,
+    /// The start of the key, to tell keys apart
+    pub prefix: String,
+    #[sqlx(try_from = "String")]
+    pub scope: ApiKeyScope,
+    /// When the key stops working; absent means never
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Last successful authentication with the key
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    /// The organisation the key was created in, the only one it works in
+    pub tenant_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ApiKey {
+    pub fn is_usable(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+}
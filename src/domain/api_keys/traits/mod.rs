@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::api_keys::{ApiKey, NewApiKey};
+use crate::domain::todos::{PaginatedResponse, PaginationQuery};
+use crate::error::ApiError;
+
+/// API keys, always looked up within one user's keys except when
+/// authenticating. A missing `id` yields `ApiError::NotFound`.
+#[async_trait]
+pub trait ApiKeyRepository: Send + Sync {
+    async fn create(&self, key: NewApiKey) -> Result<ApiKey, ApiError>;
+
+    async fn find_by_id(&self, user_id: Uuid, id: Uuid) -> Result<Option<ApiKey>, ApiError>;
+
+    /// Reads one page of the user's keys, revoked and expired ones included,
+    /// oldest first.
+    async fn find_all_paginated(&self, user_id: Uuid, pagination: PaginationQuery) -> Result<PaginatedResponse<ApiKey>, ApiError>;
+
+    /// Revokes the key at `now`; revoking it again keeps the first time.
+    async fn revoke(&self, user_id: Uuid, id: Uuid, now: DateTime<Utc>) -> Result<ApiKey, ApiError>;
+
+    /// The key hashed as `key_hash`, if it is usable at `now`, recording
+    /// `now` as its last use.
+    async fn authenticate(&self, key_hash: &str, now: DateTime<Utc>) -> Result<Option<ApiKey>, ApiError>;
+}
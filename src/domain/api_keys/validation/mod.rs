@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::api_keys::CreateApiKeyRequest;
+use crate::domain::todos::validation::FieldError;
+use crate::error::ApiError;
+
+pub const NAME_MAX_LENGTH: usize = 100;
+
+impl CreateApiKeyRequest {
+    pub fn validate(&self, now: DateTime<Utc>) -> Result<(), ApiError> {
+        let mut errors = Vec::new();
+
+        if self.name.trim().is_empty() {
+            errors.push(FieldError::new("name", "blank", "name must not be empty"));
+        } else if self.name.chars().count() > NAME_MAX_LENGTH {
+            errors.push(FieldError::new("name", "too_long", format!("name must be at most {NAME_MAX_LENGTH} characters")));
+        } else if self.name.chars().any(char::is_control) {
+            errors.push(FieldError::new("name", "control_characters", "name must not contain control characters"));
+        }
+        if self.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            errors.push(FieldError::new("expires_at", "in_past", "expires_at must be in the future"));
+        }
+
+        if errors.is_empty() { Ok(()) } else { Err(ApiError::Validation(errors)) }
+    }
+}
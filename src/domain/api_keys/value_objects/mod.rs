@@ -0,0 +1,81 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::api_keys::ApiKey;
+
+pub mod secret;
+
+pub use secret::*;
+
+/// What a key may do. Each scope includes the ones before it.
+#[derive(Serialize, Deserialize, ToSchema, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyScope {
+    /// `GET` requests only
+    #[default]
+    ReadOnly,
+    /// Also create, change and delete todos and webhooks
+    ReadWrite,
+    /// Also manage API keys; signed-in users always have this scope
+    Admin,
+}
+
+impl ApiKeyScope {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ApiKeyScope::ReadOnly => "read_only",
+            ApiKeyScope::ReadWrite => "read_write",
+            ApiKeyScope::Admin => "admin",
+        }
+    }
+
+    pub fn includes(self, required: ApiKeyScope) -> bool {
+        self >= required
+    }
+}
+
+impl TryFrom<String> for ApiKeyScope {
+    type Error = String;
+
+    fn try_from(scope: String) -> Result<Self, Self::Error> {
+        match scope.as_str() {
+            "read_only" => Ok(ApiKeyScope::ReadOnly),
+            "read_write" => Ok(ApiKeyScope::ReadWrite),
+            "admin" => Ok(ApiKeyScope::Admin),
+            _ => Err(format!("unknown API key scope `{scope}`")),
+        }
+    }
+}
+
+#[derive(Deserialize, ToSchema, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CreateApiKeyRequest {
+    /// What the key is for, e.g. `nightly export`; 1 to 100 characters
+    pub name: String,
+    /// Defaults to `read_only`
+    #[serde(default)]
+    pub scope: ApiKeyScope,
+    /// When the key stops working; omit for a key that does not expire
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// A key as stored by `ApiKeyRepository::create`.
+#[derive(Clone, Debug)]
+pub struct NewApiKey {
+    pub user_id: Uuid,
+    pub name: String,
+    pub scope: ApiKeyScope,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub prefix: String,
+    pub key_hash: String,
+}
+
+/// Response to `POST /api-keys`, the only one that contains the key.
+#[derive(Serialize, ToSchema, Clone, Debug)]
+pub struct CreatedApiKey {
+    /// Send as `Authorization: ApiKey <key>`; it cannot be shown again
+    pub key: String,
+    pub api_key: ApiKey,
+}
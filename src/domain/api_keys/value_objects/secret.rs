@@ -0,0 +1,34 @@
+//! Keys look like `ak_1f0c9e2b_<43 random characters>`. The part before the
+//! second underscore is kept in clear so users can tell their keys apart;
+//! the whole key is only ever stored as its SHA-256 hash.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+pub const API_KEY_PREFIX: &str = "ak_";
+
+/// A freshly minted key: `key` goes to the client, the rest to the store.
+#[derive(Clone, Debug)]
+pub struct ApiKeySecret {
+    pub key: String,
+    pub prefix: String,
+    pub hash: String,
+}
+
+impl ApiKeySecret {
+    pub fn generate() -> Self {
+        let mut id = [0u8; 4];
+        let mut secret = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut id);
+        rand::thread_rng().fill_bytes(&mut secret);
+
+        let prefix = format!("{API_KEY_PREFIX}{}", hex::encode(id));
+        let key = format!("{prefix}_{}", URL_SAFE_NO_PAD.encode(secret));
+        Self { hash: hash_api_key(&key), prefix, key }
+    }
+}
+
+pub fn hash_api_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
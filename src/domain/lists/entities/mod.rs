@@ -0,0 +1,3 @@
+pub mod todo_list;
+
+pub use todo_list::*;
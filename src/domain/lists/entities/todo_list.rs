@@ -0,0 +1,23 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// A named group of todos. Todos join a list when they are created.
+#[derive(Serialize, Clone, Debug, ToSchema, FromRow)]
+pub struct TodoList {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    /// `#rrggbb` hex colour, for clients to tell lists apart
+    pub color: Option<String>,
+    /// Archived lists and their todos are left out of the default listings
+    pub archived: bool,
+    /// The user who created the list
+    pub owner_id: Option<Uuid>,
+    /// The organisation the list belongs to
+    pub tenant_id: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
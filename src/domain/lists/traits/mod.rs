@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::lists::{CreateTodoListRequest, TodoList, UpdateTodoListRequest};
+use crate::domain::todos::{PaginatedResponse, PaginationQuery};
+use crate::error::ApiError;
+
+/// Todo lists of the current user; background tasks see every list. A
+/// missing `id`, or another user's list, yields `ApiError::NotFound`.
+#[async_trait]
+pub trait TodoListStore {
+    async fn create(&self, data: CreateTodoListRequest) -> Result<TodoList, ApiError>;
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<TodoList>, ApiError>;
+
+    /// Reads one page of the lists whose `archived` flag matches, oldest
+    /// first.
+    async fn find_all_paginated(&self, archived: bool, pagination: PaginationQuery) -> Result<PaginatedResponse<TodoList>, ApiError>;
+
+    /// Applies the given fields.
+    async fn update(&self, id: Uuid, data: UpdateTodoListRequest) -> Result<TodoList, ApiError>;
+
+    /// Removes the list. Todos still in it, live or trashed, stay behind
+    /// without a list.
+    async fn delete(&self, id: Uuid) -> Result<(), ApiError>;
+}
+
+#[async_trait]
+pub trait TodoListArchiver {
+    /// Archives the list, or restores it when `archived` is `false`. While
+    /// it is archived, todo listings without a `list_id` filter leave its
+    /// todos out.
+    async fn set_archived(&self, id: Uuid, archived: bool) -> Result<TodoList, ApiError>;
+}
+
+/// Every todo list capability in one object-safe trait.
+pub trait TodoListRepository: TodoListStore + TodoListArchiver + Send + Sync {}
+
+impl<T> TodoListRepository for T where T: TodoListStore + TodoListArchiver + Send + Sync {}
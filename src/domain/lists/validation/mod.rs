@@ -0,0 +1,75 @@
+use crate::domain::lists::{CreateTodoListRequest, UpdateTodoListRequest};
+use crate::domain::todos::validation::FieldError;
+use crate::error::ApiError;
+
+/// Counted in characters, not bytes.
+pub const NAME_MAX_LENGTH: usize = 100;
+pub const DESCRIPTION_MAX_LENGTH: usize = 1000;
+
+fn validate_name(name: &str, errors: &mut Vec<FieldError>) {
+    if name.trim().is_empty() {
+        errors.push(FieldError::new("name", "blank", "name must not be empty"));
+    } else if name.chars().count() > NAME_MAX_LENGTH {
+        errors.push(FieldError::new("name", "too_long", format!("name must be at most {NAME_MAX_LENGTH} characters")));
+    }
+
+    if name.chars().any(char::is_control) {
+        errors.push(FieldError::new("name", "control_characters", "name must not contain control characters"));
+    }
+}
+
+fn validate_description(description: &str, errors: &mut Vec<FieldError>) {
+    if description.chars().count() > DESCRIPTION_MAX_LENGTH {
+        errors.push(FieldError::new(
+            "description",
+            "too_long",
+            format!("description must be at most {DESCRIPTION_MAX_LENGTH} characters"),
+        ));
+    }
+}
+
+fn validate_color(color: &str, errors: &mut Vec<FieldError>) {
+    let hex = color.strip_prefix('#').unwrap_or_default();
+    if hex.len() != 6 || !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        errors.push(FieldError::new("color", "invalid", "color must be a hex colour such as `#1e90ff`"));
+    }
+}
+
+fn into_result(errors: Vec<FieldError>) -> Result<(), ApiError> {
+    if errors.is_empty() { Ok(()) } else { Err(ApiError::Validation(errors)) }
+}
+
+impl CreateTodoListRequest {
+    pub fn validate(&self) -> Result<(), ApiError> {
+        let mut errors = Vec::new();
+        validate_name(&self.name, &mut errors);
+        if let Some(description) = &self.description {
+            validate_description(description, &mut errors);
+        }
+        if let Some(color) = &self.color {
+            validate_color(color, &mut errors);
+        }
+        into_result(errors)
+    }
+}
+
+impl UpdateTodoListRequest {
+    pub fn validate(&self) -> Result<(), ApiError> {
+        let mut errors = Vec::new();
+
+        if self.name.is_none() && self.description.is_none() && self.color.is_none() {
+            errors.push(FieldError::new("body", "empty_update", "at least one field must be set"));
+        }
+        if let Some(name) = &self.name {
+            validate_name(name, &mut errors);
+        }
+        if let Some(description) = &self.description {
+            validate_description(description, &mut errors);
+        }
+        if let Some(color) = &self.color {
+            validate_color(color, &mut errors);
+        }
+
+        into_result(errors)
+    }
+}
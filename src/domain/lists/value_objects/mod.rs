@@ -0,0 +1,44 @@
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::domain::todos::{default_limit, default_page, PaginationQuery};
+
+#[derive(Deserialize, ToSchema, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CreateTodoListRequest {
+    pub name: String,
+    pub description: Option<String>,
+    /// `#rrggbb` hex colour, e.g. `#1e90ff`
+    pub color: Option<String>,
+}
+
+/// Partial update; only the given fields change. Lists are archived and
+/// restored through their own endpoints.
+#[derive(Deserialize, ToSchema, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateTodoListRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub color: Option<String>,
+}
+
+/// Query for `GET /lists`.
+#[derive(Deserialize, IntoParams, Clone, Debug)]
+#[into_params(parameter_in = Query)]
+pub struct TodoListQuery {
+    /// Page number (default: 1)
+    #[serde(default = "default_page")]
+    pub page: u32,
+    /// Items per page (default: 10, max: 100)
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+    /// List archived lists instead of active ones (default: false)
+    #[serde(default)]
+    pub archived: bool,
+}
+
+impl TodoListQuery {
+    pub fn pagination(&self) -> PaginationQuery {
+        PaginationQuery { page: self.page, limit: self.limit, ..Default::default() }
+    }
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// A label its owner attaches to todos, any number per todo.
+#[derive(Serialize, Clone, Debug, ToSchema, FromRow)]
+pub struct Tag {
+    pub id: Uuid,
+    /// Unique among the owner's tags
+    pub name: String,
+    /// The user who created the tag
+    pub owner_id: Option<Uuid>,
+    /// The organisation the tag belongs to
+    pub tenant_id: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A tag as embedded in the todos it is attached to.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, ToSchema)]
+pub struct TagRef {
+    pub id: Uuid,
+    pub name: String,
+}
+
+impl From<&Tag> for TagRef {
+    fn from(tag: &Tag) -> Self {
+        Self { id: tag.id, name: tag.name.clone() }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod entities;
+pub mod value_objects;
+pub mod traits;
+pub mod validation;
+
+pub use entities::*;
+pub use value_objects::*;
@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::tags::{CreateTagRequest, RenameTagRequest, Tag};
+use crate::domain::todos::{DomainEvents, PaginatedResponse, PaginationQuery};
+use crate::error::ApiError;
+
+/// Tags of the current user; background tasks see every tag. A missing
+/// `id`, or another user's tag, yields `ApiError::NotFound`, and a name the
+/// user already gave another tag yields `ApiError::Conflict`.
+#[async_trait]
+pub trait TagStore {
+    async fn create(&self, data: CreateTagRequest) -> Result<Tag, ApiError>;
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Tag>, ApiError>;
+
+    /// Reads one page of the tags, by name.
+    async fn find_all_paginated(&self, pagination: PaginationQuery) -> Result<PaginatedResponse<Tag>, ApiError>;
+
+    /// Renames the tag on every todo it is attached to. Each live one of
+    /// those todos gets a new version, and its update is recorded with
+    /// `events` in the same transaction, as any other todo update is.
+    async fn rename(&self, id: Uuid, data: RenameTagRequest, events: DomainEvents) -> Result<Tag, ApiError>;
+
+    /// Removes the tag and detaches it from every todo, live or trashed.
+    /// Live todos are updated as by [`TagStore::rename`]; trashed ones are
+    /// announced with their tags when restored.
+    async fn delete(&self, id: Uuid, events: DomainEvents) -> Result<(), ApiError>;
+}
+
+/// Every tag capability in one object-safe trait.
+pub trait TagRepository: TagStore + Send + Sync {}
+
+impl<T> TagRepository for T where T: TagStore + Send + Sync {}
@@ -0,0 +1,38 @@
+use crate::domain::tags::{CreateTagRequest, RenameTagRequest};
+use crate::domain::todos::validation::FieldError;
+use crate::error::ApiError;
+
+/// Counted in characters, not bytes.
+pub const NAME_MAX_LENGTH: usize = 50;
+
+/// Names are matched exactly by the `tag` filter of todo listings, so
+/// surrounding whitespace, which no one would type into a query, is refused.
+fn validate_name(name: &str) -> Result<(), ApiError> {
+    let mut errors = Vec::new();
+
+    if name.trim().is_empty() {
+        errors.push(FieldError::new("name", "blank", "name must not be empty"));
+    } else if name.chars().count() > NAME_MAX_LENGTH {
+        errors.push(FieldError::new("name", "too_long", format!("name must be at most {NAME_MAX_LENGTH} characters")));
+    } else if name.trim() != name {
+        errors.push(FieldError::new("name", "untrimmed", "name must not start or end with whitespace"));
+    }
+
+    if name.chars().any(char::is_control) {
+        errors.push(FieldError::new("name", "control_characters", "name must not contain control characters"));
+    }
+
+    if errors.is_empty() { Ok(()) } else { Err(ApiError::Validation(errors)) }
+}
+
+impl CreateTagRequest {
+    pub fn validate(&self) -> Result<(), ApiError> {
+        validate_name(&self.name)
+    }
+}
+
+impl RenameTagRequest {
+    pub fn validate(&self) -> Result<(), ApiError> {
+        validate_name(&self.name)
+    }
+}
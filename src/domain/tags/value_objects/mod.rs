@@ -0,0 +1,35 @@
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::domain::todos::{default_limit, default_page, PaginationQuery};
+
+#[derive(Deserialize, ToSchema, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CreateTagRequest {
+    pub name: String,
+}
+
+/// Renames a tag everywhere it is attached.
+#[derive(Deserialize, ToSchema, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RenameTagRequest {
+    pub name: String,
+}
+
+/// Query for `GET /tags`.
+#[derive(Deserialize, IntoParams, Clone, Debug)]
+#[into_params(parameter_in = Query)]
+pub struct TagQuery {
+    /// Page number (default: 1)
+    #[serde(default = "default_page")]
+    pub page: u32,
+    /// Items per page (default: 10, max: 100)
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+}
+
+impl TagQuery {
+    pub fn pagination(&self) -> PaginationQuery {
+        PaginationQuery { page: self.page, limit: self.limit, ..Default::default() }
+    }
+}
@@ -4,11 +4,56 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use sqlx::FromRow;
 
+use crate::domain::tags::TagRef;
+use crate::domain::todos::TodoPriority;
+use crate::request_context::DEFAULT_TENANT;
+
 #[derive(Serialize, Deserialize, Clone, Debug, ToSchema, FromRow)]
 pub struct Todo {
     pub id: Uuid,
     pub title: String,
+    /// Markdown; `null` when the todo has none
+    #[serde(default)]
+    pub description: Option<String>,
     pub done: bool,
+    /// When the todo was marked done; cleared when it is reopened
+    #[serde(default)]
+    pub completed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub due_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    #[sqlx(try_from = "String")]
+    pub priority: TodoPriority,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Incremented on every update; the resource's ETag.
+    pub version: i64,
+    /// The list the todo belongs to; `null` for todos in no list
+    #[serde(default)]
+    pub list_id: Option<Uuid>,
+    /// The todo's tags, by name
+    #[serde(default)]
+    #[sqlx(json)]
+    pub tags: Vec<TagRef>,
+    /// The user who created the todo; `null` for todos that predate accounts
+    #[serde(default)]
+    pub owner_id: Option<Uuid>,
+    /// The organisation the todo belongs to
+    #[serde(default = "default_tenant")]
+    pub tenant_id: String,
+}
+
+impl Todo {
+    /// Marks the todo done or open at `at`. `completed_at` is stamped when
+    /// it becomes done and cleared when it is reopened.
+    pub fn set_done(&mut self, done: bool, at: DateTime<Utc>) {
+        if done != self.done {
+            self.completed_at = done.then_some(at);
+        }
+        self.done = done;
+    }
+}
+
+fn default_tenant() -> String {
+    DEFAULT_TENANT.to_string()
 }
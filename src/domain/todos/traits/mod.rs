@@ -1,31 +1,205 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
-use crate::domain::todos::{Todo, CreateTodoRequest, UpdateTodoRequest, PaginationQuery, PaginatedResponse};
+use crate::domain::todos::{
+    Todo, CreateTodoRequest, UpdateTodoRequest, PaginationQuery, PaginatedResponse, KeysetQuery, KeysetPage,
+    TodoSearch, TodoSearchHit, BatchOperation, BatchMode, BatchOutcome, TrashedTodo, TodoEvent,
+    OutboxMessage, TodoRole, TodoShare, DomainEvents
+};
 use crate::error::ApiError;
 
 #[async_trait]
 pub trait TodoCreator {
-    async fn create(&self, data: CreateTodoRequest) -> Result<Todo, ApiError>;
+    async fn create(&self, data: CreateTodoRequest, events: DomainEvents) -> Result<Todo, ApiError>;
 }
 
+/// Reads go through [`TodoFinder`], [`TodoPaginator`], [`TodoSearcher`] and
+/// [`TodoHistory`], which show a user the todos they own or were given
+/// access to. Writes are only scoped the same way: the role each one needs
+/// is checked beforehand against [`TodoRoles`].
 #[async_trait]
 pub trait TodoFinder {
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Todo>, ApiError>;
-    async fn find_by_done(&self, done: bool) -> Result<Vec<Todo>, ApiError>;
 }
 
 #[async_trait]
 pub trait TodoPaginator {
+    /// Reads one page of the todos matching `pagination.filter()`, ordered by
+    /// `pagination.sort_keys()` with `id DESC` breaking ties.
     async fn find_all_paginated(&self, pagination: PaginationQuery) -> Result<PaginatedResponse<Todo>, ApiError>;
+
+    /// Reads up to `query.limit` matching todos strictly past the start
+    /// position, in `created_at DESC, id DESC` order, without counting unless
+    /// asked to.
+    async fn find_keyset(&self, query: KeysetQuery) -> Result<KeysetPage<Todo>, ApiError>;
+}
+
+/// Word search over todo titles. Every term must match; hits come most
+/// relevant first, then newest first. Backends choose their own ranking and
+/// word matching (Postgres stems English words, others match them exactly).
+#[async_trait]
+pub trait TodoSearcher {
+    async fn search(&self, search: TodoSearch) -> Result<PaginatedResponse<TodoSearchHit>, ApiError>;
 }
 
+/// Mutators share one contract: a missing or trashed `id` yields
+/// `ApiError::NotFound`, never a backend-specific error, and when
+/// `expected_version` is given and differs from the stored version they fail
+/// with `ApiError::PreconditionFailed` without writing. Updates bump `version`.
+/// Every successful write, here and in the other mutating traits, records a
+/// [`TodoEvent`] and queues in the outbox whatever `events` builds from it,
+/// both committing or rolling back together with it.
 #[async_trait]
 pub trait TodoUpdater {
-    async fn update(&self, id: Uuid, data: UpdateTodoRequest) -> Result<Todo, ApiError>;
+    async fn update(
+        &self,
+        id: Uuid,
+        data: UpdateTodoRequest,
+        expected_version: Option<i64>,
+        events: DomainEvents,
+    ) -> Result<Todo, ApiError>;
 }
 
+/// Deleting moves a todo to the trash: it disappears from every
+/// [`TodoFinder`], [`TodoPaginator`] and [`TodoSearcher`] query until it is
+/// restored. See [`TodoUpdater`] for the not-found and version contract.
 #[async_trait]
 pub trait TodoDeleter {
-    async fn delete(&self, id: Uuid) -> Result<(), ApiError>;
+    async fn delete(&self, id: Uuid, expected_version: Option<i64>, events: DomainEvents) -> Result<(), ApiError>;
 }
 
+/// Soft-deleted todos. Every method only sees trashed todos, so a live `id`
+/// yields `ApiError::NotFound`.
+#[async_trait]
+pub trait TodoTrash {
+    /// Reads one page of the trash, most recently deleted first.
+    async fn find_trashed(&self, pagination: PaginationQuery) -> Result<PaginatedResponse<TrashedTodo>, ApiError>;
+
+    /// Takes a todo out of the trash, bumping its `version`.
+    async fn restore(&self, id: Uuid, events: DomainEvents) -> Result<Todo, ApiError>;
+
+    /// Permanently removes a trashed todo. Purges are recorded but queue
+    /// no domain events: consumers already saw the deletion.
+    async fn purge(&self, id: Uuid) -> Result<(), ApiError>;
+
+    /// Permanently removes every todo trashed before `cutoff`, returning how
+    /// many were removed.
+    async fn purge_trashed_before(&self, cutoff: DateTime<Utc>) -> Result<u64, ApiError>;
+}
+
+#[async_trait]
+pub trait TodoHistory {
+    /// Reads one page of the events recorded for `id`, oldest first. Events
+    /// outlive the todo, so a purged todo keeps its history.
+    async fn find_history(&self, id: Uuid, pagination: PaginationQuery) -> Result<PaginatedResponse<TodoEvent>, ApiError>;
+
+    /// Id of the most recent event of any todo, or 0 when none was recorded.
+    async fn last_event_id(&self) -> Result<i64, ApiError>;
+
+    /// Up to `limit` events of any todo recorded after the event `after`,
+    /// oldest first.
+    async fn events_since(&self, after: i64, limit: u32) -> Result<Vec<TodoEvent>, ApiError>;
+}
+
+#[async_trait]
+pub trait TodoRoles {
+    /// The role `user_id` holds on todo `id`, live or trashed: `Owner` for
+    /// its owner, the granted role for users it is shared with, and `None`
+    /// for everyone else or when there is no such todo.
+    async fn role_of(&self, id: Uuid, user_id: Uuid) -> Result<Option<TodoRole>, ApiError>;
+}
+
+/// Who else may see or edit a todo. Shares go away with the todo when it is
+/// purged.
+#[async_trait]
+pub trait TodoSharing {
+    /// Everyone todo `id` is shared with, in the order access was granted.
+    async fn find_shares(&self, id: Uuid) -> Result<Vec<TodoShare>, ApiError>;
+
+    /// Gives `user_id` `role` on todo `id`, replacing the role they had.
+    async fn grant(&self, id: Uuid, user_id: Uuid, role: TodoRole) -> Result<TodoShare, ApiError>;
+
+    /// Takes access away again; `ApiError::NotFound` when `user_id` had none.
+    async fn revoke(&self, id: Uuid, user_id: Uuid) -> Result<(), ApiError>;
+}
+
+/// Tags on a todo. Attaching and detaching are writes like any other: they
+/// follow the [`TodoUpdater`] contract, bumping `version` and recording an
+/// update whose changes list the tags before and after.
+#[async_trait]
+pub trait TodoTagging {
+    /// Attaches tag `tag_id`. Attaching a tag the todo already has changes
+    /// nothing.
+    async fn attach_tag(
+        &self,
+        id: Uuid,
+        tag_id: Uuid,
+        expected_version: Option<i64>,
+        events: DomainEvents,
+    ) -> Result<Todo, ApiError>;
+
+    /// Detaches tag `tag_id`; `ApiError::NotFound` when the todo does not
+    /// have it.
+    async fn detach_tag(
+        &self,
+        id: Uuid,
+        tag_id: Uuid,
+        expected_version: Option<i64>,
+        events: DomainEvents,
+    ) -> Result<Todo, ApiError>;
+}
+
+/// Storage side of the transactional outbox. Messages are queued by the
+/// mutating traits and drained by a relay into an [`EventPublisher`].
+#[async_trait]
+pub trait TodoOutbox {
+    /// Up to `limit` messages due at `now`, oldest first. A message is held
+    /// back while an earlier message for the same todo is waiting for a
+    /// retry, so each todo's events are delivered in order.
+    async fn pending_messages(&self, limit: u32, now: DateTime<Utc>) -> Result<Vec<OutboxMessage>, ApiError>;
+
+    /// Removes a delivered message.
+    async fn mark_published(&self, id: i64) -> Result<(), ApiError>;
+
+    /// Counts a failed delivery and holds the message back until `retry_at`.
+    async fn mark_failed(&self, id: i64, error: &str, retry_at: DateTime<Utc>) -> Result<(), ApiError>;
+}
+
+/// Delivers outbox messages downstream. Delivery is at-least-once: a message
+/// may be published again after a crash or a failed acknowledgement, so
+/// consumers should deduplicate on `event_id`.
+#[async_trait]
+pub trait EventPublisher: Send + Sync {
+    async fn publish(&self, message: &OutboxMessage) -> anyhow::Result<()>;
+}
+
+/// Applies several writes at once, returning one result per operation in
+/// order. Each operation follows the [`TodoUpdater`] contract. In
+/// `BatchMode::Atomic` either every result is `Ok` and all writes are
+/// committed, or nothing is written and the results are settled by
+/// [`BatchMode::settle`]. In `BatchMode::BestEffort` every operation is
+/// attempted on its own. `Err` is reserved for failures that no single
+/// operation caused, such as a failed commit.
+#[async_trait]
+pub trait TodoBatchWriter {
+    async fn apply_batch(
+        &self,
+        operations: Vec<BatchOperation>,
+        mode: BatchMode,
+        events: DomainEvents,
+    ) -> Result<Vec<Result<BatchOutcome, ApiError>>, ApiError>;
+}
+
+/// Every todo capability in one object-safe trait, so callers can hold any
+/// backend behind `Arc<dyn TodoRepository>`.
+pub trait TodoRepository:
+    TodoCreator + TodoFinder + TodoPaginator + TodoSearcher + TodoUpdater + TodoDeleter + TodoTrash
+    + TodoHistory + TodoOutbox + TodoBatchWriter + TodoRoles + TodoSharing + TodoTagging + Send + Sync
+{
+}
+
+impl<T> TodoRepository for T where
+    T: TodoCreator + TodoFinder + TodoPaginator + TodoSearcher + TodoUpdater + TodoDeleter + TodoTrash
+        + TodoHistory + TodoOutbox + TodoBatchWriter + TodoRoles + TodoSharing + TodoTagging + Send + Sync
+{
+}
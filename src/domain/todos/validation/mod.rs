@@ -0,0 +1,244 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::domain::todos::{
+    BatchOperation, BatchRequest, CreateTodoRequest, GrantAccessRequest, PaginationQuery, SearchTodosQuery, SortField, SortKey,
+    TodoFilter, TodoRole, TodoSearch, UpdateTodoRequest,
+};
+use crate::error::ApiError;
+
+/// Matches the `VARCHAR(255)` column; counted in characters, not bytes.
+pub const TITLE_MAX_LENGTH: usize = 255;
+/// Counted in characters, not bytes.
+pub const DESCRIPTION_MAX_LENGTH: usize = 10_000;
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq, ToSchema)]
+pub struct FieldError {
+    pub field: String,
+    pub code: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &str, code: &str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            code: code.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+fn validate_title(title: &str, errors: &mut Vec<FieldError>) {
+    if title.trim().is_empty() {
+        errors.push(FieldError::new("title", "blank", "title must not be empty"));
+    } else if title.trim() != title {
+        errors.push(FieldError::new(
+            "title",
+            "surrounding_whitespace",
+            "title must not start or end with whitespace",
+        ));
+    } else if title.chars().count() > TITLE_MAX_LENGTH {
+        errors.push(FieldError::new(
+            "title",
+            "too_long",
+            format!("title must be at most {TITLE_MAX_LENGTH} characters"),
+        ));
+    }
+
+    if title.chars().any(char::is_control) {
+        errors.push(FieldError::new(
+            "title",
+            "control_characters",
+            "title must not contain control characters",
+        ));
+    }
+}
+
+/// Markdown may span lines, so only line breaks and tabs are allowed among
+/// the control characters.
+fn validate_description(description: &str, errors: &mut Vec<FieldError>) {
+    if description.chars().count() > DESCRIPTION_MAX_LENGTH {
+        errors.push(FieldError::new(
+            "description",
+            "too_long",
+            format!("description must be at most {DESCRIPTION_MAX_LENGTH} characters"),
+        ));
+    }
+
+    if description.chars().any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t')) {
+        errors.push(FieldError::new(
+            "description",
+            "control_characters",
+            "description must not contain control characters other than line breaks and tabs",
+        ));
+    }
+}
+
+fn into_result(errors: Vec<FieldError>) -> Result<(), ApiError> {
+    if errors.is_empty() { Ok(()) } else { Err(ApiError::Validation(errors)) }
+}
+
+impl CreateTodoRequest {
+    pub fn validate(&self) -> Result<(), ApiError> {
+        let mut errors = Vec::new();
+        validate_title(&self.title, &mut errors);
+        if let Some(description) = &self.description {
+            validate_description(description, &mut errors);
+        }
+        into_result(errors)
+    }
+}
+
+impl UpdateTodoRequest {
+    pub fn validate(&self) -> Result<(), ApiError> {
+        let mut errors = Vec::new();
+
+        if self.is_empty() {
+            errors.push(FieldError::new("body", "empty_update", "at least one field must be set"));
+        }
+        if let Some(title) = &self.title {
+            validate_title(title, &mut errors);
+        }
+        if let Some(Some(description)) = &self.description {
+            validate_description(description, &mut errors);
+        }
+
+        into_result(errors)
+    }
+}
+
+impl BatchRequest {
+    /// Checks the batch as a whole; each operation has its own `validate`.
+    pub fn validate(&self) -> Result<(), ApiError> {
+        let mut errors = Vec::new();
+
+        if self.operations.is_empty() {
+            errors.push(FieldError::new("operations", "empty", "operations must not be empty"));
+        } else if self.operations.len() > Self::MAX_OPERATIONS {
+            errors.push(FieldError::new(
+                "operations",
+                "too_many",
+                format!("a batch holds at most {} operations", Self::MAX_OPERATIONS),
+            ));
+        }
+
+        into_result(errors)
+    }
+}
+
+impl BatchOperation {
+    pub fn validate(&self) -> Result<(), ApiError> {
+        if let Some(request) = self.create_request() {
+            return request.validate();
+        }
+        if let Some(request) = self.update_request() {
+            return request.validate();
+        }
+        Ok(())
+    }
+}
+
+impl GrantAccessRequest {
+    /// The owner role comes with creating a todo and cannot be granted.
+    pub fn validate(&self) -> Result<(), ApiError> {
+        let mut errors = Vec::new();
+
+        if self.email.trim().is_empty() {
+            errors.push(FieldError::new("email", "blank", "email must not be empty"));
+        }
+        if self.role == TodoRole::Owner {
+            errors.push(FieldError::new("role", "not_grantable", "role must be viewer or editor"));
+        }
+
+        into_result(errors)
+    }
+}
+
+impl TodoFilter {
+    pub fn validate(&self) -> Result<(), ApiError> {
+        let mut errors = Vec::new();
+        validate_filter(self, &mut errors);
+        into_result(errors)
+    }
+}
+
+fn validate_filter(filter: &TodoFilter, errors: &mut Vec<FieldError>) {
+    if let (Some(after), Some(before)) = (filter.created_after, filter.created_before)
+        && after >= before
+    {
+        errors.push(FieldError::new(
+            "created_before",
+            "invalid_range",
+            "created_before must be later than created_after",
+        ));
+    }
+}
+
+fn validate_sort(sort: &str, errors: &mut Vec<FieldError>) {
+    let mut seen = Vec::new();
+
+    for part in sort.split(',').map(str::trim) {
+        let name = part.strip_prefix('-').unwrap_or(part);
+        match SortField::from_name(name) {
+            Some(field) if seen.contains(&field) => errors.push(FieldError::new(
+                "sort",
+                "duplicate_sort_field",
+                format!("`{name}` appears more than once"),
+            )),
+            Some(field) => seen.push(field),
+            None => {
+                let allowed: Vec<&str> = SortField::ALL.iter().map(|field| field.name()).collect();
+                errors.push(FieldError::new(
+                    "sort",
+                    "unknown_sort_field",
+                    format!("`{name}` is not one of {}", allowed.join(", ")),
+                ));
+            }
+        }
+    }
+}
+
+impl PaginationQuery {
+    pub fn validate(&self) -> Result<(), ApiError> {
+        let mut errors = Vec::new();
+
+        validate_filter(&self.filter(), &mut errors);
+        if self.overdue && self.done == Some(true) {
+            errors.push(FieldError::new("overdue", "conflicting_filter", "done todos are never overdue"));
+        }
+        if self.tag.len() > Self::MAX_TAGS {
+            errors.push(FieldError::new("tag", "too_many", format!("at most {} tags can be given", Self::MAX_TAGS)));
+        }
+        if let Some(sort) = &self.sort {
+            validate_sort(sort, &mut errors);
+        }
+        if self.cursor.is_some() && errors.is_empty() && self.sort_keys() != SortKey::DEFAULT {
+            errors.push(FieldError::new(
+                "sort",
+                "unsupported_with_cursor",
+                "cursor pagination only supports the default `-created_at` order",
+            ));
+        }
+
+        into_result(errors)
+    }
+}
+
+impl SearchTodosQuery {
+    pub fn validate(&self) -> Result<(), ApiError> {
+        let mut errors = Vec::new();
+
+        if self.q.chars().count() > Self::MAX_QUERY_LENGTH {
+            errors.push(FieldError::new(
+                "q",
+                "too_long",
+                format!("q must be at most {} characters", Self::MAX_QUERY_LENGTH),
+            ));
+        } else if TodoSearch::parse(self).terms.is_empty() {
+            errors.push(FieldError::new("q", "blank", "q must contain at least one word"));
+        }
+
+        into_result(errors)
+    }
+}
@@ -0,0 +1,65 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// What a user may do with a todo. Each role includes the ones before it.
+#[derive(Serialize, Deserialize, ToSchema, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoRole {
+    /// Read the todo and its history
+    Viewer,
+    /// Also change its title and status
+    Editor,
+    /// Also delete, restore and purge it and decide who else has access;
+    /// held by whoever created the todo and never granted
+    Owner,
+}
+
+impl TodoRole {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TodoRole::Viewer => "viewer",
+            TodoRole::Editor => "editor",
+            TodoRole::Owner => "owner",
+        }
+    }
+
+    pub fn includes(self, required: TodoRole) -> bool {
+        self >= required
+    }
+}
+
+impl TryFrom<String> for TodoRole {
+    type Error = String;
+
+    fn try_from(role: String) -> Result<Self, Self::Error> {
+        match role.as_str() {
+            "viewer" => Ok(TodoRole::Viewer),
+            "editor" => Ok(TodoRole::Editor),
+            "owner" => Ok(TodoRole::Owner),
+            _ => Err(format!("unknown todo role `{role}`")),
+        }
+    }
+}
+
+/// Access to a todo given to someone other than its owner.
+#[derive(Serialize, ToSchema, FromRow, Clone, Debug, PartialEq)]
+pub struct TodoShare {
+    pub todo_id: Uuid,
+    pub user_id: Uuid,
+    #[sqlx(try_from = "String")]
+    pub role: TodoRole,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Body of `POST /todos/{id}/access`.
+#[derive(Deserialize, ToSchema, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct GrantAccessRequest {
+    /// Email of the account to share with
+    pub email: String,
+    /// `viewer` or `editor`
+    pub role: TodoRole,
+}
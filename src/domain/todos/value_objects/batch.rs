@@ -0,0 +1,191 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::todos::{CreateTodoRequest, Todo, TodoPriority, TodoRole, UpdateTodoRequest};
+use crate::error::{ApiError, ProblemDetails};
+
+/// Body of `POST /todos/batch`.
+#[derive(Deserialize, ToSchema, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct BatchRequest {
+    /// `atomic` (default) or `best_effort`
+    #[serde(default)]
+    pub mode: BatchMode,
+    /// Applied in order, at most `BatchRequest::MAX_OPERATIONS`
+    pub operations: Vec<BatchOperation>,
+}
+
+impl BatchRequest {
+    pub const MAX_OPERATIONS: usize = 1000;
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchMode {
+    /// All operations are applied, or none are.
+    #[default]
+    Atomic,
+    /// Each operation succeeds or fails on its own.
+    BestEffort,
+}
+
+/// One write in a batch, tagged by `op`.
+#[derive(Deserialize, ToSchema, Clone, Debug)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum BatchOperation {
+    Create {
+        title: String,
+        done: Option<bool>,
+        description: Option<String>,
+        due_at: Option<DateTime<Utc>>,
+        priority: Option<TodoPriority>,
+        /// Puts the todo in this list
+        list_id: Option<Uuid>,
+    },
+    /// Partial update; only the given fields change.
+    Update {
+        id: Uuid,
+        title: Option<String>,
+        done: Option<bool>,
+        /// `null` removes the description
+        #[serde(default, deserialize_with = "super::nullable")]
+        #[schema(value_type = Option<String>)]
+        description: Option<Option<String>>,
+        /// `null` removes the due date
+        #[serde(default, deserialize_with = "super::nullable")]
+        #[schema(value_type = Option<DateTime<Utc>>)]
+        due_at: Option<Option<DateTime<Utc>>>,
+        priority: Option<TodoPriority>,
+        /// Moves the todo to this list; `null` takes it out of its list
+        #[serde(default, deserialize_with = "super::nullable")]
+        #[schema(value_type = Option<Uuid>)]
+        list_id: Option<Option<Uuid>>,
+        /// Only apply if the todo still has this version (its ETag, unquoted)
+        expected_version: Option<i64>,
+    },
+    Delete {
+        id: Uuid,
+        /// Only apply if the todo still has this version (its ETag, unquoted)
+        expected_version: Option<i64>,
+    },
+}
+
+impl BatchOperation {
+    pub fn create_request(&self) -> Option<CreateTodoRequest> {
+        match self {
+            BatchOperation::Create { title, done, description, due_at, priority, list_id } => {
+                Some(CreateTodoRequest {
+                    title: title.clone(),
+                    done: *done,
+                    description: description.clone(),
+                    due_at: *due_at,
+                    priority: *priority,
+                    list_id: *list_id,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    pub fn update_request(&self) -> Option<UpdateTodoRequest> {
+        match self {
+            BatchOperation::Update { title, done, description, due_at, priority, list_id, .. } => {
+                Some(UpdateTodoRequest {
+                    title: title.clone(),
+                    done: *done,
+                    description: description.clone(),
+                    due_at: *due_at,
+                    priority: *priority,
+                    list_id: *list_id,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// The todo the operation writes to and the role it takes; creates
+    /// need none.
+    pub fn required_role(&self) -> Option<(Uuid, TodoRole)> {
+        match self {
+            BatchOperation::Create { .. } => None,
+            BatchOperation::Update { id, .. } => Some((*id, TodoRole::Editor)),
+            BatchOperation::Delete { id, .. } => Some((*id, TodoRole::Owner)),
+        }
+    }
+}
+
+/// What a successful operation did.
+#[derive(Clone, Debug)]
+pub enum BatchOutcome {
+    Created(Todo),
+    Updated(Todo),
+    Deleted,
+}
+
+impl BatchMode {
+    /// Turns per-operation results into the final list. `None` marks an
+    /// operation that was never attempted. In atomic mode a single failure
+    /// means nothing was written, so every other operation reports
+    /// `ApiError::FailedDependency`.
+    pub fn settle(self, results: Vec<Option<Result<BatchOutcome, ApiError>>>) -> Vec<Result<BatchOutcome, ApiError>> {
+        let failed = results.iter().any(|result| matches!(result, Some(Err(_))));
+
+        results
+            .into_iter()
+            .map(|result| match result {
+                Some(Err(error)) => Err(error),
+                Some(Ok(_)) if self == BatchMode::Atomic && failed => Err(ApiError::FailedDependency),
+                Some(Ok(outcome)) => Ok(outcome),
+                None => Err(ApiError::FailedDependency),
+            })
+            .collect()
+    }
+}
+
+/// Result of one operation, like a `207 Multi-Status` entry.
+#[derive(Serialize, ToSchema, Clone, Debug)]
+pub struct BatchItemResult {
+    /// Position of the operation in the request
+    pub index: usize,
+    /// The status the equivalent single request would have returned
+    pub status: u16,
+    /// The created or updated todo
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub todo: Option<Todo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ProblemDetails>,
+}
+
+#[derive(Serialize, ToSchema, Clone, Debug)]
+pub struct BatchResponse {
+    pub mode: BatchMode,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// One entry per operation, in request order
+    pub results: Vec<BatchItemResult>,
+}
+
+impl BatchResponse {
+    pub fn new(mode: BatchMode, results: Vec<Result<BatchOutcome, ApiError>>) -> Self {
+        let results: Vec<BatchItemResult> = results
+            .into_iter()
+            .enumerate()
+            .map(|(index, result)| match result {
+                Ok(BatchOutcome::Created(todo)) => BatchItemResult { index, status: 201, todo: Some(todo), error: None },
+                Ok(BatchOutcome::Updated(todo)) => BatchItemResult { index, status: 200, todo: Some(todo), error: None },
+                Ok(BatchOutcome::Deleted) => BatchItemResult { index, status: 204, todo: None, error: None },
+                Err(error) => {
+                    if error.status().is_server_error() {
+                        tracing::error!(index, code = error.code(), error = %error, "batch operation failed");
+                    }
+                    BatchItemResult { index, status: error.status().as_u16(), todo: None, error: Some(error.to_problem()) }
+                }
+            })
+            .collect();
+        let failed = results.iter().filter(|result| result.error.is_some()).count();
+
+        Self { mode, succeeded: results.len() - failed, failed, results }
+    }
+}
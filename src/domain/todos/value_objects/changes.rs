@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::domain::todos::{Todo, TodoEvent, TodoEventKind};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoChangeKind {
+    Created,
+    /// Also sent when a todo is restored from the trash
+    Updated,
+    /// The todo moved to the trash or was removed
+    Deleted,
+}
+
+impl TodoChangeKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TodoChangeKind::Created => "created",
+            TodoChangeKind::Updated => "updated",
+            TodoChangeKind::Deleted => "deleted",
+        }
+    }
+}
+
+/// One notification of the live change stream.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TodoChange {
+    /// Position in the stream, sent as the SSE event id
+    pub seq: i64,
+    pub kind: TodoChangeKind,
+    /// The todo after the change; for `deleted`, the todo it removed
+    pub todo: Todo,
+}
+
+impl TodoChange {
+    /// The change an audit event announces; a purge announces nothing, the
+    /// deletion was already sent.
+    pub fn from_event(event: &TodoEvent) -> Option<Self> {
+        let (kind, todo) = match (event.kind, &event.before, &event.after) {
+            (TodoEventKind::Created, _, Some(todo)) => (TodoChangeKind::Created, todo),
+            (TodoEventKind::Updated | TodoEventKind::Restored, _, Some(todo)) => (TodoChangeKind::Updated, todo),
+            (TodoEventKind::Deleted, Some(todo), _) => (TodoChangeKind::Deleted, todo),
+            _ => return None,
+        };
+        Some(Self { seq: event.id, kind, todo: todo.clone() })
+    }
+}
+
+/// Query of `GET /todos/events`.
+#[derive(Deserialize, IntoParams, Clone, Debug, Default)]
+#[into_params(parameter_in = Query)]
+pub struct TodoChangesQuery {
+    /// Only send changes of todos with this `done` status
+    pub done: Option<bool>,
+}
+
+impl TodoChangesQuery {
+    pub fn matches(&self, change: &TodoChange) -> bool {
+        self.done.is_none_or(|done| change.todo.done == done)
+    }
+}
+
+/// What a WebSocket subscription receives: every change, the changes of a
+/// list narrowed by `done`, or those of individual todos.
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct TodoSubscription {
+    pub todo_ids: Option<Vec<Uuid>>,
+    pub done: Option<bool>,
+}
+
+impl TodoSubscription {
+    pub fn matches(&self, change: &TodoChange) -> bool {
+        self.todo_ids.as_ref().is_none_or(|ids| ids.contains(&change.todo.id))
+            && self.done.is_none_or(|done| change.todo.done == done)
+    }
+}
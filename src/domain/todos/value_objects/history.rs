@@ -0,0 +1,174 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::todos::Todo;
+use crate::request_context::RequestContext;
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoEventKind {
+    Created,
+    Updated,
+    /// Moved to the trash
+    Deleted,
+    Restored,
+    /// Permanently removed from the trash
+    Purged,
+}
+
+impl TodoEventKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TodoEventKind::Created => "created",
+            TodoEventKind::Updated => "updated",
+            TodoEventKind::Deleted => "deleted",
+            TodoEventKind::Restored => "restored",
+            TodoEventKind::Purged => "purged",
+        }
+    }
+}
+
+impl TryFrom<String> for TodoEventKind {
+    type Error = String;
+
+    fn try_from(kind: String) -> Result<Self, Self::Error> {
+        match kind.as_str() {
+            "created" => Ok(TodoEventKind::Created),
+            "updated" => Ok(TodoEventKind::Updated),
+            "deleted" => Ok(TodoEventKind::Deleted),
+            "restored" => Ok(TodoEventKind::Restored),
+            "purged" => Ok(TodoEventKind::Purged),
+            _ => Err(format!("unknown todo event kind `{kind}`")),
+        }
+    }
+}
+
+/// One field whose value differs between the snapshots.
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    #[schema(value_type = Object)]
+    pub from: Value,
+    #[schema(value_type = Object)]
+    pub to: Value,
+}
+
+/// An entry of a todo's audit log. Events are append-only and outlive the
+/// todo itself.
+#[derive(Serialize, ToSchema, Clone, Debug, FromRow)]
+pub struct TodoEvent {
+    /// Increases with every recorded event
+    pub id: i64,
+    pub todo_id: Uuid,
+    #[sqlx(try_from = "String")]
+    pub kind: TodoEventKind,
+    /// The todo's version once the change was made
+    pub version: i64,
+    /// The todo before the change; absent for `created` and `restored`
+    #[sqlx(json(nullable))]
+    pub before: Option<Todo>,
+    /// The todo after the change; absent for `deleted` and `purged`
+    #[sqlx(json(nullable))]
+    pub after: Option<Todo>,
+    /// Fields set by `created` or modified by `updated`
+    #[sqlx(json)]
+    pub changes: Vec<FieldChange>,
+    pub actor: Option<String>,
+    pub request_id: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// An event to record alongside a write; the store assigns `id` and
+/// `occurred_at`. Actor and request id come from the current request.
+#[derive(Clone, Debug)]
+pub struct NewTodoEvent {
+    pub todo_id: Uuid,
+    /// Owner of the todo, whose requests may read the event
+    pub owner_id: Option<Uuid>,
+    /// Tenant of the todo, outside of which the event is hidden
+    pub tenant_id: String,
+    pub kind: TodoEventKind,
+    pub version: i64,
+    pub before: Option<Todo>,
+    pub after: Option<Todo>,
+    pub changes: Vec<FieldChange>,
+    pub actor: Option<String>,
+    pub request_id: Option<String>,
+}
+
+/// Bookkeeping fields that change on every write, `completed_at`, which
+/// follows `done`, and the owner and tenant, which never change, are left out
+/// of diffs.
+const UNDIFFED_FIELDS: [&str; 7] = ["id", "created_at", "updated_at", "version", "completed_at", "owner_id", "tenant_id"];
+
+impl NewTodoEvent {
+    fn new(kind: TodoEventKind, todo: &Todo, before: Option<&Todo>, after: Option<&Todo>) -> Self {
+        let context = RequestContext::current();
+        let changes = match kind {
+            TodoEventKind::Created | TodoEventKind::Updated => diff(before, after),
+            _ => Vec::new(),
+        };
+
+        Self {
+            todo_id: todo.id,
+            owner_id: todo.owner_id,
+            tenant_id: todo.tenant_id.clone(),
+            kind,
+            version: todo.version,
+            before: before.cloned(),
+            after: after.cloned(),
+            changes,
+            actor: context.actor,
+            request_id: context.request_id,
+        }
+    }
+
+    pub fn created(todo: &Todo) -> Self {
+        Self::new(TodoEventKind::Created, todo, None, Some(todo))
+    }
+
+    pub fn updated(before: &Todo, after: &Todo) -> Self {
+        Self::new(TodoEventKind::Updated, after, Some(before), Some(after))
+    }
+
+    /// `before` is the live todo; `version` is the one it has in the trash.
+    pub fn deleted(before: &Todo, version: i64) -> Self {
+        Self { version, ..Self::new(TodoEventKind::Deleted, before, Some(before), None) }
+    }
+
+    pub fn restored(todo: &Todo) -> Self {
+        Self::new(TodoEventKind::Restored, todo, None, Some(todo))
+    }
+
+    pub fn purged(todo: &Todo) -> Self {
+        Self::new(TodoEventKind::Purged, todo, Some(todo), None)
+    }
+}
+
+/// Field-level differences between two snapshots, sorted by field. A
+/// missing snapshot counts as every field being `null`.
+pub fn diff(before: Option<&Todo>, after: Option<&Todo>) -> Vec<FieldChange> {
+    let fields = |todo: Option<&Todo>| match todo.map(serde_json::to_value) {
+        Some(Ok(Value::Object(fields))) => fields,
+        _ => serde_json::Map::new(),
+    };
+    let (before, after) = (fields(before), fields(after));
+
+    let mut names: Vec<&String> = before.keys().chain(after.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    names
+        .into_iter()
+        .filter(|name| !UNDIFFED_FIELDS.contains(&name.as_str()))
+        .filter_map(|name| {
+            let from = before.get(name).cloned().unwrap_or(Value::Null);
+            let to = after.get(name).cloned().unwrap_or(Value::Null);
+            (from != to).then(|| FieldChange { field: name.clone(), from, to })
+        })
+        .collect()
+}
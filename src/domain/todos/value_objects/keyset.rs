@@ -0,0 +1,64 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::todos::{Todo, TodoFilter};
+
+/// A todo's place in the list order, `created_at DESC, id DESC`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeysetPosition {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl From<&Todo> for KeysetPosition {
+    fn from(todo: &Todo) -> Self {
+        Self { created_at: todo.created_at, id: todo.id }
+    }
+}
+
+/// Which side of a position a page is read from: `After` continues towards
+/// older todos, `Before` goes back towards newer ones.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum KeysetDirection {
+    After,
+    Before,
+}
+
+#[derive(Clone, Debug)]
+pub struct KeysetQuery {
+    /// `None` reads the first page.
+    pub start: Option<(KeysetDirection, KeysetPosition)>,
+    pub limit: u32,
+    pub include_total: bool,
+    pub filter: TodoFilter,
+}
+
+/// One keyset page, always in list order whatever the direction.
+#[derive(Clone, Debug)]
+pub struct KeysetPage<T> {
+    pub data: Vec<T>,
+    /// More rows exist past this page in the direction it was read.
+    pub has_more: bool,
+    pub total: Option<u64>,
+}
+
+#[derive(Serialize, Clone, Debug, ToSchema)]
+#[aliases(TodoCursorPage = CursorPaginatedResponse<Todo>)]
+pub struct CursorPaginatedResponse<T> {
+    pub data: Vec<T>,
+    pub pagination: CursorMeta,
+}
+
+#[derive(Serialize, Clone, Debug, ToSchema)]
+pub struct CursorMeta {
+    pub limit: u32,
+    /// Pass as `cursor` to read the following (older) page
+    pub next_cursor: Option<String>,
+    /// Pass as `cursor` to read the preceding (newer) page
+    pub prev_cursor: Option<String>,
+    /// Matching todos; only computed when `include_total=true`
+    pub total: Option<u64>,
+}
@@ -0,0 +1,120 @@
+use std::cmp::Ordering;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::todos::Todo;
+
+/// Conditions a listed todo must meet; `None` fields do not filter.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TodoFilter {
+    pub done: Option<bool>,
+    /// Case-insensitive substring of the title
+    pub title: Option<String>,
+    /// Exclusive lower bound on `created_at`
+    pub created_after: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on `created_at`
+    pub created_before: Option<DateTime<Utc>>,
+    /// Inclusive lower bound on `updated_at`
+    pub updated_since: Option<DateTime<Utc>>,
+    /// Only todos in this list. When `None`, repositories leave out the
+    /// todos of archived lists, which `matches` cannot see.
+    pub list_id: Option<Uuid>,
+    /// Inclusive lower bound on `due_at`; todos without one never match
+    pub due_after: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on `due_at`; todos without one never match
+    pub due_before: Option<DateTime<Utc>>,
+    /// Tag names, without duplicates; empty does not filter
+    pub tags: Vec<String>,
+    pub tag_match: TagMatch,
+}
+
+/// How a todo must match the `tag` filter.
+#[derive(Serialize, Deserialize, ToSchema, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TagMatch {
+    /// At least one of the tags
+    #[default]
+    Any,
+    /// Every tag
+    All,
+}
+
+impl TodoFilter {
+    pub fn matches(&self, todo: &Todo) -> bool {
+        self.done.is_none_or(|done| todo.done == done)
+            && self
+                .title
+                .as_ref()
+                .is_none_or(|title| todo.title.to_lowercase().contains(&title.to_lowercase()))
+            && self.created_after.is_none_or(|at| todo.created_at > at)
+            && self.created_before.is_none_or(|at| todo.created_at < at)
+            && self.updated_since.is_none_or(|at| todo.updated_at >= at)
+            && self.list_id.is_none_or(|list_id| todo.list_id == Some(list_id))
+            && self.due_after.is_none_or(|at| todo.due_at.is_some_and(|due| due >= at))
+            && self.due_before.is_none_or(|at| todo.due_at.is_some_and(|due| due < at))
+            && (self.tags.is_empty() || {
+                let tagged = |name: &String| todo.tags.iter().any(|tag| &tag.name == name);
+                match self.tag_match {
+                    TagMatch::Any => self.tags.iter().any(tagged),
+                    TagMatch::All => self.tags.iter().all(tagged),
+                }
+            })
+    }
+}
+
+/// Fields a list may be sorted by. Anything else is rejected, so the names
+/// can be mapped to columns without ever reaching SQL as input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortField {
+    CreatedAt,
+    UpdatedAt,
+    Title,
+    Done,
+}
+
+impl SortField {
+    pub const ALL: [SortField; 4] = [Self::CreatedAt, Self::UpdatedAt, Self::Title, Self::Done];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::CreatedAt => "created_at",
+            Self::UpdatedAt => "updated_at",
+            Self::Title => "title",
+            Self::Done => "done",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|field| field.name() == name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SortKey {
+    pub field: SortField,
+    pub descending: bool,
+}
+
+impl SortKey {
+    /// The list order when no `sort` is given: newest first.
+    pub const DEFAULT: [SortKey; 1] = [SortKey { field: SortField::CreatedAt, descending: true }];
+
+    /// Orders two todos by `keys`, then by `id DESC` so the order is total.
+    pub fn compare(keys: &[SortKey], a: &Todo, b: &Todo) -> Ordering {
+        keys.iter()
+            .map(|key| {
+                let ordering = match key.field {
+                    SortField::CreatedAt => a.created_at.cmp(&b.created_at),
+                    SortField::UpdatedAt => a.updated_at.cmp(&b.updated_at),
+                    SortField::Title => a.title.cmp(&b.title),
+                    SortField::Done => a.done.cmp(&b.done),
+                };
+                if key.descending { ordering.reverse() } else { ordering }
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| b.id.cmp(&a.id))
+    }
+}
@@ -1,27 +1,187 @@
-use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
 
-#[derive(Deserialize, ToSchema)]
+pub mod access;
+pub mod batch;
+pub mod changes;
+pub mod history;
+pub mod keyset;
+pub mod listing;
+pub mod outbox;
+pub mod patch;
+pub mod priority;
+pub mod search;
+pub mod trash;
+
+pub use access::*;
+pub use batch::*;
+pub use changes::*;
+pub use history::*;
+pub use keyset::*;
+pub use listing::*;
+pub use outbox::*;
+pub use patch::*;
+pub use priority::*;
+pub use search::*;
+pub use trash::*;
+
+#[derive(Deserialize, ToSchema, Clone, Debug, Default)]
 pub struct CreateTodoRequest {
     pub title: String,
     pub done: Option<bool>,
+    /// Markdown
+    pub description: Option<String>,
+    pub due_at: Option<DateTime<Utc>>,
+    /// Default: `medium`
+    pub priority: Option<TodoPriority>,
+    /// Adds the todo to this list, which must not be archived
+    pub list_id: Option<Uuid>,
 }
 
-#[derive(Deserialize, ToSchema)]
+#[derive(Deserialize, ToSchema, Clone, Debug, Default)]
 pub struct UpdateTodoRequest {
     pub title: Option<String>,
     pub done: Option<bool>,
+    /// Markdown; `null` removes the description
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub description: Option<Option<String>>,
+    /// `null` removes the due date
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<DateTime<Utc>>)]
+    pub due_at: Option<Option<DateTime<Utc>>>,
+    pub priority: Option<TodoPriority>,
+    /// Moves the todo to this list, which must not be archived; `null`
+    /// takes it out of its list
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<Uuid>)]
+    pub list_id: Option<Option<Uuid>>,
+}
+
+impl UpdateTodoRequest {
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.done.is_none()
+            && self.description.is_none()
+            && self.due_at.is_none()
+            && self.priority.is_none()
+            && self.list_id.is_none()
+    }
+}
+
+/// Tells a field sent as `null` (`Some(None)`) from one left out (`None`,
+/// through `#[serde(default)]`).
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Full replacement body for `PUT /todos/{id}`. `title` and `done` are
+/// required; the other fields are reset to their defaults when left out.
+#[derive(Deserialize, ToSchema, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ReplaceTodoRequest {
+    pub title: String,
+    pub done: bool,
+    pub description: Option<String>,
+    pub due_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub priority: TodoPriority,
+    pub list_id: Option<Uuid>,
+}
+
+impl From<ReplaceTodoRequest> for UpdateTodoRequest {
+    fn from(request: ReplaceTodoRequest) -> Self {
+        Self {
+            title: Some(request.title),
+            done: Some(request.done),
+            description: Some(request.description),
+            due_at: Some(request.due_at),
+            priority: Some(request.priority),
+            list_id: Some(request.list_id),
+        }
+    }
 }
 
-#[derive(Deserialize, ToSchema)]
+/// Query for `GET /todos`. Without `cursor` the list is paged by page
+/// number; with `cursor` (empty for the first page) it is keyset-paginated.
+#[derive(Deserialize, ToSchema, IntoParams, Clone, Debug)]
+#[into_params(parameter_in = Query)]
+#[serde(default)]
 pub struct PaginationQuery {
+    /// Page number (default: 1); ignored in cursor mode
+    pub page: u32,
+    /// Items per page (default: 10, max: 100)
+    pub limit: u32,
+    /// Opaque token from `next_cursor`/`prev_cursor`; empty starts at the newest todo
+    pub cursor: Option<String>,
+    /// Also count every matching todo in cursor mode (page mode always counts)
+    pub include_total: bool,
+    /// Only todos with this completion status
+    pub done: Option<bool>,
+    /// Case-insensitive substring of the title
+    pub title: Option<String>,
+    /// Only todos created after this instant (RFC 3339)
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only todos created before this instant (RFC 3339)
+    pub created_before: Option<DateTime<Utc>>,
+    /// Only todos updated at or after this instant (RFC 3339)
+    pub updated_since: Option<DateTime<Utc>>,
+    /// Only todos in this list. Without it, todos in archived lists are left out.
+    pub list_id: Option<Uuid>,
+    /// Only open todos whose due date has passed
+    pub overdue: bool,
+    /// Only todos due today (UTC)
+    pub due_today: bool,
+    /// Only todos with these tags, by name. Taken from every `tag` parameter
+    /// of the query string, which plain `Query` cannot collect.
+    #[serde(skip)]
+    pub tag: Vec<String>,
+    /// Whether todos need `any` (default) or `all` of the `tag`s
+    #[param(inline)]
+    pub tag_match: TagMatch,
+    /// Comma-separated fields, `-` for descending, e.g. `-updated_at,title`.
+    /// One of `created_at`, `updated_at`, `title`, `done`; default `-created_at`.
+    /// Cursor mode only supports the default.
+    pub sort: Option<String>,
+}
+
+/// Page-number query for listings without filters, such as the trash and
+/// a todo's history.
+#[derive(Deserialize, IntoParams, Clone, Debug)]
+#[into_params(parameter_in = Query)]
+pub struct PageQuery {
+    /// Page number (default: 1)
     #[serde(default = "default_page")]
     pub page: u32,
+    /// Items per page (default: 10, max: 100)
     #[serde(default = "default_limit")]
     pub limit: u32,
 }
 
+impl PageQuery {
+    pub fn pagination(&self) -> PaginationQuery {
+        PaginationQuery { page: self.page, limit: self.limit, ..Default::default() }
+    }
+}
+
 #[derive(Serialize, Clone, Debug, ToSchema)]
+#[aliases(
+    TodoPage = PaginatedResponse<crate::domain::todos::Todo>,
+    TodoSearchPage = PaginatedResponse<TodoSearchHit>,
+    TrashPage = PaginatedResponse<TrashedTodo>,
+    TodoHistoryPage = PaginatedResponse<TodoEvent>,
+    TodoListPage = PaginatedResponse<crate::domain::lists::TodoList>,
+    TagPage = PaginatedResponse<crate::domain::tags::Tag>,
+    WebhookPage = PaginatedResponse<crate::domain::webhooks::Webhook>,
+    ApiKeyPage = PaginatedResponse<crate::domain::api_keys::ApiKey>,
+    WebhookDeliveryPage = PaginatedResponse<crate::domain::webhooks::WebhookDelivery>
+)]
 pub struct PaginatedResponse<T> {
     pub data: Vec<T>,
     pub pagination: PaginationMeta,
@@ -37,5 +197,111 @@ pub struct PaginationMeta {
     pub has_prev: bool,
 }
 
+impl Default for PaginationQuery {
+    fn default() -> Self {
+        Self {
+            page: default_page(),
+            limit: default_limit(),
+            cursor: None,
+            include_total: false,
+            done: None,
+            title: None,
+            created_after: None,
+            created_before: None,
+            updated_since: None,
+            list_id: None,
+            overdue: false,
+            due_today: false,
+            tag: Vec::new(),
+            tag_match: TagMatch::Any,
+            sort: None,
+        }
+    }
+}
+
+impl PaginationQuery {
+    pub const MAX_LIMIT: u32 = 100;
+    pub const MAX_TAGS: usize = 20;
+
+    /// The filter as of now: `overdue` and `due_today` become bounds on
+    /// the due date. Repeated tags count once.
+    pub fn filter(&self) -> TodoFilter {
+        let now = Utc::now();
+        let today = now.date_naive().and_time(NaiveTime::MIN).and_utc();
+        let (mut done, mut due_after, mut due_before) = (self.done, None, None);
+        if self.due_today {
+            due_after = Some(today);
+            due_before = Some(today + Duration::days(1));
+        }
+        if self.overdue {
+            done = Some(false);
+            due_before = Some(due_before.map_or(now, |before: DateTime<Utc>| before.min(now)));
+        }
+        let mut tags = self.tag.clone();
+        tags.sort();
+        tags.dedup();
+
+        TodoFilter {
+            done,
+            title: self.title.clone(),
+            created_after: self.created_after,
+            created_before: self.created_before,
+            updated_since: self.updated_since,
+            list_id: self.list_id,
+            due_after,
+            due_before,
+            tags,
+            tag_match: self.tag_match,
+        }
+    }
+
+    /// Parsed `sort`, or the default order when absent. Invalid fields are
+    /// reported by `validate`; here they are skipped.
+    pub fn sort_keys(&self) -> Vec<SortKey> {
+        let keys: Vec<SortKey> = self
+            .sort
+            .iter()
+            .flat_map(|sort| sort.split(','))
+            .filter_map(|part| {
+                let (descending, name) = match part.trim().strip_prefix('-') {
+                    Some(name) => (true, name),
+                    None => (false, part.trim()),
+                };
+                SortField::from_name(name).map(|field| SortKey { field, descending })
+            })
+            .collect();
+
+        if keys.is_empty() { SortKey::DEFAULT.to_vec() } else { keys }
+    }
+
+    /// Page number, never below 1.
+    pub fn page(&self) -> u32 {
+        self.page.max(1)
+    }
+
+    /// Page size, clamped to `1..=MAX_LIMIT`.
+    pub fn limit(&self) -> u32 {
+        self.limit.clamp(1, Self::MAX_LIMIT)
+    }
+
+    pub fn offset(&self) -> u64 {
+        (self.page() as u64 - 1) * self.limit() as u64
+    }
+}
+
+impl PaginationMeta {
+    pub fn new(page: u32, limit: u32, total: u64) -> Self {
+        let total_pages = total.div_ceil(limit as u64) as u32;
+        Self {
+            page,
+            limit,
+            total,
+            total_pages,
+            has_next: page < total_pages,
+            has_prev: page > 1,
+        }
+    }
+}
+
 pub fn default_page() -> u32 { 1 }
 pub fn default_limit() -> u32 { 10 }
@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::domain::todos::{FieldChange, NewTodoEvent, Todo};
+
+/// What downstream services are told about a todo, serialized as
+/// `{"type": "TodoCreated", "data": {...}}`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "type", content = "data")]
+pub enum DomainEvent {
+    TodoCreated { todo: Todo },
+    /// Also sent when a todo is restored from the trash; `todo` is the full
+    /// new state, so consumers can upsert.
+    TodoUpdated { todo: Todo, changes: Vec<FieldChange> },
+    /// Sent after the `TodoUpdated` that set `done`.
+    TodoCompleted { todo: Todo },
+    /// The todo moved to the trash.
+    TodoDeleted { todo_id: Uuid, version: i64 },
+}
+
+impl DomainEvent {
+    /// Every value `event_type` can return.
+    pub const TYPES: [&'static str; 4] = ["TodoCreated", "TodoUpdated", "TodoCompleted", "TodoDeleted"];
+
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            DomainEvent::TodoCreated { .. } => "TodoCreated",
+            DomainEvent::TodoUpdated { .. } => "TodoUpdated",
+            DomainEvent::TodoCompleted { .. } => "TodoCompleted",
+            DomainEvent::TodoDeleted { .. } => "TodoDeleted",
+        }
+    }
+}
+
+/// Builds the domain events a write emits, in publishing order, from the
+/// change it records. Use cases choose one for each write and repositories
+/// queue what it returns in the outbox, in the write's transaction.
+pub type DomainEvents = fn(&NewTodoEvent) -> Vec<DomainEvent>;
+
+/// A domain event waiting in the outbox. Serializes to the envelope handed
+/// to publishers.
+#[derive(Serialize, Clone, Debug, FromRow)]
+pub struct OutboxMessage {
+    /// Outbox position; publishing order within a todo
+    #[serde(skip)]
+    pub id: i64,
+    /// Stable across redeliveries, for consumers to deduplicate on
+    pub event_id: Uuid,
+    pub todo_id: Uuid,
+    /// Owner of the todo; only this user's webhooks receive the event
+    #[serde(skip)]
+    pub owner_id: Option<Uuid>,
+    /// Tenant of the todo; only this tenant's webhooks receive the event
+    #[serde(skip)]
+    pub tenant_id: String,
+    #[serde(flatten)]
+    #[sqlx(json, rename = "payload")]
+    pub event: DomainEvent,
+    pub occurred_at: DateTime<Utc>,
+    /// Failed deliveries so far
+    #[serde(skip)]
+    pub attempts: i32,
+    /// Not handed out before this instant
+    #[serde(skip)]
+    pub available_at: DateTime<Utc>,
+}
+
+impl OutboxMessage {
+    /// The messages to store alongside a recorded change, one per event;
+    /// the store assigns `id`.
+    pub fn queue(change: &NewTodoEvent, events: Vec<DomainEvent>) -> Vec<OutboxMessage> {
+        let now = Utc::now();
+        events
+            .into_iter()
+            .map(|domain_event| OutboxMessage {
+                id: 0,
+                event_id: Uuid::new_v4(),
+                todo_id: change.todo_id,
+                owner_id: change.owner_id,
+                tenant_id: change.tenant_id.clone(),
+                event: domain_event,
+                occurred_at: now,
+                attempts: 0,
+                available_at: now,
+            })
+            .collect()
+    }
+}
+
+/// Exponential backoff between delivery attempts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub base: Duration,
+    pub max: Duration,
+}
+
+impl RetryPolicy {
+    /// Delay before retrying a message that has failed `attempts` times:
+    /// `base`, then doubling, capped at `max`.
+    pub fn delay(&self, attempts: i32) -> Duration {
+        let exponent = attempts.saturating_sub(1).clamp(0, 30) as u32;
+        self.base.saturating_mul(1 << exponent).min(self.max)
+    }
+}
+
+/// Outcome of one relay pass.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RelayReport {
+    /// Messages handed out by the outbox
+    pub fetched: usize,
+    pub published: usize,
+    pub failed: usize,
+}
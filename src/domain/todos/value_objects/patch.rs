@@ -0,0 +1,147 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::domain::todos::{Todo, TodoPriority, ReplaceTodoRequest};
+use crate::domain::todos::validation::FieldError;
+use crate::error::ApiError;
+
+/// A partial update in one of the two standard JSON patch formats.
+#[derive(Debug, Clone)]
+pub enum TodoPatch {
+    /// RFC 7396 `application/merge-patch+json`
+    Merge(Value),
+    /// RFC 6902 `application/json-patch+json`
+    Json(json_patch::Patch),
+}
+
+impl TodoPatch {
+    pub const MERGE_CONTENT_TYPE: &'static str = "application/merge-patch+json";
+    pub const JSON_CONTENT_TYPE: &'static str = "application/json-patch+json";
+
+    /// Parses a request body according to its media type (parameters such as
+    /// `charset` are ignored).
+    pub fn parse(content_type: &str, body: &[u8]) -> Result<Self, ApiError> {
+        let media_type = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+
+        match media_type.as_str() {
+            Self::MERGE_CONTENT_TYPE => serde_json::from_slice(body)
+                .map(TodoPatch::Merge)
+                .map_err(|e| ApiError::BadRequest(format!("invalid merge patch: {e}"))),
+            Self::JSON_CONTENT_TYPE => serde_json::from_slice(body)
+                .map(TodoPatch::Json)
+                .map_err(|e| ApiError::BadRequest(format!("invalid JSON patch: {e}"))),
+            _ => Err(ApiError::UnsupportedMediaType(format!(
+                "expected {} or {}",
+                Self::MERGE_CONTENT_TYPE,
+                Self::JSON_CONTENT_TYPE
+            ))),
+        }
+    }
+
+    /// Applies the patch to the writable fields of `todo` and returns the
+    /// resulting full representation.
+    pub fn apply(&self, todo: &Todo) -> Result<ReplaceTodoRequest, ApiError> {
+        let mut document = serde_json::json!({
+            "title": todo.title,
+            "done": todo.done,
+            "description": todo.description,
+            "due_at": todo.due_at,
+            "priority": todo.priority,
+            "list_id": todo.list_id,
+        });
+
+        match self {
+            TodoPatch::Merge(patch) => json_patch::merge(&mut document, patch),
+            TodoPatch::Json(patch) => json_patch::patch(&mut document, patch).map_err(|e| {
+                ApiError::Validation(vec![FieldError::new("patch", "patch_failed", e.to_string())])
+            })?,
+        }
+
+        replace_request_from(document)
+    }
+}
+
+fn replace_request_from(document: Value) -> Result<ReplaceTodoRequest, ApiError> {
+    let Value::Object(mut fields) = document else {
+        return Err(ApiError::Validation(vec![FieldError::new(
+            "body",
+            "invalid_type",
+            "the patched document must be an object",
+        )]));
+    };
+
+    let mut errors = Vec::new();
+
+    let title = match fields.remove("title") {
+        Some(Value::String(title)) => Some(title),
+        other => {
+            errors.push(field_error("title", other.is_none(), "a string"));
+            None
+        }
+    };
+    let done = match fields.remove("done") {
+        Some(Value::Bool(done)) => Some(done),
+        other => {
+            errors.push(field_error("done", other.is_none(), "a boolean"));
+            None
+        }
+    };
+
+    // Nullable fields may be removed, which clears them.
+    let description = match fields.remove("description") {
+        None | Some(Value::Null) => Some(None),
+        Some(Value::String(description)) => Some(Some(description)),
+        Some(_) => {
+            errors.push(FieldError::new("description", "invalid_type", "description must be a string or null"));
+            None
+        }
+    };
+    let due_at = match fields.remove("due_at") {
+        None | Some(Value::Null) => Some(None),
+        Some(value) => match value.as_str().map(DateTime::parse_from_rfc3339) {
+            Some(Ok(due_at)) => Some(Some(due_at.with_timezone(&Utc))),
+            _ => {
+                errors.push(FieldError::new("due_at", "invalid_type", "due_at must be an RFC 3339 timestamp or null"));
+                None
+            }
+        },
+    };
+    let priority = match fields.remove("priority").map(serde_json::from_value::<TodoPriority>) {
+        Some(Ok(priority)) => Some(priority),
+        other => {
+            errors.push(field_error("priority", other.is_none(), "one of low, medium or high"));
+            None
+        }
+    };
+
+    let list_id = match fields.remove("list_id") {
+        None | Some(Value::Null) => Some(None),
+        Some(value) => match value.as_str().map(Uuid::parse_str) {
+            Some(Ok(list_id)) => Some(Some(list_id)),
+            _ => {
+                errors.push(FieldError::new("list_id", "invalid_type", "list_id must be a UUID or null"));
+                None
+            }
+        },
+    };
+
+    for unknown in fields.keys() {
+        errors.push(FieldError::new(unknown, "unknown_field", format!("{unknown} is not a writable field")));
+    }
+
+    match (title, done, description, due_at, priority, list_id) {
+        (Some(title), Some(done), Some(description), Some(due_at), Some(priority), Some(list_id)) if errors.is_empty() => {
+            Ok(ReplaceTodoRequest { title, done, description, due_at, priority, list_id })
+        }
+        _ => Err(ApiError::Validation(errors)),
+    }
+}
+
+fn field_error(name: &str, missing: bool, expected: &str) -> FieldError {
+    if missing {
+        FieldError::new(name, "required", format!("{name} cannot be removed"))
+    } else {
+        FieldError::new(name, "invalid_type", format!("{name} must be {expected}"))
+    }
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// How urgent a todo is; todos are `medium` unless told otherwise.
+#[derive(Serialize, Deserialize, ToSchema, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoPriority {
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl TodoPriority {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TodoPriority::Low => "low",
+            TodoPriority::Medium => "medium",
+            TodoPriority::High => "high",
+        }
+    }
+}
+
+impl TryFrom<String> for TodoPriority {
+    type Error = String;
+
+    fn try_from(priority: String) -> Result<Self, Self::Error> {
+        match priority.as_str() {
+            "low" => Ok(TodoPriority::Low),
+            "medium" => Ok(TodoPriority::Medium),
+            "high" => Ok(TodoPriority::High),
+            _ => Err(format!("unknown todo priority `{priority}`")),
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::domain::todos::{default_limit, default_page, PaginatedResponse, PaginationMeta, PaginationQuery, SortKey, Todo};
+
+/// Query for `GET /todos/search`.
+#[derive(Deserialize, IntoParams, Clone, Debug)]
+#[into_params(parameter_in = Query)]
+pub struct SearchTodosQuery {
+    /// Words to find. `"quoted words"` match as a phrase and a trailing `*`
+    /// matches a prefix, e.g. `"buy milk" groc*`. Every term must match.
+    pub q: String,
+    /// Page number (default: 1)
+    #[serde(default = "default_page")]
+    pub page: u32,
+    /// Items per page (default: 10, max: 100)
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+}
+
+impl SearchTodosQuery {
+    pub const MAX_QUERY_LENGTH: usize = 256;
+
+    pub fn pagination(&self) -> PaginationQuery {
+        PaginationQuery { page: self.page, limit: self.limit, ..Default::default() }
+    }
+}
+
+/// One term of a parsed search. Words hold only alphanumeric characters,
+/// lowercased, so backends can hand them to their query syntax verbatim.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SearchTerm {
+    Word { text: String, prefix: bool },
+    Phrase(Vec<String>),
+}
+
+#[derive(Clone, Debug)]
+pub struct TodoSearch {
+    pub terms: Vec<SearchTerm>,
+    pub pagination: PaginationQuery,
+}
+
+impl TodoSearch {
+    /// Marks a matched word in `TodoSearchHit::snippet`.
+    pub const HIGHLIGHT_START: &str = "<mark>";
+    pub const HIGHLIGHT_END: &str = "</mark>";
+    /// Stand in for the highlight marks until the title around them is
+    /// escaped. Titles cannot contain control characters, so these never
+    /// clash with the text.
+    pub const MATCH_START: char = '\u{2}';
+    pub const MATCH_END: char = '\u{3}';
+
+    pub fn parse(query: &SearchTodosQuery) -> Self {
+        let mut terms = Vec::new();
+
+        for (index, chunk) in query.q.split('"').enumerate() {
+            // Odd chunks were inside quotes.
+            if index % 2 == 1 {
+                match words(chunk).as_slice() {
+                    [] => {}
+                    [word] => terms.push(SearchTerm::Word { text: word.clone(), prefix: false }),
+                    phrase => terms.push(SearchTerm::Phrase(phrase.to_vec())),
+                }
+                continue;
+            }
+
+            for token in chunk.split_whitespace() {
+                let prefix = token.ends_with('*');
+                match words(token).as_slice() {
+                    [] => {}
+                    [word] => terms.push(SearchTerm::Word { text: word.clone(), prefix }),
+                    // `e-mail` is searched like the phrase `"e mail"`.
+                    phrase => terms.push(SearchTerm::Phrase(phrase.to_vec())),
+                }
+            }
+        }
+
+        Self { terms, pagination: query.pagination() }
+    }
+
+    /// Rank and highlighted title when every term matches `title`, for
+    /// backends without a text-search engine. The rank is the share of the
+    /// title's words that matched.
+    pub fn score(&self, title: &str) -> Option<(f32, String)> {
+        let spans = word_spans(title);
+        let lowered: Vec<String> = spans.iter().map(|&(start, end)| title[start..end].to_lowercase()).collect();
+        let mut matched = vec![false; spans.len()];
+
+        for term in &self.terms {
+            let mut found = false;
+            match term {
+                SearchTerm::Word { text, prefix } => {
+                    for (i, word) in lowered.iter().enumerate() {
+                        if word == text || (*prefix && word.starts_with(text.as_str())) {
+                            matched[i] = true;
+                            found = true;
+                        }
+                    }
+                }
+                SearchTerm::Phrase(phrase) => {
+                    for start in 0..lowered.len().saturating_sub(phrase.len() - 1) {
+                        if lowered[start..start + phrase.len()] == phrase[..] {
+                            matched[start..start + phrase.len()].fill(true);
+                            found = true;
+                        }
+                    }
+                }
+            }
+            if !found {
+                return None;
+            }
+        }
+
+        let rank = matched.iter().filter(|m| **m).count() as f32 / spans.len().max(1) as f32;
+
+        let mut marked = String::with_capacity(title.len());
+        let mut last = 0;
+        for (&(start, end), _) in spans.iter().zip(&matched).filter(|(_, m)| **m) {
+            marked.push_str(&title[last..start]);
+            marked.push(Self::MATCH_START);
+            marked.push_str(&title[start..end]);
+            marked.push(Self::MATCH_END);
+            last = end;
+        }
+        marked.push_str(&title[last..]);
+
+        Some((rank, Self::snippet(&marked)))
+    }
+
+    /// Builds a `TodoSearchHit::snippet` from a title whose matches are
+    /// wrapped in `MATCH_START`…`MATCH_END`: the title is HTML-escaped and the
+    /// stand-ins become `<mark>` tags.
+    pub fn snippet(marked: &str) -> String {
+        let mut snippet = String::with_capacity(marked.len());
+        for c in marked.chars() {
+            match c {
+                Self::MATCH_START => snippet.push_str(Self::HIGHLIGHT_START),
+                Self::MATCH_END => snippet.push_str(Self::HIGHLIGHT_END),
+                '&' => snippet.push_str("&amp;"),
+                '<' => snippet.push_str("&lt;"),
+                '>' => snippet.push_str("&gt;"),
+                '"' => snippet.push_str("&quot;"),
+                '\'' => snippet.push_str("&#39;"),
+                c => snippet.push(c),
+            }
+        }
+        snippet
+    }
+}
+
+impl TodoSearch {
+    /// Scores `candidates` with [`TodoSearch::score`] and returns the
+    /// requested page of hits, most relevant first.
+    pub fn rank(&self, candidates: impl IntoIterator<Item = Todo>) -> PaginatedResponse<TodoSearchHit> {
+        let mut hits: Vec<TodoSearchHit> = candidates
+            .into_iter()
+            .filter_map(|todo| {
+                let (rank, snippet) = self.score(&todo.title)?;
+                Some(TodoSearchHit { todo, rank, snippet })
+            })
+            .collect();
+        hits.sort_by(|a, b| {
+            b.rank
+                .total_cmp(&a.rank)
+                .then_with(|| SortKey::compare(&SortKey::DEFAULT, &a.todo, &b.todo))
+        });
+
+        let page = self.pagination.page();
+        let limit = self.pagination.limit();
+        let total = hits.len() as u64;
+        let data = hits
+            .into_iter()
+            .skip(self.pagination.offset() as usize)
+            .take(limit as usize)
+            .collect();
+
+        PaginatedResponse { data, pagination: PaginationMeta::new(page, limit, total) }
+    }
+
+    /// Every word the terms require, for prefiltering candidates.
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.terms.iter().flat_map(|term| match term {
+            SearchTerm::Word { text, .. } => std::slice::from_ref(text),
+            SearchTerm::Phrase(words) => words.as_slice(),
+        })
+        .map(String::as_str)
+    }
+}
+
+fn words(text: &str) -> Vec<String> {
+    word_spans(text).into_iter().map(|(start, end)| text[start..end].to_lowercase()).collect()
+}
+
+/// Byte ranges of the alphanumeric runs in `text`.
+fn word_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                spans.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        spans.push((s, text.len()));
+    }
+    spans
+}
+
+#[derive(Serialize, Clone, Debug, ToSchema)]
+pub struct TodoSearchHit {
+    #[serde(flatten)]
+    pub todo: Todo,
+    /// Relevance; higher is better. Only comparable within one search.
+    pub rank: f32,
+    /// The title, HTML-escaped, with matched words wrapped in
+    /// `<mark>`…`</mark>`. Safe to insert into a page as markup.
+    pub snippet: String,
+}
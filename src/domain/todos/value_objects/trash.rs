@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+use crate::domain::todos::Todo;
+
+/// A soft-deleted todo, listed most recently deleted first.
+#[derive(Serialize, Clone, Debug, ToSchema, FromRow)]
+pub struct TrashedTodo {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub todo: Todo,
+    pub deleted_at: DateTime<Utc>,
+}
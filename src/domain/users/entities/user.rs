@@ -0,0 +1,24 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// An account that owns todos.
+#[derive(Serialize, Clone, Debug, ToSchema, FromRow)]
+pub struct User {
+    pub id: Uuid,
+    /// Lowercased; unique across accounts
+    pub email: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A user together with the Argon2 hash of their password, for logins.
+#[derive(Clone, Debug, FromRow)]
+pub struct UserCredentials {
+    #[sqlx(flatten)]
+    pub user: User,
+    /// PHC string, e.g. `$argon2id$v=19$...`
+    pub password_hash: String,
+}
@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::users::{NewRefreshToken, RefreshRotation, User, UserCredentials};
+use crate::error::ApiError;
+
+/// Accounts belong to the tenant they registered in and are only found
+/// there; the same email may register in several tenants.
+#[async_trait]
+pub trait UserStore {
+    /// Stores a new account; an email already taken in the tenant yields
+    /// `ApiError::Conflict`. `email` must be normalized.
+    async fn create_user(&self, email: &str, password_hash: &str) -> Result<User, ApiError>;
+
+    async fn find_user(&self, id: Uuid) -> Result<Option<User>, ApiError>;
+
+    /// Looks up a normalized email, for logins.
+    async fn find_credentials(&self, email: &str) -> Result<Option<UserCredentials>, ApiError>;
+}
+
+/// Refresh tokens belong to the tenant their session was signed in to and
+/// are unknown in any other.
+#[async_trait]
+pub trait RefreshTokens {
+    async fn store_refresh_token(&self, token: NewRefreshToken) -> Result<(), ApiError>;
+
+    /// Atomically replaces the token hashed as `token_hash`, if it is valid
+    /// at `now`, with one hashed as `next_hash` in the same family.
+    async fn rotate_refresh_token(
+        &self,
+        token_hash: &str,
+        next_hash: &str,
+        expires_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<RefreshRotation, ApiError>;
+
+    /// Revokes the family of the token hashed as `token_hash`, if any.
+    async fn revoke_refresh_family(&self, token_hash: &str, now: DateTime<Utc>) -> Result<(), ApiError>;
+}
+
+/// Every user capability in one object-safe trait.
+pub trait UserRepository: UserStore + RefreshTokens + Send + Sync {}
+
+impl<T> UserRepository for T where T: UserStore + RefreshTokens + Send + Sync {}
+
+/// Hashes and checks passwords. Implementations are deliberately slow.
+#[async_trait]
+pub trait PasswordHasher: Send + Sync {
+    async fn hash(&self, password: &str) -> Result<String, ApiError>;
+
+    /// Whether `password` matches `hash`. Without a hash it spends the same
+    /// time and fails, so logins do not reveal which emails have accounts.
+    async fn verify(&self, password: &str, hash: Option<&str>) -> Result<bool, ApiError>;
+}
@@ -0,0 +1,56 @@
+use crate::domain::todos::validation::FieldError;
+use crate::domain::users::RegisterRequest;
+use crate::error::ApiError;
+
+pub const EMAIL_MAX_LENGTH: usize = 254;
+pub const PASSWORD_MIN_LENGTH: usize = 8;
+pub const PASSWORD_MAX_LENGTH: usize = 128;
+
+/// Emails are compared case-insensitively and stored lowercased.
+pub fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+fn validate_email(email: &str, errors: &mut Vec<FieldError>) {
+    if email.len() > EMAIL_MAX_LENGTH {
+        errors.push(FieldError::new("email", "too_long", format!("email must be at most {EMAIL_MAX_LENGTH} bytes")));
+        return;
+    }
+
+    let valid = match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !domain.contains('@')
+                && !email.chars().any(|c| c.is_whitespace() || c.is_control())
+        }
+        None => false,
+    };
+    if !valid {
+        errors.push(FieldError::new("email", "invalid", "email must be an address like name@example.com"));
+    }
+}
+
+fn validate_password(password: &str, errors: &mut Vec<FieldError>) {
+    let length = password.chars().count();
+    if !(PASSWORD_MIN_LENGTH..=PASSWORD_MAX_LENGTH).contains(&length) {
+        errors.push(FieldError::new(
+            "password",
+            "invalid_length",
+            format!("password must be {PASSWORD_MIN_LENGTH} to {PASSWORD_MAX_LENGTH} characters"),
+        ));
+    }
+}
+
+impl RegisterRequest {
+    /// Checks the request as it will be stored, i.e. with the email
+    /// normalized.
+    pub fn validate(&self) -> Result<(), ApiError> {
+        let mut errors = Vec::new();
+        validate_email(&normalize_email(&self.email), &mut errors);
+        validate_password(&self.password, &mut errors);
+        if errors.is_empty() { Ok(()) } else { Err(ApiError::Validation(errors)) }
+    }
+}
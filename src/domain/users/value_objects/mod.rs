@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::users::User;
+
+pub mod refresh_token;
+
+pub use refresh_token::*;
+
+#[derive(Deserialize, ToSchema, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RegisterRequest {
+    pub email: String,
+    /// 8 to 128 characters
+    pub password: String,
+}
+
+#[derive(Deserialize, ToSchema, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LoginRequest {
+    pub email: String,
+    pub password: String,
+}
+
+/// Body of `POST /auth/refresh` and `POST /auth/logout`.
+#[derive(Deserialize, ToSchema, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
+/// Credentials handed out by a login or refresh.
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct TokenResponse {
+    /// JWT to send as `Authorization: Bearer <token>`
+    pub access_token: String,
+    /// Always `Bearer`
+    pub token_type: String,
+    /// Seconds until the access token expires
+    pub expires_in: u64,
+    /// Single-use token for `POST /auth/refresh`
+    pub refresh_token: String,
+}
+
+/// A login or refresh that succeeded: the user to issue an access token
+/// for, and the refresh token that continues the session.
+#[derive(Clone, Debug)]
+pub struct Session {
+    pub user: User,
+    pub refresh_token: String,
+}
@@ -0,0 +1,77 @@
+//! Refresh tokens are random and opaque; only their SHA-256 hash is stored.
+//! Each login starts a family of tokens. A refresh replaces the presented
+//! token with the next of its family, and presenting a replaced token again
+//! revokes the whole family, since one of its holders must be an attacker.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A freshly minted token: the secret goes to the client, the hash to the
+/// store.
+#[derive(Clone, Debug)]
+pub struct RefreshSecret {
+    pub secret: String,
+    pub hash: String,
+}
+
+impl RefreshSecret {
+    pub fn generate() -> Self {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let secret = URL_SAFE_NO_PAD.encode(bytes);
+        Self { hash: hash_refresh_token(&secret), secret }
+    }
+}
+
+/// The first token of a family, as stored at login.
+#[derive(Clone, Debug)]
+pub struct NewRefreshToken {
+    pub user_id: Uuid,
+    pub family_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+pub fn hash_refresh_token(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
+/// A stored token as found by a refresh.
+#[derive(Clone, Debug, FromRow)]
+pub struct StoredRefreshToken {
+    pub user_id: Uuid,
+    pub family_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub replaced_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl StoredRefreshToken {
+    /// What presenting this token at `now` does; the store carries it out.
+    pub fn rotation(&self, now: DateTime<Utc>) -> RefreshRotation {
+        if self.revoked_at.is_some() {
+            RefreshRotation::Invalid
+        } else if self.replaced_at.is_some() {
+            RefreshRotation::Reused
+        } else if self.expires_at <= now {
+            RefreshRotation::Invalid
+        } else {
+            RefreshRotation::Rotated { user_id: self.user_id }
+        }
+    }
+}
+
+/// What presenting a refresh token did.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RefreshRotation {
+    /// The token was valid and is now replaced by the next one.
+    Rotated { user_id: Uuid },
+    /// The token was already replaced; its family is now revoked.
+    Reused,
+    /// Unknown, expired or revoked.
+    Invalid,
+}
@@ -0,0 +1,5 @@
+pub mod delivery;
+pub mod webhook;
+
+pub use delivery::*;
+pub use webhook::*;
@@ -0,0 +1,35 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// A subscription to todo events, delivered by `POST` to `url`.
+#[derive(Serialize, Clone, Debug, ToSchema, FromRow)]
+pub struct Webhook {
+    pub id: Uuid,
+    pub url: String,
+    /// Event types to deliver; empty means every type
+    #[sqlx(json)]
+    pub events: Vec<String>,
+    /// Signing key; never returned by the API
+    #[serde(skip)]
+    pub secret: String,
+    /// Cleared automatically after too many failed attempts in a row
+    pub active: bool,
+    /// Failed delivery attempts since the last success
+    pub consecutive_failures: i32,
+    /// The user whose todo events this webhook receives
+    pub owner_id: Option<Uuid>,
+    /// The organisation the webhook belongs to; it only receives that
+    /// tenant's events
+    pub tenant_id: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Webhook {
+    pub fn subscribes_to(&self, event_type: &str) -> bool {
+        self.events.is_empty() || self.events.iter().any(|event| event == event_type)
+    }
+}
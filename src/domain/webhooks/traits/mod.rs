@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::todos::{OutboxMessage, PaginatedResponse, PaginationQuery};
+use crate::domain::webhooks::{
+    CreateWebhookRequest, DeliveryOutcome, UpdateWebhookRequest, Webhook, WebhookDelivery, WebhookRequest,
+};
+use crate::error::ApiError;
+
+/// Webhook subscriptions. A missing `id` yields `ApiError::NotFound`.
+#[async_trait]
+pub trait WebhookStore {
+    async fn create(&self, data: CreateWebhookRequest) -> Result<Webhook, ApiError>;
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Webhook>, ApiError>;
+
+    /// Reads one page of webhooks, oldest first.
+    async fn find_all_paginated(&self, pagination: PaginationQuery) -> Result<PaginatedResponse<Webhook>, ApiError>;
+
+    /// Applies the given fields. Setting `active` to `true` also resets
+    /// `consecutive_failures`.
+    async fn update(&self, id: Uuid, data: UpdateWebhookRequest) -> Result<Webhook, ApiError>;
+
+    /// Removes the webhook together with its deliveries.
+    async fn delete(&self, id: Uuid) -> Result<(), ApiError>;
+}
+
+#[async_trait]
+pub trait WebhookDeliveries {
+    /// Queues `message` for every active webhook subscribed to its type,
+    /// returning how many deliveries were queued. Queuing the same event
+    /// twice for a webhook is a no-op, so the outbox may redeliver.
+    async fn enqueue(&self, message: &OutboxMessage) -> Result<u64, ApiError>;
+
+    /// Up to `limit` pending deliveries of active webhooks due at `now`,
+    /// oldest first.
+    async fn due_deliveries(&self, limit: u32, now: DateTime<Utc>) -> Result<Vec<WebhookDelivery>, ApiError>;
+
+    /// Stores the outcome of an attempt and updates the webhook's failure
+    /// count: a success resets it, a failure increments it and disables the
+    /// webhook once it reaches `disable_after`.
+    async fn record_attempt(&self, id: i64, outcome: DeliveryOutcome, disable_after: i32) -> Result<(), ApiError>;
+
+    /// Reads one page of a webhook's deliveries, newest first.
+    async fn find_deliveries(
+        &self,
+        webhook_id: Uuid,
+        pagination: PaginationQuery,
+    ) -> Result<PaginatedResponse<WebhookDelivery>, ApiError>;
+}
+
+/// Every webhook capability in one object-safe trait.
+pub trait WebhookRepository: WebhookStore + WebhookDeliveries + Send + Sync {}
+
+impl<T> WebhookRepository for T where T: WebhookStore + WebhookDeliveries + Send + Sync {}
+
+/// Sends a delivery over HTTP. `Ok` carries the response status, whatever
+/// it is; `Err` means no response was received.
+#[async_trait]
+pub trait WebhookSender: Send + Sync {
+    async fn send(&self, request: WebhookRequest) -> anyhow::Result<u16>;
+}
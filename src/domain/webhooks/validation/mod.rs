@@ -0,0 +1,87 @@
+use crate::domain::todos::DomainEvent;
+use crate::domain::todos::validation::FieldError;
+use crate::domain::webhooks::{CreateWebhookRequest, TargetPolicy, UpdateWebhookRequest};
+use crate::error::ApiError;
+
+pub const URL_MAX_LENGTH: usize = 2048;
+pub const SECRET_MIN_LENGTH: usize = 16;
+pub const SECRET_MAX_LENGTH: usize = 256;
+
+fn validate_url(url: &str, targets: TargetPolicy, errors: &mut Vec<FieldError>) {
+    if url.len() > URL_MAX_LENGTH {
+        errors.push(FieldError::new("url", "too_long", format!("url must be at most {URL_MAX_LENGTH} bytes")));
+        return;
+    }
+
+    match url::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") && parsed.host().is_some() => {
+            if !parsed.host().is_some_and(|host| targets.permits_host(&host)) {
+                errors.push(FieldError::new(
+                    "url",
+                    "private_address",
+                    "url must not point at a loopback, private, link-local or unspecified address",
+                ));
+            }
+        }
+        Ok(_) => errors.push(FieldError::new("url", "unsupported_scheme", "url must be an http or https URL")),
+        Err(_) => errors.push(FieldError::new("url", "invalid", "url must be an absolute URL")),
+    }
+}
+
+fn validate_events(events: &[String], errors: &mut Vec<FieldError>) {
+    for event in events {
+        if !DomainEvent::TYPES.contains(&event.as_str()) {
+            errors.push(FieldError::new(
+                "events",
+                "unknown_event",
+                format!("unknown event type `{event}`; expected one of {}", DomainEvent::TYPES.join(", ")),
+            ));
+        }
+    }
+}
+
+fn validate_secret(secret: &str, errors: &mut Vec<FieldError>) {
+    let length = secret.chars().count();
+    if !(SECRET_MIN_LENGTH..=SECRET_MAX_LENGTH).contains(&length) {
+        errors.push(FieldError::new(
+            "secret",
+            "invalid_length",
+            format!("secret must be {SECRET_MIN_LENGTH} to {SECRET_MAX_LENGTH} characters"),
+        ));
+    }
+}
+
+fn into_result(errors: Vec<FieldError>) -> Result<(), ApiError> {
+    if errors.is_empty() { Ok(()) } else { Err(ApiError::Validation(errors)) }
+}
+
+impl CreateWebhookRequest {
+    pub fn validate(&self, targets: TargetPolicy) -> Result<(), ApiError> {
+        let mut errors = Vec::new();
+        validate_url(&self.url, targets, &mut errors);
+        validate_events(&self.events, &mut errors);
+        validate_secret(&self.secret, &mut errors);
+        into_result(errors)
+    }
+}
+
+impl UpdateWebhookRequest {
+    pub fn validate(&self, targets: TargetPolicy) -> Result<(), ApiError> {
+        let mut errors = Vec::new();
+
+        if self.url.is_none() && self.events.is_none() && self.secret.is_none() && self.active.is_none() {
+            errors.push(FieldError::new("body", "empty_update", "at least one field must be set"));
+        }
+        if let Some(url) = &self.url {
+            validate_url(url, targets, &mut errors);
+        }
+        if let Some(events) = &self.events {
+            validate_events(events, &mut errors);
+        }
+        if let Some(secret) = &self.secret {
+            validate_secret(secret, &mut errors);
+        }
+
+        into_result(errors)
+    }
+}
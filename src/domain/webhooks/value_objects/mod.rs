@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::domain::todos::RetryPolicy;
+
+pub mod signature;
+pub mod targets;
+
+pub use signature::*;
+pub use targets::*;
+
+#[derive(Deserialize, ToSchema, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CreateWebhookRequest {
+    /// Absolute `http` or `https` URL that receives the events
+    pub url: String,
+    /// Event types to deliver, e.g. `["TodoCompleted"]`; omitted or empty means every type
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// Key for the `X-Signature` HMAC, 16 to 256 characters
+    pub secret: String,
+}
+
+/// Partial update; only the given fields change.
+#[derive(Deserialize, ToSchema, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateWebhookRequest {
+    pub url: Option<String>,
+    pub events: Option<Vec<String>>,
+    pub secret: Option<String>,
+    /// `true` re-enables a disabled webhook and resets its failure count
+    pub active: Option<bool>,
+}
+
+/// Result of one delivery attempt, as decided by the dispatcher.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DeliveryOutcome {
+    /// The receiver answered with a 2xx status.
+    Succeeded { response_status: u16 },
+    /// Try again at `retry_at`, or give up when it is `None`.
+    Failed { response_status: Option<u16>, error: String, retry_at: Option<DateTime<Utc>> },
+}
+
+/// How the dispatcher retries and when it gives up on a webhook.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DeliveryPolicy {
+    pub retry: RetryPolicy,
+    /// Attempts per delivery, the first included
+    pub max_attempts: i32,
+    /// Failed attempts in a row, across deliveries, that disable a webhook
+    pub disable_after: i32,
+}
+
+/// An HTTP request ready to be sent to a receiver.
+#[derive(Clone, Debug)]
+pub struct WebhookRequest {
+    pub url: String,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Vec<u8>,
+}
+
+/// Outcome of one dispatcher pass.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DispatchReport {
+    /// Deliveries handed out by the store
+    pub fetched: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+}
@@ -0,0 +1,53 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use url::Host;
+
+/// Which receivers webhooks may reach. Loopback, private, link-local and
+/// unspecified addresses are refused unless `allow_private` is set, so a
+/// subscription cannot be aimed at services inside the network.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TargetPolicy {
+    pub allow_private: bool,
+}
+
+impl TargetPolicy {
+    /// Whether a receiver at `ip` may be contacted.
+    pub fn permits(&self, ip: IpAddr) -> bool {
+        self.allow_private || is_public(ip)
+    }
+
+    /// Whether `host` may be targeted, as far as can be told before it is
+    /// resolved: addresses must be permitted and `localhost` names are
+    /// refused. Other names are checked again once resolved.
+    pub fn permits_host(&self, host: &Host<&str>) -> bool {
+        match host {
+            Host::Ipv4(ip) => self.permits(IpAddr::V4(*ip)),
+            Host::Ipv6(ip) => self.permits(IpAddr::V6(*ip)),
+            Host::Domain(name) => {
+                let name = name.trim_end_matches('.').to_ascii_lowercase();
+                self.allow_private || (name != "localhost" && !name.ends_with(".localhost"))
+            }
+        }
+    }
+}
+
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_public_v4(mapped),
+            None => is_public_v6(ip),
+        },
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    // 100.64.0.0/10 is carrier-grade NAT space, private in all but name.
+    let shared = a == 100 && (b & 0xc0) == 64;
+    !(ip.is_loopback() || ip.is_private() || ip.is_link_local() || ip.is_unspecified() || ip.is_broadcast() || shared)
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    !(ip.is_loopback() || ip.is_unspecified() || ip.is_unique_local() || ip.is_unicast_link_local())
+}
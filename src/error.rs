@@ -1,22 +1,229 @@
-use axum::{http::StatusCode, response::IntoResponse, Json};
+use axum::{
+    extract::Request,
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::domain::todos::validation::FieldError;
+
+pub const PROBLEM_JSON: &str = "application/problem+json";
 
 #[derive(thiserror::Error, Debug)]
 pub enum ApiError {
     #[error("not found")]
     NotFound,
+    #[error("validation failed")]
+    Validation(Vec<FieldError>),
+    #[error("bad request: {0}")]
+    BadRequest(String),
+    #[error("unauthorized: {0}")]
+    Unauthorized(String),
+    #[error("forbidden: {0}")]
+    Forbidden(String),
+    #[error("unsupported media type: {0}")]
+    UnsupportedMediaType(String),
+    #[error("payload too large: {0}")]
+    PayloadTooLarge(String),
+    #[error("conflict: {0}")]
+    Conflict(String),
+    #[error("precondition failed")]
+    PreconditionFailed,
+    #[error("precondition required")]
+    PreconditionRequired,
+    #[error("failed dependency")]
+    FailedDependency,
+    #[error("constraint violation: {0}")]
+    ConstraintViolation(String),
+    #[error("service unavailable: {0}")]
+    ServiceUnavailable(String),
     #[error("database error: {0}")]
     DatabaseError(String),
     #[error(transparent)]
     Anyhow(#[from] anyhow::Error),
 }
 
+/// RFC 7807 problem details body, served as `application/problem+json`.
+#[derive(Serialize, Clone, Debug, ToSchema)]
+pub struct ProblemDetails {
+    /// URI reference identifying the problem type
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    /// Path of the request that produced the problem
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Stable machine-readable error code
+    pub code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<FieldError>>,
+}
+
+impl ApiError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::NotFound => StatusCode::NOT_FOUND,
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ApiError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ApiError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            ApiError::Validation(_) | ApiError::ConstraintViolation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::PreconditionFailed => StatusCode::PRECONDITION_FAILED,
+            ApiError::PreconditionRequired => StatusCode::PRECONDITION_REQUIRED,
+            ApiError::FailedDependency => StatusCode::FAILED_DEPENDENCY,
+            ApiError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            ApiError::DatabaseError(_) | ApiError::Anyhow(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::NotFound => "not_found",
+            ApiError::Validation(_) => "validation_failed",
+            ApiError::BadRequest(_) => "bad_request",
+            ApiError::Unauthorized(_) => "unauthorized",
+            ApiError::Forbidden(_) => "forbidden",
+            ApiError::UnsupportedMediaType(_) => "unsupported_media_type",
+            ApiError::PayloadTooLarge(_) => "payload_too_large",
+            ApiError::Conflict(_) => "conflict",
+            ApiError::PreconditionFailed => "precondition_failed",
+            ApiError::PreconditionRequired => "precondition_required",
+            ApiError::FailedDependency => "failed_dependency",
+            ApiError::ConstraintViolation(_) => "constraint_violation",
+            ApiError::ServiceUnavailable(_) => "service_unavailable",
+            ApiError::DatabaseError(_) => "database_error",
+            ApiError::Anyhow(_) => "internal_error",
+        }
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            ApiError::NotFound => "Resource not found",
+            ApiError::Validation(_) => "Validation failed",
+            ApiError::BadRequest(_) => "Bad request",
+            ApiError::Unauthorized(_) => "Unauthorized",
+            ApiError::Forbidden(_) => "Forbidden",
+            ApiError::UnsupportedMediaType(_) => "Unsupported media type",
+            ApiError::PayloadTooLarge(_) => "Payload too large",
+            ApiError::Conflict(_) => "Conflict",
+            ApiError::PreconditionFailed => "Precondition failed",
+            ApiError::PreconditionRequired => "Precondition required",
+            ApiError::FailedDependency => "Failed dependency",
+            ApiError::ConstraintViolation(_) => "Constraint violation",
+            ApiError::ServiceUnavailable(_) => "Service unavailable",
+            ApiError::DatabaseError(_) => "Database error",
+            ApiError::Anyhow(_) => "Internal server error",
+        }
+    }
+
+    // Server-side causes are logged, never echoed back to the client.
+    fn detail(&self) -> String {
+        match self {
+            ApiError::NotFound => "The requested resource does not exist.".to_string(),
+            ApiError::Validation(errors) => format!("{} field(s) failed validation.", errors.len()),
+            ApiError::BadRequest(message)
+            | ApiError::Unauthorized(message)
+            | ApiError::Forbidden(message)
+            | ApiError::UnsupportedMediaType(message)
+            | ApiError::PayloadTooLarge(message) => message.clone(),
+            ApiError::Conflict(_) => "The request conflicts with an existing resource.".to_string(),
+            ApiError::PreconditionFailed => "The resource was modified since the given ETag; fetch it again and retry.".to_string(),
+            ApiError::PreconditionRequired => "This request must be conditional; send If-Match with the current ETag.".to_string(),
+            ApiError::FailedDependency => "Not applied because another operation in the atomic batch failed.".to_string(),
+            ApiError::ConstraintViolation(_) => "The request violates a data constraint.".to_string(),
+            ApiError::ServiceUnavailable(_) => "The database is temporarily unavailable; retry later.".to_string(),
+            ApiError::DatabaseError(_) => "An unexpected database error occurred.".to_string(),
+            ApiError::Anyhow(_) => "An unexpected error occurred.".to_string(),
+        }
+    }
+
+    pub fn to_problem(&self) -> ProblemDetails {
+        ProblemDetails {
+            problem_type: format!("/problems/{}", self.code().replace('_', "-")),
+            title: self.title().to_string(),
+            status: self.status().as_u16(),
+            detail: self.detail(),
+            instance: None,
+            code: self.code().to_string(),
+            errors: match self {
+                ApiError::Validation(errors) => Some(errors.clone()),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl From<sqlx::Error> for ApiError {
+    fn from(error: sqlx::Error) -> Self {
+        use sqlx::error::ErrorKind;
+
+        match &error {
+            sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed | sqlx::Error::Io(_) | sqlx::Error::Tls(_) => {
+                ApiError::ServiceUnavailable(error.to_string())
+            }
+            sqlx::Error::Database(db) => match db.kind() {
+                ErrorKind::UniqueViolation => ApiError::Conflict(error.to_string()),
+                ErrorKind::CheckViolation | ErrorKind::NotNullViolation | ErrorKind::ForeignKeyViolation => {
+                    ApiError::ConstraintViolation(error.to_string())
+                }
+                ErrorKind::Other => match db.code().as_deref() {
+                    // string_data_right_truncation, e.g. a title longer than VARCHAR(255)
+                    Some("22001") => ApiError::ConstraintViolation(error.to_string()),
+                    // connection_exception class, admin shutdown, too_many_connections
+                    Some(code) if code.starts_with("08") || code == "57P01" || code == "53300" => {
+                        ApiError::ServiceUnavailable(error.to_string())
+                    }
+                    _ => ApiError::DatabaseError(error.to_string()),
+                },
+                _ => ApiError::DatabaseError(error.to_string()),
+            },
+            _ => ApiError::DatabaseError(error.to_string()),
+        }
+    }
+}
+
 impl IntoResponse for ApiError {
-    fn into_response(self) -> axum::response::Response {
-        let (status, msg) = match self {
-            ApiError::NotFound => (StatusCode::NOT_FOUND, "not found"),
-            ApiError::DatabaseError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "database error"),
-            ApiError::Anyhow(_) => (StatusCode::INTERNAL_SERVER_ERROR, "internal error"),
-        };
-        (status, Json(serde_json::json!({ "error": msg }))).into_response()
-    }
-}
\ No newline at end of file
+    fn into_response(self) -> Response {
+        if self.status().is_server_error() {
+            tracing::error!(code = self.code(), error = %self, "request failed");
+        }
+
+        let problem = self.to_problem();
+        let mut response = problem_response(self.status(), &problem);
+        if matches!(self, ApiError::Unauthorized(_)) {
+            response.headers_mut().insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        }
+        response.extensions_mut().insert(problem);
+        response
+    }
+}
+
+fn problem_response(status: StatusCode, problem: &ProblemDetails) -> Response {
+    let body = serde_json::to_vec(problem).expect("problem details serialize");
+    (status, [(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON))], body).into_response()
+}
+
+/// Middleware that fills in `instance` with the request path, which
+/// `ApiError::into_response` cannot see.
+pub async fn problem_instance(request: Request, next: Next) -> Response {
+    let path = request.uri().path().to_string();
+    let mut response = next.run(request).await;
+
+    match response.extensions_mut().remove::<ProblemDetails>() {
+        Some(mut problem) => {
+            problem.instance = Some(path);
+            let (mut parts, _) = response.into_parts();
+            let rendered = problem_response(parts.status, &problem);
+            parts.headers.remove(header::CONTENT_LENGTH);
+            let (_, body) = rendered.into_parts();
+            Response::from_parts(parts, body)
+        }
+        None => response,
+    }
+}
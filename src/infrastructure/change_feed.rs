@@ -0,0 +1,213 @@
+//! In-process fan-out of live todo changes, with a bounded replay buffer
+//! for clients that reconnect, and the background tasks that feed it.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Deserialize;
+use sqlx::postgres::{PgListener, PgPool};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use uuid::Uuid;
+
+use crate::config::ChangesConfig;
+use crate::domain::todos::{TodoChange, TodoChangeKind};
+use crate::domain::todos::traits::TodoRepository;
+use crate::error::ApiError;
+use crate::infrastructure::database::repositories::PostgresTodoRepository;
+use crate::request_context::RequestContext;
+
+/// Postgres channel the `todos` trigger notifies.
+pub const CHANNEL: &str = "todo_changes";
+
+/// Changes fetched per audit log poll.
+const POLL_BATCH: u32 = 500;
+
+/// What the `todos` trigger notifies. A whole todo can outgrow the 8000
+/// bytes a notification may carry, so it only names the todo, which the
+/// listener then loads.
+#[derive(Deserialize)]
+struct ChangeNotice {
+    seq: i64,
+    kind: TodoChangeKind,
+    id: Uuid,
+    owner_id: Option<Uuid>,
+    tenant_id: String,
+}
+
+impl ChangeNotice {
+    /// The change with the todo as it is now, read in the todo's tenant;
+    /// `None` when no trace of the todo is left.
+    async fn load(&self, todos: &PostgresTodoRepository) -> Result<Option<TodoChange>, ApiError> {
+        let context = RequestContext { tenant_id: Some(self.tenant_id.clone()), ..RequestContext::default() };
+        let todo = context.scope(todos.find_changed(self.id)).await?;
+        Ok(todo.map(|todo| TodoChange { seq: self.seq, kind: self.kind, todo }))
+    }
+}
+
+/// Where a backend's changes come from.
+#[derive(Clone, Debug)]
+pub enum ChangeSource {
+    /// `LISTEN` to the trigger on `todos`, so changes made through any
+    /// instance arrive.
+    Notify(PgPool),
+    /// Tail the audit log; only sees this process's writes.
+    AuditLog,
+}
+
+pub struct ChangeFeed {
+    buffer: Mutex<VecDeque<TodoChange>>,
+    sender: broadcast::Sender<TodoChange>,
+    capacity: usize,
+}
+
+/// What a new subscriber is sent before live changes.
+pub struct Subscription {
+    /// Buffered changes after the client's last event, oldest first
+    pub replay: Vec<TodoChange>,
+    /// The last event is no longer buffered, so changes may have been missed
+    pub missed: bool,
+    pub receiver: broadcast::Receiver<TodoChange>,
+}
+
+impl ChangeFeed {
+    /// Keeps the last `capacity` changes for replay.
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { buffer: Mutex::new(VecDeque::with_capacity(capacity)), sender, capacity }
+    }
+
+    pub fn publish(&self, change: TodoChange) {
+        let mut buffer = self.buffer.lock().unwrap();
+        if buffer.len() == self.capacity {
+            buffer.pop_front();
+        }
+        if self.capacity > 0 {
+            buffer.push_back(change.clone());
+        }
+        // Sent under the lock, so a subscriber sees every change exactly
+        // once: either in its replay or on its receiver.
+        let _ = self.sender.send(change);
+    }
+
+    /// Forgets the buffered changes after changes may have been lost, so
+    /// resuming clients are told to resynchronize.
+    pub fn clear(&self) {
+        self.buffer.lock().unwrap().clear();
+    }
+
+    /// Subscribes to changes after `last_event_id`, or to new changes only
+    /// when it is `None`. Replay follows arrival order, which is the same on
+    /// every instance.
+    pub fn subscribe(&self, last_event_id: Option<i64>) -> Subscription {
+        let buffer = self.buffer.lock().unwrap();
+        let receiver = self.sender.subscribe();
+
+        let (replay, missed) = match last_event_id {
+            None => (Vec::new(), false),
+            Some(id) => match buffer.iter().position(|change| change.seq == id) {
+                Some(position) => (buffer.iter().skip(position + 1).cloned().collect(), false),
+                None => (Vec::new(), true),
+            },
+        };
+
+        Subscription { replay, missed, receiver }
+    }
+}
+
+/// Starts the task feeding `feed` from `source`. The audit log is polled
+/// across all tenants; notified changes are loaded in the tenant they name.
+pub fn spawn(
+    source: ChangeSource,
+    todo_repository: Arc<dyn TodoRepository>,
+    feed: Arc<ChangeFeed>,
+    config: &ChangesConfig,
+) -> JoinHandle<()> {
+    match source {
+        ChangeSource::Notify(pool) => tokio::spawn(listen(pool, feed)),
+        ChangeSource::AuditLog => {
+            tokio::spawn(RequestContext::all_tenants().scope(poll(todo_repository, feed, config.poll_interval())))
+        }
+    }
+}
+
+async fn listen(pool: PgPool, feed: Arc<ChangeFeed>) {
+    let todos = PostgresTodoRepository::new(pool.clone());
+    loop {
+        let mut listener = match PgListener::connect_with(&pool).await {
+            Ok(listener) => listener,
+            Err(error) => {
+                tracing::error!(error = %error, "connecting the change listener failed");
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+        };
+        if let Err(error) = listener.listen(CHANNEL).await {
+            tracing::error!(error = %error, "listening for todo changes failed");
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            continue;
+        }
+
+        loop {
+            match listener.try_recv().await {
+                Ok(Some(notification)) => match serde_json::from_str::<ChangeNotice>(notification.payload()) {
+                    Ok(notice) => match notice.load(&todos).await {
+                        Ok(Some(change)) => feed.publish(change),
+                        Ok(None) => tracing::warn!(todo_id = %notice.id, owner_id = ?notice.owner_id, "changed todo is gone"),
+                        // The change is lost, as if the notification were.
+                        Err(error) => {
+                            tracing::error!(code = error.code(), error = %error, todo_id = %notice.id, "loading a changed todo failed");
+                            feed.clear();
+                        }
+                    },
+                    Err(error) => tracing::error!(error = %error, "malformed todo change notification"),
+                },
+                // The connection dropped; notifications sent meanwhile are lost.
+                Ok(None) => {
+                    tracing::warn!("change listener reconnected; buffered changes dropped");
+                    feed.clear();
+                }
+                Err(error) => {
+                    tracing::error!(error = %error, "change listener failed");
+                    feed.clear();
+                    break;
+                }
+            }
+        }
+    }
+}
+
+async fn poll(todo_repository: Arc<dyn TodoRepository>, feed: Arc<ChangeFeed>, period: Duration) {
+    let mut interval = tokio::time::interval(period);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut last_seen = None;
+
+    loop {
+        interval.tick().await;
+        let after = match last_seen {
+            Some(id) => id,
+            // Changes made before startup are history, not news.
+            None => match todo_repository.last_event_id().await {
+                Ok(id) => *last_seen.insert(id),
+                Err(error) => {
+                    tracing::error!(code = error.code(), error = %error, "reading the audit log failed");
+                    continue;
+                }
+            },
+        };
+
+        match todo_repository.events_since(after, POLL_BATCH).await {
+            Ok(events) => {
+                for event in &events {
+                    if let Some(change) = TodoChange::from_event(event) {
+                        feed.publish(change);
+                    }
+                    last_seen = Some(event.id);
+                }
+            }
+            Err(error) => tracing::error!(code = error.code(), error = %error, "reading the audit log failed"),
+        }
+    }
+}
@@ -1,2 +1,147 @@
 pub mod repositories;
 
+use std::sync::Arc;
+use sqlx::migrate::Migrator;
+use sqlx::postgres::PgPoolOptions;
+
+use crate::config::{Config, DatabaseBackend};
+use crate::domain::api_keys::traits::ApiKeyRepository;
+use crate::domain::lists::traits::TodoListRepository;
+use crate::domain::tags::traits::TagRepository;
+use crate::domain::todos::traits::TodoRepository;
+use crate::domain::users::traits::UserRepository;
+use crate::domain::webhooks::traits::WebhookRepository;
+use crate::infrastructure::change_feed::ChangeSource;
+use repositories::{
+    InMemoryApiKeyRepository, InMemoryTodoRepository, InMemoryUserRepository, InMemoryWebhookRepository,
+    PostgresApiKeyRepository, PostgresTagRepository, PostgresTodoListRepository, PostgresTodoRepository, PostgresUserRepository,
+    PostgresWebhookRepository,
+};
+
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+#[cfg(feature = "sqlite")]
+pub static SQLITE_MIGRATOR: Migrator = sqlx::migrate!("./migrations/sqlite");
+
+/// The repositories of one backend, sharing its pool.
+#[derive(Clone)]
+pub struct Repositories {
+    pub todos: Arc<dyn TodoRepository>,
+    pub lists: Arc<dyn TodoListRepository>,
+    pub tags: Arc<dyn TagRepository>,
+    pub webhooks: Arc<dyn WebhookRepository>,
+    pub users: Arc<dyn UserRepository>,
+    pub api_keys: Arc<dyn ApiKeyRepository>,
+    pub changes: ChangeSource,
+}
+
+impl Repositories {
+    pub fn in_memory() -> Self {
+        let todos = InMemoryTodoRepository::new();
+        Self {
+            lists: Arc::new(todos.lists()),
+            tags: Arc::new(todos.tags()),
+            todos: Arc::new(todos),
+            webhooks: Arc::new(InMemoryWebhookRepository::new()),
+            users: Arc::new(InMemoryUserRepository::new()),
+            api_keys: Arc::new(InMemoryApiKeyRepository::new()),
+            changes: ChangeSource::AuditLog,
+        }
+    }
+}
+
+/// Builds the repositories selected by `database.url`, connecting the pool
+/// and running migrations where the backend needs them.
+pub async fn connect(config: &Config) -> anyhow::Result<Repositories> {
+    let database_url = config.database.url.as_deref().unwrap_or_default();
+
+    match config.database.backend() {
+        Some(DatabaseBackend::Postgres) => {
+            tracing::info!(
+                max_connections = config.database.max_connections,
+                min_connections = config.database.min_connections,
+                "Connecting to database"
+            );
+
+            let pool = PgPoolOptions::new()
+                .max_connections(config.database.max_connections)
+                .min_connections(config.database.min_connections)
+                .acquire_timeout(config.acquire_timeout())
+                .connect(database_url)
+                .await?;
+
+            if config.database.run_migrations {
+                tracing::info!("Running migrations...");
+                MIGRATOR.run(&pool).await?;
+                tracing::info!("Migrations completed successfully!");
+            } else {
+                tracing::info!("Skipping migrations (database.run_migrations = false)");
+            }
+
+            // Superusers and BYPASSRLS roles are exempt from row-level
+            // security, which would leave tenants unprotected.
+            if config.tenancy.enabled {
+                let exempt: bool =
+                    sqlx::query_scalar("SELECT rolsuper OR rolbypassrls FROM pg_roles WHERE rolname = current_user")
+                        .fetch_one(&pool)
+                        .await?;
+                if exempt {
+                    anyhow::bail!(
+                        "tenancy.enabled needs a database role that row-level security applies to, \
+                         not a superuser or a role with BYPASSRLS"
+                    );
+                }
+            }
+
+            Ok(Repositories {
+                todos: Arc::new(PostgresTodoRepository::new(pool.clone())),
+                lists: Arc::new(PostgresTodoListRepository::new(pool.clone())),
+                tags: Arc::new(PostgresTagRepository::new(pool.clone())),
+                webhooks: Arc::new(PostgresWebhookRepository::new(pool.clone())),
+                users: Arc::new(PostgresUserRepository::new(pool.clone())),
+                api_keys: Arc::new(PostgresApiKeyRepository::new(pool.clone())),
+                changes: ChangeSource::Notify(pool),
+            })
+        }
+        #[cfg(feature = "sqlite")]
+        Some(DatabaseBackend::Sqlite) => {
+            use std::str::FromStr;
+            use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+
+            tracing::info!("Opening SQLite database");
+
+            let options = SqliteConnectOptions::from_str(database_url)?.create_if_missing(true);
+            let pool = SqlitePoolOptions::new()
+                .max_connections(config.database.max_connections)
+                .min_connections(config.database.min_connections)
+                .acquire_timeout(config.acquire_timeout())
+                .connect_with(options)
+                .await?;
+
+            if config.database.run_migrations {
+                tracing::info!("Running migrations...");
+                SQLITE_MIGRATOR.run(&pool).await?;
+                tracing::info!("Migrations completed successfully!");
+            }
+
+            Ok(Repositories {
+                todos: Arc::new(repositories::SqliteTodoRepository::new(pool.clone())),
+                lists: Arc::new(repositories::SqliteTodoListRepository::new(pool.clone())),
+                tags: Arc::new(repositories::SqliteTagRepository::new(pool.clone())),
+                webhooks: Arc::new(repositories::SqliteWebhookRepository::new(pool.clone())),
+                users: Arc::new(repositories::SqliteUserRepository::new(pool.clone())),
+                api_keys: Arc::new(repositories::SqliteApiKeyRepository::new(pool)),
+                changes: ChangeSource::AuditLog,
+            })
+        }
+        #[cfg(not(feature = "sqlite"))]
+        Some(DatabaseBackend::Sqlite) => {
+            anyhow::bail!("the sqlite backend requires building with `--features sqlite`")
+        }
+        Some(DatabaseBackend::Memory) => {
+            tracing::warn!("Using the in-memory backend; data will not survive a restart");
+            Ok(Repositories::in_memory())
+        }
+        None => anyhow::bail!("unsupported database.url scheme: {database_url}"),
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::RwLock;
+
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+use crate::domain::api_keys::{ApiKey, NewApiKey};
+use crate::domain::api_keys::traits::ApiKeyRepository;
+use crate::domain::todos::{PaginatedResponse, PaginationMeta, PaginationQuery};
+use crate::error::ApiError;
+use crate::request_context::RequestContext;
+
+/// Process-local key store with the same semantics as
+/// `PostgresApiKeyRepository`. Data is lost on restart.
+#[derive(Default)]
+pub struct InMemoryApiKeyRepository {
+    /// Keys with their hashes, in insertion order
+    keys: RwLock<Vec<(String, ApiKey)>>,
+}
+
+impl InMemoryApiKeyRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Whether `key` belongs to the current request's tenant, as Postgres
+/// row-level security would decide.
+fn in_tenant(key: &ApiKey) -> bool {
+    RequestContext::tenant_scope().is_ok_and(|scope| scope.includes(&key.tenant_id))
+}
+
+#[async_trait::async_trait]
+impl ApiKeyRepository for InMemoryApiKeyRepository {
+    async fn create(&self, key: NewApiKey) -> Result<ApiKey, ApiError> {
+        let api_key = ApiKey {
+            id: Uuid::new_v4(),
+            user_id: key.user_id,
+            name: key.name,
+            prefix: key.prefix,
+            scope: key.scope,
+            expires_at: key.expires_at,
+            last_used_at: None,
+            revoked_at: None,
+            tenant_id: RequestContext::writing_tenant_id()?,
+            created_at: Utc::now(),
+        };
+        self.keys.write().unwrap().push((key.key_hash, api_key.clone()));
+        Ok(api_key)
+    }
+
+    async fn find_by_id(&self, user_id: Uuid, id: Uuid) -> Result<Option<ApiKey>, ApiError> {
+        let keys = self.keys.read().unwrap();
+        Ok(keys.iter().map(|(_, key)| key).find(|key| key.id == id && key.user_id == user_id && in_tenant(key)).cloned())
+    }
+
+    async fn find_all_paginated(&self, user_id: Uuid, pagination: PaginationQuery) -> Result<PaginatedResponse<ApiKey>, ApiError> {
+        let keys = self.keys.read().unwrap();
+        let owned: Vec<&ApiKey> = keys.iter().map(|(_, key)| key).filter(|key| key.user_id == user_id && in_tenant(key)).collect();
+        let page = pagination.page();
+        let limit = pagination.limit();
+        let data = owned
+            .iter()
+            .skip(pagination.offset() as usize)
+            .take(limit as usize)
+            .map(|key| (*key).clone())
+            .collect();
+
+        Ok(PaginatedResponse {
+            data,
+            pagination: PaginationMeta::new(page, limit, owned.len() as u64),
+        })
+    }
+
+    async fn revoke(&self, user_id: Uuid, id: Uuid, now: DateTime<Utc>) -> Result<ApiKey, ApiError> {
+        let mut keys = self.keys.write().unwrap();
+        let (_, key) = keys
+            .iter_mut()
+            .find(|(_, key)| key.id == id && key.user_id == user_id && in_tenant(key))
+            .ok_or(ApiError::NotFound)?;
+        key.revoked_at.get_or_insert(now);
+        Ok(key.clone())
+    }
+
+    async fn authenticate(&self, key_hash: &str, now: DateTime<Utc>) -> Result<Option<ApiKey>, ApiError> {
+        let mut keys = self.keys.write().unwrap();
+        let Some((_, key)) = keys.iter_mut().find(|(hash, key)| hash == key_hash && key.is_usable(now) && in_tenant(key)) else {
+            return Ok(None);
+        };
+        key.last_used_at = Some(now);
+        Ok(Some(key.clone()))
+    }
+}
@@ -0,0 +1,133 @@
+use std::sync::{Arc, RwLock};
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::tags::{CreateTagRequest, RenameTagRequest, Tag, TagRef};
+use crate::domain::tags::traits::TagStore;
+use crate::domain::todos::{DomainEvents, PaginatedResponse, PaginationMeta, PaginationQuery};
+use crate::error::ApiError;
+use crate::request_context::RequestContext;
+use super::in_memory_todo_repository::Store;
+
+/// Process-local tag store. Tags share their store with an
+/// `InMemoryTodoRepository` (see `InMemoryTodoRepository::tags`) so that
+/// renaming and deleting tags affect its todos as they would in SQL.
+#[derive(Default)]
+pub struct InMemoryTagRepository {
+    store: Arc<RwLock<Store>>,
+}
+
+/// Whether the current request may see `tag`: its own tenant's tags
+/// created by the signed-in user; background tasks see all.
+fn visible(tag: &Tag) -> bool {
+    RequestContext::tenant_scope().is_ok_and(|scope| scope.includes(&tag.tenant_id))
+        && RequestContext::current_user_id().is_none_or(|owner| tag.owner_id == Some(owner))
+}
+
+/// Refuses `name` when the owner of a tag other than `id` already uses it,
+/// as the unique index of the SQL backends does.
+fn check_unique(store: &Store, id: Uuid, tenant_id: &str, owner_id: Option<Uuid>, name: &str) -> Result<(), ApiError> {
+    let taken = store.tags.values().any(|tag| {
+        tag.id != id && tag.tenant_id == tenant_id && tag.owner_id == owner_id && tag.name == name
+    });
+    if taken {
+        return Err(ApiError::Conflict(format!("a tag named {name:?} already exists")));
+    }
+    Ok(())
+}
+
+impl InMemoryTagRepository {
+    /// A store of its own, for tests of tags alone.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(super) fn sharing(store: Arc<RwLock<Store>>) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait::async_trait]
+impl TagStore for InMemoryTagRepository {
+    async fn create(&self, data: CreateTagRequest) -> Result<Tag, ApiError> {
+        let now = Utc::now();
+        let tag = Tag {
+            id: Uuid::new_v4(),
+            name: data.name,
+            owner_id: RequestContext::current_user_id(),
+            tenant_id: RequestContext::writing_tenant_id()?,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let mut store = self.store.write().unwrap();
+        check_unique(&store, tag.id, &tag.tenant_id, tag.owner_id, &tag.name)?;
+        store.tags.insert(tag.id, tag.clone());
+        Ok(tag)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Tag>, ApiError> {
+        Ok(self.store.read().unwrap().tags.get(&id).filter(|tag| visible(tag)).cloned())
+    }
+
+    async fn find_all_paginated(&self, pagination: PaginationQuery) -> Result<PaginatedResponse<Tag>, ApiError> {
+        let page = pagination.page();
+        let limit = pagination.limit();
+
+        let mut tags: Vec<Tag> = self
+            .store
+            .read()
+            .unwrap()
+            .tags
+            .values()
+            .filter(|tag| visible(tag))
+            .cloned()
+            .collect();
+        tags.sort_by(|a, b| (&a.name, a.id).cmp(&(&b.name, b.id)));
+        let total = tags.len() as u64;
+        let data = tags
+            .into_iter()
+            .skip(pagination.offset() as usize)
+            .take(limit as usize)
+            .collect();
+
+        Ok(PaginatedResponse {
+            data,
+            pagination: PaginationMeta::new(page, limit, total),
+        })
+    }
+
+    async fn rename(&self, id: Uuid, data: RenameTagRequest, events: DomainEvents) -> Result<Tag, ApiError> {
+        let mut store = self.store.write().unwrap();
+        let tag = store.tags.get(&id).filter(|tag| visible(tag)).ok_or(ApiError::NotFound)?;
+        check_unique(&store, id, &tag.tenant_id, tag.owner_id, &data.name)?;
+
+        let tag = store.tags.get_mut(&id).expect("checked above");
+        tag.name = data.name;
+        tag.updated_at = Utc::now();
+        let tag = tag.clone();
+
+        let renamed = TagRef::from(&tag);
+        store.retag_all(
+            id,
+            |tags| {
+                for tagged in tags.iter_mut().filter(|tagged| tagged.id == id) {
+                    *tagged = renamed.clone();
+                }
+            },
+            events,
+        );
+        Ok(tag)
+    }
+
+    async fn delete(&self, id: Uuid, events: DomainEvents) -> Result<(), ApiError> {
+        let mut store = self.store.write().unwrap();
+        if !store.tags.get(&id).is_some_and(visible) {
+            return Err(ApiError::NotFound);
+        }
+        store.tags.remove(&id);
+        store.retag_all(id, |tags| tags.retain(|tagged| tagged.id != id), events);
+        Ok(())
+    }
+}
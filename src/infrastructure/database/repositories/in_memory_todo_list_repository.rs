@@ -0,0 +1,128 @@
+use std::sync::{Arc, RwLock};
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::lists::{CreateTodoListRequest, TodoList, UpdateTodoListRequest};
+use crate::domain::lists::traits::{TodoListArchiver, TodoListStore};
+use crate::domain::todos::{PaginatedResponse, PaginationMeta, PaginationQuery};
+use crate::error::ApiError;
+use crate::request_context::RequestContext;
+use super::in_memory_todo_repository::Store;
+
+/// Process-local list store. Lists share their store with an
+/// `InMemoryTodoRepository` (see `InMemoryTodoRepository::lists`) so that
+/// archiving and deleting lists affect its todos as they would in SQL.
+#[derive(Default)]
+pub struct InMemoryTodoListRepository {
+    store: Arc<RwLock<Store>>,
+}
+
+/// Whether the current request may see `list`: its own tenant's lists
+/// created by the signed-in user; background tasks see all.
+fn visible(list: &TodoList) -> bool {
+    RequestContext::tenant_scope().is_ok_and(|scope| scope.includes(&list.tenant_id))
+        && RequestContext::current_user_id().is_none_or(|owner| list.owner_id == Some(owner))
+}
+
+impl InMemoryTodoListRepository {
+    /// A store of its own, for tests of lists alone.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(super) fn sharing(store: Arc<RwLock<Store>>) -> Self {
+        Self { store }
+    }
+
+    fn modify(&self, id: Uuid, change: impl FnOnce(&mut TodoList)) -> Result<TodoList, ApiError> {
+        let mut store = self.store.write().unwrap();
+        let list = store.lists.get_mut(&id).filter(|list| visible(list)).ok_or(ApiError::NotFound)?;
+        change(list);
+        list.updated_at = Utc::now();
+        Ok(list.clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoListStore for InMemoryTodoListRepository {
+    async fn create(&self, data: CreateTodoListRequest) -> Result<TodoList, ApiError> {
+        let now = Utc::now();
+        let list = TodoList {
+            id: Uuid::new_v4(),
+            name: data.name,
+            description: data.description,
+            color: data.color,
+            archived: false,
+            owner_id: RequestContext::current_user_id(),
+            tenant_id: RequestContext::writing_tenant_id()?,
+            created_at: now,
+            updated_at: now,
+        };
+
+        self.store.write().unwrap().lists.insert(list.id, list.clone());
+        Ok(list)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<TodoList>, ApiError> {
+        Ok(self.store.read().unwrap().lists.get(&id).filter(|list| visible(list)).cloned())
+    }
+
+    async fn find_all_paginated(&self, archived: bool, pagination: PaginationQuery) -> Result<PaginatedResponse<TodoList>, ApiError> {
+        let page = pagination.page();
+        let limit = pagination.limit();
+
+        let mut lists: Vec<TodoList> = self
+            .store
+            .read()
+            .unwrap()
+            .lists
+            .values()
+            .filter(|list| list.archived == archived && visible(list))
+            .cloned()
+            .collect();
+        lists.sort_by_key(|list| (list.created_at, list.id));
+        let total = lists.len() as u64;
+        let data = lists
+            .into_iter()
+            .skip(pagination.offset() as usize)
+            .take(limit as usize)
+            .collect();
+
+        Ok(PaginatedResponse {
+            data,
+            pagination: PaginationMeta::new(page, limit, total),
+        })
+    }
+
+    async fn update(&self, id: Uuid, data: UpdateTodoListRequest) -> Result<TodoList, ApiError> {
+        self.modify(id, |list| {
+            if let Some(name) = data.name {
+                list.name = name;
+            }
+            if let Some(description) = data.description {
+                list.description = Some(description);
+            }
+            if let Some(color) = data.color {
+                list.color = Some(color);
+            }
+        })
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), ApiError> {
+        let mut store = self.store.write().unwrap();
+        if !store.lists.get(&id).is_some_and(visible) {
+            return Err(ApiError::NotFound);
+        }
+        store.lists.remove(&id);
+        store.detach_list(id);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoListArchiver for InMemoryTodoListRepository {
+    async fn set_archived(&self, id: Uuid, archived: bool) -> Result<TodoList, ApiError> {
+        self.modify(id, |list| list.archived = archived)
+    }
+}
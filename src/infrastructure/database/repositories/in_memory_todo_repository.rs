@@ -0,0 +1,651 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+use crate::domain::lists::TodoList;
+use crate::domain::tags::{Tag, TagRef};
+use crate::domain::todos::{Todo, CreateTodoRequest, UpdateTodoRequest, PaginationQuery, PaginatedResponse, PaginationMeta,
+    KeysetQuery, KeysetPage, KeysetDirection, KeysetPosition, SortKey, TodoFilter,
+    TodoSearch, TodoSearchHit, BatchOperation, BatchMode, BatchOutcome, TrashedTodo,
+    TodoEvent, NewTodoEvent, OutboxMessage, TodoRole, TodoShare, DomainEvents};
+use crate::error::ApiError;
+use crate::request_context::RequestContext;
+use crate::domain::todos::traits::{TodoCreator, TodoFinder, TodoPaginator, TodoSearcher, TodoUpdater, TodoDeleter, TodoTrash, TodoHistory,
+    TodoOutbox, TodoBatchWriter, TodoRoles, TodoSharing, TodoTagging};
+use super::{InMemoryTagRepository, InMemoryTodoListRepository};
+
+/// Process-local todo store with the same semantics as `PostgresTodoRepository`.
+/// Data is lost on restart; intended for tests and local development.
+#[derive(Default)]
+pub struct InMemoryTodoRepository {
+    store: Arc<RwLock<Store>>,
+}
+
+/// Live and trashed todos are kept apart, so reads of `live` never see the
+/// trash. Lists and tags live here too, since todo listings depend on them.
+#[derive(Default, Clone)]
+pub(super) struct Store {
+    live: HashMap<Uuid, Todo>,
+    trash: HashMap<Uuid, TrashedTodo>,
+    pub(super) lists: HashMap<Uuid, TodoList>,
+    pub(super) tags: HashMap<Uuid, Tag>,
+    /// Audit log in insertion order
+    events: Vec<TodoEvent>,
+    outbox: Vec<OutboxMessage>,
+    last_outbox_id: i64,
+    /// In the order access was granted
+    shares: Vec<TodoShare>,
+}
+
+impl Store {
+    fn record(&mut self, event: NewTodoEvent, events: DomainEvents) {
+        for mut message in OutboxMessage::queue(&event, events(&event)) {
+            self.last_outbox_id += 1;
+            message.id = self.last_outbox_id;
+            self.outbox.push(message);
+        }
+        self.events.push(TodoEvent {
+            id: self.events.len() as i64 + 1,
+            todo_id: event.todo_id,
+            kind: event.kind,
+            version: event.version,
+            before: event.before,
+            after: event.after,
+            changes: event.changes,
+            actor: event.actor,
+            request_id: event.request_id,
+            occurred_at: Utc::now(),
+        });
+    }
+
+    /// Whether the current request may see `todo`, as its owner or someone
+    /// it is shared with.
+    fn accessible(&self, todo: &Todo) -> bool {
+        in_tenant(todo) && RequestContext::current_user_id().is_none_or(|user| {
+            todo.owner_id == Some(user) || self.shares.iter().any(|share| share.todo_id == todo.id && share.user_id == user)
+        })
+    }
+
+    fn purge(&mut self, todo: &Todo) {
+        self.shares.retain(|share| share.todo_id != todo.id);
+        self.record(NewTodoEvent::purged(todo), |_| Vec::new());
+    }
+
+    /// Whether a listing matching `filter` includes `todo`: todos of
+    /// archived lists only show up when their list is asked for.
+    fn listed(&self, todo: &Todo, filter: &TodoFilter) -> bool {
+        filter.matches(todo)
+            && (filter.list_id.is_some()
+                || todo.list_id.is_none_or(|id| !self.lists.get(&id).is_some_and(|list| list.archived)))
+    }
+
+    /// Takes every todo, live or trashed, out of list `id`, as the foreign
+    /// key of the SQL backends does when the list is deleted.
+    pub(super) fn detach_list(&mut self, id: Uuid) {
+        let trashed = self.trash.values_mut().map(|trashed| &mut trashed.todo);
+        for todo in self.live.values_mut().chain(trashed) {
+            if todo.list_id == Some(id) {
+                todo.list_id = None;
+            }
+        }
+    }
+
+    /// Applies `change` to the tags of every todo tagged `tag_id`, live or
+    /// trashed, keeping them in the order the SQL backends read them in.
+    /// Live todos get a new version and their updates are recorded, in the
+    /// order of their ids as in SQL.
+    pub(super) fn retag_all(&mut self, tag_id: Uuid, change: impl Fn(&mut Vec<TagRef>), events: DomainEvents) {
+        let tagged = |todo: &Todo| todo.tags.iter().any(|tagged| tagged.id == tag_id);
+        for trashed in self.trash.values_mut().filter(|trashed| tagged(&trashed.todo)) {
+            change(&mut trashed.todo.tags);
+            sort_tags(&mut trashed.todo.tags);
+        }
+
+        let mut ids: Vec<Uuid> = self.live.values().filter(|todo| tagged(todo)).map(|todo| todo.id).collect();
+        ids.sort();
+        let now = Utc::now();
+        for id in ids {
+            let todo = self.live.get_mut(&id).expect("collected above");
+            let before = todo.clone();
+            change(&mut todo.tags);
+            sort_tags(&mut todo.tags);
+            todo.updated_at = now;
+            todo.version += 1;
+
+            let todo = todo.clone();
+            self.record(NewTodoEvent::updated(&before, &todo), events);
+        }
+    }
+}
+
+fn sort_tags(tags: &mut [TagRef]) {
+    tags.sort_by(|a, b| (&a.name, a.id).cmp(&(&b.name, b.id)));
+}
+
+/// Whether the current request owns `todo`; background tasks see all.
+fn visible(todo: &Todo) -> bool {
+    in_tenant(todo) && RequestContext::current_user_id().is_none_or(|owner| todo.owner_id == Some(owner))
+}
+
+/// Whether `todo` belongs to the current request's tenant, as Postgres
+/// row-level security would decide; background tasks running across all
+/// tenants see every one, and a context naming neither sees none.
+fn in_tenant(todo: &Todo) -> bool {
+    RequestContext::tenant_scope().is_ok_and(|scope| scope.includes(&todo.tenant_id))
+}
+
+impl InMemoryTodoRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A list repository sharing this repository's store, so that archiving
+    /// a list hides its todos here.
+    pub fn lists(&self) -> InMemoryTodoListRepository {
+        InMemoryTodoListRepository::sharing(self.store.clone())
+    }
+
+    /// A tag repository sharing this repository's store, so that renaming
+    /// or deleting a tag shows on the todos it is attached to.
+    pub fn tags(&self) -> InMemoryTagRepository {
+        InMemoryTagRepository::sharing(self.store.clone())
+    }
+
+    /// Snapshot of the todos matching `filter`, ordered by `keys` like the
+    /// SQL backends' `ORDER BY`.
+    fn sorted(&self, filter: &TodoFilter, keys: &[SortKey]) -> Vec<Todo> {
+        let store = self.store.read().unwrap();
+        let mut todos: Vec<Todo> = store
+            .live
+            .values()
+            .filter(|todo| store.accessible(todo) && store.listed(todo, filter))
+            .cloned()
+            .collect();
+        todos.sort_by(|a, b| SortKey::compare(keys, a, b));
+        todos
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoCreator for InMemoryTodoRepository {
+    async fn create(&self, data: CreateTodoRequest, events: DomainEvents) -> Result<Todo, ApiError> {
+        create_in(&mut self.store.write().unwrap(), data, events)
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoFinder for InMemoryTodoRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Todo>, ApiError> {
+        let store = self.store.read().unwrap();
+        Ok(store.live.get(&id).filter(|todo| store.accessible(todo)).cloned())
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoPaginator for InMemoryTodoRepository {
+    async fn find_all_paginated(&self, pagination: PaginationQuery) -> Result<PaginatedResponse<Todo>, ApiError> {
+        let page = pagination.page();
+        let limit = pagination.limit();
+
+        let todos = self.sorted(&pagination.filter(), &pagination.sort_keys());
+        let total = todos.len() as u64;
+        let data = todos
+            .into_iter()
+            .skip(pagination.offset() as usize)
+            .take(limit as usize)
+            .collect();
+
+        Ok(PaginatedResponse {
+            data,
+            pagination: PaginationMeta::new(page, limit, total),
+        })
+    }
+
+    async fn find_keyset(&self, query: KeysetQuery) -> Result<KeysetPage<Todo>, ApiError> {
+        let limit = query.limit.clamp(1, PaginationQuery::MAX_LIMIT) as usize;
+        let todos = self.sorted(&query.filter, &SortKey::DEFAULT);
+        let total = query.include_total.then_some(todos.len() as u64);
+        let key = |todo: &Todo| (todo.created_at, todo.id);
+
+        let (data, has_more) = match query.start {
+            None => (todos.iter().take(limit).cloned().collect(), todos.len() > limit),
+            Some((KeysetDirection::After, KeysetPosition { created_at, id })) => {
+                let rest: Vec<Todo> = todos.into_iter().filter(|t| key(t) < (created_at, id)).collect();
+                let has_more = rest.len() > limit;
+                (rest.into_iter().take(limit).collect(), has_more)
+            }
+            Some((KeysetDirection::Before, KeysetPosition { created_at, id })) => {
+                let rest: Vec<Todo> = todos.into_iter().filter(|t| key(t) > (created_at, id)).collect();
+                let has_more = rest.len() > limit;
+                (rest[rest.len().saturating_sub(limit)..].to_vec(), has_more)
+            }
+        };
+
+        Ok(KeysetPage { data, has_more, total })
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoSearcher for InMemoryTodoRepository {
+    async fn search(&self, search: TodoSearch) -> Result<PaginatedResponse<TodoSearchHit>, ApiError> {
+        let store = self.store.read().unwrap();
+        let todos: Vec<Todo> = store.live.values().filter(|todo| store.accessible(todo)).cloned().collect();
+        Ok(search.rank(todos))
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoUpdater for InMemoryTodoRepository {
+    async fn update(
+        &self,
+        id: Uuid,
+        data: UpdateTodoRequest,
+        expected_version: Option<i64>,
+        events: DomainEvents,
+    ) -> Result<Todo, ApiError> {
+        update_in(&mut self.store.write().unwrap(), id, data, expected_version, events)
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoDeleter for InMemoryTodoRepository {
+    async fn delete(&self, id: Uuid, expected_version: Option<i64>, events: DomainEvents) -> Result<(), ApiError> {
+        delete_in(&mut self.store.write().unwrap(), id, expected_version, events)
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoTrash for InMemoryTodoRepository {
+    async fn find_trashed(&self, pagination: PaginationQuery) -> Result<PaginatedResponse<TrashedTodo>, ApiError> {
+        let page = pagination.page();
+        let limit = pagination.limit();
+
+        let mut trashed: Vec<TrashedTodo> = self
+            .store
+            .read()
+            .unwrap()
+            .trash
+            .values()
+            .filter(|trashed| visible(&trashed.todo))
+            .cloned()
+            .collect();
+        trashed.sort_by_key(|trashed| Reverse((trashed.deleted_at, trashed.todo.id)));
+        let total = trashed.len() as u64;
+        let data = trashed
+            .into_iter()
+            .skip(pagination.offset() as usize)
+            .take(limit as usize)
+            .collect();
+
+        Ok(PaginatedResponse {
+            data,
+            pagination: PaginationMeta::new(page, limit, total),
+        })
+    }
+
+    async fn restore(&self, id: Uuid, events: DomainEvents) -> Result<Todo, ApiError> {
+        let mut store = self.store.write().unwrap();
+        let TrashedTodo { mut todo, .. } = take_trashed(&mut store, id)?;
+
+        todo.updated_at = Utc::now();
+        todo.version += 1;
+        store.live.insert(id, todo.clone());
+        store.record(NewTodoEvent::restored(&todo), events);
+        Ok(todo)
+    }
+
+    async fn purge(&self, id: Uuid) -> Result<(), ApiError> {
+        let mut store = self.store.write().unwrap();
+        let TrashedTodo { todo, .. } = take_trashed(&mut store, id)?;
+        store.purge(&todo);
+        Ok(())
+    }
+
+    async fn purge_trashed_before(&self, cutoff: DateTime<Utc>) -> Result<u64, ApiError> {
+        let mut store = self.store.write().unwrap();
+        let expired: Vec<Uuid> = store
+            .trash
+            .values()
+            .filter(|trashed| trashed.deleted_at < cutoff && visible(&trashed.todo))
+            .map(|trashed| trashed.todo.id)
+            .collect();
+
+        for id in &expired {
+            let TrashedTodo { todo, .. } = store.trash.remove(id).expect("collected above");
+            store.purge(&todo);
+        }
+        Ok(expired.len() as u64)
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoHistory for InMemoryTodoRepository {
+    async fn find_history(&self, id: Uuid, pagination: PaginationQuery) -> Result<PaginatedResponse<TodoEvent>, ApiError> {
+        let page = pagination.page();
+        let limit = pagination.limit();
+
+        let store = self.store.read().unwrap();
+        let events: Vec<TodoEvent> = store
+            .events
+            .iter()
+            .filter(|event| {
+                event.todo_id == id && event.after.as_ref().or(event.before.as_ref()).is_some_and(|todo| store.accessible(todo))
+            })
+            .cloned()
+            .collect();
+        let total = events.len() as u64;
+        let data = events
+            .into_iter()
+            .skip(pagination.offset() as usize)
+            .take(limit as usize)
+            .collect();
+
+        Ok(PaginatedResponse {
+            data,
+            pagination: PaginationMeta::new(page, limit, total),
+        })
+    }
+
+    async fn last_event_id(&self) -> Result<i64, ApiError> {
+        Ok(self.store.read().unwrap().events.last().map_or(0, |event| event.id))
+    }
+
+    async fn events_since(&self, after: i64, limit: u32) -> Result<Vec<TodoEvent>, ApiError> {
+        let store = self.store.read().unwrap();
+        Ok(store
+            .events
+            .iter()
+            .filter(|event| event.id > after && event.after.as_ref().or(event.before.as_ref()).is_none_or(in_tenant))
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoOutbox for InMemoryTodoRepository {
+    async fn pending_messages(&self, limit: u32, now: DateTime<Utc>) -> Result<Vec<OutboxMessage>, ApiError> {
+        let store = self.store.read().unwrap();
+        let mut waiting: HashSet<Uuid> = HashSet::new();
+        let mut due = Vec::new();
+
+        for message in &store.outbox {
+            if message.available_at > now {
+                waiting.insert(message.todo_id);
+            } else if !waiting.contains(&message.todo_id) {
+                due.push(message.clone());
+            }
+        }
+        due.truncate(limit as usize);
+        Ok(due)
+    }
+
+    async fn mark_published(&self, id: i64) -> Result<(), ApiError> {
+        self.store.write().unwrap().outbox.retain(|message| message.id != id);
+        Ok(())
+    }
+
+    async fn mark_failed(&self, id: i64, _error: &str, retry_at: DateTime<Utc>) -> Result<(), ApiError> {
+        let mut store = self.store.write().unwrap();
+        if let Some(message) = store.outbox.iter_mut().find(|message| message.id == id) {
+            message.attempts += 1;
+            message.available_at = retry_at;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoRoles for InMemoryTodoRepository {
+    async fn role_of(&self, id: Uuid, user_id: Uuid) -> Result<Option<TodoRole>, ApiError> {
+        let store = self.store.read().unwrap();
+        let todo = store.live.get(&id).or_else(|| store.trash.get(&id).map(|trashed| &trashed.todo));
+        let Some(todo) = todo.filter(|todo| in_tenant(todo)) else {
+            return Ok(None);
+        };
+        if todo.owner_id == Some(user_id) {
+            return Ok(Some(TodoRole::Owner));
+        }
+        Ok(store.shares.iter().find(|share| share.todo_id == id && share.user_id == user_id).map(|share| share.role))
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoSharing for InMemoryTodoRepository {
+    async fn find_shares(&self, id: Uuid) -> Result<Vec<TodoShare>, ApiError> {
+        let store = self.store.read().unwrap();
+        Ok(store.shares.iter().filter(|share| share.todo_id == id).cloned().collect())
+    }
+
+    async fn grant(&self, id: Uuid, user_id: Uuid, role: TodoRole) -> Result<TodoShare, ApiError> {
+        let mut store = self.store.write().unwrap();
+        if let Some(share) = store.shares.iter_mut().find(|share| share.todo_id == id && share.user_id == user_id) {
+            share.role = role;
+            return Ok(share.clone());
+        }
+
+        let share = TodoShare { todo_id: id, user_id, role, created_at: Utc::now() };
+        store.shares.push(share.clone());
+        Ok(share)
+    }
+
+    async fn revoke(&self, id: Uuid, user_id: Uuid) -> Result<(), ApiError> {
+        let mut store = self.store.write().unwrap();
+        let before = store.shares.len();
+        store.shares.retain(|share| !(share.todo_id == id && share.user_id == user_id));
+        if store.shares.len() == before {
+            return Err(ApiError::NotFound);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoTagging for InMemoryTodoRepository {
+    async fn attach_tag(
+        &self,
+        id: Uuid,
+        tag_id: Uuid,
+        expected_version: Option<i64>,
+        events: DomainEvents,
+    ) -> Result<Todo, ApiError> {
+        retag_in(&mut self.store.write().unwrap(), id, tag_id, true, expected_version, events)
+    }
+
+    async fn detach_tag(
+        &self,
+        id: Uuid,
+        tag_id: Uuid,
+        expected_version: Option<i64>,
+        events: DomainEvents,
+    ) -> Result<Todo, ApiError> {
+        retag_in(&mut self.store.write().unwrap(), id, tag_id, false, expected_version, events)
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoBatchWriter for InMemoryTodoRepository {
+    async fn apply_batch(
+        &self,
+        operations: Vec<BatchOperation>,
+        mode: BatchMode,
+        events: DomainEvents,
+    ) -> Result<Vec<Result<BatchOutcome, ApiError>>, ApiError> {
+        let mut store = self.store.write().unwrap();
+        // Atomic batches work on a copy that only replaces the store once
+        // every operation has succeeded.
+        let mut staged = match mode {
+            BatchMode::Atomic => Some(store.clone()),
+            BatchMode::BestEffort => None,
+        };
+        let target = staged.as_mut().unwrap_or(&mut *store);
+
+        let mut results = Vec::with_capacity(operations.len());
+        let mut failed = false;
+        for operation in operations {
+            if failed && mode == BatchMode::Atomic {
+                results.push(None);
+                continue;
+            }
+            let result = apply_in(target, operation, events);
+            failed |= result.is_err();
+            results.push(Some(result));
+        }
+
+        if let Some(staged) = staged
+            && !failed
+        {
+            *store = staged;
+        }
+        Ok(mode.settle(results))
+    }
+}
+
+fn create_in(store: &mut Store, data: CreateTodoRequest, events: DomainEvents) -> Result<Todo, ApiError> {
+    let now = Utc::now();
+    let done = data.done.unwrap_or(false);
+    let todo = Todo {
+        id: Uuid::new_v4(),
+        title: data.title,
+        description: data.description,
+        done,
+        completed_at: done.then_some(now),
+        due_at: data.due_at,
+        priority: data.priority.unwrap_or_default(),
+        created_at: now,
+        updated_at: now,
+        version: 1,
+        list_id: data.list_id,
+        tags: Vec::new(),
+        owner_id: RequestContext::current_user_id(),
+        tenant_id: RequestContext::writing_tenant_id()?,
+    };
+
+    store.live.insert(todo.id, todo.clone());
+    store.record(NewTodoEvent::created(&todo), events);
+    Ok(todo)
+}
+
+fn update_in(
+    store: &mut Store,
+    id: Uuid,
+    data: UpdateTodoRequest,
+    expected_version: Option<i64>,
+    events: DomainEvents,
+) -> Result<Todo, ApiError> {
+    if !store.live.get(&id).is_some_and(|todo| store.accessible(todo)) {
+        return Err(ApiError::NotFound);
+    }
+    let todo = store.live.get_mut(&id).expect("checked above");
+
+    if expected_version.is_some_and(|version| version != todo.version) {
+        return Err(ApiError::PreconditionFailed);
+    }
+
+    let before = todo.clone();
+    let now = Utc::now();
+    if let Some(title) = data.title {
+        todo.title = title;
+    }
+    if let Some(done) = data.done {
+        todo.set_done(done, now);
+    }
+    if let Some(description) = data.description {
+        todo.description = description;
+    }
+    if let Some(due_at) = data.due_at {
+        todo.due_at = due_at;
+    }
+    if let Some(priority) = data.priority {
+        todo.priority = priority;
+    }
+    if let Some(list_id) = data.list_id {
+        todo.list_id = list_id;
+    }
+    todo.updated_at = now;
+    todo.version += 1;
+
+    let todo = todo.clone();
+    store.record(NewTodoEvent::updated(&before, &todo), events);
+    Ok(todo)
+}
+
+fn retag_in(
+    store: &mut Store,
+    id: Uuid,
+    tag_id: Uuid,
+    attach: bool,
+    expected_version: Option<i64>,
+    events: DomainEvents,
+) -> Result<Todo, ApiError> {
+    let tag = store.tags.get(&tag_id).map(TagRef::from).ok_or(ApiError::NotFound)?;
+    if !store.live.get(&id).is_some_and(|todo| store.accessible(todo)) {
+        return Err(ApiError::NotFound);
+    }
+    let todo = store.live.get_mut(&id).expect("checked above");
+
+    if expected_version.is_some_and(|version| version != todo.version) {
+        return Err(ApiError::PreconditionFailed);
+    }
+
+    let attached = todo.tags.iter().any(|tagged| tagged.id == tag_id);
+    match (attach, attached) {
+        (true, true) => return Ok(todo.clone()),
+        (false, false) => return Err(ApiError::NotFound),
+        _ => {}
+    }
+
+    let before = todo.clone();
+    if attach {
+        todo.tags.push(tag);
+        sort_tags(&mut todo.tags);
+    } else {
+        todo.tags.retain(|tagged| tagged.id != tag_id);
+    }
+    todo.updated_at = Utc::now();
+    todo.version += 1;
+
+    let todo = todo.clone();
+    store.record(NewTodoEvent::updated(&before, &todo), events);
+    Ok(todo)
+}
+
+fn delete_in(store: &mut Store, id: Uuid, expected_version: Option<i64>, events: DomainEvents) -> Result<(), ApiError> {
+    let todo = store.live.get(&id).filter(|todo| store.accessible(todo)).ok_or(ApiError::NotFound)?;
+
+    if expected_version.is_some_and(|version| version != todo.version) {
+        return Err(ApiError::PreconditionFailed);
+    }
+
+    let mut todo = store.live.remove(&id).expect("checked above");
+    let before = todo.clone();
+    todo.version += 1;
+    store.record(NewTodoEvent::deleted(&before, todo.version), events);
+    store.trash.insert(id, TrashedTodo { todo, deleted_at: Utc::now() });
+    Ok(())
+}
+
+fn take_trashed(store: &mut Store, id: Uuid) -> Result<TrashedTodo, ApiError> {
+    if !store.trash.get(&id).is_some_and(|trashed| visible(&trashed.todo)) {
+        return Err(ApiError::NotFound);
+    }
+    Ok(store.trash.remove(&id).expect("checked above"))
+}
+
+fn apply_in(store: &mut Store, operation: BatchOperation, events: DomainEvents) -> Result<BatchOutcome, ApiError> {
+    match operation {
+        BatchOperation::Create { .. } => {
+            Ok(BatchOutcome::Created(create_in(store, operation.create_request().unwrap_or_default(), events)?))
+        }
+        BatchOperation::Update { id, expected_version, .. } => {
+            let request = operation.update_request().unwrap_or_default();
+            update_in(store, id, request, expected_version, events).map(BatchOutcome::Updated)
+        }
+        BatchOperation::Delete { id, expected_version } => {
+            delete_in(store, id, expected_version, events).map(|()| BatchOutcome::Deleted)
+        }
+    }
+}
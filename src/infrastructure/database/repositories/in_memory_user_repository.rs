@@ -0,0 +1,138 @@
+use std::sync::RwLock;
+
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+use crate::domain::users::{NewRefreshToken, RefreshRotation, StoredRefreshToken, User, UserCredentials};
+use crate::domain::users::traits::{RefreshTokens, UserStore};
+use crate::error::ApiError;
+use crate::request_context::RequestContext;
+
+/// Process-local account store with the same semantics as
+/// `PostgresUserRepository`. Data is lost on restart.
+#[derive(Default)]
+pub struct InMemoryUserRepository {
+    store: RwLock<Store>,
+}
+
+#[derive(Default)]
+struct Store {
+    /// Accounts with their tenants
+    users: Vec<(String, UserCredentials)>,
+    /// Tokens with their hashes and tenants
+    refresh_tokens: Vec<(String, String, StoredRefreshToken)>,
+}
+
+impl InMemoryUserRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Whether a row of `tenant_id` belongs to the current request's tenant, as
+/// Postgres row-level security would decide.
+fn in_tenant(tenant_id: &str) -> bool {
+    RequestContext::tenant_scope().is_ok_and(|scope| scope.includes(tenant_id))
+}
+
+#[async_trait::async_trait]
+impl UserStore for InMemoryUserRepository {
+    async fn create_user(&self, email: &str, password_hash: &str) -> Result<User, ApiError> {
+        let tenant_id = RequestContext::writing_tenant_id()?;
+        let mut store = self.store.write().unwrap();
+        if store.users.iter().any(|(tenant, credentials)| *tenant == tenant_id && credentials.user.email == email) {
+            return Err(ApiError::Conflict(format!("email {email} is already registered")));
+        }
+
+        let now = Utc::now();
+        let user = User { id: Uuid::new_v4(), email: email.to_string(), created_at: now, updated_at: now };
+        store.users.push((tenant_id, UserCredentials { user: user.clone(), password_hash: password_hash.to_string() }));
+        Ok(user)
+    }
+
+    async fn find_user(&self, id: Uuid) -> Result<Option<User>, ApiError> {
+        let store = self.store.read().unwrap();
+        let found = store.users.iter().find(|(tenant, credentials)| credentials.user.id == id && in_tenant(tenant));
+        Ok(found.map(|(_, credentials)| credentials.user.clone()))
+    }
+
+    async fn find_credentials(&self, email: &str) -> Result<Option<UserCredentials>, ApiError> {
+        let store = self.store.read().unwrap();
+        let found = store.users.iter().find(|(tenant, credentials)| credentials.user.email == email && in_tenant(tenant));
+        Ok(found.map(|(_, credentials)| credentials.clone()))
+    }
+}
+
+#[async_trait::async_trait]
+impl RefreshTokens for InMemoryUserRepository {
+    async fn store_refresh_token(&self, token: NewRefreshToken) -> Result<(), ApiError> {
+        let tenant_id = RequestContext::writing_tenant_id()?;
+        let stored = StoredRefreshToken {
+            user_id: token.user_id,
+            family_id: token.family_id,
+            expires_at: token.expires_at,
+            replaced_at: None,
+            revoked_at: None,
+        };
+        self.store.write().unwrap().refresh_tokens.push((token.token_hash, tenant_id, stored));
+        Ok(())
+    }
+
+    async fn rotate_refresh_token(
+        &self,
+        token_hash: &str,
+        next_hash: &str,
+        expires_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<RefreshRotation, ApiError> {
+        let tenant_id = RequestContext::writing_tenant_id()?;
+        let mut store = self.store.write().unwrap();
+        let Some((_, _, stored)) =
+            store.refresh_tokens.iter_mut().find(|(hash, tenant, _)| hash == token_hash && in_tenant(tenant))
+        else {
+            return Ok(RefreshRotation::Invalid);
+        };
+
+        let rotation = stored.rotation(now);
+        match rotation {
+            RefreshRotation::Rotated { user_id } => {
+                stored.replaced_at = Some(now);
+                let next = StoredRefreshToken {
+                    user_id,
+                    family_id: stored.family_id,
+                    expires_at,
+                    replaced_at: None,
+                    revoked_at: None,
+                };
+                store.refresh_tokens.push((next_hash.to_string(), tenant_id, next));
+            }
+            RefreshRotation::Reused => {
+                let family_id = stored.family_id;
+                revoke_family(&mut store, family_id, now);
+            }
+            RefreshRotation::Invalid => {}
+        }
+        Ok(rotation)
+    }
+
+    async fn revoke_refresh_family(&self, token_hash: &str, now: DateTime<Utc>) -> Result<(), ApiError> {
+        let mut store = self.store.write().unwrap();
+        let family_id = store
+            .refresh_tokens
+            .iter()
+            .find(|(hash, tenant, _)| hash == token_hash && in_tenant(tenant))
+            .map(|(_, _, stored)| stored.family_id);
+        if let Some(family_id) = family_id {
+            revoke_family(&mut store, family_id, now);
+        }
+        Ok(())
+    }
+}
+
+fn revoke_family(store: &mut Store, family_id: Uuid, now: DateTime<Utc>) {
+    for (_, _, stored) in &mut store.refresh_tokens {
+        if stored.family_id == family_id && stored.revoked_at.is_none() {
+            stored.revoked_at = Some(now);
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use std::sync::RwLock;
+
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+use crate::domain::todos::{OutboxMessage, PaginatedResponse, PaginationMeta, PaginationQuery};
+use crate::domain::webhooks::{
+    CreateWebhookRequest, DeliveryOutcome, DeliveryStatus, UpdateWebhookRequest, Webhook, WebhookDelivery,
+};
+use crate::domain::webhooks::traits::{WebhookDeliveries, WebhookStore};
+use crate::error::ApiError;
+use crate::request_context::RequestContext;
+
+/// Process-local webhook store with the same semantics as
+/// `PostgresWebhookRepository`. Data is lost on restart.
+#[derive(Default)]
+pub struct InMemoryWebhookRepository {
+    store: RwLock<Store>,
+}
+
+/// Both lists are kept in insertion order.
+#[derive(Default)]
+struct Store {
+    webhooks: Vec<Webhook>,
+    deliveries: Vec<WebhookDelivery>,
+    last_delivery_id: i64,
+}
+
+impl InMemoryWebhookRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Whether the current request may see `webhook`: its own tenant's webhooks
+/// created by the signed-in user; background tasks see all.
+fn visible(webhook: &Webhook) -> bool {
+    RequestContext::tenant_scope().is_ok_and(|scope| scope.includes(&webhook.tenant_id))
+        && RequestContext::current_user_id().is_none_or(|owner| webhook.owner_id == Some(owner))
+}
+
+fn page_of<T>(items: Vec<T>, pagination: &PaginationQuery) -> PaginatedResponse<T> {
+    let page = pagination.page();
+    let limit = pagination.limit();
+    let total = items.len() as u64;
+    let data = items
+        .into_iter()
+        .skip(pagination.offset() as usize)
+        .take(limit as usize)
+        .collect();
+
+    PaginatedResponse {
+        data,
+        pagination: PaginationMeta::new(page, limit, total),
+    }
+}
+
+#[async_trait::async_trait]
+impl WebhookStore for InMemoryWebhookRepository {
+    async fn create(&self, data: CreateWebhookRequest) -> Result<Webhook, ApiError> {
+        let now = Utc::now();
+        let webhook = Webhook {
+            id: Uuid::new_v4(),
+            url: data.url,
+            events: data.events,
+            secret: data.secret,
+            active: true,
+            consecutive_failures: 0,
+            owner_id: RequestContext::current_user_id(),
+            tenant_id: RequestContext::writing_tenant_id()?,
+            created_at: now,
+            updated_at: now,
+        };
+        self.store.write().unwrap().webhooks.push(webhook.clone());
+        Ok(webhook)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Webhook>, ApiError> {
+        let store = self.store.read().unwrap();
+        Ok(store.webhooks.iter().find(|webhook| webhook.id == id && visible(webhook)).cloned())
+    }
+
+    async fn find_all_paginated(&self, pagination: PaginationQuery) -> Result<PaginatedResponse<Webhook>, ApiError> {
+        let webhooks = self.store.read().unwrap().webhooks.iter().filter(|webhook| visible(webhook)).cloned().collect();
+        Ok(page_of(webhooks, &pagination))
+    }
+
+    async fn update(&self, id: Uuid, data: UpdateWebhookRequest) -> Result<Webhook, ApiError> {
+        let mut store = self.store.write().unwrap();
+        let webhook = store
+            .webhooks
+            .iter_mut()
+            .find(|webhook| webhook.id == id && visible(webhook))
+            .ok_or(ApiError::NotFound)?;
+
+        if let Some(url) = data.url {
+            webhook.url = url;
+        }
+        if let Some(events) = data.events {
+            webhook.events = events;
+        }
+        if let Some(secret) = data.secret {
+            webhook.secret = secret;
+        }
+        if let Some(active) = data.active {
+            webhook.active = active;
+            if active {
+                webhook.consecutive_failures = 0;
+            }
+        }
+        webhook.updated_at = Utc::now();
+
+        Ok(webhook.clone())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), ApiError> {
+        let mut store = self.store.write().unwrap();
+        let before = store.webhooks.len();
+        store.webhooks.retain(|webhook| webhook.id != id || !visible(webhook));
+        if store.webhooks.len() == before {
+            return Err(ApiError::NotFound);
+        }
+        store.deliveries.retain(|delivery| delivery.webhook_id != id);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl WebhookDeliveries for InMemoryWebhookRepository {
+    async fn enqueue(&self, message: &OutboxMessage) -> Result<u64, ApiError> {
+        let event_type = message.event.event_type();
+        let payload = serde_json::to_value(message).map_err(anyhow::Error::from)?;
+        let now = Utc::now();
+
+        let mut store = self.store.write().unwrap();
+        let Store { webhooks, deliveries, last_delivery_id } = &mut *store;
+        let mut queued = 0;
+        for webhook in webhooks.iter().filter(|webhook| {
+            webhook.active
+                && webhook.owner_id == message.owner_id
+                && webhook.tenant_id == message.tenant_id
+                && webhook.subscribes_to(event_type)
+        }) {
+            let duplicate = deliveries
+                .iter()
+                .any(|delivery| delivery.webhook_id == webhook.id && delivery.event_id == message.event_id);
+            if duplicate {
+                continue;
+            }
+            *last_delivery_id += 1;
+            deliveries.push(WebhookDelivery {
+                id: *last_delivery_id,
+                webhook_id: webhook.id,
+                event_id: message.event_id,
+                event_type: event_type.to_string(),
+                payload: payload.clone(),
+                status: DeliveryStatus::Pending,
+                attempts: 0,
+                next_attempt_at: Some(now),
+                response_status: None,
+                last_error: None,
+                created_at: now,
+                delivered_at: None,
+            });
+            queued += 1;
+        }
+
+        Ok(queued)
+    }
+
+    async fn due_deliveries(&self, limit: u32, now: DateTime<Utc>) -> Result<Vec<WebhookDelivery>, ApiError> {
+        let store = self.store.read().unwrap();
+        let active = |webhook_id: Uuid| store.webhooks.iter().any(|webhook| webhook.id == webhook_id && webhook.active);
+
+        let mut due: Vec<WebhookDelivery> = store
+            .deliveries
+            .iter()
+            .filter(|delivery| delivery.status == DeliveryStatus::Pending)
+            .filter(|delivery| delivery.next_attempt_at.is_some_and(|at| at <= now))
+            .filter(|delivery| active(delivery.webhook_id))
+            .cloned()
+            .collect();
+        due.sort_by_key(|delivery| (delivery.next_attempt_at, delivery.id));
+        due.truncate(limit as usize);
+
+        Ok(due)
+    }
+
+    async fn record_attempt(&self, id: i64, outcome: DeliveryOutcome, disable_after: i32) -> Result<(), ApiError> {
+        let mut store = self.store.write().unwrap();
+        // The webhook was deleted while the attempt was in flight.
+        let Some(delivery) = store.deliveries.iter_mut().find(|delivery| delivery.id == id) else { return Ok(()) };
+
+        delivery.attempts += 1;
+        let succeeded = match outcome {
+            DeliveryOutcome::Succeeded { response_status } => {
+                delivery.status = DeliveryStatus::Succeeded;
+                delivery.next_attempt_at = None;
+                delivery.response_status = Some(i32::from(response_status));
+                delivery.last_error = None;
+                delivery.delivered_at = Some(Utc::now());
+                true
+            }
+            DeliveryOutcome::Failed { response_status, error, retry_at } => {
+                delivery.status = if retry_at.is_some() { DeliveryStatus::Pending } else { DeliveryStatus::Failed };
+                delivery.next_attempt_at = retry_at;
+                delivery.response_status = response_status.map(i32::from);
+                delivery.last_error = Some(error);
+                false
+            }
+        };
+
+        let webhook_id = delivery.webhook_id;
+        if let Some(webhook) = store.webhooks.iter_mut().find(|webhook| webhook.id == webhook_id) {
+            if succeeded {
+                webhook.consecutive_failures = 0;
+            } else {
+                webhook.consecutive_failures += 1;
+                webhook.active &= webhook.consecutive_failures < disable_after;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn find_deliveries(
+        &self,
+        webhook_id: Uuid,
+        pagination: PaginationQuery,
+    ) -> Result<PaginatedResponse<WebhookDelivery>, ApiError> {
+        let deliveries: Vec<WebhookDelivery> = self
+            .store
+            .read()
+            .unwrap()
+            .deliveries
+            .iter()
+            .rev()
+            .filter(|delivery| delivery.webhook_id == webhook_id)
+            .cloned()
+            .collect();
+
+        Ok(page_of(deliveries, &pagination))
+    }
+}
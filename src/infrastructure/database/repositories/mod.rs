@@ -1,3 +1,50 @@
+pub mod postgres_api_key_repository;
+pub mod postgres_tag_repository;
+pub mod postgres_todo_list_repository;
 pub mod postgres_todo_repository;
+pub mod postgres_user_repository;
+pub mod postgres_webhook_repository;
+pub mod in_memory_api_key_repository;
+pub mod in_memory_tag_repository;
+pub mod in_memory_todo_list_repository;
+pub mod in_memory_todo_repository;
+pub mod in_memory_user_repository;
+pub mod in_memory_webhook_repository;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_api_key_repository;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_tag_repository;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_todo_list_repository;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_todo_repository;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_user_repository;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_webhook_repository;
+mod sql;
 
+pub use postgres_api_key_repository::PostgresApiKeyRepository;
+pub use postgres_tag_repository::PostgresTagRepository;
+pub use postgres_todo_list_repository::PostgresTodoListRepository;
 pub use postgres_todo_repository::PostgresTodoRepository;
+pub use postgres_user_repository::PostgresUserRepository;
+pub use postgres_webhook_repository::PostgresWebhookRepository;
+pub use in_memory_api_key_repository::InMemoryApiKeyRepository;
+pub use in_memory_tag_repository::InMemoryTagRepository;
+pub use in_memory_todo_list_repository::InMemoryTodoListRepository;
+pub use in_memory_todo_repository::InMemoryTodoRepository;
+pub use in_memory_user_repository::InMemoryUserRepository;
+pub use in_memory_webhook_repository::InMemoryWebhookRepository;
+#[cfg(feature = "sqlite")]
+pub use sqlite_api_key_repository::SqliteApiKeyRepository;
+#[cfg(feature = "sqlite")]
+pub use sqlite_tag_repository::SqliteTagRepository;
+#[cfg(feature = "sqlite")]
+pub use sqlite_todo_list_repository::SqliteTodoListRepository;
+#[cfg(feature = "sqlite")]
+pub use sqlite_todo_repository::SqliteTodoRepository;
+#[cfg(feature = "sqlite")]
+pub use sqlite_user_repository::SqliteUserRepository;
+#[cfg(feature = "sqlite")]
+pub use sqlite_webhook_repository::SqliteWebhookRepository;
@@ -0,0 +1,113 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+use crate::domain::api_keys::{ApiKey, NewApiKey};
+use crate::domain::api_keys::traits::ApiKeyRepository;
+use crate::domain::todos::{PaginatedResponse, PaginationMeta, PaginationQuery};
+use crate::error::ApiError;
+use crate::request_context::RequestContext;
+use super::postgres_todo_repository::tenant_connection;
+use super::sql::API_KEY_COLUMNS;
+
+pub struct PostgresApiKeyRepository {
+    pool: PgPool,
+}
+
+impl PostgresApiKeyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl ApiKeyRepository for PostgresApiKeyRepository {
+    async fn create(&self, key: NewApiKey) -> Result<ApiKey, ApiError> {
+        let mut conn = tenant_connection(&self.pool).await?;
+        let api_key = sqlx::query_as::<_, ApiKey>(&format!(
+            "INSERT INTO api_keys (id, user_id, name, prefix, key_hash, scope, expires_at, tenant_id, created_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) RETURNING {API_KEY_COLUMNS}"
+        ))
+        .bind(Uuid::new_v4())
+        .bind(key.user_id)
+        .bind(&key.name)
+        .bind(&key.prefix)
+        .bind(&key.key_hash)
+        .bind(key.scope.as_str())
+        .bind(key.expires_at)
+        .bind(RequestContext::writing_tenant_id()?)
+        .bind(Utc::now())
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(api_key)
+    }
+
+    async fn find_by_id(&self, user_id: Uuid, id: Uuid) -> Result<Option<ApiKey>, ApiError> {
+        let mut conn = tenant_connection(&self.pool).await?;
+        let api_key = sqlx::query_as::<_, ApiKey>(&format!(
+            "SELECT {API_KEY_COLUMNS} FROM api_keys WHERE id = $1 AND user_id = $2"
+        ))
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(api_key)
+    }
+
+    async fn find_all_paginated(&self, user_id: Uuid, pagination: PaginationQuery) -> Result<PaginatedResponse<ApiKey>, ApiError> {
+        let mut conn = tenant_connection(&self.pool).await?;
+        let page = pagination.page();
+        let limit = pagination.limit();
+
+        let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM api_keys WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_one(&mut *conn)
+            .await?;
+
+        let api_keys = sqlx::query_as::<_, ApiKey>(&format!(
+            "SELECT {API_KEY_COLUMNS} FROM api_keys WHERE user_id = $3 ORDER BY created_at, id LIMIT $1 OFFSET $2"
+        ))
+        .bind(limit as i64)
+        .bind(pagination.offset() as i64)
+        .bind(user_id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(PaginatedResponse {
+            data: api_keys,
+            pagination: PaginationMeta::new(page, limit, total as u64),
+        })
+    }
+
+    async fn revoke(&self, user_id: Uuid, id: Uuid, now: DateTime<Utc>) -> Result<ApiKey, ApiError> {
+        let mut conn = tenant_connection(&self.pool).await?;
+        let api_key = sqlx::query_as::<_, ApiKey>(&format!(
+            "UPDATE api_keys SET revoked_at = COALESCE(revoked_at, $3) WHERE id = $1 AND user_id = $2 \
+             RETURNING {API_KEY_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(user_id)
+        .bind(now)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        api_key.ok_or(ApiError::NotFound)
+    }
+
+    async fn authenticate(&self, key_hash: &str, now: DateTime<Utc>) -> Result<Option<ApiKey>, ApiError> {
+        let mut conn = tenant_connection(&self.pool).await?;
+        let api_key = sqlx::query_as::<_, ApiKey>(&format!(
+            "UPDATE api_keys SET last_used_at = $2 \
+             WHERE key_hash = $1 AND revoked_at IS NULL AND (expires_at IS NULL OR expires_at > $2) \
+             RETURNING {API_KEY_COLUMNS}"
+        ))
+        .bind(key_hash)
+        .bind(now)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(api_key)
+    }
+}
@@ -0,0 +1,146 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use chrono::Utc;
+
+use crate::domain::lists::{CreateTodoListRequest, TodoList, UpdateTodoListRequest};
+use crate::domain::lists::traits::{TodoListArchiver, TodoListStore};
+use crate::domain::todos::{PaginatedResponse, PaginationMeta, PaginationQuery};
+use crate::error::ApiError;
+use crate::request_context::RequestContext;
+use super::postgres_todo_repository::tenant_connection;
+use super::sql::LIST_COLUMNS;
+
+pub struct PostgresTodoListRepository {
+    pool: PgPool,
+}
+
+impl PostgresTodoListRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoListStore for PostgresTodoListRepository {
+    async fn create(&self, data: CreateTodoListRequest) -> Result<TodoList, ApiError> {
+        let mut conn = tenant_connection(&self.pool).await?;
+        let list = sqlx::query_as::<_, TodoList>(&format!(
+            "INSERT INTO todo_lists (id, name, description, color, owner_id, tenant_id, created_at, updated_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $7) RETURNING {LIST_COLUMNS}"
+        ))
+        .bind(Uuid::new_v4())
+        .bind(&data.name)
+        .bind(&data.description)
+        .bind(&data.color)
+        .bind(RequestContext::current_user_id())
+        .bind(RequestContext::writing_tenant_id()?)
+        .bind(Utc::now())
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(list)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<TodoList>, ApiError> {
+        let mut conn = tenant_connection(&self.pool).await?;
+        let list = sqlx::query_as::<_, TodoList>(&format!(
+            "SELECT {LIST_COLUMNS} FROM todo_lists WHERE id = $1 AND ($2::uuid IS NULL OR owner_id = $2)"
+        ))
+        .bind(id)
+        .bind(RequestContext::current_user_id())
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(list)
+    }
+
+    async fn find_all_paginated(&self, archived: bool, pagination: PaginationQuery) -> Result<PaginatedResponse<TodoList>, ApiError> {
+        let mut conn = tenant_connection(&self.pool).await?;
+        let page = pagination.page();
+        let limit = pagination.limit();
+
+        let owner = RequestContext::current_user_id();
+        let total: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM todo_lists WHERE archived = $1 AND ($2::uuid IS NULL OR owner_id = $2)"
+        )
+        .bind(archived)
+        .bind(owner)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        let lists = sqlx::query_as::<_, TodoList>(&format!(
+            "SELECT {LIST_COLUMNS} FROM todo_lists WHERE archived = $1 AND ($2::uuid IS NULL OR owner_id = $2) \
+             ORDER BY created_at, id LIMIT $3 OFFSET $4"
+        ))
+        .bind(archived)
+        .bind(owner)
+        .bind(limit as i64)
+        .bind(pagination.offset() as i64)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(PaginatedResponse {
+            data: lists,
+            pagination: PaginationMeta::new(page, limit, total as u64),
+        })
+    }
+
+    async fn update(&self, id: Uuid, data: UpdateTodoListRequest) -> Result<TodoList, ApiError> {
+        let mut conn = tenant_connection(&self.pool).await?;
+        let list = sqlx::query_as::<_, TodoList>(&format!(
+            r#"
+            UPDATE todo_lists
+            SET name = COALESCE($1, name),
+                description = COALESCE($2, description),
+                color = COALESCE($3, color),
+                updated_at = $4
+            WHERE id = $5 AND ($6::uuid IS NULL OR owner_id = $6)
+            RETURNING {LIST_COLUMNS}
+            "#
+        ))
+        .bind(&data.name)
+        .bind(&data.description)
+        .bind(&data.color)
+        .bind(Utc::now())
+        .bind(id)
+        .bind(RequestContext::current_user_id())
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        list.ok_or(ApiError::NotFound)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), ApiError> {
+        let mut conn = tenant_connection(&self.pool).await?;
+        let result = sqlx::query("DELETE FROM todo_lists WHERE id = $1 AND ($2::uuid IS NULL OR owner_id = $2)")
+            .bind(id)
+            .bind(RequestContext::current_user_id())
+            .execute(&mut *conn)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoListArchiver for PostgresTodoListRepository {
+    async fn set_archived(&self, id: Uuid, archived: bool) -> Result<TodoList, ApiError> {
+        let mut conn = tenant_connection(&self.pool).await?;
+        let list = sqlx::query_as::<_, TodoList>(&format!(
+            "UPDATE todo_lists SET archived = $1, updated_at = $2 \
+             WHERE id = $3 AND ($4::uuid IS NULL OR owner_id = $4) RETURNING {LIST_COLUMNS}"
+        ))
+        .bind(archived)
+        .bind(Utc::now())
+        .bind(id)
+        .bind(RequestContext::current_user_id())
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        list.ok_or(ApiError::NotFound)
+    }
+}
@@ -1,10 +1,17 @@
-use sqlx::PgPool;
+use std::collections::HashMap;
+
+use sqlx::{pool::PoolConnection, types::Json, Connection, FromRow, PgConnection, PgPool, QueryBuilder, Postgres};
 use uuid::Uuid;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 
-use crate::domain::todos::{Todo, CreateTodoRequest, UpdateTodoRequest, PaginationQuery, PaginatedResponse, PaginationMeta};
+use crate::domain::todos::{Todo, CreateTodoRequest, UpdateTodoRequest, PaginationQuery, PaginatedResponse, PaginationMeta,
+    KeysetQuery, KeysetPage, KeysetDirection, TodoFilter, TodoSearch, TodoSearchHit, SearchTerm, BatchOperation, BatchMode, BatchOutcome, TrashedTodo,
+    TodoEvent, NewTodoEvent, FieldChange, DomainEvent, DomainEvents, OutboxMessage, TagMatch, TodoPriority, TodoRole, TodoShare};
 use crate::error::ApiError;
-use crate::domain::todos::traits::{TodoCreator, TodoFinder, TodoPaginator, TodoUpdater, TodoDeleter};
+use crate::request_context::{RequestContext, TenantScope};
+use crate::domain::todos::traits::{TodoCreator, TodoFinder, TodoPaginator, TodoSearcher, TodoUpdater, TodoDeleter, TodoTrash, TodoHistory,
+    TodoOutbox, TodoBatchWriter, TodoRoles, TodoSharing, TodoTagging};
+use super::sql::{accessible_to, contains_pattern, order_by, EVENT_COLUMNS, NOT_IN_ARCHIVED_LIST, OUTBOX_COLUMNS, SHARE_COLUMNS, TODO_COLUMNS};
 
 pub struct PostgresTodoRepository {
     pool: PgPool,
@@ -14,141 +21,967 @@ impl PostgresTodoRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    async fn connection(&self) -> Result<PoolConnection<Postgres>, ApiError> {
+        tenant_connection(&self.pool).await
+    }
+
+    /// The todo a change notification names, as it is stored now: trashed
+    /// todos included and, once purged, as the audit log last saw it. Not
+    /// limited to the current user, only to the current tenant.
+    pub async fn find_changed(&self, id: Uuid) -> Result<Option<Todo>, ApiError> {
+        let mut conn = self.connection().await?;
+        let todo = sqlx::query_as::<_, Todo>(&format!("SELECT {TODO_COLUMNS} FROM todos WHERE id = $1"))
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?;
+        if todo.is_some() {
+            return Ok(todo);
+        }
+
+        let purged: Option<Json<Todo>> = sqlx::query_scalar(
+            "SELECT before FROM todo_events WHERE todo_id = $1 AND kind = 'purged' ORDER BY id DESC LIMIT 1",
+        )
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .flatten();
+        Ok(purged.map(|Json(todo)| todo))
+    }
+}
+
+/// A pooled connection scoped to the current tenant. Row-level security
+/// then hides every other tenant's rows, whatever a query's `WHERE` clause
+/// says; background tasks running as `RequestContext::all_tenants` see them
+/// all, and a context with neither fails before touching the database.
+/// Both settings are written on every checkout, so none carries over from
+/// the connection's previous use.
+pub(super) async fn tenant_connection(pool: &PgPool) -> Result<PoolConnection<Postgres>, ApiError> {
+    let (tenant, all_tenants) = match RequestContext::tenant_scope()? {
+        TenantScope::Tenant(tenant) => (tenant, "off"),
+        TenantScope::AllTenants => (String::new(), "on"),
+    };
+    let mut conn = pool.acquire().await?;
+    sqlx::query("SELECT set_config('app.tenant_id', $1, false), set_config('app.all_tenants', $2, false)")
+        .bind(tenant)
+        .bind(all_tenants)
+        .execute(&mut *conn)
+        .await?;
+    Ok(conn)
+}
+
+async fn count(conn: &mut PgConnection, filter: &TodoFilter) -> Result<u64, ApiError> {
+    let mut query = QueryBuilder::new("SELECT COUNT(*) FROM todos WHERE deleted_at IS NULL");
+    push_filter(&mut query, filter);
+    let total: i64 = query.build_query_scalar().fetch_one(conn).await?;
+    Ok(total as u64)
+}
+
+/// Limits a query to the todos the current user owns or was given access
+/// to; background tasks see all.
+fn push_accessible(query: &mut QueryBuilder<'_, Postgres>) {
+    if let Some(user) = RequestContext::current_user_id() {
+        query.push(" AND (owner_id = ").push_bind(user);
+        query.push(" OR id IN (SELECT todo_id FROM todo_shares WHERE user_id = ").push_bind(user).push("))");
+    }
+}
+
+/// Rest of a subquery over the tags of `todos.id` whose names are in the
+/// array bound next.
+const TAGGED_WITH: &str =
+    " FROM todo_tags JOIN tags ON tags.id = todo_tags.tag_id WHERE todo_tags.todo_id = todos.id AND tags.name = ANY(";
+
+fn push_filter<'a>(query: &mut QueryBuilder<'a, Postgres>, filter: &TodoFilter) {
+    push_accessible(query);
+    if let Some(done) = filter.done {
+        query.push(" AND done = ").push_bind(done);
+    }
+    if let Some(title) = &filter.title {
+        query.push(" AND title ILIKE ").push_bind(contains_pattern(title)).push(" ESCAPE '\\'");
+    }
+    if let Some(at) = filter.created_after {
+        query.push(" AND created_at > ").push_bind(at);
+    }
+    if let Some(at) = filter.created_before {
+        query.push(" AND created_at < ").push_bind(at);
+    }
+    if let Some(at) = filter.updated_since {
+        query.push(" AND updated_at >= ").push_bind(at);
+    }
+    if let Some(at) = filter.due_after {
+        query.push(" AND due_at >= ").push_bind(at);
+    }
+    if let Some(at) = filter.due_before {
+        query.push(" AND due_at < ").push_bind(at);
+    }
+    match filter.list_id {
+        Some(list_id) => {
+            query.push(" AND list_id = ").push_bind(list_id);
+        }
+        None => {
+            query.push(NOT_IN_ARCHIVED_LIST);
+        }
+    }
+    if !filter.tags.is_empty() {
+        match filter.tag_match {
+            TagMatch::Any => query.push(" AND EXISTS (SELECT 1"),
+            TagMatch::All => query.push(" AND (SELECT COUNT(DISTINCT tags.name)"),
+        };
+        query.push(TAGGED_WITH).push_bind(filter.tags.clone()).push("))");
+        if filter.tag_match == TagMatch::All {
+            query.push(" = ").push_bind(filter.tags.len() as i64);
+        }
+    }
 }
 
 #[async_trait::async_trait]
 impl TodoCreator for PostgresTodoRepository {
-    async fn create(&self, data: CreateTodoRequest) -> Result<Todo, ApiError> {
-        let todo = sqlx::query_as::<_, Todo>(
-            r#"
-            INSERT INTO todos (id, title, done, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5)
-            RETURNING id, title, done, created_at, updated_at
-            "#
-        )
-        .bind(Uuid::new_v4())
-        .bind(&data.title)
-        .bind(data.done.unwrap_or(false))
-        .bind(Utc::now())
-        .bind(Utc::now())
-        .fetch_one(&self.pool)
-        .await
-        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
-
-        Ok(todo)
+    async fn create(&self, data: CreateTodoRequest, events: DomainEvents) -> Result<Todo, ApiError> {
+        let mut conn = self.connection().await?;
+        insert_in(&mut conn, &data, events).await
     }
 }
 
 #[async_trait::async_trait]
 impl TodoFinder for PostgresTodoRepository {
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Todo>, ApiError> {
-        let todo = sqlx::query_as::<_, Todo>(
-            "SELECT id, title, done, created_at, updated_at FROM todos WHERE id = $1"
-        )
+        let mut conn = self.connection().await?;
+        let todo = sqlx::query_as::<_, Todo>(&format!(
+            "SELECT {TODO_COLUMNS} FROM todos WHERE id = $1 AND deleted_at IS NULL AND {}",
+            accessible_to("$2::uuid", "id")
+        ))
         .bind(id)
-        .fetch_optional(&self.pool)
-        .await
-        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+        .bind(RequestContext::current_user_id())
+        .fetch_optional(&mut *conn)
+        .await?;
 
         Ok(todo)
     }
-
-    async fn find_by_done(&self, done: bool) -> Result<Vec<Todo>, ApiError> {
-        let todos = sqlx::query_as::<_, Todo>(
-            "SELECT id, title, done, created_at, updated_at FROM todos WHERE done = $1 ORDER BY created_at DESC"
-        )
-        .bind(done)
-        .fetch_all(&self.pool)
-        .await
-        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
-
-        Ok(todos)
-    }
 }
 
 #[async_trait::async_trait]
 impl TodoPaginator for PostgresTodoRepository {
     async fn find_all_paginated(&self, pagination: PaginationQuery) -> Result<PaginatedResponse<Todo>, ApiError> {
-        // Validate pagination parameters
-        let page = pagination.page.max(1);
-        let limit = pagination.limit.min(100).max(1); // Max 100 items per page
-        let offset = (page - 1) * limit;
-
-        // Get total count
-        let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM todos")
-            .fetch_one(&self.pool)
-            .await
-            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
-
-        // Get paginated results
-        let todos = sqlx::query_as::<_, Todo>(
-            "SELECT id, title, done, created_at, updated_at FROM todos ORDER BY created_at DESC LIMIT $1 OFFSET $2"
-        )
-        .bind(limit as i64)
-        .bind(offset as i64)
-        .fetch_all(&self.pool)
-        .await
-        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
-
-        let total = total as u64;
-        let total_pages = ((total as f64) / (limit as f64)).ceil() as u32;
-        let has_next = page < total_pages;
-        let has_prev = page > 1;
-
-        let pagination_meta = PaginationMeta {
-            page,
-            limit,
-            total,
-            total_pages,
-            has_next,
-            has_prev,
-        };
+        let mut conn = self.connection().await?;
+        let page = pagination.page();
+        let limit = pagination.limit();
+        let filter = pagination.filter();
+
+        let total = count(&mut conn, &filter).await?;
+
+        let mut query = QueryBuilder::new(format!("SELECT {TODO_COLUMNS} FROM todos WHERE deleted_at IS NULL"));
+        push_filter(&mut query, &filter);
+        query.push(order_by(&pagination.sort_keys()));
+        query.push(" LIMIT ").push_bind(limit as i64);
+        query.push(" OFFSET ").push_bind(pagination.offset() as i64);
+        let todos = query.build_query_as::<Todo>().fetch_all(&mut *conn).await?;
 
         Ok(PaginatedResponse {
             data: todos,
-            pagination: pagination_meta,
+            pagination: PaginationMeta::new(page, limit, total),
+        })
+    }
+
+    async fn find_keyset(&self, query: KeysetQuery) -> Result<KeysetPage<Todo>, ApiError> {
+        let mut conn = self.connection().await?;
+        let limit = query.limit.clamp(1, PaginationQuery::MAX_LIMIT);
+
+        let mut select = QueryBuilder::new(format!("SELECT {TODO_COLUMNS} FROM todos WHERE deleted_at IS NULL"));
+        push_filter(&mut select, &query.filter);
+        match query.start {
+            None => {
+                select.push(" ORDER BY created_at DESC, id DESC");
+            }
+            Some((KeysetDirection::After, position)) => {
+                select.push(" AND (created_at, id) < (").push_bind(position.created_at);
+                select.push(", ").push_bind(position.id).push(")");
+                select.push(" ORDER BY created_at DESC, id DESC");
+            }
+            Some((KeysetDirection::Before, position)) => {
+                select.push(" AND (created_at, id) > (").push_bind(position.created_at);
+                select.push(", ").push_bind(position.id).push(")");
+                select.push(" ORDER BY created_at ASC, id ASC");
+            }
+        }
+        // One extra row tells whether another page follows.
+        select.push(" LIMIT ").push_bind(limit as i64 + 1);
+        let mut todos = select.build_query_as::<Todo>().fetch_all(&mut *conn).await?;
+
+        let has_more = todos.len() > limit as usize;
+        todos.truncate(limit as usize);
+        if matches!(query.start, Some((KeysetDirection::Before, _))) {
+            todos.reverse();
+        }
+
+        let total = if query.include_total { Some(count(&mut conn, &query.filter).await?) } else { None };
+
+        Ok(KeysetPage { data: todos, has_more, total })
+    }
+}
+
+#[derive(FromRow)]
+struct SearchRow {
+    #[sqlx(flatten)]
+    todo: Todo,
+    rank: f32,
+    snippet: String,
+}
+
+/// Pushes a `WITH search AS (...)` clause binding the terms as one `tsquery`
+/// named `query`. Each term goes through a `*to_tsquery` function as a bound
+/// parameter; prefix words are safe to pass to `to_tsquery` because terms
+/// only contain alphanumeric characters.
+fn push_search_cte(query: &mut QueryBuilder<'_, Postgres>, search: &TodoSearch) {
+    query.push("WITH search AS (SELECT ");
+    for (i, term) in search.terms.iter().enumerate() {
+        if i > 0 {
+            query.push(" && ");
+        }
+        match term {
+            SearchTerm::Word { text, prefix: false } => {
+                query.push("plainto_tsquery('english', ").push_bind(text.clone()).push(")");
+            }
+            SearchTerm::Word { text, prefix: true } => {
+                query.push("to_tsquery('english', ").push_bind(format!("{text}:*")).push(")");
+            }
+            SearchTerm::Phrase(words) => {
+                query.push("phraseto_tsquery('english', ").push_bind(words.join(" ")).push(")");
+            }
+        }
+    }
+    query.push(" AS query) ");
+}
+
+#[async_trait::async_trait]
+impl TodoSearcher for PostgresTodoRepository {
+    async fn search(&self, search: TodoSearch) -> Result<PaginatedResponse<TodoSearchHit>, ApiError> {
+        let mut conn = self.connection().await?;
+        let page = search.pagination.page();
+        let limit = search.pagination.limit();
+
+        let mut count = QueryBuilder::new("");
+        push_search_cte(&mut count, &search);
+        count.push("SELECT COUNT(*) FROM todos, search WHERE search_vector @@ search.query AND deleted_at IS NULL");
+        push_accessible(&mut count);
+        let total: i64 = count.build_query_scalar().fetch_one(&mut *conn).await?;
+
+        let mut select = QueryBuilder::new("");
+        push_search_cte(&mut select, &search);
+        select.push(format!(
+            "SELECT {TODO_COLUMNS}, ts_rank_cd(search_vector, search.query) AS rank, \
+             ts_headline('english', title, search.query, \
+                 'StartSel=' || chr({}) || ', StopSel=' || chr({}) || ', HighlightAll=true') AS snippet \
+             FROM todos, search WHERE search_vector @@ search.query AND deleted_at IS NULL",
+            TodoSearch::MATCH_START as u32,
+            TodoSearch::MATCH_END as u32,
+        ));
+        push_accessible(&mut select);
+        select.push(" ORDER BY rank DESC, created_at DESC, id DESC");
+        select.push(" LIMIT ").push_bind(limit as i64);
+        select.push(" OFFSET ").push_bind(search.pagination.offset() as i64);
+        let rows = select.build_query_as::<SearchRow>().fetch_all(&mut *conn).await?;
+
+        Ok(PaginatedResponse {
+            data: rows
+                .into_iter()
+                .map(|row| TodoSearchHit { todo: row.todo, rank: row.rank, snippet: TodoSearch::snippet(&row.snippet) })
+                .collect(),
+            pagination: PaginationMeta::new(page, limit, total as u64),
         })
     }
 }
 
 #[async_trait::async_trait]
 impl TodoUpdater for PostgresTodoRepository {
-    async fn update(&self, id: Uuid, data: UpdateTodoRequest) -> Result<Todo, ApiError> {
-        let todo = sqlx::query_as::<_, Todo>(
-            r#"
-            UPDATE todos 
-            SET title = COALESCE($1, title),
-                done = COALESCE($2, done),
-                updated_at = $3
-            WHERE id = $4
-            RETURNING id, title, done, created_at, updated_at
-            "#
+    async fn update(
+        &self,
+        id: Uuid,
+        data: UpdateTodoRequest,
+        expected_version: Option<i64>,
+        events: DomainEvents,
+    ) -> Result<Todo, ApiError> {
+        let mut conn = self.connection().await?;
+        update_in(&mut conn, id, data, expected_version, events).await
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoDeleter for PostgresTodoRepository {
+    async fn delete(&self, id: Uuid, expected_version: Option<i64>, events: DomainEvents) -> Result<(), ApiError> {
+        let mut conn = self.connection().await?;
+        delete_in(&mut conn, id, expected_version, events).await
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoTrash for PostgresTodoRepository {
+    async fn find_trashed(&self, pagination: PaginationQuery) -> Result<PaginatedResponse<TrashedTodo>, ApiError> {
+        let mut conn = self.connection().await?;
+        let page = pagination.page();
+        let limit = pagination.limit();
+
+        let owner = RequestContext::current_user_id();
+        let total: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM todos WHERE deleted_at IS NOT NULL AND ($1::uuid IS NULL OR owner_id = $1)"
         )
-        .bind(&data.title)
-        .bind(data.done)
+        .bind(owner)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        let trashed = sqlx::query_as::<_, TrashedTodo>(&format!(
+            "SELECT {TODO_COLUMNS}, deleted_at FROM todos WHERE deleted_at IS NOT NULL AND ($3::uuid IS NULL OR owner_id = $3) \
+             ORDER BY deleted_at DESC, id DESC LIMIT $1 OFFSET $2"
+        ))
+        .bind(limit as i64)
+        .bind(pagination.offset() as i64)
+        .bind(owner)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(PaginatedResponse {
+            data: trashed,
+            pagination: PaginationMeta::new(page, limit, total as u64),
+        })
+    }
+
+    async fn restore(&self, id: Uuid, events: DomainEvents) -> Result<Todo, ApiError> {
+        let mut conn = self.connection().await?;
+        let mut tx = conn.begin().await?;
+        let todo = sqlx::query_as::<_, Todo>(&format!(
+            "UPDATE todos \
+             SET deleted_at = NULL, updated_at = $1, version = version + 1 \
+             WHERE id = $2 AND deleted_at IS NOT NULL AND ($3::uuid IS NULL OR owner_id = $3) \
+             RETURNING {TODO_COLUMNS}"
+        ))
         .bind(Utc::now())
         .bind(id)
-        .fetch_one(&self.pool)
-        .await
-        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+        .bind(RequestContext::current_user_id())
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(ApiError::NotFound)?;
 
+        record(&mut tx, &[NewTodoEvent::restored(&todo)], events).await?;
+        tx.commit().await?;
         Ok(todo)
     }
+
+    async fn purge(&self, id: Uuid) -> Result<(), ApiError> {
+        let mut conn = self.connection().await?;
+        let mut tx = conn.begin().await?;
+        let todo = sqlx::query_as::<_, Todo>(&format!(
+            "DELETE FROM todos WHERE id = $1 AND deleted_at IS NOT NULL AND ($2::uuid IS NULL OR owner_id = $2) \
+             RETURNING {TODO_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(RequestContext::current_user_id())
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+        record(&mut tx, &[NewTodoEvent::purged(&todo)], |_| Vec::new()).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn purge_trashed_before(&self, cutoff: DateTime<Utc>) -> Result<u64, ApiError> {
+        let mut conn = self.connection().await?;
+        let mut tx = conn.begin().await?;
+        let purged = sqlx::query_as::<_, Todo>(&format!(
+            "DELETE FROM todos WHERE deleted_at < $1 AND ($2::uuid IS NULL OR owner_id = $2) RETURNING {TODO_COLUMNS}"
+        ))
+        .bind(cutoff)
+        .bind(RequestContext::current_user_id())
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let events: Vec<NewTodoEvent> = purged.iter().map(NewTodoEvent::purged).collect();
+        record(&mut tx, &events, |_| Vec::new()).await?;
+        tx.commit().await?;
+        Ok(purged.len() as u64)
+    }
 }
 
 #[async_trait::async_trait]
-impl TodoDeleter for PostgresTodoRepository {
-    async fn delete(&self, id: Uuid) -> Result<(), ApiError> {
-        let result = sqlx::query("DELETE FROM todos WHERE id = $1")
+impl TodoHistory for PostgresTodoRepository {
+    async fn find_history(&self, id: Uuid, pagination: PaginationQuery) -> Result<PaginatedResponse<TodoEvent>, ApiError> {
+        let mut conn = self.connection().await?;
+        let page = pagination.page();
+        let limit = pagination.limit();
+
+        let owner = RequestContext::current_user_id();
+        let total: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM todo_events WHERE todo_id = $1 AND {}",
+            accessible_to("$2::uuid", "todo_id")
+        ))
+        .bind(id)
+        .bind(owner)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        let events = sqlx::query_as::<_, TodoEvent>(&format!(
+            "SELECT {EVENT_COLUMNS} FROM todo_events WHERE todo_id = $1 AND {} ORDER BY id LIMIT $2 OFFSET $3",
+            accessible_to("$4::uuid", "todo_id")
+        ))
+        .bind(id)
+        .bind(limit as i64)
+        .bind(pagination.offset() as i64)
+        .bind(owner)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(PaginatedResponse {
+            data: events,
+            pagination: PaginationMeta::new(page, limit, total as u64),
+        })
+    }
+
+    async fn last_event_id(&self) -> Result<i64, ApiError> {
+        let mut conn = self.connection().await?;
+        let id: Option<i64> = sqlx::query_scalar("SELECT MAX(id) FROM todo_events").fetch_one(&mut *conn).await?;
+        Ok(id.unwrap_or(0))
+    }
+
+    async fn events_since(&self, after: i64, limit: u32) -> Result<Vec<TodoEvent>, ApiError> {
+        let mut conn = self.connection().await?;
+        let events = sqlx::query_as::<_, TodoEvent>(&format!(
+            "SELECT {EVENT_COLUMNS} FROM todo_events WHERE id > $1 ORDER BY id LIMIT $2"
+        ))
+        .bind(after)
+        .bind(limit as i64)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(events)
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoOutbox for PostgresTodoRepository {
+    async fn pending_messages(&self, limit: u32, now: DateTime<Utc>) -> Result<Vec<OutboxMessage>, ApiError> {
+        let mut conn = self.connection().await?;
+        let messages = sqlx::query_as::<_, OutboxMessage>(&format!(
+            "SELECT {OUTBOX_COLUMNS} FROM outbox message \
+             WHERE available_at <= $1 AND NOT EXISTS ( \
+                 SELECT 1 FROM outbox earlier \
+                 WHERE earlier.todo_id = message.todo_id AND earlier.id < message.id AND earlier.available_at > $1 \
+             ) \
+             ORDER BY id LIMIT $2"
+        ))
+        .bind(now)
+        .bind(limit as i64)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(messages)
+    }
+
+    async fn mark_published(&self, id: i64) -> Result<(), ApiError> {
+        let mut conn = self.connection().await?;
+        sqlx::query("DELETE FROM outbox WHERE id = $1").bind(id).execute(&mut *conn).await?;
+        Ok(())
+    }
+
+    async fn mark_failed(&self, id: i64, error: &str, retry_at: DateTime<Utc>) -> Result<(), ApiError> {
+        let mut conn = self.connection().await?;
+        sqlx::query("UPDATE outbox SET attempts = attempts + 1, last_error = $1, available_at = $2 WHERE id = $3")
+            .bind(error)
+            .bind(retry_at)
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoRoles for PostgresTodoRepository {
+    async fn role_of(&self, id: Uuid, user_id: Uuid) -> Result<Option<TodoRole>, ApiError> {
+        let mut conn = self.connection().await?;
+        let role: Option<Option<String>> = sqlx::query_scalar(
+            "SELECT CASE WHEN todos.owner_id = $2 THEN 'owner' ELSE todo_shares.role END \
+             FROM todos LEFT JOIN todo_shares ON todo_shares.todo_id = todos.id AND todo_shares.user_id = $2 \
+             WHERE todos.id = $1"
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        role.flatten().map(TodoRole::try_from).transpose().map_err(|error| ApiError::from(anyhow::anyhow!(error)))
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoSharing for PostgresTodoRepository {
+    async fn find_shares(&self, id: Uuid) -> Result<Vec<TodoShare>, ApiError> {
+        let mut conn = self.connection().await?;
+        let shares = sqlx::query_as::<_, TodoShare>(&format!(
+            "SELECT {SHARE_COLUMNS} FROM todo_shares WHERE todo_id = $1 ORDER BY created_at, user_id"
+        ))
+        .bind(id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(shares)
+    }
+
+    async fn grant(&self, id: Uuid, user_id: Uuid, role: TodoRole) -> Result<TodoShare, ApiError> {
+        let mut conn = self.connection().await?;
+        let share = sqlx::query_as::<_, TodoShare>(&format!(
+            "INSERT INTO todo_shares (todo_id, user_id, role, created_at) VALUES ($1, $2, $3, $4) \
+             ON CONFLICT (todo_id, user_id) DO UPDATE SET role = EXCLUDED.role \
+             RETURNING {SHARE_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(user_id)
+        .bind(role.as_str())
+        .bind(Utc::now())
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(share)
+    }
+
+    async fn revoke(&self, id: Uuid, user_id: Uuid) -> Result<(), ApiError> {
+        let mut conn = self.connection().await?;
+        let result = sqlx::query("DELETE FROM todo_shares WHERE todo_id = $1 AND user_id = $2")
             .bind(id)
-            .execute(&self.pool)
-            .await
-            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+            .bind(user_id)
+            .execute(&mut *conn)
+            .await?;
 
         if result.rows_affected() == 0 {
             return Err(ApiError::NotFound);
         }
-
         Ok(())
     }
 }
+
+#[async_trait::async_trait]
+impl TodoTagging for PostgresTodoRepository {
+    async fn attach_tag(
+        &self,
+        id: Uuid,
+        tag_id: Uuid,
+        expected_version: Option<i64>,
+        events: DomainEvents,
+    ) -> Result<Todo, ApiError> {
+        let mut conn = self.connection().await?;
+        retag_in(&mut conn, id, tag_id, true, expected_version, events).await
+    }
+
+    async fn detach_tag(
+        &self,
+        id: Uuid,
+        tag_id: Uuid,
+        expected_version: Option<i64>,
+        events: DomainEvents,
+    ) -> Result<Todo, ApiError> {
+        let mut conn = self.connection().await?;
+        retag_in(&mut conn, id, tag_id, false, expected_version, events).await
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoBatchWriter for PostgresTodoRepository {
+    async fn apply_batch(
+        &self,
+        operations: Vec<BatchOperation>,
+        mode: BatchMode,
+        events: DomainEvents,
+    ) -> Result<Vec<Result<BatchOutcome, ApiError>>, ApiError> {
+        let results = match mode {
+            BatchMode::Atomic => {
+                let mut conn = self.connection().await?;
+                let mut tx = conn.begin().await?;
+                let results = apply_in(&mut tx, operations, mode, events).await;
+                if results.iter().all(|result| matches!(result, Some(Ok(_)))) {
+                    tx.commit().await?;
+                }
+                results
+            }
+            BatchMode::BestEffort => {
+                let mut conn = self.connection().await?;
+                apply_in(&mut conn, operations, mode, events).await
+            }
+        };
+
+        Ok(mode.settle(results))
+    }
+}
+
+/// Appends audit events, and queues the domain events `emit` builds from
+/// them in the outbox, with one `INSERT ... SELECT FROM UNNEST` statement
+/// per table.
+async fn record(conn: &mut PgConnection, events: &[NewTodoEvent], emit: DomainEvents) -> Result<(), ApiError> {
+    if events.is_empty() {
+        return Ok(());
+    }
+
+    let todo_ids: Vec<Uuid> = events.iter().map(|event| event.todo_id).collect();
+    let owner_ids: Vec<Option<Uuid>> = events.iter().map(|event| event.owner_id).collect();
+    let tenant_ids: Vec<&str> = events.iter().map(|event| event.tenant_id.as_str()).collect();
+    let kinds: Vec<&str> = events.iter().map(|event| event.kind.as_str()).collect();
+    let versions: Vec<i64> = events.iter().map(|event| event.version).collect();
+    let befores: Vec<Option<Json<&Todo>>> = events.iter().map(|event| event.before.as_ref().map(Json)).collect();
+    let afters: Vec<Option<Json<&Todo>>> = events.iter().map(|event| event.after.as_ref().map(Json)).collect();
+    let changes: Vec<Json<&Vec<FieldChange>>> = events.iter().map(|event| Json(&event.changes)).collect();
+    let actors: Vec<Option<&str>> = events.iter().map(|event| event.actor.as_deref()).collect();
+    let request_ids: Vec<Option<&str>> = events.iter().map(|event| event.request_id.as_deref()).collect();
+
+    sqlx::query(
+        r#"
+        INSERT INTO todo_events (todo_id, kind, version, before, after, changes, actor, request_id, owner_id, tenant_id, occurred_at)
+        SELECT todo_id, kind, version, before, after, changes, actor, request_id, owner_id, tenant_id, $11
+        FROM UNNEST($1::UUID[], $2::TEXT[], $3::BIGINT[], $4::JSONB[], $5::JSONB[], $6::JSONB[], $7::TEXT[], $8::TEXT[], $9::UUID[], $10::TEXT[])
+            AS events(todo_id, kind, version, before, after, changes, actor, request_id, owner_id, tenant_id)
+        "#
+    )
+    .bind(&todo_ids)
+    .bind(&kinds)
+    .bind(&versions)
+    .bind(&befores)
+    .bind(&afters)
+    .bind(&changes)
+    .bind(&actors)
+    .bind(&request_ids)
+    .bind(&owner_ids)
+    .bind(&tenant_ids)
+    .bind(Utc::now())
+    .execute(&mut *conn)
+    .await?;
+
+    let messages: Vec<OutboxMessage> = events.iter().flat_map(|event| OutboxMessage::queue(event, emit(event))).collect();
+    if messages.is_empty() {
+        return Ok(());
+    }
+
+    let event_ids: Vec<Uuid> = messages.iter().map(|message| message.event_id).collect();
+    let todo_ids: Vec<Uuid> = messages.iter().map(|message| message.todo_id).collect();
+    let owner_ids: Vec<Option<Uuid>> = messages.iter().map(|message| message.owner_id).collect();
+    let tenant_ids: Vec<&str> = messages.iter().map(|message| message.tenant_id.as_str()).collect();
+    let types: Vec<&str> = messages.iter().map(|message| message.event.event_type()).collect();
+    let payloads: Vec<Json<&DomainEvent>> = messages.iter().map(|message| Json(&message.event)).collect();
+
+    sqlx::query(
+        r#"
+        INSERT INTO outbox (event_id, todo_id, event_type, payload, owner_id, tenant_id, occurred_at, available_at)
+        SELECT event_id, todo_id, event_type, payload, owner_id, tenant_id, $7, $7
+        FROM UNNEST($1::UUID[], $2::UUID[], $3::TEXT[], $4::JSONB[], $5::UUID[], $6::TEXT[])
+            AS messages(event_id, todo_id, event_type, payload, owner_id, tenant_id)
+        "#
+    )
+    .bind(&event_ids)
+    .bind(&todo_ids)
+    .bind(&types)
+    .bind(&payloads)
+    .bind(&owner_ids)
+    .bind(&tenant_ids)
+    .bind(Utc::now())
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Reads a live todo and locks it until the transaction ends, so the write
+/// that follows sees exactly this version.
+async fn lock_live(conn: &mut PgConnection, id: Uuid, expected_version: Option<i64>) -> Result<Todo, ApiError> {
+    let todo = sqlx::query_as::<_, Todo>(&format!(
+        "SELECT {TODO_COLUMNS} FROM todos WHERE id = $1 AND deleted_at IS NULL AND {} FOR UPDATE",
+        accessible_to("$2::uuid", "id")
+    ))
+    .bind(id)
+    .bind(RequestContext::current_user_id())
+    .fetch_optional(conn)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    if expected_version.is_some_and(|version| version != todo.version) {
+        return Err(ApiError::PreconditionFailed);
+    }
+    Ok(todo)
+}
+
+// The write helpers open their own transaction, which becomes a savepoint
+// when `conn` is already inside one (as in an atomic batch).
+
+async fn insert_in(conn: &mut PgConnection, data: &CreateTodoRequest, events: DomainEvents) -> Result<Todo, ApiError> {
+    let now = Utc::now();
+    let done = data.done.unwrap_or(false);
+    let mut tx = conn.begin().await?;
+    let todo = sqlx::query_as::<_, Todo>(&format!(
+        "INSERT INTO todos (id, title, description, done, completed_at, due_at, priority, \
+         created_at, updated_at, list_id, owner_id, tenant_id) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $8, $9, $10, $11) RETURNING {TODO_COLUMNS}"
+    ))
+    .bind(Uuid::new_v4())
+    .bind(&data.title)
+    .bind(&data.description)
+    .bind(done)
+    .bind(done.then_some(now))
+    .bind(data.due_at)
+    .bind(data.priority.unwrap_or_default().as_str())
+    .bind(now)
+    .bind(data.list_id)
+    .bind(RequestContext::current_user_id())
+    .bind(RequestContext::writing_tenant_id()?)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    record(&mut tx, &[NewTodoEvent::created(&todo)], events).await?;
+    tx.commit().await?;
+    Ok(todo)
+}
+
+async fn update_in(
+    conn: &mut PgConnection,
+    id: Uuid,
+    data: UpdateTodoRequest,
+    expected_version: Option<i64>,
+    events: DomainEvents,
+) -> Result<Todo, ApiError> {
+    let mut tx = conn.begin().await?;
+    let before = lock_live(&mut tx, id, expected_version).await?;
+
+    // Nullable fields come as a flag saying whether to set them and the
+    // value to set. `completed_at` follows `done` only when it flips.
+    let todo = sqlx::query_as::<_, Todo>(&format!(
+        "UPDATE todos \
+         SET title = COALESCE($1, title), \
+             done = COALESCE($2, done), \
+             completed_at = CASE WHEN $2 IS NULL OR $2 = done THEN completed_at WHEN $2 THEN $3 END, \
+             description = CASE WHEN $4 THEN $5 ELSE description END, \
+             due_at = CASE WHEN $6 THEN $7 ELSE due_at END, \
+             priority = COALESCE($8, priority), \
+             list_id = CASE WHEN $10 THEN $11 ELSE list_id END, \
+             updated_at = $3, version = version + 1 \
+         WHERE id = $9 RETURNING {TODO_COLUMNS}"
+    ))
+    .bind(&data.title)
+    .bind(data.done)
+    .bind(Utc::now())
+    .bind(data.description.is_some())
+    .bind(data.description.flatten())
+    .bind(data.due_at.is_some())
+    .bind(data.due_at.flatten())
+    .bind(data.priority.map(TodoPriority::as_str))
+    .bind(id)
+    .bind(data.list_id.is_some())
+    .bind(data.list_id.flatten())
+    .fetch_one(&mut *tx)
+    .await?;
+
+    record(&mut tx, &[NewTodoEvent::updated(&before, &todo)], events).await?;
+    tx.commit().await?;
+    Ok(todo)
+}
+
+/// Attaches or detaches a tag, then bumps the todo's version so the
+/// returned representation, tags included, has a new ETag.
+async fn retag_in(
+    conn: &mut PgConnection,
+    id: Uuid,
+    tag_id: Uuid,
+    attach: bool,
+    expected_version: Option<i64>,
+    events: DomainEvents,
+) -> Result<Todo, ApiError> {
+    let mut tx = conn.begin().await?;
+    let before = lock_live(&mut tx, id, expected_version).await?;
+
+    let now = Utc::now();
+    let result = if attach {
+        sqlx::query("INSERT INTO todo_tags (todo_id, tag_id, created_at) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING")
+            .bind(id)
+            .bind(tag_id)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?
+    } else {
+        sqlx::query("DELETE FROM todo_tags WHERE todo_id = $1 AND tag_id = $2")
+            .bind(id)
+            .bind(tag_id)
+            .execute(&mut *tx)
+            .await?
+    };
+    if result.rows_affected() == 0 {
+        return if attach { Ok(before) } else { Err(ApiError::NotFound) };
+    }
+
+    let todo = sqlx::query_as::<_, Todo>(&format!(
+        "UPDATE todos SET updated_at = $1, version = version + 1 WHERE id = $2 RETURNING {TODO_COLUMNS}"
+    ))
+    .bind(now)
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    record(&mut tx, &[NewTodoEvent::updated(&before, &todo)], events).await?;
+    tx.commit().await?;
+    Ok(todo)
+}
+
+/// The live todos tag `tag_id` is attached to, locked until renaming or
+/// deleting the tag is recorded with [`record_retagged`].
+pub(super) async fn lock_tagged(conn: &mut PgConnection, tag_id: Uuid) -> Result<Vec<Todo>, ApiError> {
+    let todos = sqlx::query_as::<_, Todo>(&format!(
+        "SELECT {TODO_COLUMNS} FROM todos \
+         WHERE deleted_at IS NULL AND id IN (SELECT todo_id FROM todo_tags WHERE tag_id = $1) ORDER BY id FOR UPDATE"
+    ))
+    .bind(tag_id)
+    .fetch_all(conn)
+    .await?;
+
+    Ok(todos)
+}
+
+/// Bumps the version of each of `befores`, whose tags were just renamed or
+/// removed, and records the updates.
+pub(super) async fn record_retagged(conn: &mut PgConnection, befores: &[Todo], events: DomainEvents) -> Result<(), ApiError> {
+    if befores.is_empty() {
+        return Ok(());
+    }
+
+    let ids: Vec<Uuid> = befores.iter().map(|todo| todo.id).collect();
+    let afters: HashMap<Uuid, Todo> = sqlx::query_as::<_, Todo>(&format!(
+        "UPDATE todos SET updated_at = $1, version = version + 1 WHERE id = ANY($2) RETURNING {TODO_COLUMNS}"
+    ))
+    .bind(Utc::now())
+    .bind(&ids)
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .map(|todo| (todo.id, todo))
+    .collect();
+
+    let changes: Vec<NewTodoEvent> =
+        befores.iter().map(|before| NewTodoEvent::updated(before, &afters[&before.id])).collect();
+    record(conn, &changes, events).await
+}
+
+async fn delete_in(
+    conn: &mut PgConnection,
+    id: Uuid,
+    expected_version: Option<i64>,
+    events: DomainEvents,
+) -> Result<(), ApiError> {
+    let mut tx = conn.begin().await?;
+    let before = lock_live(&mut tx, id, expected_version).await?;
+
+    let version: i64 = sqlx::query_scalar(
+        "UPDATE todos SET deleted_at = $1, version = version + 1 WHERE id = $2 RETURNING version"
+    )
+    .bind(Utc::now())
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    record(&mut tx, &[NewTodoEvent::deleted(&before, version)], events).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Inserts every row with one `INSERT ... SELECT FROM UNNEST` statement and
+/// returns the todos in the order given.
+async fn insert_many(conn: &mut PgConnection, rows: &[CreateTodoRequest], events: DomainEvents) -> Result<Vec<Todo>, ApiError> {
+    let ids: Vec<Uuid> = rows.iter().map(|_| Uuid::new_v4()).collect();
+    let titles: Vec<&str> = rows.iter().map(|row| row.title.as_str()).collect();
+    let descriptions: Vec<Option<&str>> = rows.iter().map(|row| row.description.as_deref()).collect();
+    let done: Vec<bool> = rows.iter().map(|row| row.done.unwrap_or(false)).collect();
+    let due_at: Vec<Option<DateTime<Utc>>> = rows.iter().map(|row| row.due_at).collect();
+    let priorities: Vec<&str> = rows.iter().map(|row| row.priority.unwrap_or_default().as_str()).collect();
+    let list_ids: Vec<Option<Uuid>> = rows.iter().map(|row| row.list_id).collect();
+
+    let mut tx = conn.begin().await?;
+    let inserted = sqlx::query_as::<_, Todo>(&format!(
+        "INSERT INTO todos (id, title, description, done, completed_at, due_at, priority, list_id, \
+         created_at, updated_at, owner_id, tenant_id) \
+         SELECT id, title, description, done, CASE WHEN done THEN $8 END, due_at, priority, list_id, $8, $8, $9, $10 \
+         FROM UNNEST($1::UUID[], $2::TEXT[], $3::TEXT[], $4::BOOLEAN[], $5::TIMESTAMPTZ[], $6::TEXT[], $7::UUID[]) \
+             AS rows(id, title, description, done, due_at, priority, list_id) \
+         RETURNING {TODO_COLUMNS}"
+    ))
+    .bind(&ids)
+    .bind(&titles)
+    .bind(&descriptions)
+    .bind(&done)
+    .bind(&due_at)
+    .bind(&priorities)
+    .bind(&list_ids)
+    .bind(Utc::now())
+    .bind(RequestContext::current_user_id())
+    .bind(RequestContext::writing_tenant_id()?)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    // RETURNING does not promise input order.
+    let mut by_id: HashMap<Uuid, Todo> = inserted.into_iter().map(|todo| (todo.id, todo)).collect();
+    let todos: Vec<Todo> = ids.iter().filter_map(|id| by_id.remove(id)).collect();
+
+    let changes: Vec<NewTodoEvent> = todos.iter().map(NewTodoEvent::created).collect();
+    record(&mut tx, &changes, events).await?;
+    tx.commit().await?;
+    Ok(todos)
+}
+
+/// Runs the batch on one connection. Creates go first, in a single
+/// statement; they mint fresh ids, so no other operation in the batch can
+/// depend on them. Updates and deletes then run in request order. In atomic
+/// mode the first failure stops the batch, since Postgres rejects further
+/// statements in a failed transaction; unattempted operations are `None`.
+async fn apply_in(
+    conn: &mut PgConnection,
+    operations: Vec<BatchOperation>,
+    mode: BatchMode,
+    events: DomainEvents,
+) -> Vec<Option<Result<BatchOutcome, ApiError>>> {
+    let mut results: Vec<Option<Result<BatchOutcome, ApiError>>> = operations.iter().map(|_| None).collect();
+
+    let (positions, creates): (Vec<usize>, Vec<CreateTodoRequest>) = operations
+        .iter()
+        .enumerate()
+        .filter_map(|(index, operation)| operation.create_request().map(|request| (index, request)))
+        .unzip();
+
+    if !creates.is_empty() {
+        match insert_many(&mut *conn, &creates, events).await {
+            Ok(todos) => {
+                for (index, todo) in positions.iter().zip(todos) {
+                    results[*index] = Some(Ok(BatchOutcome::Created(todo)));
+                }
+            }
+            Err(error) if mode == BatchMode::Atomic => {
+                results[positions[0]] = Some(Err(error));
+                return results;
+            }
+            // Retry row by row so that only the offending rows fail.
+            Err(_) => {
+                for (index, request) in positions.iter().zip(&creates) {
+                    let result = insert_in(&mut *conn, request, events).await.map(BatchOutcome::Created);
+                    results[*index] = Some(result);
+                }
+            }
+        }
+    }
+
+    for (index, operation) in operations.into_iter().enumerate() {
+        let result = match operation {
+            BatchOperation::Create { .. } => continue,
+            BatchOperation::Update { id, expected_version, .. } => {
+                let request = operation.update_request().unwrap_or_default();
+                update_in(&mut *conn, id, request, expected_version, events).await.map(BatchOutcome::Updated)
+            }
+            BatchOperation::Delete { id, expected_version } => {
+                delete_in(&mut *conn, id, expected_version, events).await.map(|()| BatchOutcome::Deleted)
+            }
+        };
+        let failed = result.is_err();
+        results[index] = Some(result);
+        if failed && mode == BatchMode::Atomic {
+            break;
+        }
+    }
+
+    results
+}
@@ -0,0 +1,153 @@
+use sqlx::{Connection, PgPool};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+use crate::domain::users::{NewRefreshToken, RefreshRotation, StoredRefreshToken, User, UserCredentials};
+use crate::domain::users::traits::{RefreshTokens, UserStore};
+use crate::error::ApiError;
+use crate::request_context::RequestContext;
+use super::postgres_todo_repository::tenant_connection;
+use super::sql::{REFRESH_TOKEN_COLUMNS, USER_COLUMNS};
+
+pub struct PostgresUserRepository {
+    pool: PgPool,
+}
+
+impl PostgresUserRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl UserStore for PostgresUserRepository {
+    async fn create_user(&self, email: &str, password_hash: &str) -> Result<User, ApiError> {
+        let mut conn = tenant_connection(&self.pool).await?;
+        let user = sqlx::query_as::<_, User>(&format!(
+            "INSERT INTO users (id, email, password_hash, tenant_id, created_at, updated_at) \
+             VALUES ($1, $2, $3, $4, $5, $5) RETURNING {USER_COLUMNS}"
+        ))
+        .bind(Uuid::new_v4())
+        .bind(email)
+        .bind(password_hash)
+        .bind(RequestContext::writing_tenant_id()?)
+        .bind(Utc::now())
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(user)
+    }
+
+    async fn find_user(&self, id: Uuid) -> Result<Option<User>, ApiError> {
+        let mut conn = tenant_connection(&self.pool).await?;
+        let user = sqlx::query_as::<_, User>(&format!("SELECT {USER_COLUMNS} FROM users WHERE id = $1"))
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+        Ok(user)
+    }
+
+    async fn find_credentials(&self, email: &str) -> Result<Option<UserCredentials>, ApiError> {
+        let mut conn = tenant_connection(&self.pool).await?;
+        let credentials = sqlx::query_as::<_, UserCredentials>(&format!(
+            "SELECT {USER_COLUMNS}, password_hash FROM users WHERE email = $1"
+        ))
+        .bind(email)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(credentials)
+    }
+}
+
+#[async_trait::async_trait]
+impl RefreshTokens for PostgresUserRepository {
+    async fn store_refresh_token(&self, token: NewRefreshToken) -> Result<(), ApiError> {
+        let mut conn = tenant_connection(&self.pool).await?;
+        sqlx::query(
+            "INSERT INTO refresh_tokens (id, user_id, family_id, token_hash, expires_at, tenant_id, created_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7)"
+        )
+        .bind(Uuid::new_v4())
+        .bind(token.user_id)
+        .bind(token.family_id)
+        .bind(&token.token_hash)
+        .bind(token.expires_at)
+        .bind(RequestContext::writing_tenant_id()?)
+        .bind(Utc::now())
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn rotate_refresh_token(
+        &self,
+        token_hash: &str,
+        next_hash: &str,
+        expires_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<RefreshRotation, ApiError> {
+        let mut conn = tenant_connection(&self.pool).await?;
+        let mut tx = conn.begin().await?;
+        // The row lock makes concurrent refreshes with one token take turns,
+        // so only the first rotates it and the second counts as a reuse.
+        let stored = sqlx::query_as::<_, StoredRefreshToken>(&format!(
+            "SELECT {REFRESH_TOKEN_COLUMNS} FROM refresh_tokens WHERE token_hash = $1 FOR UPDATE"
+        ))
+        .bind(token_hash)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(stored) = stored else { return Ok(RefreshRotation::Invalid) };
+        let rotation = stored.rotation(now);
+        match rotation {
+            RefreshRotation::Rotated { user_id } => {
+                sqlx::query("UPDATE refresh_tokens SET replaced_at = $1 WHERE token_hash = $2")
+                    .bind(now)
+                    .bind(token_hash)
+                    .execute(&mut *tx)
+                    .await?;
+                sqlx::query(
+                    "INSERT INTO refresh_tokens (id, user_id, family_id, token_hash, expires_at, tenant_id, created_at) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7)"
+                )
+                .bind(Uuid::new_v4())
+                .bind(user_id)
+                .bind(stored.family_id)
+                .bind(next_hash)
+                .bind(expires_at)
+                .bind(RequestContext::writing_tenant_id()?)
+                .bind(now)
+                .execute(&mut *tx)
+                .await?;
+            }
+            RefreshRotation::Reused => {
+                sqlx::query("UPDATE refresh_tokens SET revoked_at = $1 WHERE family_id = $2 AND revoked_at IS NULL")
+                    .bind(now)
+                    .bind(stored.family_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            RefreshRotation::Invalid => {}
+        }
+
+        tx.commit().await?;
+        Ok(rotation)
+    }
+
+    async fn revoke_refresh_family(&self, token_hash: &str, now: DateTime<Utc>) -> Result<(), ApiError> {
+        let mut conn = tenant_connection(&self.pool).await?;
+        sqlx::query(
+            "UPDATE refresh_tokens SET revoked_at = $1 \
+             WHERE revoked_at IS NULL AND family_id = (SELECT family_id FROM refresh_tokens WHERE token_hash = $2)"
+        )
+        .bind(now)
+        .bind(token_hash)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+//! SQL fragments shared by the Postgres and SQLite repositories.
+
+use crate::domain::todos::{SortField, SortKey};
+
+/// The columns read into a `Todo`, with `$tags` selecting the todo's tags
+/// as a JSON array ordered by name, compared byte by byte on every backend.
+/// The tags are a correlated subquery on `todos.id`, so a page of todos is
+/// still read in one query.
+macro_rules! todo_columns {
+    ($tags:literal) => {
+        concat!(
+            "id, title, description, done, completed_at, due_at, priority, \
+             created_at, updated_at, version, list_id, owner_id, tenant_id, ",
+            $tags,
+            " AS tags"
+        )
+    };
+}
+
+pub(crate) const TODO_COLUMNS: &str = todo_columns!(
+    r#"COALESCE((SELECT json_agg(json_build_object('id', tags.id, 'name', tags.name) ORDER BY tags.name COLLATE "C", tags.id)
+     FROM todo_tags JOIN tags ON tags.id = todo_tags.tag_id WHERE todo_tags.todo_id = todos.id), '[]'::json)"#
+);
+
+/// `TODO_COLUMNS` for SQLite, which stores ids as blobs: `hex` turns them
+/// into the simple form of a UUID.
+#[cfg(feature = "sqlite")]
+pub(crate) const SQLITE_TODO_COLUMNS: &str = todo_columns!(
+    "(SELECT json_group_array(json_object('id', hex(tags.id), 'name', tags.name) ORDER BY tags.name, tags.id) \
+     FROM todo_tags JOIN tags ON tags.id = todo_tags.tag_id WHERE todo_tags.todo_id = todos.id)"
+);
+
+pub(crate) const EVENT_COLUMNS: &str =
+    "id, todo_id, kind, version, before, after, changes, actor, request_id, occurred_at";
+
+pub(crate) const OUTBOX_COLUMNS: &str = "id, event_id, todo_id, owner_id, tenant_id, payload, occurred_at, attempts, available_at";
+
+pub(crate) const WEBHOOK_COLUMNS: &str =
+    "id, url, events, secret, active, consecutive_failures, owner_id, tenant_id, created_at, updated_at";
+
+pub(crate) const TAG_COLUMNS: &str = "id, name, owner_id, tenant_id, created_at, updated_at";
+
+pub(crate) const LIST_COLUMNS: &str =
+    "id, name, description, color, archived, owner_id, tenant_id, created_at, updated_at";
+
+pub(crate) const USER_COLUMNS: &str = "id, email, created_at, updated_at";
+
+pub(crate) const API_KEY_COLUMNS: &str =
+    "id, user_id, name, prefix, scope, expires_at, last_used_at, revoked_at, tenant_id, created_at";
+
+pub(crate) const SHARE_COLUMNS: &str = "todo_id, user_id, role, created_at";
+
+pub(crate) const REFRESH_TOKEN_COLUMNS: &str = "user_id, family_id, expires_at, replaced_at, revoked_at";
+
+pub(crate) const DELIVERY_COLUMNS: &str = "id, webhook_id, event_id, event_type, payload, status, attempts, \
+    next_attempt_at, response_status, last_error, created_at, delivered_at";
+
+/// SQLite stores timestamps as fixed-width RFC 3339 text so that `ORDER BY`
+/// on the column matches chronological order.
+#[cfg(feature = "sqlite")]
+pub(crate) fn timestamp(at: chrono::DateTime<chrono::Utc>) -> String {
+    at.to_rfc3339_opts(chrono::SecondsFormat::Micros, true)
+}
+
+/// Rows of the todos the user bound as `user` owns or was given access to,
+/// or every row when it is NULL. `id` names the column holding the todo id.
+pub(crate) fn accessible_to(user: &str, id: &str) -> String {
+    format!("({user} IS NULL OR owner_id = {user} OR {id} IN (SELECT todo_id FROM todo_shares WHERE user_id = {user}))")
+}
+
+/// Condition leaving out the todos of archived lists, for listings that
+/// do not ask for one list in particular.
+pub(crate) const NOT_IN_ARCHIVED_LIST: &str =
+    " AND (list_id IS NULL OR list_id NOT IN (SELECT id FROM todo_lists WHERE archived))";
+
+fn column(field: SortField) -> &'static str {
+    match field {
+        SortField::CreatedAt => "created_at",
+        SortField::UpdatedAt => "updated_at",
+        SortField::Title => "title",
+        SortField::Done => "done",
+    }
+}
+
+/// `ORDER BY` clause for `keys` with `id DESC` as the final tie-breaker,
+/// matching `SortKey::compare`. Built only from whitelisted column names.
+pub(crate) fn order_by(keys: &[SortKey]) -> String {
+    let mut terms: Vec<String> = keys
+        .iter()
+        .map(|key| format!("{} {}", column(key.field), if key.descending { "DESC" } else { "ASC" }))
+        .collect();
+    terms.push("id DESC".to_string());
+    format!(" ORDER BY {}", terms.join(", "))
+}
+
+/// `LIKE` pattern matching `needle` anywhere, with `\` as the escape
+/// character for wildcards in the needle itself.
+pub(crate) fn contains_pattern(needle: &str) -> String {
+    let escaped = needle
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{escaped}%")
+}
@@ -0,0 +1,117 @@
+use sqlx::SqlitePool;
+use uuid::Uuid;
+use chrono::Utc;
+
+use crate::domain::tags::{CreateTagRequest, RenameTagRequest, Tag};
+use crate::domain::tags::traits::TagStore;
+use crate::domain::todos::{DomainEvents, PaginatedResponse, PaginationMeta, PaginationQuery};
+use crate::error::ApiError;
+use crate::request_context::RequestContext;
+use super::sql::{timestamp, TAG_COLUMNS};
+use super::sqlite_todo_repository::{find_tagged, record_retagged};
+
+pub struct SqliteTagRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteTagRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl TagStore for SqliteTagRepository {
+    async fn create(&self, data: CreateTagRequest) -> Result<Tag, ApiError> {
+        let tag = sqlx::query_as::<_, Tag>(&format!(
+            "INSERT INTO tags (id, name, owner_id, created_at, updated_at) \
+             VALUES (?1, ?2, ?3, ?4, ?4) RETURNING {TAG_COLUMNS}"
+        ))
+        .bind(Uuid::new_v4())
+        .bind(&data.name)
+        .bind(RequestContext::current_user_id())
+        .bind(timestamp(Utc::now()))
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(tag)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Tag>, ApiError> {
+        let tag = sqlx::query_as::<_, Tag>(&format!(
+            "SELECT {TAG_COLUMNS} FROM tags WHERE id = ?1 AND (?2 IS NULL OR owner_id = ?2)"
+        ))
+        .bind(id)
+        .bind(RequestContext::current_user_id())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(tag)
+    }
+
+    async fn find_all_paginated(&self, pagination: PaginationQuery) -> Result<PaginatedResponse<Tag>, ApiError> {
+        let page = pagination.page();
+        let limit = pagination.limit();
+
+        let owner = RequestContext::current_user_id();
+        let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tags WHERE (?1 IS NULL OR owner_id = ?1)")
+            .bind(owner)
+            .fetch_one(&self.pool)
+            .await?;
+
+        let tags = sqlx::query_as::<_, Tag>(&format!(
+            "SELECT {TAG_COLUMNS} FROM tags WHERE (?1 IS NULL OR owner_id = ?1) \
+             ORDER BY name, id LIMIT ?2 OFFSET ?3"
+        ))
+        .bind(owner)
+        .bind(limit as i64)
+        .bind(pagination.offset() as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(PaginatedResponse {
+            data: tags,
+            pagination: PaginationMeta::new(page, limit, total as u64),
+        })
+    }
+
+    async fn rename(&self, id: Uuid, data: RenameTagRequest, events: DomainEvents) -> Result<Tag, ApiError> {
+        let mut tx = self.pool.begin().await?;
+        let tagged = find_tagged(&mut tx, id).await?;
+
+        let tag = sqlx::query_as::<_, Tag>(&format!(
+            "UPDATE tags SET name = ?1, updated_at = ?2 \
+             WHERE id = ?3 AND (?4 IS NULL OR owner_id = ?4) RETURNING {TAG_COLUMNS}"
+        ))
+        .bind(&data.name)
+        .bind(timestamp(Utc::now()))
+        .bind(id)
+        .bind(RequestContext::current_user_id())
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+        record_retagged(&mut tx, &tagged, events).await?;
+        tx.commit().await?;
+        Ok(tag)
+    }
+
+    async fn delete(&self, id: Uuid, events: DomainEvents) -> Result<(), ApiError> {
+        let mut tx = self.pool.begin().await?;
+        let tagged = find_tagged(&mut tx, id).await?;
+
+        let result = sqlx::query("DELETE FROM tags WHERE id = ?1 AND (?2 IS NULL OR owner_id = ?2)")
+            .bind(id)
+            .bind(RequestContext::current_user_id())
+            .execute(&mut *tx)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound);
+        }
+
+        record_retagged(&mut tx, &tagged, events).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,766 @@
+use sqlx::{types::Json, Connection, SqliteConnection, SqlitePool, QueryBuilder, Sqlite};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+use crate::domain::todos::{Todo, CreateTodoRequest, UpdateTodoRequest, PaginationQuery, PaginatedResponse, PaginationMeta,
+    KeysetQuery, KeysetPage, KeysetDirection, TodoFilter, TodoSearch, TodoSearchHit, BatchOperation, BatchMode, BatchOutcome, TrashedTodo,
+    TodoEvent, NewTodoEvent, OutboxMessage, DomainEvents, TagMatch, TodoPriority, TodoRole, TodoShare};
+use crate::error::ApiError;
+use crate::request_context::RequestContext;
+use crate::domain::todos::traits::{TodoCreator, TodoFinder, TodoPaginator, TodoSearcher, TodoUpdater, TodoDeleter, TodoTrash, TodoHistory,
+    TodoOutbox, TodoBatchWriter, TodoRoles, TodoSharing, TodoTagging};
+use super::sql::{accessible_to, contains_pattern, order_by, timestamp, EVENT_COLUMNS, NOT_IN_ARCHIVED_LIST, OUTBOX_COLUMNS, SHARE_COLUMNS, SQLITE_TODO_COLUMNS};
+
+pub struct SqliteTodoRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteTodoRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    async fn count(&self, filter: &TodoFilter) -> Result<u64, ApiError> {
+        let mut query = QueryBuilder::new("SELECT COUNT(*) FROM todos WHERE deleted_at IS NULL");
+        push_filter(&mut query, filter);
+        let total: i64 = query.build_query_scalar().fetch_one(&self.pool).await?;
+        Ok(total as u64)
+    }
+
+}
+
+/// Limits a query to the todos the current user owns or was given access
+/// to; background tasks see all.
+fn push_accessible(query: &mut QueryBuilder<'_, Sqlite>) {
+    if let Some(user) = RequestContext::current_user_id() {
+        query.push(" AND (owner_id = ").push_bind(user);
+        query.push(" OR id IN (SELECT todo_id FROM todo_shares WHERE user_id = ").push_bind(user).push("))");
+    }
+}
+
+/// Rest of a subquery over the tags of `todos.id` whose names are in the
+/// list pushed next.
+const TAGGED_WITH: &str =
+    " FROM todo_tags JOIN tags ON tags.id = todo_tags.tag_id WHERE todo_tags.todo_id = todos.id AND tags.name IN (";
+
+fn push_filter<'a>(query: &mut QueryBuilder<'a, Sqlite>, filter: &TodoFilter) {
+    push_accessible(query);
+    if let Some(done) = filter.done {
+        query.push(" AND done = ").push_bind(done);
+    }
+    if let Some(title) = &filter.title {
+        query.push(" AND title LIKE ").push_bind(contains_pattern(title)).push(" ESCAPE '\\'");
+    }
+    if let Some(at) = filter.created_after {
+        query.push(" AND created_at > ").push_bind(timestamp(at));
+    }
+    if let Some(at) = filter.created_before {
+        query.push(" AND created_at < ").push_bind(timestamp(at));
+    }
+    if let Some(at) = filter.updated_since {
+        query.push(" AND updated_at >= ").push_bind(timestamp(at));
+    }
+    if let Some(at) = filter.due_after {
+        query.push(" AND due_at >= ").push_bind(timestamp(at));
+    }
+    if let Some(at) = filter.due_before {
+        query.push(" AND due_at < ").push_bind(timestamp(at));
+    }
+    match filter.list_id {
+        Some(list_id) => {
+            query.push(" AND list_id = ").push_bind(list_id);
+        }
+        None => {
+            query.push(NOT_IN_ARCHIVED_LIST);
+        }
+    }
+    if !filter.tags.is_empty() {
+        match filter.tag_match {
+            TagMatch::Any => query.push(" AND EXISTS (SELECT 1"),
+            TagMatch::All => query.push(" AND (SELECT COUNT(DISTINCT tags.name)"),
+        };
+        query.push(TAGGED_WITH);
+        let mut names = query.separated(", ");
+        for name in &filter.tags {
+            names.push_bind(name.clone());
+        }
+        query.push("))");
+        if filter.tag_match == TagMatch::All {
+            query.push(" = ").push_bind(filter.tags.len() as i64);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoCreator for SqliteTodoRepository {
+    async fn create(&self, data: CreateTodoRequest, events: DomainEvents) -> Result<Todo, ApiError> {
+        let mut conn = self.pool.acquire().await?;
+        insert_in(&mut conn, &data, events).await
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoFinder for SqliteTodoRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Todo>, ApiError> {
+        let todo = sqlx::query_as::<_, Todo>(&format!(
+            "SELECT {SQLITE_TODO_COLUMNS} FROM todos WHERE id = ?1 AND deleted_at IS NULL AND {}",
+            accessible_to("?2", "id")
+        ))
+        .bind(id)
+        .bind(RequestContext::current_user_id())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(todo)
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoPaginator for SqliteTodoRepository {
+    async fn find_all_paginated(&self, pagination: PaginationQuery) -> Result<PaginatedResponse<Todo>, ApiError> {
+        let page = pagination.page();
+        let limit = pagination.limit();
+        let filter = pagination.filter();
+
+        let total = self.count(&filter).await?;
+
+        let mut query = QueryBuilder::new(format!("SELECT {SQLITE_TODO_COLUMNS} FROM todos WHERE deleted_at IS NULL"));
+        push_filter(&mut query, &filter);
+        query.push(order_by(&pagination.sort_keys()));
+        query.push(" LIMIT ").push_bind(limit as i64);
+        query.push(" OFFSET ").push_bind(pagination.offset() as i64);
+        let todos = query.build_query_as::<Todo>().fetch_all(&self.pool).await?;
+
+        Ok(PaginatedResponse {
+            data: todos,
+            pagination: PaginationMeta::new(page, limit, total),
+        })
+    }
+
+    async fn find_keyset(&self, query: KeysetQuery) -> Result<KeysetPage<Todo>, ApiError> {
+        let limit = query.limit.clamp(1, PaginationQuery::MAX_LIMIT);
+
+        let mut select = QueryBuilder::new(format!("SELECT {SQLITE_TODO_COLUMNS} FROM todos WHERE deleted_at IS NULL"));
+        push_filter(&mut select, &query.filter);
+        match query.start {
+            None => {
+                select.push(" ORDER BY created_at DESC, id DESC");
+            }
+            Some((KeysetDirection::After, position)) => {
+                select.push(" AND (created_at, id) < (").push_bind(timestamp(position.created_at));
+                select.push(", ").push_bind(position.id).push(")");
+                select.push(" ORDER BY created_at DESC, id DESC");
+            }
+            Some((KeysetDirection::Before, position)) => {
+                select.push(" AND (created_at, id) > (").push_bind(timestamp(position.created_at));
+                select.push(", ").push_bind(position.id).push(")");
+                select.push(" ORDER BY created_at ASC, id ASC");
+            }
+        }
+        // One extra row tells whether another page follows.
+        select.push(" LIMIT ").push_bind(limit as i64 + 1);
+        let mut todos = select.build_query_as::<Todo>().fetch_all(&self.pool).await?;
+
+        let has_more = todos.len() > limit as usize;
+        todos.truncate(limit as usize);
+        if matches!(query.start, Some((KeysetDirection::Before, _))) {
+            todos.reverse();
+        }
+
+        let total = if query.include_total { Some(self.count(&query.filter).await?) } else { None };
+
+        Ok(KeysetPage { data: todos, has_more, total })
+    }
+}
+
+// No text-search engine here: candidates containing every word are ranked
+// in memory the same way as `InMemoryTodoRepository` does.
+#[async_trait::async_trait]
+impl TodoSearcher for SqliteTodoRepository {
+    async fn search(&self, search: TodoSearch) -> Result<PaginatedResponse<TodoSearchHit>, ApiError> {
+        let mut query = QueryBuilder::new(format!("SELECT {SQLITE_TODO_COLUMNS} FROM todos WHERE deleted_at IS NULL"));
+        push_accessible(&mut query);
+        for word in search.words() {
+            query.push(" AND title LIKE ").push_bind(contains_pattern(word)).push(" ESCAPE '\\'");
+        }
+        let candidates = query.build_query_as::<Todo>().fetch_all(&self.pool).await?;
+
+        Ok(search.rank(candidates))
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoUpdater for SqliteTodoRepository {
+    async fn update(
+        &self,
+        id: Uuid,
+        data: UpdateTodoRequest,
+        expected_version: Option<i64>,
+        events: DomainEvents,
+    ) -> Result<Todo, ApiError> {
+        let mut conn = self.pool.acquire().await?;
+        update_in(&mut conn, id, data, expected_version, events).await
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoDeleter for SqliteTodoRepository {
+    async fn delete(&self, id: Uuid, expected_version: Option<i64>, events: DomainEvents) -> Result<(), ApiError> {
+        let mut conn = self.pool.acquire().await?;
+        delete_in(&mut conn, id, expected_version, events).await
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoTrash for SqliteTodoRepository {
+    async fn find_trashed(&self, pagination: PaginationQuery) -> Result<PaginatedResponse<TrashedTodo>, ApiError> {
+        let page = pagination.page();
+        let limit = pagination.limit();
+
+        let owner = RequestContext::current_user_id();
+        let total: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM todos WHERE deleted_at IS NOT NULL AND (?1 IS NULL OR owner_id = ?1)"
+        )
+        .bind(owner)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let trashed = sqlx::query_as::<_, TrashedTodo>(&format!(
+            "SELECT {SQLITE_TODO_COLUMNS}, deleted_at FROM todos WHERE deleted_at IS NOT NULL AND (?3 IS NULL OR owner_id = ?3) \
+             ORDER BY deleted_at DESC, id DESC LIMIT ?1 OFFSET ?2"
+        ))
+        .bind(limit as i64)
+        .bind(pagination.offset() as i64)
+        .bind(owner)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(PaginatedResponse {
+            data: trashed,
+            pagination: PaginationMeta::new(page, limit, total as u64),
+        })
+    }
+
+    async fn restore(&self, id: Uuid, events: DomainEvents) -> Result<Todo, ApiError> {
+        let mut tx = self.pool.begin().await?;
+        let todo = sqlx::query_as::<_, Todo>(&format!(
+            "UPDATE todos \
+             SET deleted_at = NULL, updated_at = ?1, version = version + 1 \
+             WHERE id = ?2 AND deleted_at IS NOT NULL AND (?3 IS NULL OR owner_id = ?3) \
+             RETURNING {SQLITE_TODO_COLUMNS}"
+        ))
+        .bind(timestamp(Utc::now()))
+        .bind(id)
+        .bind(RequestContext::current_user_id())
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+        record(&mut tx, NewTodoEvent::restored(&todo), events).await?;
+        tx.commit().await?;
+        Ok(todo)
+    }
+
+    async fn purge(&self, id: Uuid) -> Result<(), ApiError> {
+        let mut tx = self.pool.begin().await?;
+        let todo = sqlx::query_as::<_, Todo>(&format!(
+            "DELETE FROM todos WHERE id = ?1 AND deleted_at IS NOT NULL AND (?2 IS NULL OR owner_id = ?2) \
+             RETURNING {SQLITE_TODO_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(RequestContext::current_user_id())
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+        record(&mut tx, NewTodoEvent::purged(&todo), |_| Vec::new()).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn purge_trashed_before(&self, cutoff: DateTime<Utc>) -> Result<u64, ApiError> {
+        let mut tx = self.pool.begin().await?;
+        let purged = sqlx::query_as::<_, Todo>(&format!(
+            "DELETE FROM todos WHERE deleted_at < ?1 AND (?2 IS NULL OR owner_id = ?2) RETURNING {SQLITE_TODO_COLUMNS}"
+        ))
+        .bind(timestamp(cutoff))
+        .bind(RequestContext::current_user_id())
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for todo in &purged {
+            record(&mut tx, NewTodoEvent::purged(todo), |_| Vec::new()).await?;
+        }
+        tx.commit().await?;
+        Ok(purged.len() as u64)
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoHistory for SqliteTodoRepository {
+    async fn find_history(&self, id: Uuid, pagination: PaginationQuery) -> Result<PaginatedResponse<TodoEvent>, ApiError> {
+        let page = pagination.page();
+        let limit = pagination.limit();
+
+        let owner = RequestContext::current_user_id();
+        let total: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM todo_events WHERE todo_id = ?1 AND {}",
+            accessible_to("?2", "todo_id")
+        ))
+        .bind(id)
+        .bind(owner)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let events = sqlx::query_as::<_, TodoEvent>(&format!(
+            "SELECT {EVENT_COLUMNS} FROM todo_events WHERE todo_id = ?1 AND {} ORDER BY id LIMIT ?2 OFFSET ?3",
+            accessible_to("?4", "todo_id")
+        ))
+        .bind(id)
+        .bind(limit as i64)
+        .bind(pagination.offset() as i64)
+        .bind(owner)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(PaginatedResponse {
+            data: events,
+            pagination: PaginationMeta::new(page, limit, total as u64),
+        })
+    }
+
+    async fn last_event_id(&self) -> Result<i64, ApiError> {
+        let id: Option<i64> = sqlx::query_scalar("SELECT MAX(id) FROM todo_events").fetch_one(&self.pool).await?;
+        Ok(id.unwrap_or(0))
+    }
+
+    async fn events_since(&self, after: i64, limit: u32) -> Result<Vec<TodoEvent>, ApiError> {
+        let events = sqlx::query_as::<_, TodoEvent>(&format!(
+            "SELECT {EVENT_COLUMNS} FROM todo_events WHERE id > ?1 ORDER BY id LIMIT ?2"
+        ))
+        .bind(after)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(events)
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoOutbox for SqliteTodoRepository {
+    async fn pending_messages(&self, limit: u32, now: DateTime<Utc>) -> Result<Vec<OutboxMessage>, ApiError> {
+        let messages = sqlx::query_as::<_, OutboxMessage>(&format!(
+            "SELECT {OUTBOX_COLUMNS} FROM outbox message \
+             WHERE available_at <= ?1 AND NOT EXISTS ( \
+                 SELECT 1 FROM outbox earlier \
+                 WHERE earlier.todo_id = message.todo_id AND earlier.id < message.id AND earlier.available_at > ?1 \
+             ) \
+             ORDER BY id LIMIT ?2"
+        ))
+        .bind(timestamp(now))
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(messages)
+    }
+
+    async fn mark_published(&self, id: i64) -> Result<(), ApiError> {
+        sqlx::query("DELETE FROM outbox WHERE id = ?1").bind(id).execute(&self.pool).await?;
+        Ok(())
+    }
+
+    async fn mark_failed(&self, id: i64, error: &str, retry_at: DateTime<Utc>) -> Result<(), ApiError> {
+        sqlx::query("UPDATE outbox SET attempts = attempts + 1, last_error = ?1, available_at = ?2 WHERE id = ?3")
+            .bind(error)
+            .bind(timestamp(retry_at))
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+/// SQLite has no array parameters, so a batch is one statement per
+/// operation, in request order, inside a transaction when atomic.
+#[async_trait::async_trait]
+impl TodoRoles for SqliteTodoRepository {
+    async fn role_of(&self, id: Uuid, user_id: Uuid) -> Result<Option<TodoRole>, ApiError> {
+        let role: Option<Option<String>> = sqlx::query_scalar(
+            "SELECT CASE WHEN todos.owner_id = ?2 THEN 'owner' ELSE todo_shares.role END \
+             FROM todos LEFT JOIN todo_shares ON todo_shares.todo_id = todos.id AND todo_shares.user_id = ?2 \
+             WHERE todos.id = ?1"
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        role.flatten().map(TodoRole::try_from).transpose().map_err(|error| ApiError::from(anyhow::anyhow!(error)))
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoSharing for SqliteTodoRepository {
+    async fn find_shares(&self, id: Uuid) -> Result<Vec<TodoShare>, ApiError> {
+        let shares = sqlx::query_as::<_, TodoShare>(&format!(
+            "SELECT {SHARE_COLUMNS} FROM todo_shares WHERE todo_id = ?1 ORDER BY created_at, user_id"
+        ))
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(shares)
+    }
+
+    async fn grant(&self, id: Uuid, user_id: Uuid, role: TodoRole) -> Result<TodoShare, ApiError> {
+        let share = sqlx::query_as::<_, TodoShare>(&format!(
+            "INSERT INTO todo_shares (todo_id, user_id, role, created_at) VALUES (?1, ?2, ?3, ?4) \
+             ON CONFLICT (todo_id, user_id) DO UPDATE SET role = excluded.role \
+             RETURNING {SHARE_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(user_id)
+        .bind(role.as_str())
+        .bind(timestamp(Utc::now()))
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(share)
+    }
+
+    async fn revoke(&self, id: Uuid, user_id: Uuid) -> Result<(), ApiError> {
+        let result = sqlx::query("DELETE FROM todo_shares WHERE todo_id = ?1 AND user_id = ?2")
+            .bind(id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoTagging for SqliteTodoRepository {
+    async fn attach_tag(
+        &self,
+        id: Uuid,
+        tag_id: Uuid,
+        expected_version: Option<i64>,
+        events: DomainEvents,
+    ) -> Result<Todo, ApiError> {
+        let mut conn = self.pool.acquire().await?;
+        retag_in(&mut conn, id, tag_id, true, expected_version, events).await
+    }
+
+    async fn detach_tag(
+        &self,
+        id: Uuid,
+        tag_id: Uuid,
+        expected_version: Option<i64>,
+        events: DomainEvents,
+    ) -> Result<Todo, ApiError> {
+        let mut conn = self.pool.acquire().await?;
+        retag_in(&mut conn, id, tag_id, false, expected_version, events).await
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoBatchWriter for SqliteTodoRepository {
+    async fn apply_batch(
+        &self,
+        operations: Vec<BatchOperation>,
+        mode: BatchMode,
+        events: DomainEvents,
+    ) -> Result<Vec<Result<BatchOutcome, ApiError>>, ApiError> {
+        let results = match mode {
+            BatchMode::Atomic => {
+                let mut tx = self.pool.begin().await?;
+                let results = apply_in(&mut tx, operations, mode, events).await;
+                if results.iter().all(|result| matches!(result, Some(Ok(_)))) {
+                    tx.commit().await?;
+                }
+                results
+            }
+            BatchMode::BestEffort => {
+                let mut conn = self.pool.acquire().await?;
+                apply_in(&mut conn, operations, mode, events).await
+            }
+        };
+
+        Ok(mode.settle(results))
+    }
+}
+
+/// Appends an audit event and queues the domain events `emit` builds from
+/// it in the outbox.
+async fn record(conn: &mut SqliteConnection, event: NewTodoEvent, emit: DomainEvents) -> Result<(), ApiError> {
+    let now = timestamp(Utc::now());
+    for message in OutboxMessage::queue(&event, emit(&event)) {
+        sqlx::query(
+            r#"
+            INSERT INTO outbox (event_id, todo_id, event_type, payload, owner_id, occurred_at, available_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)
+            "#
+        )
+        .bind(message.event_id)
+        .bind(message.todo_id)
+        .bind(message.event.event_type())
+        .bind(Json(&message.event))
+        .bind(message.owner_id)
+        .bind(&now)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO todo_events (todo_id, kind, version, before, after, changes, actor, request_id, owner_id, occurred_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+        "#
+    )
+    .bind(event.todo_id)
+    .bind(event.kind.as_str())
+    .bind(event.version)
+    .bind(event.before.map(Json))
+    .bind(event.after.map(Json))
+    .bind(Json(event.changes))
+    .bind(event.actor)
+    .bind(event.request_id)
+    .bind(event.owner_id)
+    .bind(&now)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Returns a live todo once this connection holds the write lock. SQLite has
+/// no `SELECT ... FOR UPDATE`; a no-op `UPDATE` takes the lock up front
+/// instead of upgrading a read lock later, which could fail with `SQLITE_BUSY`.
+async fn lock_live(conn: &mut SqliteConnection, id: Uuid, expected_version: Option<i64>) -> Result<Todo, ApiError> {
+    let todo = sqlx::query_as::<_, Todo>(&format!(
+        "UPDATE todos SET version = version WHERE id = ?1 AND deleted_at IS NULL AND {} RETURNING {SQLITE_TODO_COLUMNS}",
+        accessible_to("?2", "id")
+    ))
+    .bind(id)
+    .bind(RequestContext::current_user_id())
+    .fetch_optional(conn)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    if expected_version.is_some_and(|version| version != todo.version) {
+        return Err(ApiError::PreconditionFailed);
+    }
+    Ok(todo)
+}
+
+// The write helpers open their own transaction, which becomes a savepoint
+// when `conn` is already inside one (as in an atomic batch).
+
+async fn insert_in(conn: &mut SqliteConnection, data: &CreateTodoRequest, events: DomainEvents) -> Result<Todo, ApiError> {
+    let now = timestamp(Utc::now());
+    let done = data.done.unwrap_or(false);
+    let mut tx = conn.begin().await?;
+    let todo = sqlx::query_as::<_, Todo>(&format!(
+        "INSERT INTO todos (id, title, description, done, completed_at, due_at, priority, \
+         created_at, updated_at, list_id, owner_id) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?8, ?9, ?10) RETURNING {SQLITE_TODO_COLUMNS}"
+    ))
+    .bind(Uuid::new_v4())
+    .bind(&data.title)
+    .bind(&data.description)
+    .bind(done)
+    .bind(done.then_some(&now))
+    .bind(data.due_at.map(timestamp))
+    .bind(data.priority.unwrap_or_default().as_str())
+    .bind(&now)
+    .bind(data.list_id)
+    .bind(RequestContext::current_user_id())
+    .fetch_one(&mut *tx)
+    .await?;
+
+    record(&mut tx, NewTodoEvent::created(&todo), events).await?;
+    tx.commit().await?;
+    Ok(todo)
+}
+
+async fn update_in(
+    conn: &mut SqliteConnection,
+    id: Uuid,
+    data: UpdateTodoRequest,
+    expected_version: Option<i64>,
+    events: DomainEvents,
+) -> Result<Todo, ApiError> {
+    let mut tx = conn.begin().await?;
+    let before = lock_live(&mut tx, id, expected_version).await?;
+
+    // Nullable fields come as a flag saying whether to set them and the
+    // value to set. `completed_at` follows `done` only when it flips.
+    let todo = sqlx::query_as::<_, Todo>(&format!(
+        "UPDATE todos \
+         SET title = COALESCE(?1, title), \
+             done = COALESCE(?2, done), \
+             completed_at = CASE WHEN ?2 IS NULL OR ?2 = done THEN completed_at WHEN ?2 THEN ?3 END, \
+             description = CASE WHEN ?4 THEN ?5 ELSE description END, \
+             due_at = CASE WHEN ?6 THEN ?7 ELSE due_at END, \
+             priority = COALESCE(?8, priority), \
+             list_id = CASE WHEN ?10 THEN ?11 ELSE list_id END, \
+             updated_at = ?3, version = version + 1 \
+         WHERE id = ?9 RETURNING {SQLITE_TODO_COLUMNS}"
+    ))
+    .bind(&data.title)
+    .bind(data.done)
+    .bind(timestamp(Utc::now()))
+    .bind(data.description.is_some())
+    .bind(data.description.flatten())
+    .bind(data.due_at.is_some())
+    .bind(data.due_at.flatten().map(timestamp))
+    .bind(data.priority.map(TodoPriority::as_str))
+    .bind(id)
+    .bind(data.list_id.is_some())
+    .bind(data.list_id.flatten())
+    .fetch_one(&mut *tx)
+    .await?;
+
+    record(&mut tx, NewTodoEvent::updated(&before, &todo), events).await?;
+    tx.commit().await?;
+    Ok(todo)
+}
+
+/// The live todos tag `tag_id` is attached to, read in the transaction
+/// that renames or deletes the tag and then calls [`record_retagged`].
+pub(super) async fn find_tagged(conn: &mut SqliteConnection, tag_id: Uuid) -> Result<Vec<Todo>, ApiError> {
+    let todos = sqlx::query_as::<_, Todo>(&format!(
+        "SELECT {SQLITE_TODO_COLUMNS} FROM todos \
+         WHERE deleted_at IS NULL AND id IN (SELECT todo_id FROM todo_tags WHERE tag_id = ?1) ORDER BY id"
+    ))
+    .bind(tag_id)
+    .fetch_all(conn)
+    .await?;
+
+    Ok(todos)
+}
+
+/// Bumps the version of each of `befores`, whose tags were just renamed or
+/// removed, and records the updates.
+pub(super) async fn record_retagged(conn: &mut SqliteConnection, befores: &[Todo], events: DomainEvents) -> Result<(), ApiError> {
+    let now = timestamp(Utc::now());
+    for before in befores {
+        let todo = sqlx::query_as::<_, Todo>(&format!(
+            "UPDATE todos SET updated_at = ?1, version = version + 1 WHERE id = ?2 RETURNING {SQLITE_TODO_COLUMNS}"
+        ))
+        .bind(&now)
+        .bind(before.id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        record(&mut *conn, NewTodoEvent::updated(before, &todo), events).await?;
+    }
+    Ok(())
+}
+
+async fn delete_in(
+    conn: &mut SqliteConnection,
+    id: Uuid,
+    expected_version: Option<i64>,
+    events: DomainEvents,
+) -> Result<(), ApiError> {
+    let mut tx = conn.begin().await?;
+    let before = lock_live(&mut tx, id, expected_version).await?;
+
+    let version: i64 = sqlx::query_scalar(
+        "UPDATE todos SET deleted_at = ?1, version = version + 1 WHERE id = ?2 RETURNING version"
+    )
+    .bind(timestamp(Utc::now()))
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    record(&mut tx, NewTodoEvent::deleted(&before, version), events).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Attaches or detaches a tag, then bumps the todo's version so the
+/// returned representation, tags included, has a new ETag.
+async fn retag_in(
+    conn: &mut SqliteConnection,
+    id: Uuid,
+    tag_id: Uuid,
+    attach: bool,
+    expected_version: Option<i64>,
+    events: DomainEvents,
+) -> Result<Todo, ApiError> {
+    let mut tx = conn.begin().await?;
+    let before = lock_live(&mut tx, id, expected_version).await?;
+
+    let now = timestamp(Utc::now());
+    let result = if attach {
+        sqlx::query("INSERT INTO todo_tags (todo_id, tag_id, created_at) VALUES (?1, ?2, ?3) ON CONFLICT DO NOTHING")
+            .bind(id)
+            .bind(tag_id)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await?
+    } else {
+        sqlx::query("DELETE FROM todo_tags WHERE todo_id = ?1 AND tag_id = ?2")
+            .bind(id)
+            .bind(tag_id)
+            .execute(&mut *tx)
+            .await?
+    };
+    if result.rows_affected() == 0 {
+        return if attach { Ok(before) } else { Err(ApiError::NotFound) };
+    }
+
+    let todo = sqlx::query_as::<_, Todo>(&format!(
+        "UPDATE todos SET updated_at = ?1, version = version + 1 WHERE id = ?2 RETURNING {SQLITE_TODO_COLUMNS}"
+    ))
+    .bind(&now)
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    record(&mut tx, NewTodoEvent::updated(&before, &todo), events).await?;
+    tx.commit().await?;
+    Ok(todo)
+}
+
+/// In atomic mode the first failure stops the batch; unattempted
+/// operations are `None`.
+async fn apply_in(
+    conn: &mut SqliteConnection,
+    operations: Vec<BatchOperation>,
+    mode: BatchMode,
+    events: DomainEvents,
+) -> Vec<Option<Result<BatchOutcome, ApiError>>> {
+    let mut results: Vec<Option<Result<BatchOutcome, ApiError>>> = operations.iter().map(|_| None).collect();
+
+    for (index, operation) in operations.into_iter().enumerate() {
+        let result = match operation {
+            BatchOperation::Create { .. } => {
+                let request = operation.create_request().unwrap_or_default();
+                insert_in(&mut *conn, &request, events).await.map(BatchOutcome::Created)
+            }
+            BatchOperation::Update { id, expected_version, .. } => {
+                let request = operation.update_request().unwrap_or_default();
+                update_in(&mut *conn, id, request, expected_version, events).await.map(BatchOutcome::Updated)
+            }
+            BatchOperation::Delete { id, expected_version } => {
+                delete_in(&mut *conn, id, expected_version, events).await.map(|()| BatchOutcome::Deleted)
+            }
+        };
+        let failed = result.is_err();
+        results[index] = Some(result);
+        if failed && mode == BatchMode::Atomic {
+            break;
+        }
+    }
+
+    results
+}
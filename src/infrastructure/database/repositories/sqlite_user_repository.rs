@@ -0,0 +1,142 @@
+use sqlx::SqlitePool;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+use crate::domain::users::{NewRefreshToken, RefreshRotation, StoredRefreshToken, User, UserCredentials};
+use crate::domain::users::traits::{RefreshTokens, UserStore};
+use crate::error::ApiError;
+use super::sql::{timestamp, REFRESH_TOKEN_COLUMNS, USER_COLUMNS};
+
+pub struct SqliteUserRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteUserRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl UserStore for SqliteUserRepository {
+    async fn create_user(&self, email: &str, password_hash: &str) -> Result<User, ApiError> {
+        let user = sqlx::query_as::<_, User>(&format!(
+            "INSERT INTO users (id, email, password_hash, created_at, updated_at) \
+             VALUES (?1, ?2, ?3, ?4, ?4) RETURNING {USER_COLUMNS}"
+        ))
+        .bind(Uuid::new_v4())
+        .bind(email)
+        .bind(password_hash)
+        .bind(timestamp(Utc::now()))
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(user)
+    }
+
+    async fn find_user(&self, id: Uuid) -> Result<Option<User>, ApiError> {
+        let user = sqlx::query_as::<_, User>(&format!("SELECT {USER_COLUMNS} FROM users WHERE id = ?1"))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(user)
+    }
+
+    async fn find_credentials(&self, email: &str) -> Result<Option<UserCredentials>, ApiError> {
+        let credentials = sqlx::query_as::<_, UserCredentials>(&format!(
+            "SELECT {USER_COLUMNS}, password_hash FROM users WHERE email = ?1"
+        ))
+        .bind(email)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(credentials)
+    }
+}
+
+#[async_trait::async_trait]
+impl RefreshTokens for SqliteUserRepository {
+    async fn store_refresh_token(&self, token: NewRefreshToken) -> Result<(), ApiError> {
+        sqlx::query(
+            "INSERT INTO refresh_tokens (id, user_id, family_id, token_hash, expires_at, created_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)"
+        )
+        .bind(Uuid::new_v4())
+        .bind(token.user_id)
+        .bind(token.family_id)
+        .bind(&token.token_hash)
+        .bind(timestamp(token.expires_at))
+        .bind(timestamp(Utc::now()))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn rotate_refresh_token(
+        &self,
+        token_hash: &str,
+        next_hash: &str,
+        expires_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<RefreshRotation, ApiError> {
+        let mut tx = self.pool.begin().await?;
+        // A no-op UPDATE takes the write lock up front, standing in for
+        // `SELECT ... FOR UPDATE`.
+        let stored = sqlx::query_as::<_, StoredRefreshToken>(&format!(
+            "UPDATE refresh_tokens SET token_hash = token_hash WHERE token_hash = ?1 RETURNING {REFRESH_TOKEN_COLUMNS}"
+        ))
+        .bind(token_hash)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(stored) = stored else { return Ok(RefreshRotation::Invalid) };
+        let rotation = stored.rotation(now);
+        match rotation {
+            RefreshRotation::Rotated { user_id } => {
+                sqlx::query("UPDATE refresh_tokens SET replaced_at = ?1 WHERE token_hash = ?2")
+                    .bind(timestamp(now))
+                    .bind(token_hash)
+                    .execute(&mut *tx)
+                    .await?;
+                sqlx::query(
+                    "INSERT INTO refresh_tokens (id, user_id, family_id, token_hash, expires_at, created_at) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)"
+                )
+                .bind(Uuid::new_v4())
+                .bind(user_id)
+                .bind(stored.family_id)
+                .bind(next_hash)
+                .bind(timestamp(expires_at))
+                .bind(timestamp(now))
+                .execute(&mut *tx)
+                .await?;
+            }
+            RefreshRotation::Reused => {
+                sqlx::query("UPDATE refresh_tokens SET revoked_at = ?1 WHERE family_id = ?2 AND revoked_at IS NULL")
+                    .bind(timestamp(now))
+                    .bind(stored.family_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            RefreshRotation::Invalid => {}
+        }
+
+        tx.commit().await?;
+        Ok(rotation)
+    }
+
+    async fn revoke_refresh_family(&self, token_hash: &str, now: DateTime<Utc>) -> Result<(), ApiError> {
+        sqlx::query(
+            "UPDATE refresh_tokens SET revoked_at = ?1 \
+             WHERE revoked_at IS NULL AND family_id = (SELECT family_id FROM refresh_tokens WHERE token_hash = ?2)"
+        )
+        .bind(timestamp(now))
+        .bind(token_hash)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,250 @@
+use sqlx::{types::Json, SqlitePool};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+use crate::domain::todos::{OutboxMessage, PaginatedResponse, PaginationMeta, PaginationQuery};
+use crate::domain::webhooks::{
+    CreateWebhookRequest, DeliveryOutcome, DeliveryStatus, UpdateWebhookRequest, Webhook, WebhookDelivery,
+};
+use crate::domain::webhooks::traits::{WebhookDeliveries, WebhookStore};
+use crate::error::ApiError;
+use crate::request_context::RequestContext;
+use super::sql::{timestamp, DELIVERY_COLUMNS, WEBHOOK_COLUMNS};
+
+pub struct SqliteWebhookRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteWebhookRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl WebhookStore for SqliteWebhookRepository {
+    async fn create(&self, data: CreateWebhookRequest) -> Result<Webhook, ApiError> {
+        let now = timestamp(Utc::now());
+        let webhook = sqlx::query_as::<_, Webhook>(&format!(
+            "INSERT INTO webhooks (id, url, events, secret, owner_id, created_at, updated_at) \
+             VALUES (?1, ?2, ?3, ?4, ?6, ?5, ?5) RETURNING {WEBHOOK_COLUMNS}"
+        ))
+        .bind(Uuid::new_v4())
+        .bind(&data.url)
+        .bind(Json(&data.events))
+        .bind(&data.secret)
+        .bind(now)
+        .bind(RequestContext::current_user_id())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(webhook)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Webhook>, ApiError> {
+        let webhook = sqlx::query_as::<_, Webhook>(&format!(
+            "SELECT {WEBHOOK_COLUMNS} FROM webhooks WHERE id = ?1 AND (?2 IS NULL OR owner_id = ?2)"
+        ))
+        .bind(id)
+        .bind(RequestContext::current_user_id())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(webhook)
+    }
+
+    async fn find_all_paginated(&self, pagination: PaginationQuery) -> Result<PaginatedResponse<Webhook>, ApiError> {
+        let page = pagination.page();
+        let limit = pagination.limit();
+
+        let owner = RequestContext::current_user_id();
+        let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM webhooks WHERE (?1 IS NULL OR owner_id = ?1)")
+            .bind(owner)
+            .fetch_one(&self.pool)
+            .await?;
+
+        let webhooks = sqlx::query_as::<_, Webhook>(&format!(
+            "SELECT {WEBHOOK_COLUMNS} FROM webhooks WHERE (?3 IS NULL OR owner_id = ?3) ORDER BY created_at, id LIMIT ?1 OFFSET ?2"
+        ))
+        .bind(limit as i64)
+        .bind(pagination.offset() as i64)
+        .bind(owner)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(PaginatedResponse {
+            data: webhooks,
+            pagination: PaginationMeta::new(page, limit, total as u64),
+        })
+    }
+
+    async fn update(&self, id: Uuid, data: UpdateWebhookRequest) -> Result<Webhook, ApiError> {
+        let webhook = sqlx::query_as::<_, Webhook>(&format!(
+            r#"
+            UPDATE webhooks
+            SET url = COALESCE(?1, url),
+                events = COALESCE(?2, events),
+                secret = COALESCE(?3, secret),
+                active = COALESCE(?4, active),
+                consecutive_failures = CASE WHEN ?4 THEN 0 ELSE consecutive_failures END,
+                updated_at = ?5
+            WHERE id = ?6 AND (?7 IS NULL OR owner_id = ?7)
+            RETURNING {WEBHOOK_COLUMNS}
+            "#
+        ))
+        .bind(&data.url)
+        .bind(data.events.as_ref().map(Json))
+        .bind(&data.secret)
+        .bind(data.active)
+        .bind(timestamp(Utc::now()))
+        .bind(id)
+        .bind(RequestContext::current_user_id())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        webhook.ok_or(ApiError::NotFound)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), ApiError> {
+        let result = sqlx::query("DELETE FROM webhooks WHERE id = ?1 AND (?2 IS NULL OR owner_id = ?2)")
+            .bind(id)
+            .bind(RequestContext::current_user_id())
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound);
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl WebhookDeliveries for SqliteWebhookRepository {
+    async fn enqueue(&self, message: &OutboxMessage) -> Result<u64, ApiError> {
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO webhook_deliveries (webhook_id, event_id, event_type, payload, next_attempt_at, created_at)
+            SELECT id, ?1, ?2, ?3, ?4, ?4
+            FROM webhooks
+            WHERE active AND owner_id IS ?5 AND (
+                json_array_length(events) = 0
+                OR EXISTS (SELECT 1 FROM json_each(webhooks.events) WHERE json_each.value = ?2)
+            )
+            "#
+        )
+        .bind(message.event_id)
+        .bind(message.event.event_type())
+        .bind(Json(message))
+        .bind(timestamp(Utc::now()))
+        .bind(message.owner_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn due_deliveries(&self, limit: u32, now: DateTime<Utc>) -> Result<Vec<WebhookDelivery>, ApiError> {
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(&format!(
+            "SELECT {DELIVERY_COLUMNS} FROM webhook_deliveries \
+             WHERE status = 'pending' AND next_attempt_at <= ?1 \
+               AND webhook_id IN (SELECT id FROM webhooks WHERE active) \
+             ORDER BY next_attempt_at, id LIMIT ?2"
+        ))
+        .bind(timestamp(now))
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    async fn record_attempt(&self, id: i64, outcome: DeliveryOutcome, disable_after: i32) -> Result<(), ApiError> {
+        let now = timestamp(Utc::now());
+        let mut tx = self.pool.begin().await?;
+
+        let webhook_id: Option<Uuid> = match &outcome {
+            DeliveryOutcome::Succeeded { response_status } => {
+                sqlx::query_scalar(
+                    r#"
+                    UPDATE webhook_deliveries
+                    SET status = ?1, attempts = attempts + 1, next_attempt_at = NULL,
+                        response_status = ?2, last_error = NULL, delivered_at = ?3
+                    WHERE id = ?4
+                    RETURNING webhook_id
+                    "#
+                )
+                .bind(DeliveryStatus::Succeeded.as_str())
+                .bind(i32::from(*response_status))
+                .bind(now)
+                .bind(id)
+                .fetch_optional(&mut *tx)
+                .await?
+            }
+            DeliveryOutcome::Failed { response_status, error, retry_at } => {
+                let status = if retry_at.is_some() { DeliveryStatus::Pending } else { DeliveryStatus::Failed };
+                sqlx::query_scalar(
+                    r#"
+                    UPDATE webhook_deliveries
+                    SET status = ?1, attempts = attempts + 1, next_attempt_at = ?2,
+                        response_status = ?3, last_error = ?4
+                    WHERE id = ?5
+                    RETURNING webhook_id
+                    "#
+                )
+                .bind(status.as_str())
+                .bind(retry_at.map(timestamp))
+                .bind(response_status.map(i32::from))
+                .bind(error)
+                .bind(id)
+                .fetch_optional(&mut *tx)
+                .await?
+            }
+        };
+
+        // The webhook was deleted while the attempt was in flight.
+        let Some(webhook_id) = webhook_id else { return Ok(()) };
+
+        let query = match outcome {
+            DeliveryOutcome::Succeeded { .. } => sqlx::query("UPDATE webhooks SET consecutive_failures = 0 WHERE id = ?1"),
+            DeliveryOutcome::Failed { .. } => sqlx::query(
+                "UPDATE webhooks SET consecutive_failures = consecutive_failures + 1, \
+                 active = active AND consecutive_failures + 1 < ?2 WHERE id = ?1",
+            ),
+        };
+        query.bind(webhook_id).bind(disable_after).execute(&mut *tx).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn find_deliveries(
+        &self,
+        webhook_id: Uuid,
+        pagination: PaginationQuery,
+    ) -> Result<PaginatedResponse<WebhookDelivery>, ApiError> {
+        let page = pagination.page();
+        let limit = pagination.limit();
+
+        let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM webhook_deliveries WHERE webhook_id = ?1")
+            .bind(webhook_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(&format!(
+            "SELECT {DELIVERY_COLUMNS} FROM webhook_deliveries WHERE webhook_id = ?1 \
+             ORDER BY id DESC LIMIT ?2 OFFSET ?3"
+        ))
+        .bind(webhook_id)
+        .bind(limit as i64)
+        .bind(pagination.offset() as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(PaginatedResponse {
+            data: deliveries,
+            pagination: PaginationMeta::new(page, limit, total as u64),
+        })
+    }
+}
@@ -0,0 +1,119 @@
+//! Built-in [`EventPublisher`]s: JSON lines for local testing, and the
+//! fan-out to webhook subscriptions.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use tokio::fs::{File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+use crate::config::{OutboxConfig, PublisherKind};
+use crate::domain::todos::OutboxMessage;
+use crate::domain::todos::traits::EventPublisher;
+use crate::domain::webhooks::traits::WebhookRepository;
+
+fn json_line(message: &OutboxMessage) -> anyhow::Result<Vec<u8>> {
+    let mut line = serde_json::to_vec(message)?;
+    line.push(b'\n');
+    Ok(line)
+}
+
+pub struct StdoutPublisher;
+
+#[async_trait::async_trait]
+impl EventPublisher for StdoutPublisher {
+    async fn publish(&self, message: &OutboxMessage) -> anyhow::Result<()> {
+        let mut stdout = tokio::io::stdout();
+        stdout.write_all(&json_line(message)?).await?;
+        stdout.flush().await?;
+        Ok(())
+    }
+}
+
+/// Appends to a file, creating it if needed.
+pub struct FilePublisher {
+    file: Mutex<File>,
+}
+
+impl FilePublisher {
+    pub async fn open(path: &Path) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path).await?;
+        Ok(Self { file: Mutex::new(file) })
+    }
+}
+
+#[async_trait::async_trait]
+impl EventPublisher for FilePublisher {
+    async fn publish(&self, message: &OutboxMessage) -> anyhow::Result<()> {
+        let mut file = self.file.lock().await;
+        file.write_all(&json_line(message)?).await?;
+        file.flush().await?;
+        Ok(())
+    }
+}
+
+/// Queues a webhook delivery per subscribed webhook; the dispatcher sends
+/// them.
+pub struct WebhookFanout {
+    webhook_repository: Arc<dyn WebhookRepository>,
+}
+
+impl WebhookFanout {
+    pub fn new(webhook_repository: Arc<dyn WebhookRepository>) -> Self {
+        Self { webhook_repository }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventPublisher for WebhookFanout {
+    async fn publish(&self, message: &OutboxMessage) -> anyhow::Result<()> {
+        self.webhook_repository.enqueue(message).await?;
+        Ok(())
+    }
+}
+
+/// Publishes every message to each publisher in turn. A failure fails the
+/// whole message, so publishers before it may see the retry again.
+pub struct FanoutPublisher {
+    publishers: Vec<Arc<dyn EventPublisher>>,
+}
+
+impl FanoutPublisher {
+    pub fn new(publishers: Vec<Arc<dyn EventPublisher>>) -> Self {
+        Self { publishers }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventPublisher for FanoutPublisher {
+    async fn publish(&self, message: &OutboxMessage) -> anyhow::Result<()> {
+        for publisher in &self.publishers {
+            publisher.publish(message).await?;
+        }
+        Ok(())
+    }
+}
+
+/// The publisher selected by `outbox.publisher`, followed by the webhook
+/// fan-out when `webhooks` is given. `None` disables the relay.
+pub async fn from_config(
+    config: &OutboxConfig,
+    webhooks: Option<Arc<dyn WebhookRepository>>,
+) -> anyhow::Result<Option<Arc<dyn EventPublisher>>> {
+    let mut publishers: Vec<Arc<dyn EventPublisher>> = Vec::new();
+    match config.publisher {
+        PublisherKind::None => {}
+        PublisherKind::Stdout => publishers.push(Arc::new(StdoutPublisher)),
+        PublisherKind::File => publishers.push(Arc::new(FilePublisher::open(&config.file_path).await?)),
+    }
+    if let Some(webhook_repository) = webhooks {
+        publishers.push(Arc::new(WebhookFanout::new(webhook_repository)));
+    }
+
+    Ok(match publishers.len() {
+        0 => None,
+        1 => publishers.pop(),
+        _ => Some(Arc::new(FanoutPublisher::new(publishers))),
+    })
+}
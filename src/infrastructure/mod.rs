@@ -1,2 +1,8 @@
+pub mod change_feed;
 pub mod database;
-
+pub mod event_publishers;
+pub mod outbox_relay;
+pub mod password_hasher;
+pub mod trash_purger;
+pub mod webhook_dispatcher;
+pub mod webhook_sender;
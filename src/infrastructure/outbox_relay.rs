@@ -0,0 +1,45 @@
+//! Background task that drains the outbox into the configured
+//! [`EventPublisher`].
+
+use std::sync::Arc;
+
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::application::todos::RelayOutboxUseCase;
+use crate::config::OutboxConfig;
+use crate::domain::todos::traits::{EventPublisher, TodoRepository};
+use crate::request_context::RequestContext;
+
+/// Starts the relay loop. Every `outbox.poll_interval_ms` it publishes due
+/// messages, batch after batch, until the outbox has no full batch left.
+/// The relay runs in [`RequestContext::all_tenants`], as it serves them all.
+pub fn spawn(
+    todo_repository: Arc<dyn TodoRepository>,
+    publisher: Arc<dyn EventPublisher>,
+    config: &OutboxConfig,
+) -> JoinHandle<()> {
+    let period = config.poll_interval();
+    let batch_size = config.batch_size;
+    let retry = config.retry_policy();
+
+    tokio::spawn(RequestContext::all_tenants().scope(async move {
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+            let use_case = RelayOutboxUseCase::new(&*todo_repository, &*publisher);
+            loop {
+                match use_case.execute(batch_size, retry).await {
+                    Ok(report) if report.fetched == batch_size as usize && report.published > 0 => {}
+                    Ok(_) => break,
+                    Err(error) => {
+                        tracing::error!(code = error.code(), error = %error, "outbox relay failed");
+                        break;
+                    }
+                }
+            }
+        }
+    }))
+}
@@ -0,0 +1,59 @@
+use std::sync::OnceLock;
+
+use argon2::password_hash::{PasswordHash, PasswordHasher as _, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use rand::RngCore;
+
+use crate::domain::users::traits::PasswordHasher;
+use crate::error::ApiError;
+
+/// Argon2id with the crate's default (OWASP-recommended) parameters. Work
+/// runs on the blocking pool so a login does not stall other requests.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Argon2PasswordHasher;
+
+impl Argon2PasswordHasher {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+fn hash_blocking(password: &str) -> Result<String, ApiError> {
+    let mut salt = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let salt = SaltString::encode_b64(&salt).map_err(|error| anyhow::anyhow!("argon2 salt: {error}"))?;
+    let hash = Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map_err(|error| anyhow::anyhow!("argon2 hash: {error}"))?;
+    Ok(hash.to_string())
+}
+
+/// Checked against when an email has no account, so that the miss costs as
+/// much as a wrong password.
+fn dummy_hash() -> &'static str {
+    static DUMMY: OnceLock<String> = OnceLock::new();
+    DUMMY.get_or_init(|| hash_blocking("not a password").expect("hashing a constant succeeds"))
+}
+
+#[async_trait::async_trait]
+impl PasswordHasher for Argon2PasswordHasher {
+    async fn hash(&self, password: &str) -> Result<String, ApiError> {
+        let password = password.to_string();
+        tokio::task::spawn_blocking(move || hash_blocking(&password))
+            .await
+            .map_err(anyhow::Error::from)?
+    }
+
+    async fn verify(&self, password: &str, hash: Option<&str>) -> Result<bool, ApiError> {
+        let password = password.to_string();
+        let hash = hash.map(str::to_string);
+        tokio::task::spawn_blocking(move || {
+            let known = hash.is_some();
+            let hash = hash.as_deref().unwrap_or_else(|| dummy_hash());
+            let Ok(parsed) = PasswordHash::new(hash) else { return false };
+            Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok() && known
+        })
+        .await
+        .map_err(|error| anyhow::Error::from(error).into())
+    }
+}
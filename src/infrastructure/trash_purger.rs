@@ -0,0 +1,34 @@
+//! Background task that permanently removes todos whose time in the trash
+//! exceeds `trash.retention_days`.
+
+use std::sync::Arc;
+
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::application::todos::PurgeExpiredTrashUseCase;
+use crate::config::TrashConfig;
+use crate::domain::todos::traits::TodoRepository;
+use crate::request_context::RequestContext;
+
+/// Starts the purge loop, running once immediately and then every
+/// `trash.purge_interval_secs`, over the trash of every tenant. Returns
+/// `None` when retention is disabled.
+pub fn spawn(todo_repository: Arc<dyn TodoRepository>, config: &TrashConfig) -> Option<JoinHandle<()>> {
+    let retention = config.retention()?;
+    let period = config.purge_interval();
+
+    Some(tokio::spawn(RequestContext::all_tenants().scope(async move {
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+            match PurgeExpiredTrashUseCase::new(&*todo_repository).execute(retention).await {
+                Ok(0) => {}
+                Ok(purged) => tracing::info!(purged, "purged expired todos from the trash"),
+                Err(error) => tracing::error!(code = error.code(), error = %error, "trash purge failed"),
+            }
+        }
+    })))
+}
@@ -0,0 +1,44 @@
+//! Background task that sends due webhook deliveries.
+
+use std::sync::Arc;
+
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::application::webhooks::DeliverWebhooksUseCase;
+use crate::config::WebhooksConfig;
+use crate::domain::webhooks::traits::{WebhookRepository, WebhookSender};
+use crate::request_context::RequestContext;
+
+/// Starts the dispatcher loop. Every `webhooks.poll_interval_ms` it attempts
+/// due deliveries, batch after batch, until no full batch is left. Every
+/// tenant's deliveries are sent, in [`RequestContext::all_tenants`].
+pub fn spawn(
+    webhook_repository: Arc<dyn WebhookRepository>,
+    sender: Arc<dyn WebhookSender>,
+    config: &WebhooksConfig,
+) -> JoinHandle<()> {
+    let period = config.poll_interval();
+    let batch_size = config.batch_size;
+    let policy = config.delivery_policy();
+
+    tokio::spawn(RequestContext::all_tenants().scope(async move {
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+            let use_case = DeliverWebhooksUseCase::new(&*webhook_repository, &*sender);
+            loop {
+                match use_case.execute(batch_size, policy).await {
+                    Ok(report) if report.fetched == batch_size as usize && report.succeeded > 0 => {}
+                    Ok(_) => break,
+                    Err(error) => {
+                        tracing::error!(code = error.code(), error = %error, "webhook dispatch failed");
+                        break;
+                    }
+                }
+            }
+        }
+    }))
+}
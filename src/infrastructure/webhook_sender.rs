@@ -0,0 +1,72 @@
+//! [`WebhookSender`] over HTTP.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+
+use crate::domain::webhooks::traits::WebhookSender;
+use crate::domain::webhooks::{TargetPolicy, WebhookRequest};
+
+/// Posts deliveries with `reqwest`. Redirects are not followed: a receiver
+/// that moved must be updated through the API.
+///
+/// URLs were checked against the `TargetPolicy` when they were registered,
+/// but a name may resolve elsewhere by the time a delivery is sent, so the
+/// addresses are checked again after resolution, right before connecting.
+pub struct HttpWebhookSender {
+    client: reqwest::Client,
+    targets: TargetPolicy,
+}
+
+impl HttpWebhookSender {
+    pub fn new(timeout: Duration, targets: TargetPolicy) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .redirect(reqwest::redirect::Policy::none())
+            .dns_resolver(Arc::new(CheckedResolver { targets }))
+            .user_agent(concat!("axum-api-webhooks/", env!("CARGO_PKG_VERSION")))
+            .build()?;
+        Ok(Self { client, targets })
+    }
+}
+
+#[async_trait::async_trait]
+impl WebhookSender for HttpWebhookSender {
+    async fn send(&self, request: WebhookRequest) -> anyhow::Result<u16> {
+        // Addresses in the URL skip the resolver.
+        let url = reqwest::Url::parse(&request.url)?;
+        if !url.host().is_some_and(|host| self.targets.permits_host(&host)) {
+            anyhow::bail!("{} is not an address webhooks may reach", url.host_str().unwrap_or_default());
+        }
+
+        let mut builder = self.client.post(url).body(request.body);
+        for (name, value) in request.headers {
+            builder = builder.header(name, value);
+        }
+        let response = builder.send().await?;
+        Ok(response.status().as_u16())
+    }
+}
+
+/// Resolves with the system resolver and keeps only the addresses the
+/// policy permits.
+struct CheckedResolver {
+    targets: TargetPolicy,
+}
+
+impl Resolve for CheckedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let targets = self.targets;
+        Box::pin(async move {
+            let host = name.as_str();
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((host, 0)).await?.filter(|addr| targets.permits(addr.ip())).collect();
+            if addrs.is_empty() {
+                return Err(format!("{host} resolves to no address webhooks may reach").into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
@@ -1,6 +1,8 @@
 pub mod app;
+pub mod config;
 pub mod state;
 pub mod error;
+pub mod request_context;
 pub mod doc;
 pub mod domain;
 pub mod application;
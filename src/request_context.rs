@@ -0,0 +1,141 @@
+//! Who is making the current request, for layers that cannot see it, such
+//! as repositories recording the audit log.
+
+use std::future::Future;
+
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use uuid::Uuid;
+
+use crate::error::ApiError;
+
+pub static REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest client-supplied request id that is kept; longer ones are replaced.
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+/// The tenant of every request while multi-tenancy is disabled.
+pub const DEFAULT_TENANT: &str = "default";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RequestContext {
+    pub request_id: Option<String>,
+    /// The authenticated caller as recorded in the audit log; `None` for
+    /// anonymous requests and background tasks.
+    pub actor: Option<String>,
+    /// The authenticated user. Repositories only let a request see the todos
+    /// this user owns; `None`, as in background tasks, means every todo.
+    pub user_id: Option<Uuid>,
+    /// The organisation the request acts in. Postgres row-level security
+    /// hides every other tenant's rows. Repositories refuse to run without
+    /// one unless `all_tenants` is set.
+    pub tenant_id: Option<String>,
+    /// Set only by [`RequestContext::all_tenants`], for background tasks
+    /// that work across every tenant.
+    pub all_tenants: bool,
+}
+
+/// Which tenants' rows the current task may reach.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TenantScope {
+    Tenant(String),
+    AllTenants,
+}
+
+impl TenantScope {
+    pub fn includes(&self, tenant_id: &str) -> bool {
+        match self {
+            TenantScope::Tenant(tenant) => tenant == tenant_id,
+            TenantScope::AllTenants => true,
+        }
+    }
+}
+
+tokio::task_local! {
+    static CURRENT: RequestContext;
+}
+
+impl RequestContext {
+    /// The context of the request being served, or an empty one outside a
+    /// request.
+    pub fn current() -> Self {
+        CURRENT.try_with(Clone::clone).unwrap_or_default()
+    }
+
+    /// The user the current request acts for, without cloning the context.
+    pub fn current_user_id() -> Option<Uuid> {
+        CURRENT.try_with(|context| context.user_id).ok().flatten()
+    }
+
+    /// The tenant the current request acts in, without cloning the context.
+    pub fn current_tenant_id() -> Option<String> {
+        CURRENT.try_with(|context| context.tenant_id.clone()).ok().flatten()
+    }
+
+    /// The tenants the current task may reach. A context that names no
+    /// tenant and was not made by [`RequestContext::all_tenants`] is a bug,
+    /// reported as an internal error rather than widened to every tenant.
+    pub fn tenant_scope() -> Result<TenantScope, ApiError> {
+        let (tenant_id, all_tenants) = CURRENT
+            .try_with(|context| (context.tenant_id.clone(), context.all_tenants))
+            .unwrap_or_default();
+        match (tenant_id, all_tenants) {
+            (Some(tenant_id), _) => Ok(TenantScope::Tenant(tenant_id)),
+            (None, true) => Ok(TenantScope::AllTenants),
+            (None, false) => Err(anyhow::anyhow!("no tenant in the request context").into()),
+        }
+    }
+
+    /// The tenant new rows belong to. Writing without one is an internal
+    /// error, even for background tasks across all tenants.
+    pub fn writing_tenant_id() -> Result<String, ApiError> {
+        Self::current_tenant_id().ok_or_else(|| anyhow::anyhow!("no tenant to write rows in").into())
+    }
+
+    /// The current context acting for `user_id`.
+    pub fn for_user(user_id: Uuid) -> Self {
+        Self { actor: Some(user_id.to_string()), user_id: Some(user_id), ..Self::current() }
+    }
+
+    /// The current context acting in `tenant_id`.
+    pub fn for_tenant(tenant_id: &str) -> Self {
+        Self { tenant_id: Some(tenant_id.to_string()), ..Self::current() }
+    }
+
+    /// The context of a background task that reads and changes the rows of
+    /// every tenant, such as the outbox relay or the trash purge. Nothing
+    /// serving a request may use it.
+    pub fn all_tenants() -> Self {
+        Self { all_tenants: true, ..Self::default() }
+    }
+
+    /// Runs `future` with `self` as the current context.
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        CURRENT.scope(self, future).await
+    }
+}
+
+/// Middleware that adopts the caller's `X-Request-Id` (or mints one), echoes
+/// it on the response, and makes the context current for the rest of the
+/// request.
+pub async fn request_context(request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| !id.is_empty() && id.len() <= MAX_REQUEST_ID_LENGTH)
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let context = RequestContext { request_id: Some(request_id.clone()), ..RequestContext::default() };
+
+    let mut response = context.scope(next.run(request)).await;
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER.clone(), value);
+    }
+    response
+}
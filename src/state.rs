@@ -1,17 +1,58 @@
 use std::sync::Arc;
-use sqlx::PgPool;
 
-use crate::infrastructure::database::repositories::PostgresTodoRepository;
+use crate::api::auth::AccessTokens;
+use crate::api::cursor::CursorCodec;
+use crate::config::Config;
+use crate::domain::api_keys::traits::ApiKeyRepository;
+use crate::domain::lists::traits::TodoListRepository;
+use crate::domain::tags::traits::TagRepository;
+use crate::domain::todos::traits::TodoRepository;
+use crate::domain::users::traits::{PasswordHasher, UserRepository};
+use crate::domain::webhooks::traits::WebhookRepository;
+use crate::infrastructure::change_feed::ChangeFeed;
+use crate::infrastructure::database::Repositories;
+use crate::infrastructure::password_hasher::Argon2PasswordHasher;
 
 #[derive(Clone)]
 pub struct AppState {
-    pub todo_repository: Arc<PostgresTodoRepository>,
+    pub todo_repository: Arc<dyn TodoRepository>,
+    pub list_repository: Arc<dyn TodoListRepository>,
+    pub tag_repository: Arc<dyn TagRepository>,
+    pub webhook_repository: Arc<dyn WebhookRepository>,
+    pub user_repository: Arc<dyn UserRepository>,
+    pub api_key_repository: Arc<dyn ApiKeyRepository>,
+    pub password_hasher: Arc<dyn PasswordHasher>,
+    pub config: Arc<Config>,
+    pub cursor_codec: CursorCodec,
+    pub access_tokens: AccessTokens,
+    /// Live todo changes for `GET /todos/events`
+    pub change_feed: Arc<ChangeFeed>,
 }
 
 impl AppState {
-    pub fn new(pool: PgPool) -> Self {
+    pub fn new(repositories: Repositories, config: &Config) -> Self {
+        let cursor_codec = match &config.pagination.cursor_secret {
+            Some(secret) => CursorCodec::new(secret.as_bytes()),
+            None => CursorCodec::random(),
+        };
+
+        let access_tokens = match &config.auth.jwt_secret {
+            Some(secret) => AccessTokens::new(secret.as_bytes(), config.auth.access_token_ttl()),
+            None => AccessTokens::random(config.auth.access_token_ttl()),
+        };
+
         Self {
-            todo_repository: Arc::new(PostgresTodoRepository::new(pool)),
+            todo_repository: repositories.todos,
+            list_repository: repositories.lists,
+            tag_repository: repositories.tags,
+            webhook_repository: repositories.webhooks,
+            user_repository: repositories.users,
+            api_key_repository: repositories.api_keys,
+            password_hasher: Arc::new(Argon2PasswordHasher::new()),
+            config: Arc::new(config.clone()),
+            cursor_codec,
+            access_tokens,
+            change_feed: Arc::new(ChangeFeed::new(config.changes.replay_buffer)),
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,35 @@
+use axum_api::{
+    api::cursor::CursorCodec,
+    domain::todos::{KeysetDirection, KeysetPosition},
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+fn position() -> KeysetPosition {
+    KeysetPosition { created_at: Utc::now(), id: Uuid::new_v4() }
+}
+
+#[test]
+fn test_cursor_round_trips() {
+    let codec = CursorCodec::new(b"0123456789abcdef0123456789abcdef");
+    let position = position();
+
+    let cursor = codec.encode(KeysetDirection::Before, position);
+    assert_eq!(codec.decode(&cursor).unwrap(), (KeysetDirection::Before, position));
+}
+
+#[test]
+fn test_tampered_cursor_is_rejected() {
+    let codec = CursorCodec::new(b"0123456789abcdef0123456789abcdef");
+    let cursor = codec.encode(KeysetDirection::After, position());
+    let (payload, tag) = cursor.split_once('.').unwrap();
+
+    let forged = codec.encode(KeysetDirection::After, position());
+    let (other_payload, _) = forged.split_once('.').unwrap();
+    assert!(codec.decode(&format!("{other_payload}.{tag}")).is_err());
+    assert!(codec.decode(payload).is_err());
+    assert!(codec.decode("not a cursor").is_err());
+
+    let other_key = CursorCodec::new(b"another-secret-another-secret-!!");
+    assert!(other_key.decode(&cursor).is_err());
+}
@@ -0,0 +1,123 @@
+use axum::{http::StatusCode, Router};
+use chrono::{Duration, Utc};
+use serde_json::{json, Value};
+
+use crate::support::{send_with, test_app, TEST_USER};
+
+/// Sends a request as `TEST_USER`, or with `Authorization: ApiKey <key>`.
+async fn send(app: &Router, method: &str, uri: &str, key: Option<&str>, body: Option<Value>) -> (StatusCode, Value) {
+    let authorization = key.map(|key| format!("ApiKey {key}"));
+    let headers: Vec<_> = authorization.iter().map(|value| ("authorization", value.as_str())).collect();
+    let (status, _, json) = send_with(app, method, uri, &headers, body).await;
+    (status, json)
+}
+
+async fn create_key(app: &Router, scope: &str) -> Value {
+    let (status, created) = send(app, "POST", "/api-keys", None, Some(json!({ "name": "ci", "scope": scope }))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    created
+}
+
+#[tokio::test]
+async fn test_key_is_shown_once() {
+    let app = test_app();
+    let created = create_key(&app, "read_write").await;
+    let key = created["key"].as_str().unwrap();
+    let api_key = &created["api_key"];
+    assert!(key.starts_with(api_key["prefix"].as_str().unwrap()));
+    assert!(key.starts_with("ak_"));
+    assert_eq!(api_key["scope"], "read_write");
+    assert_eq!(api_key["user_id"], TEST_USER.to_string());
+    assert_eq!(api_key["last_used_at"], Value::Null);
+
+    let (status, page) = send(&app, "GET", "/api-keys", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["pagination"]["total"], 1);
+    assert!(!page.to_string().contains(key));
+    assert!(page["data"][0].get("key_hash").is_none());
+
+    let (status, _) = send(&app, "POST", "/api-keys", None, Some(json!({ "name": " " }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let yesterday = Utc::now() - Duration::days(1);
+    let (status, error) = send(&app, "POST", "/api-keys", None, Some(json!({ "name": "old", "expires_at": yesterday }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(error["errors"][0]["field"], "expires_at");
+}
+
+#[tokio::test]
+async fn test_keys_authenticate_and_record_their_use() {
+    let app = test_app();
+    let created = create_key(&app, "read_write").await;
+    let key = created["key"].as_str().unwrap();
+
+    let (status, todo) = send(&app, "POST", "/todos", Some(key), Some(json!({ "title": "From CI" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    // The key acts as its user.
+    let (status, _) = send(&app, "GET", &format!("/todos/{}", todo["id"].as_str().unwrap()), None, None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let uri = format!("/api-keys/{}", created["api_key"]["id"].as_str().unwrap());
+    let (_, api_key) = send(&app, "GET", &uri, None, None).await;
+    assert!(api_key["last_used_at"].is_string());
+
+    let (status, error) = send(&app, "GET", "/todos", Some("ak_00000000_not-a-real-key"), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(error["detail"], "The API key is invalid, expired or revoked.");
+    let (status, _) = send(&app, "GET", "/todos", Some("garbage"), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_scopes_limit_what_a_key_may_do() {
+    let app = test_app();
+    let read_only = create_key(&app, "read_only").await;
+    let read_only = read_only["key"].as_str();
+    let read_write = create_key(&app, "read_write").await;
+    let read_write = read_write["key"].as_str();
+    let admin = create_key(&app, "admin").await;
+    let admin = admin["key"].as_str();
+
+    let (status, _) = send(&app, "GET", "/todos", read_only, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, error) = send(&app, "POST", "/todos", read_only, Some(json!({ "title": "Nope" }))).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(error["code"], "forbidden");
+    let (status, _) = send(&app, "POST", "/webhooks", read_only, Some(json!({
+        "url": "https://example.com/hooks",
+        "secret": "0123456789abcdef"
+    }))).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = send(&app, "POST", "/todos", read_write, Some(json!({ "title": "Yes" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    for key in [read_only, read_write] {
+        let (status, _) = send(&app, "GET", "/api-keys", key, None).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, _) = send(&app, "POST", "/api-keys", key, Some(json!({ "name": "escalate", "scope": "admin" }))).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    let (status, page) = send(&app, "GET", "/api-keys", admin, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["pagination"]["total"], 3);
+}
+
+#[tokio::test]
+async fn test_revoked_keys_stop_working() {
+    let app = test_app();
+    let created = create_key(&app, "read_only").await;
+    let key = created["key"].as_str();
+    let uri = format!("/api-keys/{}", created["api_key"]["id"].as_str().unwrap());
+
+    let (status, _) = send(&app, "DELETE", &uri, None, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = send(&app, "GET", "/todos", key, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, api_key) = send(&app, "GET", &uri, None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(api_key["revoked_at"].is_string());
+
+    let (status, _) = send(&app, "DELETE", "/api-keys/00000000-0000-0000-0000-000000000000", None, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,217 @@
+use axum::{body::Body, http::{HeaderMap, Request, StatusCode}, Router};
+use axum_api::{
+    app::build_app,
+    config::Config,
+    infrastructure::database::Repositories,
+    request_context::DEFAULT_TENANT,
+    state::AppState,
+};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+use crate::support::send_with;
+
+fn test_app() -> Router {
+    let config = Config::default();
+    build_app(&config, AppState::new(Repositories::in_memory(), &config))
+}
+
+async fn send(app: &Router, method: &str, uri: &str, token: Option<&str>, body: Option<Value>) -> (StatusCode, HeaderMap, Value) {
+    let authorization = token.map(|token| format!("Bearer {token}"));
+    let headers: Vec<_> = authorization.iter().map(|value| ("authorization", value.as_str())).collect();
+    send_with(app, method, uri, &headers, body).await
+}
+
+/// Registers and signs in, returning the token response.
+async fn sign_up(app: &Router, email: &str) -> Value {
+    let credentials = json!({ "email": email, "password": "correct horse" });
+    let (status, _, _) = send(app, "POST", "/auth/register", None, Some(credentials.clone())).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _, tokens) = send(app, "POST", "/auth/login", None, Some(credentials)).await;
+    assert_eq!(status, StatusCode::OK);
+    tokens
+}
+
+#[tokio::test]
+async fn test_register_and_login() {
+    let app = test_app();
+
+    let (status, _, user) = send(&app, "POST", "/auth/register", None, Some(json!({
+        "email": " Ada@Example.com ",
+        "password": "correct horse"
+    }))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(user["email"], "ada@example.com");
+    assert!(user.get("password_hash").is_none());
+
+    let (status, _, error) = send(&app, "POST", "/auth/register", None, Some(json!({
+        "email": "ADA@example.com",
+        "password": "another password"
+    }))).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(error["code"], "conflict");
+
+    let (status, _, error) = send(&app, "POST", "/auth/register", None, Some(json!({
+        "email": "not an email",
+        "password": "short"
+    }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(error["errors"].as_array().unwrap().len(), 2);
+
+    let (status, _, tokens) = send(&app, "POST", "/auth/login", None, Some(json!({
+        "email": "ada@example.com",
+        "password": "correct horse"
+    }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(tokens["token_type"], "Bearer");
+    assert_eq!(tokens["expires_in"], 900);
+
+    let (status, _, me) = send(&app, "GET", "/auth/me", tokens["access_token"].as_str(), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(me["id"], user["id"]);
+}
+
+#[tokio::test]
+async fn test_login_failures_look_alike() {
+    let app = test_app();
+    sign_up(&app, "ada@example.com").await;
+
+    let (wrong_password, _, first) = send(&app, "POST", "/auth/login", None, Some(json!({
+        "email": "ada@example.com",
+        "password": "wrong horse"
+    }))).await;
+    let (unknown_email, _, second) = send(&app, "POST", "/auth/login", None, Some(json!({
+        "email": "bob@example.com",
+        "password": "correct horse"
+    }))).await;
+    assert_eq!(wrong_password, StatusCode::UNAUTHORIZED);
+    assert_eq!(unknown_email, StatusCode::UNAUTHORIZED);
+    assert_eq!(first["detail"], second["detail"]);
+}
+
+#[tokio::test]
+async fn test_protected_routes_require_a_token() {
+    let app = test_app();
+
+    let (status, headers, error) = send(&app, "GET", "/todos", None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(headers["www-authenticate"], "Bearer");
+    assert_eq!(error["code"], "unauthorized");
+
+    let (status, _, _) = send(&app, "GET", "/todos", Some("not.a.token"), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let request = Request::builder().uri("/todos").header("authorization", "Basic YWRhOmhvcnNl").body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // Tokens signed with another key are rejected.
+    let other = AppState::new(Repositories::in_memory(), &Config::default());
+    let forged = other.access_tokens.issue(Uuid::new_v4(), "mallory@example.com", DEFAULT_TENANT);
+    let (status, _, _) = send(&app, "GET", "/todos", Some(&forged), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _, _) = send(&app, "GET", "/health", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_expired_access_tokens_are_rejected() {
+    let mut config = Config::default();
+    config.auth.jwt_secret = Some("an-access-token-signing-secret-of-some-length".to_string());
+    config.auth.access_token_ttl_secs = 1;
+    let state = AppState::new(Repositories::in_memory(), &config);
+    let token = state.access_tokens.issue(Uuid::new_v4(), "ada@example.com", DEFAULT_TENANT);
+    let app = build_app(&config, state);
+
+    let (status, _, _) = send(&app, "GET", "/todos", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    tokio::time::sleep(std::time::Duration::from_millis(2100)).await;
+    let (status, _, error) = send(&app, "GET", "/todos", Some(&token), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(error["detail"], "The access token is invalid or has expired.");
+}
+
+#[tokio::test]
+async fn test_refresh_tokens_rotate() {
+    let app = test_app();
+    let tokens = sign_up(&app, "ada@example.com").await;
+    let first = tokens["refresh_token"].as_str().unwrap();
+
+    let (status, _, rotated) = send(&app, "POST", "/auth/refresh", None, Some(json!({ "refresh_token": first }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let second = rotated["refresh_token"].as_str().unwrap();
+    assert_ne!(first, second);
+    let (status, _, _) = send(&app, "GET", "/auth/me", rotated["access_token"].as_str(), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Presenting the replaced token again revokes the whole family.
+    let (status, _, error) = send(&app, "POST", "/auth/refresh", None, Some(json!({ "refresh_token": first }))).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(error["detail"], "The refresh token was already used; sign in again.");
+    let (status, _, _) = send(&app, "POST", "/auth/refresh", None, Some(json!({ "refresh_token": second }))).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _, _) = send(&app, "POST", "/auth/refresh", None, Some(json!({ "refresh_token": "made-up" }))).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_logout_ends_the_session() {
+    let app = test_app();
+    let tokens = sign_up(&app, "ada@example.com").await;
+    let other_session = {
+        let (_, _, tokens) = send(&app, "POST", "/auth/login", None, Some(json!({
+            "email": "ada@example.com",
+            "password": "correct horse"
+        }))).await;
+        tokens["refresh_token"].as_str().unwrap().to_string()
+    };
+
+    let refresh = json!({ "refresh_token": tokens["refresh_token"] });
+    let (status, _, _) = send(&app, "POST", "/auth/logout", None, Some(refresh.clone())).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _, _) = send(&app, "POST", "/auth/refresh", None, Some(refresh.clone())).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Signing out twice, or with an unknown token, is not an error.
+    let (status, _, _) = send(&app, "POST", "/auth/logout", None, Some(refresh)).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    // Other sessions stay signed in.
+    let (status, _, _) = send(&app, "POST", "/auth/refresh", None, Some(json!({ "refresh_token": other_session }))).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_users_only_see_their_own_todos() {
+    let app = test_app();
+    let ada = sign_up(&app, "ada@example.com").await;
+    let bob = sign_up(&app, "bob@example.com").await;
+    let ada = ada["access_token"].as_str();
+    let bob = bob["access_token"].as_str();
+
+    let (status, _, todo) = send(&app, "POST", "/todos", ada, Some(json!({ "title": "Ada's todo" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let uri = format!("/todos/{}", todo["id"].as_str().unwrap());
+
+    let (status, _, page) = send(&app, "GET", "/todos", bob, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["pagination"]["total"], 0);
+    let (status, _, _) = send(&app, "GET", "/todos/search?q=todo", bob, None).await;
+    assert_eq!(status, StatusCode::OK);
+    for (method, body) in [("GET", None), ("PUT", Some(json!({ "title": "Bob's now", "done": true }))), ("DELETE", None)] {
+        let (status, _, _) = send(&app, method, &uri, bob, body).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{method}");
+    }
+    let (status, _, _) = send(&app, "GET", &format!("{uri}/history"), bob, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _, todo) = send(&app, "GET", &uri, ada, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(todo["title"], "Ada's todo");
+    let (_, _, page) = send(&app, "GET", "/todos", ada, None).await;
+    assert_eq!(page["pagination"]["total"], 1);
+}
@@ -0,0 +1,155 @@
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+
+use crate::support::{create, send, send_with, test_app, titles, TEST_USER};
+
+#[tokio::test]
+async fn test_list_crud() {
+    let app = test_app();
+
+    let (status, created) = send(&app, "POST", "/lists", Some(json!({ "name": "Groceries", "color": "#ff8800" }))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(created["archived"], false);
+    assert_eq!(created["description"], Value::Null);
+    assert_eq!(created["owner_id"], TEST_USER.to_string());
+    let uri = format!("/lists/{}", created["id"].as_str().unwrap());
+
+    let (status, found) = send(&app, "GET", &uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(found["name"], "Groceries");
+
+    let (status, updated) = send(&app, "PATCH", &uri, Some(json!({ "description": "Weekly shop" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["description"], "Weekly shop");
+    assert_eq!(updated["color"], "#ff8800");
+
+    let (status, page) = send(&app, "GET", "/lists", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["pagination"]["total"], 1);
+
+    let (status, _) = send(&app, "DELETE", &uri, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, body) = send(&app, "GET", &uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "not_found");
+}
+
+#[tokio::test]
+async fn test_invalid_list_is_unprocessable() {
+    let app = test_app();
+
+    let (status, body) = send(&app, "POST", "/lists", Some(json!({ "name": " ", "color": "orange" }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "validation_failed");
+    assert_eq!(body["errors"][0]["field"], "name");
+    assert_eq!(body["errors"][1]["field"], "color");
+}
+
+#[tokio::test]
+async fn test_archived_list_leaves_todo_listings() {
+    let app = test_app();
+    let id = create(&app, "/lists", json!({ "name": "Someday" })).await;
+    send(&app, "POST", "/todos", Some(json!({ "title": "Learn the cello", "list_id": id }))).await;
+    send(&app, "POST", "/todos", Some(json!({ "title": "Buy milk" }))).await;
+
+    let (status, archived) = send(&app, "POST", &format!("/lists/{id}/archive"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(archived["archived"], true);
+
+    let (_, page) = send(&app, "GET", "/todos", None).await;
+    assert_eq!(titles(&page), vec!["Buy milk"]);
+    let (_, page) = send(&app, "GET", &format!("/todos?list_id={id}"), None).await;
+    assert_eq!(titles(&page), vec!["Learn the cello"]);
+    let (_, lists) = send(&app, "GET", "/lists?archived=true", None).await;
+    assert_eq!(lists["data"][0]["id"], id);
+
+    let (status, body) = send(&app, "POST", "/todos", Some(json!({ "title": "Learn the oboe", "list_id": id }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"][0]["code"], "archived_list");
+
+    let (status, _) = send(&app, "POST", &format!("/lists/{id}/unarchive"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, page) = send(&app, "GET", "/todos", None).await;
+    assert_eq!(page["pagination"]["total"], 2);
+}
+
+#[tokio::test]
+async fn test_moving_todos_between_lists() {
+    let app = test_app();
+    let groceries = create(&app, "/lists", json!({ "name": "Groceries" })).await;
+    let errands = create(&app, "/lists", json!({ "name": "Errands" })).await;
+    let todo = create(&app, "/todos", json!({ "title": "Buy milk", "list_id": groceries })).await;
+    let uri = format!("/todos/{todo}");
+
+    let (status, moved) = send(&app, "PUT", &uri, Some(json!({ "title": "Buy milk", "done": false, "list_id": errands }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(moved["list_id"], errands);
+    let (_, page) = send(&app, "GET", &format!("/lists/{errands}/todos"), None).await;
+    assert_eq!(titles(&page), vec!["Buy milk"]);
+
+    let merge = [("content-type", "application/merge-patch+json")];
+    let (status, _, moved) = send_with(&app, "PATCH", &uri, &merge, Some(json!({ "list_id": groceries }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(moved["list_id"], groceries);
+
+    let (status, body) = send(&app, "POST", "/todos/batch", Some(json!({
+        "operations": [{ "op": "update", "id": todo, "list_id": null }]
+    }))).await;
+    assert_eq!(status, StatusCode::MULTI_STATUS);
+    assert_eq!(body["results"][0]["todo"]["list_id"], Value::Null);
+    let (_, page) = send(&app, "GET", &format!("/lists/{groceries}/todos"), None).await;
+    assert_eq!(page["pagination"]["total"], 0);
+
+    send(&app, "POST", &format!("/lists/{errands}/archive"), None).await;
+    let (status, body) = send(&app, "PUT", &uri, Some(json!({ "title": "Buy milk", "done": false, "list_id": errands }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"][0]["code"], "archived_list");
+    let (_, _, body) = send_with(&app, "PATCH", &uri, &merge, Some(json!({ "list_id": uuid::Uuid::new_v4() }))).await;
+    assert_eq!(body["errors"][0]["code"], "unknown_list");
+    let (_, body) = send(&app, "POST", "/todos/batch", Some(json!({
+        "operations": [{ "op": "update", "id": todo, "list_id": errands }]
+    }))).await;
+    assert_eq!(body["results"][0]["status"], 422);
+    assert_eq!(body["results"][0]["error"]["errors"][0]["code"], "archived_list");
+}
+
+#[tokio::test]
+async fn test_list_todos_pages_like_todos() {
+    let app = test_app();
+    let id = create(&app, "/lists", json!({ "name": "Groceries" })).await;
+    for i in 0..3 {
+        send(&app, "POST", "/todos", Some(json!({ "title": format!("todo {i}"), "list_id": id }))).await;
+    }
+    send(&app, "POST", "/todos", Some(json!({ "title": "elsewhere" }))).await;
+
+    let (status, page) = send(&app, "GET", &format!("/lists/{id}/todos?limit=2"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(titles(&page), vec!["todo 2", "todo 1"]);
+    assert_eq!(page["pagination"]["total"], 3);
+
+    let (_, first) = send(&app, "GET", &format!("/lists/{id}/todos?cursor=&limit=2"), None).await;
+    let next = first["pagination"]["next_cursor"].as_str().unwrap();
+    let (status, second) = send(&app, "GET", &format!("/lists/{id}/todos?limit=2&cursor={next}"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(titles(&second), vec!["todo 0"]);
+
+    let (status, _) = send(&app, "GET", &format!("/lists/{}/todos", uuid::Uuid::new_v4()), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_delete_list_trashes_its_todos() {
+    let app = test_app();
+    let id = create(&app, "/lists", json!({ "name": "Groceries" })).await;
+    let (_, todo) = send(&app, "POST", "/todos", Some(json!({ "title": "Buy milk", "list_id": id }))).await;
+    let todo_uri = format!("/todos/{}", todo["id"].as_str().unwrap());
+
+    let (status, _) = send(&app, "DELETE", &format!("/lists/{id}"), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, _) = send(&app, "GET", &todo_uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, restored) = send(&app, "POST", &format!("{todo_uri}/restore"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(restored["list_id"], Value::Null);
+}
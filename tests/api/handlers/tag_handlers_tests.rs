@@ -0,0 +1,99 @@
+use axum::http::StatusCode;
+use serde_json::json;
+
+use crate::support::{create, send, send_with, test_app, titles, TEST_USER};
+
+#[tokio::test]
+async fn test_tag_crud() {
+    let app = test_app();
+
+    let (status, created) = send(&app, "POST", "/tags", Some(json!({ "name": "work" }))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(created["owner_id"], TEST_USER.to_string());
+    let uri = format!("/tags/{}", created["id"].as_str().unwrap());
+
+    let (status, body) = send(&app, "POST", "/tags", Some(json!({ "name": "work" }))).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], "conflict");
+    let (status, body) = send(&app, "POST", "/tags", Some(json!({ "name": " work" }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"][0]["code"], "untrimmed");
+
+    let (status, renamed) = send(&app, "PATCH", &uri, Some(json!({ "name": "office" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(renamed["name"], "office");
+
+    let (status, page) = send(&app, "GET", "/tags", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["pagination"]["total"], 1);
+    assert_eq!(page["data"][0]["name"], "office");
+
+    let (status, _) = send(&app, "DELETE", &uri, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, body) = send(&app, "DELETE", &uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "not_found");
+}
+
+#[tokio::test]
+async fn test_attach_and_detach_tags() {
+    let app = test_app();
+    let work = create(&app, "/tags", json!({ "name": "work" })).await;
+    let todo = create(&app, "/todos", json!({ "title": "Ship the release" })).await;
+    let uri = format!("/todos/{todo}/tags/{work}");
+
+    let (status, headers, tagged) = send_with(&app, "PUT", &uri, &[("if-match", "\"1\"")], None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers["etag"], "\"2\"");
+    assert_eq!(tagged["tags"], json!([{ "id": work, "name": "work" }]));
+
+    let (status, _, _) = send_with(&app, "PUT", &uri, &[("if-match", "\"1\"")], None).await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+    let (status, found) = send(&app, "GET", &format!("/todos/{todo}"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(found["tags"][0]["name"], "work");
+
+    let missing = format!("/todos/{todo}/tags/{}", uuid::Uuid::new_v4());
+    let (status, _) = send(&app, "PUT", &missing, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, headers, untagged) = send_with(&app, "DELETE", &uri, &[], None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers["etag"], "\"3\"");
+    assert_eq!(untagged["tags"], json!([]));
+    let (status, _) = send(&app, "DELETE", &uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_list_todos_by_tag() {
+    let app = test_app();
+    let work = create(&app, "/tags", json!({ "name": "work" })).await;
+    let urgent = create(&app, "/tags", json!({ "name": "urgent" })).await;
+    let list = create(&app, "/lists", json!({ "name": "Release" })).await;
+    let both = create(&app, "/todos", json!({ "title": "Ship the release", "list_id": list })).await;
+    let only_work = create(&app, "/todos", json!({ "title": "Review the PR" })).await;
+    create(&app, "/todos", json!({ "title": "Buy milk" })).await;
+    for (todo, tag) in [(&both, &work), (&both, &urgent), (&only_work, &work)] {
+        let (status, _) = send(&app, "PUT", &format!("/todos/{todo}/tags/{tag}"), None).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let (status, page) = send(&app, "GET", "/todos?tag=work&tag=urgent", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(titles(&page), vec!["Review the PR", "Ship the release"]);
+    let (_, page) = send(&app, "GET", "/todos?tag=work&tag=urgent&tag_match=all", None).await;
+    assert_eq!(titles(&page), vec!["Ship the release"]);
+    assert_eq!(page["data"][0]["tags"].as_array().unwrap().len(), 2);
+    let (_, page) = send(&app, "GET", "/todos?tag=urgent&cursor=", None).await;
+    assert_eq!(titles(&page), vec!["Ship the release"]);
+    let (_, page) = send(&app, "GET", &format!("/lists/{list}/todos?tag=work"), None).await;
+    assert_eq!(titles(&page), vec!["Ship the release"]);
+
+    let (status, _) = send(&app, "GET", "/todos?tag=work&tag_match=some", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let too_many: String = (0..21).map(|i| format!("tag=t{i}&")).collect();
+    let (status, body) = send(&app, "GET", &format!("/todos?{too_many}"), None).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"][0]["code"], "too_many");
+}
@@ -1,23 +1,50 @@
-use axum_api::domain::todos::{
-    Todo, CreateTodoRequest, UpdateTodoRequest, PaginationQuery
+use axum::{body::Body, http::{Request, StatusCode}, Router};
+use axum_api::{
+    config::Config,
+    domain::todos::{Todo, CreateTodoRequest, UpdateTodoRequest, PaginationQuery},
+    infrastructure::database::Repositories,
+    request_context::DEFAULT_TENANT,
+    state::AppState,
 };
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use tower::ServiceExt;
 use uuid::Uuid;
 use chrono::Utc;
 
+use crate::support::{send, send_with, signed_in, test_app, TEST_USER};
+
 fn create_test_todo() -> Todo {
     Todo {
         id: Uuid::new_v4(),
         title: "Test Todo".to_string(),
         done: false,
+        description: None,
+        completed_at: None,
+        due_at: None,
+        priority: Default::default(),
         created_at: Utc::now(),
         updated_at: Utc::now(),
+        version: 1,
+        list_id: None,
+        tags: Vec::new(),
+        owner_id: Some(TEST_USER),
+        tenant_id: "default".to_string(),
     }
 }
 
+async fn send_as(app: &Router, method: &str, uri: &str, content_type: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let (status, _, json) = send_with(app, method, uri, &[("content-type", content_type)], body).await;
+    (status, json)
+}
+
 #[test]
 fn test_create_todo_request() {
     let request = CreateTodoRequest {
         title: "Test Todo".to_string(),
+        done: None,
+        list_id: None,
+        ..Default::default()
     };
     assert_eq!(request.title, "Test Todo");
 }
@@ -27,6 +54,7 @@ fn test_update_todo_request() {
     let request = UpdateTodoRequest {
         title: Some("Updated Todo".to_string()),
         done: Some(true),
+        ..Default::default()
     };
     assert_eq!(request.title, Some("Updated Todo".to_string()));
     assert_eq!(request.done, Some(true));
@@ -37,6 +65,7 @@ fn test_pagination_query() {
     let query = PaginationQuery {
         page: 1,
         limit: 10,
+        ..Default::default()
     };
     assert_eq!(query.page, 1);
     assert_eq!(query.limit, 10);
@@ -46,5 +75,668 @@ fn test_pagination_query() {
 fn test_todo_entity() {
     let todo = create_test_todo();
     assert_eq!(todo.title, "Test Todo");
-    assert_eq!(todo.done, false);
+    assert!(!todo.done);
+}
+
+#[tokio::test]
+async fn test_crud_round_trip() {
+    let app = test_app();
+
+    let (status, created) = send(&app, "POST", "/todos", Some(json!({ "title": "Buy milk" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(created["title"], "Buy milk");
+    assert_eq!(created["done"], false);
+    let id = created["id"].as_str().unwrap().to_string();
+
+    let (status, fetched) = send(&app, "GET", &format!("/todos/{id}"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(fetched["id"], created["id"]);
+
+    let (status, updated) = send(&app, "PUT", &format!("/todos/{id}"), Some(json!({ "title": "Buy milk", "done": true }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["title"], "Buy milk");
+    assert_eq!(updated["done"], true);
+
+    let (status, done) = send(&app, "GET", "/todos/done/true", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(done["data"].as_array().unwrap().len(), 1);
+
+    let (status, _) = send(&app, "DELETE", &format!("/todos/{id}"), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, _) = send(&app, "GET", &format!("/todos/{id}"), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_list_todos_is_paginated() {
+    let app = test_app();
+    for i in 0..3 {
+        send(&app, "POST", "/todos", Some(json!({ "title": format!("todo {i}") }))).await;
+    }
+
+    let (status, body) = send(&app, "GET", "/todos?page=2&limit=2", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+    assert_eq!(body["pagination"]["total"], 3);
+    assert_eq!(body["pagination"]["total_pages"], 2);
+    assert_eq!(body["pagination"]["has_prev"], true);
+}
+
+#[tokio::test]
+async fn test_feature_toggles_remove_routes() {
+    let mut config = Config::default();
+    config.features.performance_test = false;
+    config.features.swagger_ui = false;
+    let state = AppState::new(Repositories::in_memory(), &config);
+    let app = signed_in(&config, state);
+
+    let (status, _) = send(&app, "POST", "/todos/performance-test", Some(json!({ "message_count": 1 }))).await;
+    assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+
+    let (status, _) = send(&app, "GET", "/api-docs/openapi.json", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_invalid_title_is_unprocessable() {
+    let app = test_app();
+
+    let (status, body) = send(&app, "POST", "/todos", Some(json!({ "title": "  " }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["code"], "validation_failed");
+    assert_eq!(body["instance"], "/todos");
+    assert_eq!(body["errors"][0]["field"], "title");
+    assert_eq!(body["errors"][0]["code"], "blank");
+}
+
+#[tokio::test]
+async fn test_errors_are_problem_json() {
+    let app = test_app();
+    let uri = format!("/todos/{}", Uuid::new_v4());
+
+    let response = app
+        .clone()
+        .oneshot(Request::builder().uri(&uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.headers()["content-type"], "application/problem+json");
+
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["type"], "/problems/not-found");
+    assert_eq!(body["title"], "Resource not found");
+    assert_eq!(body["status"], 404);
+    assert_eq!(body["code"], "not_found");
+    assert_eq!(body["instance"], uri);
+}
+
+#[tokio::test]
+async fn test_malformed_requests_are_problem_json() {
+    let app = test_app();
+    let (_, created) = send(&app, "POST", "/todos", Some(json!({ "title": "Buy milk" }))).await;
+    let uri = format!("/todos/{}", created["id"].as_str().unwrap());
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/todos")
+        .header("content-type", "application/json")
+        .body(Body::from("{\"title\": "))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(response.headers()["content-type"], "application/problem+json");
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!((body["code"].as_str(), body["instance"].as_str()), (Some("bad_request"), Some("/todos")));
+
+    let cases = [
+        ("POST", "/todos", &[("content-type", "text/plain")][..], Some(json!({ "title": "x" })), 415, "unsupported_media_type"),
+        ("POST", "/todos", &[], Some(json!({ "title": "x", "description": "a".repeat(3 << 20) })), 413, "payload_too_large"),
+        ("GET", "/todos/not-a-uuid", &[], None, 400, "bad_request"),
+        ("GET", "/todos?page=first", &[], None, 400, "bad_request"),
+        ("GET", "/todos/search?limit=10", &[], None, 400, "bad_request"),
+        ("POST", "/todos", &[], Some(json!({ "done": true })), 422, "validation_failed"),
+    ];
+    for (method, uri, headers, body, status, code) in cases {
+        let (actual, headers, body) = send_with(&app, method, uri, headers, body).await;
+        assert_eq!((actual.as_u16(), body["code"].as_str()), (status, Some(code)), "{method} {uri}: {body}");
+        assert_eq!(headers["content-type"], "application/problem+json");
+        assert_eq!(body["instance"], uri.split('?').next().unwrap());
+    }
+
+    // Body fields that are missing, unknown or of the wrong type are named.
+    let (_, body) = send(&app, "POST", "/todos", Some(json!({ "done": true }))).await;
+    assert_eq!(body["errors"], json!([{ "field": "title", "code": "required", "message": "missing field `title`" }]));
+    let (_, body) = send(&app, "PUT", &uri, Some(json!({ "title": "x", "done": false, "colour": "red" }))).await;
+    assert_eq!((body["errors"][0]["field"].as_str(), body["errors"][0]["code"].as_str()), (Some("colour"), Some("unknown_field")));
+    let (_, body) = send(&app, "POST", "/todos", Some(json!({ "title": "x", "done": "yes" }))).await;
+    assert_eq!((body["errors"][0]["field"].as_str(), body["errors"][0]["code"].as_str()), (Some("done"), Some("invalid_type")));
+    let batch = json!({ "operations": [{ "op": "create", "title": "x" }, { "op": "create", "title": 7 }] });
+    let (status, body) = send(&app, "POST", "/todos/batch", Some(batch)).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"][0]["field"], "operations[1]", "{body}");
+}
+
+#[tokio::test]
+async fn test_mutating_missing_todo_is_not_found() {
+    let app = test_app();
+    let uri = format!("/todos/{}", Uuid::new_v4());
+
+    let (status, body) = send(&app, "PUT", &uri, Some(json!({ "title": "x", "done": true }))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "not_found");
+
+    let (status, _) = send(&app, "DELETE", &uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_put_requires_full_representation() {
+    let app = test_app();
+    let (_, created) = send(&app, "POST", "/todos", Some(json!({ "title": "Buy milk" }))).await;
+    let uri = format!("/todos/{}", created["id"].as_str().unwrap());
+
+    let (status, _) = send(&app, "PUT", &uri, Some(json!({ "done": true }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn test_todo_details_and_due_filters() {
+    let app = test_app();
+    let body = json!({
+        "title": "Pay rent",
+        "description": "Transfer to **landlord**",
+        "due_at": "2020-01-01T09:00:00Z",
+        "priority": "high"
+    });
+    let (status, created) = send(&app, "POST", "/todos", Some(body)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(created["description"], "Transfer to **landlord**");
+    assert_eq!(created["priority"], "high");
+    assert_eq!(created["completed_at"], Value::Null);
+    let uri = format!("/todos/{}", created["id"].as_str().unwrap());
+    send(&app, "POST", "/todos", Some(json!({ "title": "Buy milk" }))).await;
+
+    let (status, body) = send(&app, "GET", "/todos?overdue=true", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["pagination"]["total"], 1);
+    assert_eq!(body["data"][0]["title"], "Pay rent");
+    let (_, body) = send(&app, "GET", "/todos?due_today=true", None).await;
+    assert_eq!(body["pagination"]["total"], 0);
+    let (status, body) = send(&app, "GET", "/todos?overdue=true&done=true", None).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"][0]["code"], "conflicting_filter");
+
+    let patch = json!({ "done": true, "description": null });
+    let (status, patched) = send_as(&app, "PATCH", &uri, "application/merge-patch+json", Some(patch)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(patched["completed_at"].is_string());
+    assert_eq!(patched["description"], Value::Null);
+    assert_eq!(patched["priority"], "high");
+
+    let (_, replaced) = send(&app, "PUT", &uri, Some(json!({ "title": "Pay rent", "done": false }))).await;
+    assert_eq!(replaced["completed_at"], Value::Null);
+    assert_eq!(replaced["due_at"], Value::Null);
+    assert_eq!(replaced["priority"], "medium");
+
+    let (status, body) = send(&app, "POST", "/todos", Some(json!({ "title": "x", "priority": "urgent" }))).await;
+    assert!(status.is_client_error(), "got {status} {body}");
+}
+
+#[tokio::test]
+async fn test_patch_with_merge_and_json_patch() {
+    let app = test_app();
+    let (_, created) = send(&app, "POST", "/todos", Some(json!({ "title": "Buy milk" }))).await;
+    let uri = format!("/todos/{}", created["id"].as_str().unwrap());
+
+    let (status, patched) = send_as(&app, "PATCH", &uri, "application/merge-patch+json", Some(json!({ "done": true }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(patched["title"], "Buy milk");
+    assert_eq!(patched["done"], true);
+
+    let ops = json!([
+        { "op": "test", "path": "/done", "value": true },
+        { "op": "replace", "path": "/title", "value": "Buy oat milk" }
+    ]);
+    let (status, patched) = send_as(&app, "PATCH", &uri, "application/json-patch+json", Some(ops)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(patched["title"], "Buy oat milk");
+
+    let failing = json!([{ "op": "test", "path": "/done", "value": false }]);
+    let (status, body) = send_as(&app, "PATCH", &uri, "application/json-patch+json", Some(failing)).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"][0]["code"], "patch_failed");
+}
+
+#[tokio::test]
+async fn test_patch_errors() {
+    let app = test_app();
+    let (_, created) = send(&app, "POST", "/todos", Some(json!({ "title": "Buy milk" }))).await;
+    let uri = format!("/todos/{}", created["id"].as_str().unwrap());
+
+    let (status, body) = send_as(&app, "PATCH", &uri, "application/json", Some(json!({ "done": true }))).await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_eq!(body["code"], "unsupported_media_type");
+
+    let (status, body) = send_as(&app, "PATCH", &uri, "application/merge-patch+json", Some(json!({ "title": null }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"][0]["code"], "required");
+
+    let missing = format!("/todos/{}", Uuid::new_v4());
+    let (status, _) = send_as(&app, "PATCH", &missing, "application/merge-patch+json", Some(json!({ "done": true }))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_etag_and_if_none_match() {
+    let app = test_app();
+    let (_, headers, created) = send_with(&app, "POST", "/todos", &[("content-type", "application/json")], Some(json!({ "title": "Buy milk" }))).await;
+    assert_eq!(headers["etag"], "\"1\"");
+    let uri = format!("/todos/{}", created["id"].as_str().unwrap());
+
+    let (status, headers, _) = send_with(&app, "GET", &uri, &[], None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers["etag"], "\"1\"");
+
+    let (status, headers, body) = send_with(&app, "GET", &uri, &[("if-none-match", "W/\"1\"")], None).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert_eq!(headers["etag"], "\"1\"");
+    assert_eq!(body, Value::Null);
+
+    let (status, headers, _) = send_with(&app, "PATCH", &uri, &[("content-type", "application/merge-patch+json")], Some(json!({ "done": true }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers["etag"], "\"2\"");
+
+    let (status, _, _) = send_with(&app, "GET", &uri, &[("if-none-match", "\"1\"")], None).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_if_match_guards_mutations() {
+    let app = test_app();
+    let (_, created) = send(&app, "POST", "/todos", Some(json!({ "title": "Buy milk" }))).await;
+    let uri = format!("/todos/{}", created["id"].as_str().unwrap());
+    let json_type = ("content-type", "application/json");
+
+    let (status, headers, _) = send_with(&app, "PUT", &uri, &[json_type, ("if-match", "\"1\"")], Some(json!({ "title": "Buy oat milk", "done": false }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers["etag"], "\"2\"");
+
+    let (status, _, body) = send_with(&app, "PUT", &uri, &[json_type, ("if-match", "\"1\"")], Some(json!({ "title": "lost", "done": true }))).await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+    assert_eq!(body["code"], "precondition_failed");
+
+    let (status, _, _) = send_with(&app, "PATCH", &uri, &[("content-type", "application/merge-patch+json"), ("if-match", "W/\"2\"")], Some(json!({ "done": true }))).await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+
+    let (status, _, _) = send_with(&app, "DELETE", &uri, &[("if-match", "\"1\"")], None).await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+
+    let (status, _, _) = send_with(&app, "DELETE", &uri, &[("if-match", "\"2\"")], None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn test_if_match_can_be_required() {
+    let mut config = Config::default();
+    config.features.require_if_match = true;
+    let state = AppState::new(Repositories::in_memory(), &config);
+    let app = signed_in(&config, state);
+
+    let (_, created) = send(&app, "POST", "/todos", Some(json!({ "title": "Buy milk" }))).await;
+    let uri = format!("/todos/{}", created["id"].as_str().unwrap());
+
+    let (status, body) = send(&app, "DELETE", &uri, None).await;
+    assert_eq!(status, StatusCode::PRECONDITION_REQUIRED);
+    assert_eq!(body["code"], "precondition_required");
+
+    let (status, _, _) = send_with(&app, "DELETE", &uri, &[("if-match", "*")], None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn test_list_todos_by_cursor() {
+    let app = test_app();
+    for i in 0..3 {
+        send(&app, "POST", "/todos", Some(json!({ "title": format!("todo {i}") }))).await;
+    }
+
+    let (status, first) = send(&app, "GET", "/todos?cursor=&limit=2&include_total=true", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(first["data"].as_array().unwrap().len(), 2);
+    assert_eq!(first["data"][0]["title"], "todo 2");
+    assert_eq!(first["pagination"]["total"], 3);
+    assert_eq!(first["pagination"]["prev_cursor"], Value::Null);
+
+    let next = first["pagination"]["next_cursor"].as_str().unwrap();
+    let (_, second) = send(&app, "GET", &format!("/todos?limit=2&cursor={next}"), None).await;
+    assert_eq!(second["data"].as_array().unwrap().len(), 1);
+    assert_eq!(second["data"][0]["title"], "todo 0");
+    assert_eq!(second["pagination"]["next_cursor"], Value::Null);
+    assert_eq!(second["pagination"]["total"], Value::Null);
+
+    let prev = second["pagination"]["prev_cursor"].as_str().unwrap();
+    let (_, back) = send(&app, "GET", &format!("/todos?limit=2&cursor={prev}"), None).await;
+    assert_eq!(back["data"], first["data"]);
+
+    let (status, body) = send(&app, "GET", &format!("/todos?cursor=x{next}"), None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "bad_request");
+}
+
+#[tokio::test]
+async fn test_list_todos_filters_and_sorts() {
+    let app = test_app();
+    for (title, done) in [("walk dog", true), ("Buy milk", false), ("buy bread", true)] {
+        send(&app, "POST", "/todos", Some(json!({ "title": title, "done": done }))).await;
+    }
+
+    let (status, body) = send(&app, "GET", "/todos?title=BUY&sort=title", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let titles: Vec<&str> = body["data"].as_array().unwrap().iter().map(|t| t["title"].as_str().unwrap()).collect();
+    assert_eq!(titles, vec!["Buy milk", "buy bread"]);
+    assert_eq!(body["pagination"]["total"], 2);
+
+    let (status, body) = send(&app, "GET", "/todos/done/true?limit=1", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"][0]["title"], "buy bread");
+    assert_eq!(body["pagination"]["total"], 2);
+
+    let (status, body) = send(&app, "GET", "/todos?done=true&cursor=", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"].as_array().unwrap().len(), 2);
+
+    let (status, body) = send(&app, "GET", "/todos?sort=-id", None).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"][0]["field"], "sort");
+
+    let (status, _) = send(&app, "GET", "/todos?created_after=yesterday", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_search_todos() {
+    let app = test_app();
+    send(&app, "POST", "/todos", Some(json!({ "title": "Buy oat milk" }))).await;
+    send(&app, "POST", "/todos", Some(json!({ "title": "Walk the dog" }))).await;
+
+    let (status, body) = send(&app, "GET", "/todos/search?q=milk", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["pagination"]["total"], 1);
+    assert_eq!(body["data"][0]["title"], "Buy oat milk");
+    assert_eq!(body["data"][0]["snippet"], "Buy oat <mark>milk</mark>");
+    assert!(body["data"][0]["rank"].as_f64().unwrap() > 0.0);
+
+    let (status, body) = send(&app, "GET", "/todos/search?q=%20", None).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"][0]["field"], "q");
+}
+
+#[tokio::test]
+async fn test_batch_todos() {
+    let app = test_app();
+    let (_, existing) = send(&app, "POST", "/todos", Some(json!({ "title": "Buy milk" }))).await;
+    let id = existing["id"].as_str().unwrap();
+
+    let (status, body) = send(&app, "POST", "/todos/batch", Some(json!({
+        "operations": [
+            { "op": "create", "title": "Walk the dog" },
+            { "op": "update", "id": id, "done": true, "expected_version": 2 }
+        ]
+    }))).await;
+    assert_eq!(status, StatusCode::MULTI_STATUS);
+    assert_eq!(body["mode"], "atomic");
+    assert_eq!(body["failed"], 2);
+    assert_eq!(body["results"][0]["status"], 424);
+    assert_eq!(body["results"][1]["status"], 412);
+    assert_eq!(body["results"][1]["error"]["code"], "precondition_failed");
+
+    let (status, body) = send(&app, "POST", "/todos/batch", Some(json!({
+        "mode": "best_effort",
+        "operations": [
+            { "op": "create", "title": "Walk the dog" },
+            { "op": "create", "title": " " },
+            { "op": "delete", "id": id }
+        ]
+    }))).await;
+    assert_eq!(status, StatusCode::MULTI_STATUS);
+    assert_eq!(body["succeeded"], 2);
+    assert_eq!(body["results"][0]["status"], 201);
+    assert_eq!(body["results"][0]["todo"]["title"], "Walk the dog");
+    assert_eq!(body["results"][1]["status"], 422);
+    assert_eq!(body["results"][1]["error"]["errors"][0]["field"], "title");
+    assert_eq!(body["results"][2]["status"], 204);
+
+    let (_, list) = send(&app, "GET", "/todos", None).await;
+    assert_eq!(list["pagination"]["total"], 1);
+
+    let (status, body) = send(&app, "POST", "/todos/batch", Some(json!({ "operations": [] }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"][0]["code"], "empty");
+}
+
+#[tokio::test]
+async fn test_trash_restore_and_purge() {
+    let app = test_app();
+    let (_, created) = send(&app, "POST", "/todos", Some(json!({ "title": "Buy milk" }))).await;
+    let id = created["id"].as_str().unwrap();
+
+    let (status, _) = send(&app, "DELETE", &format!("/todos/{id}"), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, trash) = send(&app, "GET", "/todos/trash", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(trash["data"][0]["id"], id);
+    assert!(trash["data"][0]["deleted_at"].is_string());
+
+    let (status, headers, restored) = send_with(&app, "POST", &format!("/todos/{id}/restore"), &[], None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(restored["title"], "Buy milk");
+    assert_eq!(headers["etag"], "\"3\"");
+
+    let (status, _) = send(&app, "DELETE", &format!("/todos/trash/{id}"), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    send(&app, "DELETE", &format!("/todos/{id}"), None).await;
+    let (status, _) = send(&app, "DELETE", &format!("/todos/trash/{id}"), None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, _) = send(&app, "POST", &format!("/todos/{id}/restore"), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_history_records_request_ids() {
+    let app = test_app();
+    let json_type = ("content-type", "application/json");
+    let (_, headers, created) =
+        send_with(&app, "POST", "/todos", &[json_type, ("x-request-id", "create-1")], Some(json!({ "title": "Buy milk" }))).await;
+    assert_eq!(headers["x-request-id"], "create-1");
+    let id = created["id"].as_str().unwrap();
+
+    let uri = format!("/todos/{id}");
+    let (_, headers, _) = send_with(&app, "PUT", &uri, &[json_type], Some(json!({ "title": "Buy oat milk", "done": false }))).await;
+    let generated = headers["x-request-id"].to_str().unwrap().to_string();
+    assert!(!generated.is_empty());
+
+    let (status, history) = send(&app, "GET", &format!("/todos/{id}/history"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(history["pagination"]["total"], 2);
+    assert_eq!(history["data"][0]["kind"], "created");
+    assert_eq!(history["data"][0]["request_id"], "create-1");
+    assert_eq!(history["data"][0]["actor"], TEST_USER.to_string());
+    assert_eq!(history["data"][1]["kind"], "updated");
+    assert_eq!(history["data"][1]["request_id"], generated.as_str());
+    assert_eq!(history["data"][1]["changes"], json!([{ "field": "title", "from": "Buy milk", "to": "Buy oat milk" }]));
+    assert_eq!(history["data"][1]["before"]["version"], 1);
+
+    send(&app, "DELETE", &uri, None).await;
+    send(&app, "DELETE", &format!("/todos/trash/{id}"), None).await;
+    let (status, history) = send(&app, "GET", &format!("/todos/{id}/history?limit=1&page=4"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(history["data"][0]["kind"], "purged");
+
+    let (status, _) = send(&app, "GET", &format!("/todos/{}/history", Uuid::new_v4()), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_shared_todos_respect_roles() {
+    let config = Config::default();
+    let state = AppState::new(Repositories::in_memory(), &config);
+    let app = signed_in(&config, state.clone());
+    let (_, bob) = send(&app, "POST", "/auth/register", Some(json!({ "email": "bob@example.com", "password": "correct horse" }))).await;
+    let bob_id: Uuid = bob["id"].as_str().unwrap().parse().unwrap();
+    let bob_token = format!("Bearer {}", state.access_tokens.issue(bob_id, "bob@example.com", DEFAULT_TENANT));
+    let as_bob = [("content-type", "application/json"), ("authorization", bob_token.as_str())];
+
+    let (_, todo) = send(&app, "POST", "/todos", Some(json!({ "title": "Team milk" }))).await;
+    let uri = format!("/todos/{}", todo["id"].as_str().unwrap());
+    let access = format!("{uri}/access");
+
+    // Until it is shared, the todo does not exist for Bob.
+    let (status, _, _) = send_with(&app, "GET", &uri, &as_bob, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _, _) = send_with(&app, "PUT", &uri, &as_bob, Some(json!({ "title": "Mine", "done": true }))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _, _) = send_with(&app, "GET", &access, &as_bob, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, error) = send(&app, "POST", &access, Some(json!({ "email": "eve@example.com", "role": "viewer" }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(error["errors"][0]["code"], "unknown");
+    let (status, error) = send(&app, "POST", &access, Some(json!({ "email": "bob@example.com", "role": "owner" }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(error["errors"][0]["code"], "not_grantable");
+
+    let (status, share) = send(&app, "POST", &access, Some(json!({ "email": " Bob@Example.com", "role": "viewer" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(share["user_id"], bob_id.to_string());
+    assert_eq!(share["role"], "viewer");
+
+    let (status, _, _) = send_with(&app, "GET", &uri, &as_bob, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, _, page) = send_with(&app, "GET", "/todos", &as_bob, None).await;
+    assert_eq!(page["pagination"]["total"], 1);
+    let (_, _, shares) = send_with(&app, "GET", &access, &as_bob, None).await;
+    assert_eq!(shares[0]["user_id"], bob_id.to_string());
+
+    // Viewers may look but not touch.
+    let (status, _, error) = send_with(&app, "PUT", &uri, &as_bob, Some(json!({ "title": "Mine", "done": true }))).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(error["code"], "forbidden");
+    let (status, _, _) = send_with(&app, "POST", &access, &as_bob, Some(json!({ "email": "bob@example.com", "role": "editor" }))).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    send(&app, "POST", &access, Some(json!({ "email": "bob@example.com", "role": "editor" }))).await;
+    let (status, _, updated) = send_with(&app, "PUT", &uri, &as_bob, Some(json!({ "title": "Team oat milk", "done": true }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["owner_id"], TEST_USER.to_string());
+    let (status, _, _) = send_with(&app, "DELETE", &uri, &as_bob, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Bob gives the todo back.
+    let (status, _, _) = send_with(&app, "DELETE", &format!("{access}/{bob_id}"), &as_bob, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _, _) = send_with(&app, "GET", &uri, &as_bob, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send(&app, "DELETE", &format!("{access}/{bob_id}"), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+/// Reads the body until the next SSE message and returns its non-comment lines.
+async fn next_sse_message(body: &mut Body) -> Vec<String> {
+    let mut buffer = String::new();
+    loop {
+        let frame = tokio::time::timeout(std::time::Duration::from_secs(5), body.frame())
+            .await
+            .expect("no event arrived")
+            .unwrap()
+            .unwrap();
+        buffer.push_str(std::str::from_utf8(frame.data_ref().unwrap()).unwrap());
+        if let Some(end) = buffer.find("\n\n") {
+            let lines: Vec<String> = buffer[..end].lines().filter(|line| !line.starts_with(':')).map(str::to_string).collect();
+            if !lines.is_empty() {
+                return lines;
+            }
+            buffer.drain(..end + 2);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_todo_events_stream() {
+    use axum_api::domain::todos::{TodoChange, TodoChangeKind};
+
+    let config = Config::default();
+    let state = AppState::new(Repositories::in_memory(), &config);
+    let feed = state.change_feed.clone();
+    let app = signed_in(&config, state);
+    let change = |seq: i64, kind: TodoChangeKind, done: bool| TodoChange { seq, kind, todo: Todo { done, ..create_test_todo() } };
+    feed.publish(change(1, TodoChangeKind::Created, false));
+    feed.publish(change(2, TodoChangeKind::Updated, false));
+    feed.publish(change(3, TodoChangeKind::Updated, true));
+
+    let open = |last_event_id: &str| {
+        let request = Request::builder().uri("/todos/events?done=true").header("last-event-id", last_event_id);
+        app.clone().oneshot(request.body(Body::empty()).unwrap())
+    };
+
+    let response = open("1").await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+    let mut body = response.into_body();
+    let message = next_sse_message(&mut body).await;
+    assert!(message.contains(&"event: updated".to_string()));
+    assert!(message.contains(&"id: 3".to_string()));
+    let data: Value = serde_json::from_str(message.iter().find_map(|line| line.strip_prefix("data: ")).unwrap()).unwrap();
+    assert_eq!(data["done"], true);
+
+    feed.publish(change(4, TodoChangeKind::Deleted, false));
+    feed.publish(change(5, TodoChangeKind::Deleted, true));
+    let message = next_sse_message(&mut body).await;
+    assert!(message.contains(&"event: deleted".to_string()));
+    assert!(message.contains(&"id: 5".to_string()));
+
+    let mut body = open("42").await.unwrap().into_body();
+    assert!(next_sse_message(&mut body).await.contains(&"event: resync".to_string()));
+
+    assert_eq!(open("soon").await.unwrap().status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_todo_events_reach_viewers_of_shared_todos() {
+    use axum_api::domain::todos::{TodoChange, TodoChangeKind};
+
+    let config = Config::default();
+    let state = AppState::new(Repositories::in_memory(), &config);
+    let feed = state.change_feed.clone();
+    let app = signed_in(&config, state.clone());
+    let (_, bob) = send(&app, "POST", "/auth/register", Some(json!({ "email": "bob@example.com", "password": "correct horse" }))).await;
+    let bob_id: Uuid = bob["id"].as_str().unwrap().parse().unwrap();
+    let bob_token = format!("Bearer {}", state.access_tokens.issue(bob_id, "bob@example.com", DEFAULT_TENANT));
+
+    let (_, shared) = send(&app, "POST", "/todos", Some(json!({ "title": "Team milk" }))).await;
+    let (_, private) = send(&app, "POST", "/todos", Some(json!({ "title": "Diary" }))).await;
+    let access = format!("/todos/{}/access", shared["id"].as_str().unwrap());
+    let (status, _) = send(&app, "POST", &access, Some(json!({ "email": "bob@example.com", "role": "viewer" }))).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let request = Request::builder().uri("/todos/events").header("authorization", bob_token.as_str());
+    let response = app.clone().oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let mut body = response.into_body();
+
+    let change = |seq: i64, todo: &Value| TodoChange {
+        seq,
+        kind: TodoChangeKind::Updated,
+        todo: serde_json::from_value(todo.clone()).unwrap(),
+    };
+    feed.publish(change(1, &private));
+    feed.publish(change(2, &shared));
+    let message = next_sse_message(&mut body).await;
+    assert!(message.contains(&"id: 2".to_string()), "{message:?}");
+    assert!(message.contains(&"event: updated".to_string()));
 }
@@ -0,0 +1,104 @@
+use axum::{http::StatusCode, Router};
+use axum_api::{
+    application::todos::TodoDomainEvents,
+    config::Config,
+    domain::todos::{NewTodoEvent, OutboxMessage, Todo},
+    infrastructure::database::Repositories,
+    state::AppState,
+};
+use chrono::Utc;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::support::{send, signed_in, TEST_USER};
+
+fn test_app() -> (Router, Repositories) {
+    let config = Config::default();
+    let repositories = Repositories::in_memory();
+    (signed_in(&config, AppState::new(repositories.clone(), &config)), repositories)
+}
+
+#[tokio::test]
+async fn test_webhook_crud() {
+    let (app, _) = test_app();
+
+    let (status, created) = send(&app, "POST", "/webhooks", Some(json!({
+        "url": "https://example.com/hooks",
+        "events": ["TodoCompleted"],
+        "secret": "0123456789abcdef"
+    }))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(created["events"], json!(["TodoCompleted"]));
+    assert_eq!(created["active"], true);
+    assert!(created.get("secret").is_none());
+    let uri = format!("/webhooks/{}", created["id"].as_str().unwrap());
+
+    let (status, found) = send(&app, "GET", &uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(found["url"], "https://example.com/hooks");
+
+    let (status, list) = send(&app, "GET", "/webhooks", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(list["pagination"]["total"], 1);
+
+    let (status, updated) = send(&app, "PATCH", &uri, Some(json!({ "events": [], "active": false }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["events"], json!([]));
+    assert_eq!(updated["active"], false);
+
+    let (status, _) = send(&app, "DELETE", &uri, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, body) = send(&app, "GET", &uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["status"], 404);
+}
+
+#[tokio::test]
+async fn test_webhook_validation() {
+    let (app, _) = test_app();
+
+    let (status, body) = send(&app, "POST", "/webhooks", Some(json!({
+        "url": "ftp://example.com",
+        "events": ["TodoArchived"],
+        "secret": "short"
+    }))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"].as_array().unwrap().len(), 3);
+
+    let metadata = json!({ "url": "http://169.254.169.254/latest/meta-data", "secret": "0123456789abcdef" });
+    let (status, body) = send(&app, "POST", "/webhooks", Some(metadata)).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["errors"][0]["code"], "private_address");
+
+    let (status, _) = send(&app, "PATCH", &format!("/webhooks/{}", Uuid::new_v4()), Some(json!({}))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    let (status, _) = send(&app, "PATCH", &format!("/webhooks/{}", Uuid::new_v4()), Some(json!({ "active": true }))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_delivery_log() {
+    let (app, repositories) = test_app();
+    let (_, created) = send(&app, "POST", "/webhooks", Some(json!({
+        "url": "https://example.com/hooks",
+        "secret": "0123456789abcdef"
+    }))).await;
+
+    let now = Utc::now();
+    let todo = Todo { id: Uuid::new_v4(), title: "Buy milk".to_string(), done: false, description: None, completed_at: None, due_at: None, priority: Default::default(), created_at: now, updated_at: now, version: 1, list_id: None, tags: Vec::new(), owner_id: Some(TEST_USER), tenant_id: "default".to_string() };
+    let change = NewTodoEvent::created(&todo);
+    for message in OutboxMessage::queue(&change, TodoDomainEvents::created(&change)) {
+        repositories.webhooks.enqueue(&message).await.unwrap();
+    }
+
+    let uri = format!("/webhooks/{}/deliveries", created["id"].as_str().unwrap());
+    let (status, log) = send(&app, "GET", &uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(log["pagination"]["total"], 1);
+    assert_eq!(log["data"][0]["event_type"], "TodoCreated");
+    assert_eq!(log["data"][0]["status"], "pending");
+    assert_eq!(log["data"][0]["payload"]["data"]["todo"]["title"], "Buy milk");
+
+    let (status, _) = send(&app, "GET", &format!("/webhooks/{}/deliveries", Uuid::new_v4()), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,267 @@
+use std::time::Duration;
+
+use axum::http::{header::AUTHORIZATION, HeaderValue};
+use axum_api::{
+    app::build_app,
+    application::api_keys::{CreateApiKeyUseCase, RevokeApiKeyUseCase},
+    config::Config,
+    domain::api_keys::{ApiKeyScope, CreateApiKeyRequest},
+    infrastructure::{change_feed, database::Repositories},
+    request_context::{RequestContext, DEFAULT_TENANT},
+    state::AppState,
+};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{client::IntoClientRequest, protocol::frame::coding::CloseCode, Error, Message},
+    MaybeTlsStream, WebSocketStream,
+};
+
+use crate::support::{access_token, TEST_USER};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Serves the app on a local port, with the change feed polling the
+/// in-memory audit log. The URL carries an access token, the way browsers
+/// authenticate the handshake.
+async fn serve(config: Config) -> String {
+    let (url, state) = serve_anonymous(config).await;
+    format!("{url}?access_token={}", access_token(&state))
+}
+
+/// Like `serve`, but the URL carries no credentials; the state gives access
+/// to the app's repositories.
+async fn serve_anonymous(mut config: Config) -> (String, AppState) {
+    config.changes.poll_interval_ms = 10;
+    let repositories = Repositories::in_memory();
+    let source = repositories.changes.clone();
+    let state = AppState::new(repositories, &config);
+    change_feed::spawn(source, state.todo_repository.clone(), state.change_feed.clone(), &config.changes);
+    let app = build_app(&config, state.clone());
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (format!("ws://{address}/ws"), state)
+}
+
+async fn connect(url: &str) -> Socket {
+    connect_async(url).await.unwrap().0
+}
+
+async fn send(socket: &mut Socket, message: Value) {
+    socket.send(Message::text(message.to_string())).await.unwrap();
+}
+
+/// The next JSON message, skipping heartbeats.
+async fn receive(socket: &mut Socket) -> Value {
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(5), socket.next())
+            .await
+            .expect("no message arrived")
+            .unwrap()
+            .unwrap();
+        if let Message::Text(text) = message {
+            return serde_json::from_str(&text).unwrap();
+        }
+    }
+}
+
+async fn request(socket: &mut Socket, message: Value) -> Value {
+    send(socket, message).await;
+    receive(socket).await
+}
+
+/// The close frame the server ends the connection with, skipping heartbeats.
+async fn closed_with(socket: &mut Socket) -> (CloseCode, String) {
+    loop {
+        match tokio::time::timeout(Duration::from_secs(5), socket.next()).await.expect("the connection stayed open") {
+            Some(Ok(Message::Close(Some(frame)))) => return (frame.code, frame.reason.into_owned()),
+            Some(Ok(Message::Ping(_) | Message::Pong(_))) => {}
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_subscriptions_receive_changes_made_over_the_socket() {
+    let url = serve(Config::default()).await;
+    let mut editor = connect(&url).await;
+    let mut watcher = connect(&url).await;
+
+    let reply = request(&mut watcher, json!({"type": "subscribe", "ref": "1", "subscription": "open", "done": false})).await;
+    assert_eq!(reply, json!({"type": "ack", "ref": "1"}));
+
+    let reply = request(&mut editor, json!({"type": "create", "ref": "2", "todo": {"title": "Buy milk"}})).await;
+    assert_eq!(reply["type"], "result");
+    assert_eq!(reply["ref"], "2");
+    assert_eq!(reply["todo"]["version"], 1);
+    let id = reply["todo"]["id"].as_str().unwrap().to_string();
+
+    let change = receive(&mut watcher).await;
+    assert_eq!(change["type"], "change");
+    assert_eq!(change["kind"], "created");
+    assert_eq!(change["subscriptions"], json!(["open"]));
+    assert_eq!(change["todo"]["id"], id.as_str());
+    assert!(change["seq"].is_i64());
+
+    // Completing the todo takes it out of "open"; only the todo's own subscription sees it.
+    let reply = request(&mut watcher, json!({"type": "subscribe", "subscription": "milk", "todo_ids": [id]})).await;
+    assert_eq!(reply, json!({"type": "ack"}));
+    let reply = request(
+        &mut editor,
+        json!({"type": "update", "ref": "3", "id": id, "changes": {"done": true}, "version": 1}),
+    )
+    .await;
+    assert_eq!(reply["todo"]["done"], true);
+    let change = receive(&mut watcher).await;
+    assert_eq!(change["kind"], "updated");
+    assert_eq!(change["subscriptions"], json!(["milk"]));
+
+    let reply = request(&mut watcher, json!({"type": "unsubscribe", "subscription": "milk"})).await;
+    assert_eq!(reply["type"], "ack");
+    let reply = request(&mut editor, json!({"type": "delete", "ref": "4", "id": id, "version": 2})).await;
+    assert_eq!(reply, json!({"type": "ack", "ref": "4"}));
+    let reply = request(&mut editor, json!({"type": "create", "todo": {"title": "Walk the dog"}})).await;
+    let dog = reply["todo"]["id"].clone();
+
+    // The deleted todo was done and "milk" is gone, so the next change is the new todo's.
+    let change = receive(&mut watcher).await;
+    assert_eq!(change["kind"], "created");
+    assert_eq!(change["todo"]["id"], dog);
+}
+
+#[tokio::test]
+async fn test_errors_are_problem_details() {
+    let url = serve(Config::default()).await;
+    let mut socket = connect(&url).await;
+
+    let reply = request(&mut socket, json!({"type": "fly", "ref": "1"})).await;
+    assert_eq!(reply["type"], "error");
+    assert_eq!(reply["error"]["code"], "bad_request");
+
+    let reply = request(&mut socket, json!({"type": "create", "ref": "2", "todo": {"title": " "}})).await;
+    assert_eq!(reply["ref"], "2");
+    assert_eq!(reply["error"]["status"], 422);
+    assert_eq!(reply["error"]["errors"][0]["field"], "title");
+
+    let reply = request(&mut socket, json!({"type": "create", "todo": {"title": "Buy milk"}})).await;
+    let id = reply["todo"]["id"].clone();
+    let reply = request(&mut socket, json!({"type": "update", "ref": "3", "id": id, "changes": {"done": true}, "version": 7})).await;
+    assert_eq!(reply["error"]["code"], "precondition_failed");
+    let reply = request(&mut socket, json!({"type": "delete", "ref": "4", "id": "00000000-0000-0000-0000-000000000000"})).await;
+    assert_eq!(reply["error"]["code"], "not_found");
+    let reply = request(&mut socket, json!({"type": "unsubscribe", "subscription": "nothing"})).await;
+    assert_eq!(reply["error"]["status"], 404);
+    let reply = request(&mut socket, json!({"type": "subscribe", "subscription": ""})).await;
+    assert_eq!(reply["error"]["status"], 400);
+
+    socket.send(Message::binary(vec![1, 2, 3])).await.unwrap();
+    assert_eq!(receive(&mut socket).await["error"]["code"], "bad_request");
+
+    // The connection survives every error.
+    assert_eq!(request(&mut socket, json!({"type": "ping", "ref": "5"})).await, json!({"type": "pong", "ref": "5"}));
+}
+
+#[tokio::test]
+async fn test_limits_and_required_versions() {
+    let mut config = Config::default();
+    config.websocket.max_subscriptions = 1;
+    config.websocket.max_message_bytes = 1024;
+    config.features.require_if_match = true;
+    let url = serve(config).await;
+    let mut socket = connect(&url).await;
+
+    assert_eq!(request(&mut socket, json!({"type": "subscribe", "subscription": "a"})).await["type"], "ack");
+    assert_eq!(request(&mut socket, json!({"type": "subscribe", "subscription": "a", "done": true})).await["type"], "ack");
+    let reply = request(&mut socket, json!({"type": "subscribe", "subscription": "b"})).await;
+    assert_eq!(reply["error"]["status"], 400);
+
+    let reply = request(&mut socket, json!({"type": "create", "todo": {"title": "Buy milk"}})).await;
+    let id = reply["todo"]["id"].clone();
+    let reply = request(&mut socket, json!({"type": "update", "id": id, "changes": {"done": true}})).await;
+    assert_eq!(reply["error"]["code"], "precondition_required");
+    let reply = request(&mut socket, json!({"type": "delete", "id": id})).await;
+    assert_eq!(reply["error"]["code"], "precondition_required");
+
+    // An oversized message closes the connection.
+    let title = "x".repeat(2048);
+    send(&mut socket, json!({"type": "create", "todo": {"title": title}})).await;
+    let closed = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            match socket.next().await {
+                None | Some(Err(_)) | Some(Ok(Message::Close(_))) => return,
+                Some(Ok(_)) => {}
+            }
+        }
+    })
+    .await;
+    assert!(closed.is_ok(), "the connection stayed open");
+}
+
+#[tokio::test]
+async fn test_silent_clients_are_disconnected() {
+    let mut config = Config::default();
+    config.websocket.ping_interval_secs = 1;
+    config.websocket.idle_timeout_secs = 2;
+    let url = serve(config).await;
+    let mut socket = connect(&url).await;
+
+    // Not reading means the server's pings go unanswered.
+    tokio::time::sleep(Duration::from_millis(2500)).await;
+    let mut pings = 0;
+    loop {
+        match tokio::time::timeout(Duration::from_secs(5), socket.next()).await.unwrap() {
+            Some(Ok(Message::Ping(_))) => pings += 1,
+            Some(Ok(Message::Close(Some(frame)))) => {
+                assert_eq!(frame.reason, "idle timeout");
+                break;
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+    assert!(pings >= 1);
+}
+
+#[tokio::test]
+async fn test_connections_close_when_the_access_token_expires() {
+    let mut config = Config::default();
+    config.auth.access_token_ttl_secs = 1;
+    let url = serve(config).await;
+    let mut socket = connect(&url).await;
+
+    assert_eq!(closed_with(&mut socket).await, (CloseCode::Policy, "credentials expired".to_string()));
+}
+
+#[tokio::test]
+async fn test_connections_close_when_the_api_key_is_revoked() {
+    let (url, state) = serve_anonymous(Config::default()).await;
+    let context = RequestContext { tenant_id: Some(DEFAULT_TENANT.to_string()), ..RequestContext::for_user(TEST_USER) };
+    let keys = &*state.api_key_repository;
+    let key = CreateApiKeyRequest { name: "sync".to_string(), scope: ApiKeyScope::ReadWrite, expires_at: None };
+    let created = context.clone().scope(CreateApiKeyUseCase::new(keys).execute(TEST_USER, key)).await.unwrap();
+
+    let mut handshake = url.into_client_request().unwrap();
+    let authorization = HeaderValue::from_str(&format!("ApiKey {}", created.key)).unwrap();
+    handshake.headers_mut().insert(AUTHORIZATION, authorization);
+    let mut socket = connect_async(handshake).await.unwrap().0;
+    let reply = request(&mut socket, json!({"type": "create", "todo": {"title": "Buy milk"}})).await;
+    assert_eq!(reply["type"], "result");
+
+    context.scope(RevokeApiKeyUseCase::new(keys).execute(TEST_USER, created.api_key.id)).await.unwrap();
+    send(&mut socket, json!({"type": "create", "todo": {"title": "Walk the dog"}})).await;
+    assert_eq!(closed_with(&mut socket).await, (CloseCode::Policy, "API key revoked".to_string()));
+}
+
+#[tokio::test]
+async fn test_handshake_requires_an_access_token() {
+    let url = serve(Config::default()).await;
+    let (anonymous, _) = url.split_once('?').unwrap();
+
+    match connect_async(anonymous).await {
+        Err(Error::Http(response)) => assert_eq!(response.status(), 401),
+        other => panic!("expected a 401 response, got {other:?}"),
+    }
+}
@@ -0,0 +1,15 @@
+mod support;
+
+mod handlers {
+    mod api_key_handlers_tests;
+    mod auth_handlers_tests;
+    mod health_tests;
+    mod list_handlers_tests;
+    mod tag_handlers_tests;
+    mod todo_handlers_tests;
+    mod webhook_handlers_tests;
+    mod ws_handlers_tests;
+}
+
+mod cursor_tests;
+mod tenant_tests;
@@ -0,0 +1,86 @@
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header::AUTHORIZATION, HeaderMap, HeaderValue, StatusCode},
+    middleware, Router,
+};
+use axum_api::{
+    app::build_app, config::Config, infrastructure::database::Repositories, request_context::DEFAULT_TENANT, state::AppState,
+};
+use http_body_util::BodyExt;
+use serde_json::Value;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+/// The user `signed_in` apps authenticate as.
+pub const TEST_USER: Uuid = Uuid::from_u128(1);
+
+pub fn access_token(state: &AppState) -> String {
+    state.access_tokens.issue(TEST_USER, "tester@example.com", DEFAULT_TENANT)
+}
+
+/// The app, with every request that carries no `Authorization` header
+/// signed in as `TEST_USER`.
+pub fn signed_in(config: &Config, state: AppState) -> Router {
+    let header = HeaderValue::from_str(&format!("Bearer {}", access_token(&state))).unwrap();
+    build_app(config, state).layer(middleware::map_request(move |mut request: Request| {
+        let header = header.clone();
+        async move {
+            request.headers_mut().entry(AUTHORIZATION).or_insert(header);
+            request
+        }
+    }))
+}
+
+/// An in-memory app signed in as `TEST_USER`.
+pub fn test_app() -> Router {
+    let config = Config::default();
+    signed_in(&config, AppState::new(Repositories::in_memory(), &config))
+}
+
+pub async fn send(app: &Router, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let (status, _, json) = send_with(app, method, uri, &[], body).await;
+    (status, json)
+}
+
+/// Sends `body` as JSON, unless `headers` name another content type, and
+/// returns the response with its headers. Bodies that are not JSON come
+/// back as `Value::Null`.
+pub async fn send_with(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    headers: &[(&str, &str)],
+    body: Option<Value>,
+) -> (StatusCode, HeaderMap, Value) {
+    let mut request = Request::builder().method(method).uri(uri);
+    if !headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("content-type")) {
+        request = request.header("content-type", "application/json");
+    }
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let request = match body {
+        Some(body) => request.body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    }
+    .unwrap();
+
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, headers, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+/// Creates a resource and returns its id.
+pub async fn create(app: &Router, uri: &str, body: Value) -> String {
+    let (status, created) = send(app, "POST", uri, Some(body)).await;
+    assert!(status.is_success(), "POST {uri} returned {status}: {created}");
+    created["id"].as_str().unwrap().to_string()
+}
+
+/// The titles of a page of todos, in order.
+pub fn titles(page: &Value) -> Vec<&str> {
+    page["data"].as_array().unwrap().iter().map(|todo| todo["title"].as_str().unwrap()).collect()
+}
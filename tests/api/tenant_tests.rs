@@ -0,0 +1,225 @@
+use axum::{http::StatusCode, Router};
+use axum_api::{
+    api::tenant::{is_tenant_id, tenant_for},
+    app::build_app,
+    config::Config,
+    error::ApiError,
+    infrastructure::database::Repositories,
+    state::AppState,
+};
+use serde_json::json;
+
+use crate::support::send_with;
+
+fn test_app(configure: impl FnOnce(&mut Config)) -> Router {
+    let mut config = Config::default();
+    configure(&mut config);
+    build_app(&config, AppState::new(Repositories::in_memory(), &config))
+}
+
+/// Registers `email` in the tenant `headers` name, signs in there and
+/// returns the `Authorization` header value.
+async fn sign_in(app: &Router, email: &str, headers: &[(&str, &str)]) -> String {
+    let credentials = json!({ "email": email, "password": "correct horse" });
+    let (status, _, _) = send_with(app, "POST", "/auth/register", headers, Some(credentials.clone())).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _, tokens) = send_with(app, "POST", "/auth/login", headers, Some(credentials)).await;
+    assert_eq!(status, StatusCode::OK);
+    format!("Bearer {}", tokens["access_token"].as_str().unwrap())
+}
+
+#[test]
+fn test_tenant_ids_are_dns_labels() {
+    assert!(is_tenant_id("acme"));
+    assert!(is_tenant_id("acme-eu-2"));
+    assert!(!is_tenant_id(""));
+    assert!(!is_tenant_id("Acme"));
+    assert!(!is_tenant_id("-acme"));
+    assert!(!is_tenant_id("acme.eu"));
+    assert!(!is_tenant_id(&"a".repeat(64)));
+}
+
+#[test]
+fn test_access_tokens_pin_their_tenant() {
+    assert_eq!(tenant_for("acme", None).unwrap(), "acme");
+    assert_eq!(tenant_for("acme", Some("acme")).unwrap(), "acme");
+    assert!(matches!(tenant_for("acme", Some("globex")), Err(ApiError::Forbidden(_))));
+}
+
+#[tokio::test]
+async fn test_tenants_are_kept_apart() {
+    let app = test_app(|config| config.tenancy.enabled = true);
+    let acme = sign_in(&app, "ada@acme.example", &[("x-tenant-id", "acme")]).await;
+    let globex = sign_in(&app, "bob@globex.example", &[("x-tenant-id", "globex")]).await;
+
+    let (status, _, todo) = send_with(&app, "POST", "/todos", &[("authorization", &acme)], Some(json!({ "title": "Quarterly report" }))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(todo["tenant_id"], "acme");
+    let uri = format!("/todos/{}", todo["id"].as_str().unwrap());
+
+    let (status, _, _) = send_with(&app, "GET", &uri, &[("authorization", &acme)], None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _, _) = send_with(&app, "GET", &uri, &[("authorization", &globex)], None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, _, page) = send_with(&app, "GET", "/todos", &[("authorization", &globex)], None).await;
+    assert_eq!(page["pagination"]["total"], 0);
+
+    // A token stays in the tenant it was issued in.
+    let (status, _, error) = send_with(&app, "GET", "/todos", &[("authorization", &acme), ("x-tenant-id", "globex")], None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(error["code"], "forbidden");
+    let (status, _, _) = send_with(&app, "GET", "/todos", &[("authorization", &acme), ("x-tenant-id", "Acme!")], None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_signing_in_needs_a_tenant() {
+    let app = test_app(|config| config.tenancy.enabled = true);
+    let credentials = json!({ "email": "ada@acme.example", "password": "correct horse" });
+
+    for path in ["/auth/register", "/auth/login"] {
+        let (status, _, error) = send_with(&app, "POST", path, &[], Some(credentials.clone())).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{path}");
+        assert_eq!(error["code"], "bad_request");
+    }
+}
+
+#[tokio::test]
+async fn test_accounts_only_sign_in_to_their_tenant() {
+    let app = test_app(|config| config.tenancy.enabled = true);
+    let (acme, globex) = ([("x-tenant-id", "acme")], [("x-tenant-id", "globex")]);
+    let ada = json!({ "email": "ada@acme.example", "password": "correct horse" });
+    sign_in(&app, "ada@acme.example", &acme).await;
+
+    let (status, _, error) = send_with(&app, "POST", "/auth/login", &globex, Some(ada.clone())).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(error["code"], "unauthorized");
+
+    // A refresh token only renews its session in the tenant it was issued in.
+    let (_, _, tokens) = send_with(&app, "POST", "/auth/login", &acme, Some(ada)).await;
+    let refresh = json!({ "refresh_token": tokens["refresh_token"] });
+    let (status, _, _) = send_with(&app, "POST", "/auth/refresh", &globex, Some(refresh.clone())).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _, _) = send_with(&app, "POST", "/auth/refresh", &acme, Some(refresh)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // The same email may register separately in another tenant.
+    let (status, _, _) = send_with(
+        &app,
+        "POST",
+        "/auth/register",
+        &globex,
+        Some(json!({ "email": "ada@acme.example", "password": "another horse" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn test_api_keys_only_work_in_their_tenant() {
+    let app = test_app(|config| config.tenancy.enabled = true);
+    let acme = sign_in(&app, "ada@acme.example", &[("x-tenant-id", "acme")]).await;
+    let (status, _, created) = send_with(
+        &app,
+        "POST",
+        "/api-keys",
+        &[("authorization", &acme)],
+        Some(json!({ "name": "ci", "scope": "read_write" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(created["api_key"]["tenant_id"], "acme");
+    let key = format!("ApiKey {}", created["key"].as_str().unwrap());
+
+    let (status, _, todo) = send_with(
+        &app,
+        "POST",
+        "/todos",
+        &[("authorization", &key), ("x-tenant-id", "acme")],
+        Some(json!({ "title": "Quarterly report" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(todo["tenant_id"], "acme");
+
+    // Keys are looked up in the tenant the request names, so elsewhere the
+    // key is unknown, and a request naming none cannot use one.
+    let (status, _, _) = send_with(&app, "GET", "/todos", &[("authorization", &key), ("x-tenant-id", "globex")], None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _, _) = send_with(&app, "GET", "/todos", &[("authorization", &key)], None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Nor is the key listed there.
+    let globex = sign_in(&app, "bob@globex.example", &[("x-tenant-id", "globex")]).await;
+    let (_, _, page) = send_with(&app, "GET", "/api-keys", &[("authorization", &globex)], None).await;
+    assert_eq!(page["pagination"]["total"], 0);
+}
+
+#[tokio::test]
+async fn test_webhooks_stay_in_their_tenant() {
+    let app = test_app(|config| config.tenancy.enabled = true);
+    let acme = sign_in(&app, "ada@acme.example", &[("x-tenant-id", "acme")]).await;
+    let globex = sign_in(&app, "bob@globex.example", &[("x-tenant-id", "globex")]).await;
+    let (status, _, webhook) = send_with(
+        &app,
+        "POST",
+        "/webhooks",
+        &[("authorization", &acme)],
+        Some(json!({ "url": "https://example.com/hooks", "secret": "0123456789abcdef" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(webhook["tenant_id"], "acme");
+    let uri = format!("/webhooks/{}", webhook["id"].as_str().unwrap());
+
+    let (status, _, _) = send_with(&app, "GET", &uri, &[("authorization", &globex)], None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, _, page) = send_with(&app, "GET", "/webhooks", &[("authorization", &globex)], None).await;
+    assert_eq!(page["pagination"]["total"], 0);
+}
+
+#[tokio::test]
+async fn test_subdomains_name_the_tenant() {
+    let app = test_app(|config| {
+        config.tenancy.enabled = true;
+        config.tenancy.base_domain = Some("todos.example.com".to_string());
+    });
+    let token = sign_in(&app, "ada@acme.example", &[("host", "acme.todos.example.com")]).await;
+
+    let (status, _, todo) = send_with(
+        &app,
+        "POST",
+        "/todos",
+        &[("authorization", &token), ("host", "acme.todos.example.com:3000")],
+        Some(json!({ "title": "Quarterly report" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(todo["tenant_id"], "acme");
+
+    let (_, _, page) = send_with(&app, "GET", "/todos", &[("authorization", &token), ("host", "acme.todos.example.com")], None).await;
+    assert_eq!(page["pagination"]["total"], 1);
+    let (status, _, _) = send_with(&app, "GET", "/todos", &[("authorization", &token), ("host", "globex.todos.example.com")], None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (_, _, page) = send_with(&app, "GET", "/todos", &[("authorization", &token), ("host", "todos.example.com")], None).await;
+    assert_eq!(page["pagination"]["total"], 1);
+    let (status, _, _) = send_with(&app, "POST", "/auth/login", &[("host", "todos.example.com")], Some(json!({ "email": "ada@acme.example", "password": "correct horse" }))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_without_tenancy_every_request_shares_the_default_tenant() {
+    let app = test_app(|_| {});
+    let token = sign_in(&app, "ada@example.com", &[]).await;
+
+    let (status, _, todo) = send_with(
+        &app,
+        "POST",
+        "/todos",
+        &[("authorization", &token), ("x-tenant-id", "acme")],
+        Some(json!({ "title": "Quarterly report" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(todo["tenant_id"], "default");
+}
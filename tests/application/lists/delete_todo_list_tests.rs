@@ -0,0 +1,79 @@
+use axum_api::{
+    application::lists::DeleteTodoListUseCase,
+    application::todos::TodoDomainEvents,
+    domain::lists::{traits::TodoListStore, CreateTodoListRequest},
+    domain::todos::{
+        traits::{TodoCreator, TodoFinder, TodoSharing, TodoTrash},
+        CreateTodoRequest, PaginationQuery, TodoRole,
+    },
+    error::ApiError,
+    infrastructure::database::repositories::InMemoryTodoRepository,
+    request_context::{RequestContext, DEFAULT_TENANT},
+};
+use uuid::Uuid;
+
+fn list_request() -> CreateTodoListRequest {
+    CreateTodoListRequest { name: "Groceries".to_string(), description: None, color: None }
+}
+
+fn todo_in(list_id: Uuid, title: &str) -> CreateTodoRequest {
+    CreateTodoRequest { title: title.to_string(), done: None, list_id: Some(list_id), ..Default::default() }
+}
+
+#[tokio::test]
+async fn test_delete_list_trashes_its_todos() {
+    RequestContext::for_tenant(DEFAULT_TENANT).scope(async {
+        let todos = InMemoryTodoRepository::new();
+        let lists = todos.lists();
+        let list = lists.create(list_request()).await.unwrap();
+        let milk = todos.create(todo_in(list.id, "Buy milk"), TodoDomainEvents::created).await.unwrap();
+        let bread = todos.create(todo_in(list.id, "Buy bread"), TodoDomainEvents::created).await.unwrap();
+        let loose = todos.create(CreateTodoRequest { title: "Call mum".to_string(), done: None, list_id: None, ..Default::default() }, TodoDomainEvents::created).await.unwrap();
+
+        DeleteTodoListUseCase::new(&lists, &todos).execute(list.id).await.unwrap();
+
+        assert!(lists.find_by_id(list.id).await.unwrap().is_none());
+        assert!(todos.find_by_id(milk.id).await.unwrap().is_none());
+        assert!(todos.find_by_id(loose.id).await.unwrap().is_some());
+        let mut trashed: Vec<Uuid> = todos.find_trashed(PaginationQuery::default()).await.unwrap().data.iter().map(|trashed| trashed.todo.id).collect();
+        trashed.sort();
+        let mut expected = vec![milk.id, bread.id];
+        expected.sort();
+        assert_eq!(trashed, expected);
+        assert_eq!(todos.restore(milk.id, TodoDomainEvents::restored).await.unwrap().list_id, None);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_delete_missing_list_is_not_found() {
+    let todos = InMemoryTodoRepository::new();
+    let lists = todos.lists();
+
+    let result = DeleteTodoListUseCase::new(&lists, &todos).execute(Uuid::new_v4()).await;
+
+    assert!(matches!(result, Err(ApiError::NotFound)));
+}
+
+#[tokio::test]
+async fn test_delete_list_holding_a_shared_todo_is_forbidden() {
+    RequestContext::for_tenant(DEFAULT_TENANT).scope(async {
+        let todos = InMemoryTodoRepository::new();
+        let lists = todos.lists();
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let list = RequestContext::for_user(alice).scope(lists.create(list_request())).await.unwrap();
+        let own = RequestContext::for_user(alice).scope(todos.create(todo_in(list.id, "Buy milk"), TodoDomainEvents::created)).await.unwrap();
+        let shared = RequestContext::for_user(bob).scope(todos.create(todo_in(list.id, "Buy bread"), TodoDomainEvents::created)).await.unwrap();
+        todos.grant(shared.id, alice, TodoRole::Editor).await.unwrap();
+
+        let result = RequestContext::for_user(alice)
+            .scope(DeleteTodoListUseCase::new(&lists, &todos).execute(list.id))
+            .await;
+
+        assert!(matches!(result, Err(ApiError::Forbidden(_))));
+        assert!(lists.find_by_id(list.id).await.unwrap().is_some());
+        assert!(todos.find_by_id(own.id).await.unwrap().is_some());
+        assert!(todos.find_by_id(shared.id).await.unwrap().is_some());
+    })
+    .await;
+}
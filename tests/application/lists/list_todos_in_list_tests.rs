@@ -0,0 +1,46 @@
+use axum_api::{
+    application::lists::{ArchiveTodoListUseCase, ListTodosInListUseCase},
+    application::todos::TodoDomainEvents,
+    domain::lists::{traits::TodoListStore, CreateTodoListRequest},
+    domain::todos::{traits::TodoCreator, CreateTodoRequest, PaginationQuery},
+    error::ApiError,
+    infrastructure::database::repositories::InMemoryTodoRepository,
+    request_context::{RequestContext, DEFAULT_TENANT},
+};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_lists_todos_of_an_archived_list() {
+    RequestContext::for_tenant(DEFAULT_TENANT).scope(async {
+        let todos = InMemoryTodoRepository::new();
+        let lists = todos.lists();
+        let list = lists.create(CreateTodoListRequest { name: "Someday".to_string(), description: None, color: None }).await.unwrap();
+        let todo = todos.create(CreateTodoRequest { title: "Learn the cello".to_string(), done: None, list_id: Some(list.id), ..Default::default() }, TodoDomainEvents::created).await.unwrap();
+        todos.create(CreateTodoRequest { title: "Buy milk".to_string(), done: None, list_id: None, ..Default::default() }, TodoDomainEvents::created).await.unwrap();
+        ArchiveTodoListUseCase::new(&lists).execute(list.id, true).await.unwrap();
+
+        let page = ListTodosInListUseCase::new(&lists, &todos).execute(list.id, PaginationQuery::default()).await.unwrap();
+
+        assert_eq!(page.data.iter().map(|todo| todo.id).collect::<Vec<_>>(), vec![todo.id]);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_someone_elses_list_is_not_found() {
+    RequestContext::for_tenant(DEFAULT_TENANT).scope(async {
+        let todos = InMemoryTodoRepository::new();
+        let lists = todos.lists();
+        let list = RequestContext::for_user(Uuid::new_v4())
+            .scope(lists.create(CreateTodoListRequest { name: "Private".to_string(), description: None, color: None }))
+            .await
+            .unwrap();
+
+        let result = RequestContext::for_user(Uuid::new_v4())
+            .scope(ListTodosInListUseCase::new(&lists, &todos).execute(list.id, PaginationQuery::default()))
+            .await;
+
+        assert!(matches!(result, Err(ApiError::NotFound)));
+    })
+    .await;
+}
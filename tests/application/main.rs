@@ -0,0 +1,22 @@
+mod todos {
+    mod authorization_tests;
+    mod batch_todos_tests;
+    mod create_todo_tests;
+    mod domain_events_tests;
+    mod get_todo_tests;
+    mod list_todos_tests;
+    mod update_todo_tests;
+    mod patch_todo_tests;
+    mod purge_expired_trash_tests;
+    mod relay_outbox_tests;
+    mod search_todos_tests;
+}
+
+mod lists {
+    mod delete_todo_list_tests;
+    mod list_todos_in_list_tests;
+}
+
+mod webhooks {
+    mod deliver_webhooks_tests;
+}
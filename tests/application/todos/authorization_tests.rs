@@ -0,0 +1,56 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use axum_api::{
+    application::todos::TodoAuthorizer,
+    domain::todos::{TodoRole, traits::TodoRoles},
+    error::ApiError,
+    request_context::RequestContext,
+};
+use uuid::Uuid;
+
+#[derive(Default)]
+struct MockRepo {
+    role: Option<TodoRole>,
+    lookups: AtomicUsize,
+}
+
+#[async_trait::async_trait]
+impl TodoRoles for MockRepo {
+    async fn role_of(&self, _id: Uuid, _user_id: Uuid) -> Result<Option<TodoRole>, ApiError> {
+        self.lookups.fetch_add(1, Ordering::Relaxed);
+        Ok(self.role)
+    }
+}
+
+async fn authorize(role: Option<TodoRole>, required: TodoRole) -> Result<(), ApiError> {
+    let repo = MockRepo { role, ..Default::default() };
+    RequestContext::for_user(Uuid::new_v4())
+        .scope(TodoAuthorizer::new(&repo).authorize(Uuid::new_v4(), required))
+        .await
+}
+
+#[tokio::test]
+async fn test_roles_include_the_ones_below_them() {
+    assert!(authorize(Some(TodoRole::Owner), TodoRole::Owner).await.is_ok());
+    assert!(authorize(Some(TodoRole::Owner), TodoRole::Editor).await.is_ok());
+    assert!(authorize(Some(TodoRole::Editor), TodoRole::Editor).await.is_ok());
+    assert!(authorize(Some(TodoRole::Viewer), TodoRole::Viewer).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_users_who_can_see_the_todo_are_forbidden() {
+    assert!(matches!(authorize(Some(TodoRole::Viewer), TodoRole::Editor).await, Err(ApiError::Forbidden(_))));
+    assert!(matches!(authorize(Some(TodoRole::Editor), TodoRole::Owner).await, Err(ApiError::Forbidden(_))));
+}
+
+#[tokio::test]
+async fn test_users_without_access_do_not_learn_the_todo_exists() {
+    assert!(matches!(authorize(None, TodoRole::Viewer).await, Err(ApiError::NotFound)));
+}
+
+#[tokio::test]
+async fn test_background_work_is_not_checked() {
+    let repo = MockRepo::default();
+    TodoAuthorizer::new(&repo).authorize(Uuid::new_v4(), TodoRole::Owner).await.unwrap();
+    assert_eq!(repo.lookups.load(Ordering::Relaxed), 0);
+}
@@ -0,0 +1,176 @@
+use std::sync::Mutex;
+
+use axum_api::{
+    application::todos::batch_todos::BatchTodosUseCase,
+    domain::lists::{traits::TodoListStore, CreateTodoListRequest},
+    domain::todos::{
+        BatchMode, BatchOperation, BatchOutcome, BatchRequest, DomainEvents, Todo, TodoRole,
+        traits::{TodoBatchWriter, TodoFinder, TodoRoles},
+    },
+    error::ApiError,
+    infrastructure::database::repositories::InMemoryTodoListRepository,
+    request_context::{RequestContext, DEFAULT_TENANT},
+};
+use uuid::Uuid;
+
+#[derive(Default)]
+struct MockRepo {
+    received: Mutex<Option<(usize, BatchMode)>>,
+    /// The list of each create that reached the repository
+    created_in: Mutex<Vec<Option<Uuid>>>,
+    /// The signed-in user's role on every todo
+    role: Option<TodoRole>,
+}
+
+#[async_trait::async_trait]
+impl TodoFinder for MockRepo {
+    async fn find_by_id(&self, _id: Uuid) -> Result<Option<Todo>, ApiError> {
+        Ok(None)
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoRoles for MockRepo {
+    async fn role_of(&self, _id: Uuid, _user_id: Uuid) -> Result<Option<TodoRole>, ApiError> {
+        Ok(self.role)
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoBatchWriter for MockRepo {
+    async fn apply_batch(
+        &self,
+        operations: Vec<BatchOperation>,
+        mode: BatchMode,
+        _events: DomainEvents,
+    ) -> Result<Vec<Result<BatchOutcome, ApiError>>, ApiError> {
+        *self.received.lock().unwrap() = Some((operations.len(), mode));
+        let creates = operations.iter().filter_map(BatchOperation::create_request);
+        self.created_in.lock().unwrap().extend(creates.map(|request| request.list_id));
+        Ok(operations.iter().map(|_| Ok(BatchOutcome::Deleted)).collect())
+    }
+}
+
+fn request(mode: BatchMode) -> BatchRequest {
+    BatchRequest {
+        mode,
+        operations: vec![
+            BatchOperation::Delete { id: Uuid::new_v4(), expected_version: None },
+            BatchOperation::Create { title: " ".to_string(), done: None, description: None, due_at: None, priority: None, list_id: None },
+            BatchOperation::Delete { id: Uuid::new_v4(), expected_version: Some(1) },
+        ],
+    }
+}
+
+#[tokio::test]
+async fn test_invalid_operation_stops_atomic_batch() {
+    let repo = MockRepo::default();
+    let lists = InMemoryTodoListRepository::new();
+    let use_case = BatchTodosUseCase::new(&repo, &lists);
+
+    let response = use_case.execute(request(BatchMode::Atomic), false).await.unwrap();
+    let statuses: Vec<u16> = response.results.iter().map(|result| result.status).collect();
+    assert_eq!(statuses, vec![424, 422, 424]);
+    assert_eq!(response.failed, 3);
+    assert!(repo.received.lock().unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_best_effort_sends_only_valid_operations() {
+    let repo = MockRepo::default();
+    let lists = InMemoryTodoListRepository::new();
+    let use_case = BatchTodosUseCase::new(&repo, &lists);
+
+    let response = use_case.execute(request(BatchMode::BestEffort), false).await.unwrap();
+    let statuses: Vec<u16> = response.results.iter().map(|result| result.status).collect();
+    assert_eq!(statuses, vec![204, 422, 204]);
+    assert_eq!(response.succeeded, 2);
+    assert_eq!(*repo.received.lock().unwrap(), Some((2, BatchMode::BestEffort)));
+}
+
+#[tokio::test]
+async fn test_required_version_rejects_unversioned_writes() {
+    let repo = MockRepo::default();
+    let lists = InMemoryTodoListRepository::new();
+    let use_case = BatchTodosUseCase::new(&repo, &lists);
+
+    let response = use_case.execute(request(BatchMode::BestEffort), true).await.unwrap();
+    assert_eq!(response.results[0].status, 428);
+    assert_eq!(response.results[2].status, 204);
+    assert_eq!(*repo.received.lock().unwrap(), Some((1, BatchMode::BestEffort)));
+}
+
+#[tokio::test]
+async fn test_operations_beyond_the_users_role_are_refused() {
+    let repo = MockRepo { role: Some(TodoRole::Editor), ..Default::default() };
+    let lists = InMemoryTodoListRepository::new();
+    let use_case = BatchTodosUseCase::new(&repo, &lists);
+    let request = BatchRequest {
+        mode: BatchMode::BestEffort,
+        operations: vec![
+            BatchOperation::Update { id: Uuid::new_v4(), title: None, done: Some(true), description: None, due_at: None, priority: None, list_id: None, expected_version: None },
+            BatchOperation::Delete { id: Uuid::new_v4(), expected_version: None },
+        ],
+    };
+
+    let response = RequestContext::for_user(Uuid::new_v4()).scope(use_case.execute(request, false)).await.unwrap();
+    let statuses: Vec<u16> = response.results.iter().map(|result| result.status).collect();
+    assert_eq!(statuses, vec![204, 403]);
+    assert_eq!(*repo.received.lock().unwrap(), Some((1, BatchMode::BestEffort)));
+}
+
+fn in_tenant(user_id: Uuid) -> RequestContext {
+    RequestContext { tenant_id: Some(DEFAULT_TENANT.to_string()), ..RequestContext::for_user(user_id) }
+}
+
+fn create_in(list_id: Uuid) -> BatchOperation {
+    BatchOperation::Create {
+        title: "Buy milk".to_string(),
+        done: None,
+        description: None,
+        due_at: None,
+        priority: None,
+        list_id: Some(list_id),
+    }
+}
+
+#[tokio::test]
+async fn test_creates_go_into_the_users_list() {
+    let repo = MockRepo::default();
+    let lists = InMemoryTodoListRepository::new();
+    let use_case = BatchTodosUseCase::new(&repo, &lists);
+    let alice = in_tenant(Uuid::new_v4());
+    let list = CreateTodoListRequest { name: "Groceries".to_string(), description: None, color: None };
+    let list = alice.clone().scope(lists.create(list)).await.unwrap();
+
+    let request = BatchRequest { mode: BatchMode::Atomic, operations: vec![create_in(list.id)] };
+    let response = alice.scope(use_case.execute(request, false)).await.unwrap();
+    assert_eq!(response.succeeded, 1);
+    assert_eq!(*repo.created_in.lock().unwrap(), vec![Some(list.id)]);
+}
+
+#[tokio::test]
+async fn test_creates_into_another_users_list_are_refused() {
+    let repo = MockRepo::default();
+    let lists = InMemoryTodoListRepository::new();
+    let use_case = BatchTodosUseCase::new(&repo, &lists);
+    let list = CreateTodoListRequest { name: "Groceries".to_string(), description: None, color: None };
+    let list = in_tenant(Uuid::new_v4()).scope(lists.create(list)).await.unwrap();
+
+    let request = BatchRequest { mode: BatchMode::BestEffort, operations: vec![create_in(list.id)] };
+    let response = in_tenant(Uuid::new_v4()).scope(use_case.execute(request, false)).await.unwrap();
+    assert_eq!(response.results[0].status, 422);
+    let errors = response.results[0].error.as_ref().and_then(|problem| problem.errors.clone()).unwrap();
+    assert_eq!(errors[0].code, "unknown_list");
+    assert!(repo.received.lock().unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_empty_batch_is_rejected() {
+    let repo = MockRepo::default();
+    let lists = InMemoryTodoListRepository::new();
+    let use_case = BatchTodosUseCase::new(&repo, &lists);
+
+    let result = use_case.execute(BatchRequest { mode: BatchMode::Atomic, operations: vec![] }, false).await;
+    assert!(matches!(result, Err(ApiError::Validation(errors)) if errors[0].code == "empty"));
+}
@@ -1,40 +1,111 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use axum_api::{
     application::todos::create_todo::CreateTodoUseCase,
-    domain::todos::{Todo, CreateTodoRequest, traits::TodoCreator},
+    domain::lists::{traits::{TodoListArchiver, TodoListStore}, CreateTodoListRequest},
+    domain::todos::{Todo, CreateTodoRequest, DomainEvents, traits::TodoCreator},
     error::ApiError,
+    infrastructure::database::repositories::InMemoryTodoListRepository,
+    request_context::{RequestContext, DEFAULT_TENANT},
 };
 use uuid::Uuid;
 use chrono::Utc;
 
-struct MockRepo;
+#[derive(Default)]
+struct MockRepo {
+    calls: AtomicUsize,
+}
 
 #[async_trait::async_trait]
 impl TodoCreator for MockRepo {
-    async fn create(&self, _data: CreateTodoRequest) -> Result<Todo, ApiError> {
+    async fn create(&self, data: CreateTodoRequest, _events: DomainEvents) -> Result<Todo, ApiError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
         Ok(Todo {
             id: Uuid::new_v4(),
             title: "Test".to_string(),
             done: false,
+            description: None,
+            completed_at: None,
+            due_at: None,
+            priority: Default::default(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            version: 1,
+            list_id: data.list_id,
+            tags: Vec::new(),
+            owner_id: None,
+            tenant_id: "default".to_string(),
         })
     }
 }
 
 #[tokio::test]
 async fn test_create_todo_use_case_creation() {
-    let mock_repo = MockRepo;
-    let _use_case = CreateTodoUseCase::new(&mock_repo);
-    
-    // Test that the use case was created successfully
-    assert!(true); // If we get here, the use case was created
+    let mock_repo = MockRepo::default();
+    let lists = InMemoryTodoListRepository::new();
+    let use_case = CreateTodoUseCase::new(&mock_repo, &lists);
+
+    let todo = use_case
+        .execute(CreateTodoRequest { title: "Test".to_string(), done: None, list_id: None, ..Default::default() })
+        .await
+        .unwrap();
+    assert_eq!(todo.title, "Test");
+    assert_eq!(mock_repo.calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_create_todo_rejects_invalid_title_before_repository() {
+    let mock_repo = MockRepo::default();
+    let lists = InMemoryTodoListRepository::new();
+    let use_case = CreateTodoUseCase::new(&mock_repo, &lists);
+
+    let result = use_case
+        .execute(CreateTodoRequest { title: "   ".to_string(), done: None, list_id: None, ..Default::default() })
+        .await;
+
+    match result {
+        Err(ApiError::Validation(errors)) => assert_eq!(errors[0].code, "blank"),
+        other => panic!("expected validation error, got {other:?}"),
+    }
+    assert_eq!(mock_repo.calls.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn test_create_todo_only_joins_active_lists() {
+    RequestContext::for_tenant(DEFAULT_TENANT).scope(async {
+        let mock_repo = MockRepo::default();
+        let lists = InMemoryTodoListRepository::new();
+        let use_case = CreateTodoUseCase::new(&mock_repo, &lists);
+        let list = lists
+            .create(CreateTodoListRequest { name: "Groceries".to_string(), description: None, color: None })
+            .await
+            .unwrap();
+        let request = |list_id| CreateTodoRequest { title: "Buy milk".to_string(), done: None, list_id: Some(list_id), ..Default::default() };
+
+        let todo = use_case.execute(request(list.id)).await.unwrap();
+        assert_eq!(todo.list_id, Some(list.id));
+
+        lists.set_archived(list.id, true).await.unwrap();
+        for (list_id, code) in [(Uuid::new_v4(), "unknown_list"), (list.id, "archived_list")] {
+            match use_case.execute(request(list_id)).await {
+                Err(ApiError::Validation(errors)) => assert_eq!(errors[0].code, code),
+                other => panic!("expected validation error, got {other:?}"),
+            }
+        }
+        assert_eq!(mock_repo.calls.load(Ordering::SeqCst), 1);
+    })
+    .await;
 }
 
 #[test]
 fn test_create_todo_request_validation() {
     let request = CreateTodoRequest {
         title: "Test Todo".to_string(),
+        done: None,
+        list_id: None,
+        ..Default::default()
     };
-    
+
     assert_eq!(request.title, "Test Todo");
+    assert!(request.validate().is_ok());
 }
@@ -0,0 +1,38 @@
+use axum_api::{
+    application::todos::TodoDomainEvents,
+    domain::todos::{DomainEvent, DomainEvents, NewTodoEvent, Todo},
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+fn todo(done: bool, version: i64) -> Todo {
+    Todo { id: Uuid::nil(), title: "Buy milk".to_string(), done, description: None, completed_at: None, due_at: None, priority: Default::default(), created_at: Utc::now(), updated_at: Utc::now(), version, list_id: None, tags: Vec::new(), owner_id: None, tenant_id: "default".to_string() }
+}
+
+fn types(events: DomainEvents, change: &NewTodoEvent) -> Vec<&'static str> {
+    events(change).iter().map(DomainEvent::event_type).collect()
+}
+
+#[test]
+fn test_completing_a_todo_emits_completed_after_updated() {
+    let updated = TodoDomainEvents::updated;
+    assert_eq!(types(updated, &NewTodoEvent::updated(&todo(false, 1), &todo(true, 2))), vec!["TodoUpdated", "TodoCompleted"]);
+    assert_eq!(types(updated, &NewTodoEvent::updated(&todo(true, 2), &todo(true, 3))), vec!["TodoUpdated"]);
+    assert_eq!(types(updated, &NewTodoEvent::updated(&todo(true, 3), &todo(false, 4))), vec!["TodoUpdated"]);
+}
+
+#[test]
+fn test_trash_changes_map_to_deleted_and_updated() {
+    assert_eq!(types(TodoDomainEvents::created, &NewTodoEvent::created(&todo(true, 1))), vec!["TodoCreated"]);
+    assert_eq!(types(TodoDomainEvents::deleted, &NewTodoEvent::deleted(&todo(false, 1), 2)), vec!["TodoDeleted"]);
+    assert_eq!(types(TodoDomainEvents::restored, &NewTodoEvent::restored(&todo(false, 3))), vec!["TodoUpdated"]);
+}
+
+#[test]
+fn test_batches_emit_by_kind_and_purges_emit_nothing() {
+    let written = TodoDomainEvents::written;
+    assert_eq!(types(written, &NewTodoEvent::created(&todo(false, 1))), vec!["TodoCreated"]);
+    assert_eq!(types(written, &NewTodoEvent::updated(&todo(false, 1), &todo(true, 2))), vec!["TodoUpdated", "TodoCompleted"]);
+    assert_eq!(types(written, &NewTodoEvent::deleted(&todo(true, 2), 3)), vec!["TodoDeleted"]);
+    assert!(types(written, &NewTodoEvent::purged(&todo(false, 3))).is_empty());
+}
@@ -12,18 +12,14 @@ impl TodoFinder for MockRepo {
     async fn find_by_id(&self, _id: Uuid) -> Result<Option<Todo>, ApiError> {
         Ok(None)
     }
-    
-    async fn find_by_done(&self, _done: bool) -> Result<Vec<Todo>, ApiError> {
-        Ok(vec![])
-    }
 }
 
 #[tokio::test]
 async fn test_get_todo_use_case_creation() {
     let mock_repo = MockRepo;
-    let _use_case = GetTodoUseCase::new(&mock_repo);
-    
-    assert!(true); // If we get here, the use case was created
+    let use_case = GetTodoUseCase::new(&mock_repo);
+
+    assert!(use_case.execute(Uuid::new_v4()).await.unwrap().is_none());
 }
 
 #[test]
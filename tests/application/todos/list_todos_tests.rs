@@ -1,6 +1,6 @@
 use axum_api::{
     application::todos::list_todos::ListTodosUseCase,
-    domain::todos::{Todo, PaginationQuery, PaginatedResponse, PaginationMeta, traits::TodoPaginator},
+    domain::todos::{Todo, PaginationQuery, PaginatedResponse, PaginationMeta, KeysetQuery, KeysetPage, traits::TodoPaginator},
     error::ApiError,
 };
 
@@ -21,14 +21,19 @@ impl TodoPaginator for MockRepo {
             },
         })
     }
+
+    async fn find_keyset(&self, query: KeysetQuery) -> Result<KeysetPage<Todo>, ApiError> {
+        Ok(KeysetPage { data: vec![], has_more: false, total: query.include_total.then_some(0) })
+    }
 }
 
 #[tokio::test]
 async fn test_list_todos_use_case_creation() {
     let mock_repo = MockRepo;
-    let _use_case = ListTodosUseCase::new(&mock_repo);
-    
-    assert!(true); // If we get here, the use case was created
+    let use_case = ListTodosUseCase::new(&mock_repo);
+
+    let result = use_case.execute(PaginationQuery { page: 1, limit: 10, ..Default::default() }).await.unwrap();
+    assert!(result.data.is_empty());
 }
 
 #[test]
@@ -36,8 +41,31 @@ fn test_pagination_query_validation() {
     let pagination = PaginationQuery {
         page: 1,
         limit: 10,
+        ..Default::default()
     };
     
     assert_eq!(pagination.page, 1);
     assert_eq!(pagination.limit, 10);
 }
+
+#[tokio::test]
+async fn test_list_todos_keyset() {
+    let use_case = ListTodosUseCase::new(&MockRepo);
+
+    let page = use_case
+        .execute_keyset(PaginationQuery { include_total: true, ..Default::default() }, None)
+        .await
+        .unwrap();
+    assert!(page.data.is_empty());
+    assert_eq!(page.total, Some(0));
+}
+
+#[tokio::test]
+async fn test_list_todos_rejects_unknown_sort_field() {
+    let use_case = ListTodosUseCase::new(&MockRepo);
+
+    let result = use_case
+        .execute(PaginationQuery { sort: Some("-priority".to_string()), ..Default::default() })
+        .await;
+    assert!(matches!(result, Err(ApiError::Validation(errors)) if errors[0].code == "unknown_sort_field"));
+}
@@ -0,0 +1,131 @@
+use std::sync::Mutex;
+
+use axum_api::{
+    application::todos::patch_todo::PatchTodoUseCase,
+    domain::todos::{DomainEvents, Todo, TodoPatch, TodoRole, UpdateTodoRequest, traits::{TodoFinder, TodoRoles, TodoUpdater}},
+    error::ApiError,
+    infrastructure::database::repositories::InMemoryTodoListRepository,
+};
+use serde_json::json;
+use uuid::Uuid;
+use chrono::Utc;
+
+struct MockRepo {
+    todo: Todo,
+    last_update: Mutex<Option<(UpdateTodoRequest, Option<i64>)>>,
+}
+
+#[async_trait::async_trait]
+impl TodoFinder for MockRepo {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Todo>, ApiError> {
+        Ok((id == self.todo.id).then(|| self.todo.clone()))
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoRoles for MockRepo {
+    async fn role_of(&self, _id: Uuid, _user_id: Uuid) -> Result<Option<TodoRole>, ApiError> {
+        Ok(Some(TodoRole::Owner))
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoUpdater for MockRepo {
+    async fn update(
+        &self,
+        _id: Uuid,
+        data: UpdateTodoRequest,
+        expected_version: Option<i64>,
+        _events: DomainEvents,
+    ) -> Result<Todo, ApiError> {
+        let mut todo = self.todo.clone();
+        todo.title = data.title.clone().unwrap();
+        todo.done = data.done.unwrap();
+        *self.last_update.lock().unwrap() = Some((data, expected_version));
+        Ok(todo)
+    }
+}
+
+fn mock_repo() -> MockRepo {
+    MockRepo {
+        todo: Todo {
+            id: Uuid::new_v4(),
+            title: "Buy milk".to_string(),
+            done: false,
+            description: None,
+            completed_at: None,
+            due_at: None,
+            priority: Default::default(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            version: 1,
+            list_id: None,
+            tags: Vec::new(),
+            owner_id: None,
+            tenant_id: "default".to_string(),
+        },
+        last_update: Mutex::new(None),
+    }
+}
+
+#[tokio::test]
+async fn test_patch_sends_full_state_to_repository() {
+    let repo = mock_repo();
+    let lists = InMemoryTodoListRepository::new();
+    let use_case = PatchTodoUseCase::new(&repo, &lists);
+
+    let todo = use_case.execute(repo.todo.id, TodoPatch::Merge(json!({ "done": true })), None).await.unwrap();
+    assert!(todo.done);
+
+    let (sent, expected_version) = repo.last_update.lock().unwrap().take().unwrap();
+    assert_eq!(sent.title.as_deref(), Some("Buy milk"));
+    assert_eq!(sent.done, Some(true));
+    assert_eq!(expected_version, Some(1));
+}
+
+#[tokio::test]
+async fn test_patch_validates_result() {
+    let repo = mock_repo();
+    let lists = InMemoryTodoListRepository::new();
+    let use_case = PatchTodoUseCase::new(&repo, &lists);
+
+    let result = use_case.execute(repo.todo.id, TodoPatch::Merge(json!({ "title": " " })), None).await;
+    assert!(matches!(result, Err(ApiError::Validation(_))));
+    assert!(repo.last_update.lock().unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_patch_missing_todo_is_not_found() {
+    let repo = mock_repo();
+    let lists = InMemoryTodoListRepository::new();
+    let use_case = PatchTodoUseCase::new(&repo, &lists);
+
+    let result = use_case.execute(Uuid::new_v4(), TodoPatch::Merge(json!({})), None).await;
+    assert!(matches!(result, Err(ApiError::NotFound)));
+}
+
+#[tokio::test]
+async fn test_patch_stale_version_is_precondition_failed() {
+    let repo = mock_repo();
+    let lists = InMemoryTodoListRepository::new();
+    let use_case = PatchTodoUseCase::new(&repo, &lists);
+
+    let result = use_case.execute(repo.todo.id, TodoPatch::Merge(json!({ "done": true })), Some(7)).await;
+    assert!(matches!(result, Err(ApiError::PreconditionFailed)));
+    assert!(repo.last_update.lock().unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_patch_rejects_moving_to_unknown_list() {
+    let repo = mock_repo();
+    let lists = InMemoryTodoListRepository::new();
+    let use_case = PatchTodoUseCase::new(&repo, &lists);
+
+    let patch = TodoPatch::Merge(json!({ "list_id": Uuid::new_v4() }));
+    let result = use_case.execute(repo.todo.id, patch, None).await;
+    match result {
+        Err(ApiError::Validation(errors)) => assert_eq!(errors[0].code, "unknown_list"),
+        other => panic!("expected validation error, got {other:?}"),
+    }
+    assert!(repo.last_update.lock().unwrap().is_none());
+}
@@ -0,0 +1,60 @@
+use std::sync::Mutex;
+
+use axum_api::{
+    application::todos::purge_expired_trash::PurgeExpiredTrashUseCase,
+    domain::todos::{DomainEvents, PaginatedResponse, PaginationMeta, PaginationQuery, Todo, TrashedTodo, traits::TodoTrash},
+    error::ApiError,
+};
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+/// Records every trash call; only the bulk purge reports any rows.
+#[derive(Default)]
+struct MockRepo {
+    calls: Mutex<Vec<&'static str>>,
+    cutoff: Mutex<Option<DateTime<Utc>>>,
+}
+
+impl MockRepo {
+    fn record(&self, call: &'static str) {
+        self.calls.lock().unwrap().push(call);
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoTrash for MockRepo {
+    async fn find_trashed(&self, pagination: PaginationQuery) -> Result<PaginatedResponse<TrashedTodo>, ApiError> {
+        self.record("find_trashed");
+        Ok(PaginatedResponse { data: Vec::new(), pagination: PaginationMeta::new(pagination.page, pagination.limit, 0) })
+    }
+
+    async fn restore(&self, _id: Uuid, _events: DomainEvents) -> Result<Todo, ApiError> {
+        self.record("restore");
+        Err(ApiError::NotFound)
+    }
+
+    async fn purge(&self, _id: Uuid) -> Result<(), ApiError> {
+        self.record("purge");
+        Ok(())
+    }
+
+    async fn purge_trashed_before(&self, cutoff: DateTime<Utc>) -> Result<u64, ApiError> {
+        self.record("purge_trashed_before");
+        *self.cutoff.lock().unwrap() = Some(cutoff);
+        Ok(4)
+    }
+}
+
+#[tokio::test]
+async fn test_purges_todos_older_than_retention() {
+    let repo = MockRepo::default();
+    let use_case = PurgeExpiredTrashUseCase::new(&repo);
+
+    let purged = use_case.execute(Duration::days(30)).await.unwrap();
+    assert_eq!(purged, 4);
+    assert_eq!(*repo.calls.lock().unwrap(), vec!["purge_trashed_before"]);
+
+    let cutoff = repo.cutoff.lock().unwrap().unwrap();
+    let expected = Utc::now() - Duration::days(30);
+    assert!((expected - cutoff).num_seconds().abs() < 5);
+}
@@ -0,0 +1,86 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use axum_api::{
+    application::todos::{relay_outbox::RelayOutboxUseCase, TodoDomainEvents},
+    domain::todos::{
+        CreateTodoRequest, OutboxMessage, RelayReport, RetryPolicy, UpdateTodoRequest,
+        traits::{EventPublisher, TodoCreator, TodoOutbox, TodoUpdater},
+    },
+    infrastructure::database::repositories::InMemoryTodoRepository,
+    request_context::{RequestContext, DEFAULT_TENANT},
+};
+
+const RETRY: RetryPolicy = RetryPolicy { base: Duration::from_secs(60), max: Duration::from_secs(600) };
+
+/// Records what it publishes and fails the first `failures` attempts.
+#[derive(Default)]
+struct FlakyPublisher {
+    failures: Mutex<usize>,
+    published: Mutex<Vec<String>>,
+}
+
+#[async_trait::async_trait]
+impl EventPublisher for FlakyPublisher {
+    async fn publish(&self, message: &OutboxMessage) -> anyhow::Result<()> {
+        let mut failures = self.failures.lock().unwrap();
+        if *failures > 0 {
+            *failures -= 1;
+            anyhow::bail!("broker unavailable");
+        }
+        self.published.lock().unwrap().push(message.event.event_type().to_string());
+        Ok(())
+    }
+}
+
+async fn create(repo: &InMemoryTodoRepository, title: &str) -> uuid::Uuid {
+    repo.create(CreateTodoRequest { title: title.to_string(), done: None, list_id: None, ..Default::default() }, TodoDomainEvents::created).await.unwrap().id
+}
+
+#[tokio::test]
+async fn test_publishes_and_removes_messages() {
+    RequestContext::for_tenant(DEFAULT_TENANT).scope(async {
+        let repo = InMemoryTodoRepository::new();
+        let id = create(&repo, "Buy milk").await;
+        repo.update(id, UpdateTodoRequest { title: None, done: Some(true), ..Default::default() }, None, TodoDomainEvents::updated).await.unwrap();
+        let publisher = FlakyPublisher::default();
+
+        let report = RelayOutboxUseCase::new(&repo, &publisher).execute(10, RETRY).await.unwrap();
+
+        assert_eq!(report, RelayReport { fetched: 3, published: 3, failed: 0 });
+        assert_eq!(*publisher.published.lock().unwrap(), vec!["TodoCreated", "TodoUpdated", "TodoCompleted"]);
+        assert!(repo.pending_messages(10, chrono::Utc::now()).await.unwrap().is_empty());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_failure_schedules_a_retry_and_keeps_todo_order() {
+    RequestContext::for_tenant(DEFAULT_TENANT).scope(async {
+        let repo = InMemoryTodoRepository::new();
+        let first = create(&repo, "first").await;
+        repo.update(first, UpdateTodoRequest { title: Some("renamed".to_string()), done: None, ..Default::default() }, None, TodoDomainEvents::updated).await.unwrap();
+        create(&repo, "second").await;
+        let publisher = FlakyPublisher { failures: Mutex::new(1), ..FlakyPublisher::default() };
+
+        let report = RelayOutboxUseCase::new(&repo, &publisher).execute(10, RETRY).await.unwrap();
+
+        // The first todo's update waits behind its failed creation.
+        assert_eq!(report, RelayReport { fetched: 3, published: 1, failed: 1 });
+        assert_eq!(*publisher.published.lock().unwrap(), vec!["TodoCreated"]);
+
+        let retry_due = chrono::Utc::now() + chrono::Duration::seconds(61);
+        let pending = repo.pending_messages(10, retry_due).await.unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].attempts, 1);
+        assert!(repo.pending_messages(10, chrono::Utc::now()).await.unwrap().is_empty());
+    })
+    .await;
+}
+
+#[test]
+fn test_retry_delay_doubles_up_to_the_cap() {
+    let delays: Vec<u64> = (1..=6).map(|attempts| RETRY.delay(attempts).as_secs()).collect();
+    assert_eq!(delays, vec![60, 120, 240, 480, 600, 600]);
+    assert_eq!(RETRY.delay(i32::MAX), RETRY.max);
+}
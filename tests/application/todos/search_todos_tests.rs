@@ -0,0 +1,50 @@
+use std::sync::Mutex;
+
+use axum_api::{
+    application::todos::search_todos::SearchTodosUseCase,
+    domain::todos::{
+        PaginatedResponse, PaginationMeta, SearchTerm, SearchTodosQuery, TodoSearch, TodoSearchHit,
+        traits::TodoSearcher,
+    },
+    error::ApiError,
+};
+
+#[derive(Default)]
+struct MockRepo {
+    last_search: Mutex<Option<TodoSearch>>,
+}
+
+#[async_trait::async_trait]
+impl TodoSearcher for MockRepo {
+    async fn search(&self, search: TodoSearch) -> Result<PaginatedResponse<TodoSearchHit>, ApiError> {
+        let pagination = PaginationMeta::new(search.pagination.page(), search.pagination.limit(), 0);
+        *self.last_search.lock().unwrap() = Some(search);
+        Ok(PaginatedResponse { data: vec![], pagination })
+    }
+}
+
+#[tokio::test]
+async fn test_search_parses_query() {
+    let repo = MockRepo::default();
+    let use_case = SearchTodosUseCase::new(&repo);
+
+    let result = use_case
+        .execute(SearchTodosQuery { q: "milk*".to_string(), page: 2, limit: 5 })
+        .await
+        .unwrap();
+    assert_eq!(result.pagination.page, 2);
+
+    let search = repo.last_search.lock().unwrap().take().unwrap();
+    assert_eq!(search.terms, vec![SearchTerm::Word { text: "milk".to_string(), prefix: true }]);
+    assert_eq!(search.pagination.limit(), 5);
+}
+
+#[tokio::test]
+async fn test_search_rejects_blank_query() {
+    let repo = MockRepo::default();
+    let use_case = SearchTodosUseCase::new(&repo);
+
+    let result = use_case.execute(SearchTodosQuery { q: "  ".to_string(), page: 1, limit: 10 }).await;
+    assert!(matches!(result, Err(ApiError::Validation(_))));
+    assert!(repo.last_search.lock().unwrap().is_none());
+}
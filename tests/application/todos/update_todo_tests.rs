@@ -0,0 +1,108 @@
+use axum_api::{
+    application::todos::update_todo::UpdateTodoUseCase,
+    domain::lists::{traits::{TodoListArchiver, TodoListStore}, CreateTodoListRequest},
+    domain::todos::{DomainEvents, Todo, TodoRole, UpdateTodoRequest, traits::{TodoFinder, TodoRoles, TodoUpdater}},
+    error::ApiError,
+    infrastructure::database::repositories::InMemoryTodoListRepository,
+    request_context::{RequestContext, DEFAULT_TENANT},
+};
+use uuid::Uuid;
+use chrono::Utc;
+
+struct MockRepo;
+
+#[async_trait::async_trait]
+impl TodoRoles for MockRepo {
+    async fn role_of(&self, _id: Uuid, _user_id: Uuid) -> Result<Option<TodoRole>, ApiError> {
+        Ok(Some(TodoRole::Owner))
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoFinder for MockRepo {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Todo>, ApiError> {
+        Ok(Some(todo(id, UpdateTodoRequest::default())))
+    }
+}
+
+#[async_trait::async_trait]
+impl TodoUpdater for MockRepo {
+    async fn update(
+        &self,
+        id: Uuid,
+        data: UpdateTodoRequest,
+        _expected_version: Option<i64>,
+        _events: DomainEvents,
+    ) -> Result<Todo, ApiError> {
+        Ok(todo(id, data))
+    }
+}
+
+fn todo(id: Uuid, data: UpdateTodoRequest) -> Todo {
+    Todo {
+        id,
+        title: data.title.unwrap_or_else(|| "Test".to_string()),
+        done: data.done.unwrap_or(false),
+        description: None,
+        completed_at: None,
+        due_at: None,
+        priority: Default::default(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        version: 1,
+        list_id: data.list_id.flatten(),
+        tags: Vec::new(),
+        owner_id: None,
+        tenant_id: "default".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_update_todo_use_case() {
+    let lists = InMemoryTodoListRepository::new();
+    let use_case = UpdateTodoUseCase::new(&MockRepo, &lists);
+    let id = Uuid::new_v4();
+
+    let todo = use_case
+        .execute(id, UpdateTodoRequest { title: None, done: Some(true), ..Default::default() }, None)
+        .await
+        .unwrap();
+    assert_eq!(todo.id, id);
+    assert!(todo.done);
+}
+
+#[tokio::test]
+async fn test_update_todo_rejects_empty_update() {
+    let lists = InMemoryTodoListRepository::new();
+    let use_case = UpdateTodoUseCase::new(&MockRepo, &lists);
+
+    let result = use_case
+        .execute(Uuid::new_v4(), UpdateTodoRequest { title: None, done: None, ..Default::default() }, None)
+        .await;
+    assert!(matches!(result, Err(ApiError::Validation(_))));
+}
+
+#[tokio::test]
+async fn test_update_todo_only_moves_to_active_lists() {
+    RequestContext::for_tenant(DEFAULT_TENANT).scope(async {
+        let lists = InMemoryTodoListRepository::new();
+        let use_case = UpdateTodoUseCase::new(&MockRepo, &lists);
+        let list = lists
+            .create(CreateTodoListRequest { name: "Groceries".to_string(), description: None, color: None })
+            .await
+            .unwrap();
+        let request = |list_id| UpdateTodoRequest { list_id: Some(Some(list_id)), ..Default::default() };
+
+        let todo = use_case.execute(Uuid::new_v4(), request(list.id), None).await.unwrap();
+        assert_eq!(todo.list_id, Some(list.id));
+
+        lists.set_archived(list.id, true).await.unwrap();
+        for (list_id, code) in [(Uuid::new_v4(), "unknown_list"), (list.id, "archived_list")] {
+            match use_case.execute(Uuid::new_v4(), request(list_id), None).await {
+                Err(ApiError::Validation(errors)) => assert_eq!(errors[0].code, code),
+                other => panic!("expected validation error, got {other:?}"),
+            }
+        }
+    })
+    .await;
+}
@@ -0,0 +1,226 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::{body::Bytes, extract::State, http::{HeaderMap, StatusCode}, routing::post, Router};
+use axum_api::{
+    application::todos::TodoDomainEvents,
+    application::webhooks::DeliverWebhooksUseCase,
+    domain::todos::{NewTodoEvent, OutboxMessage, PaginationQuery, RetryPolicy, Todo},
+    domain::webhooks::{
+        verify, CreateWebhookRequest, DeliveryPolicy, DeliveryStatus, DispatchReport, TargetPolicy, Webhook,
+        traits::{WebhookDeliveries, WebhookStore},
+    },
+    infrastructure::{database::repositories::InMemoryWebhookRepository, webhook_sender::HttpWebhookSender},
+    request_context::{RequestContext, DEFAULT_TENANT},
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+const SECRET: &str = "0123456789abcdef";
+
+fn policy(retry_base: Duration, max_attempts: i32, disable_after: i32) -> DeliveryPolicy {
+    DeliveryPolicy {
+        retry: RetryPolicy { base: retry_base, max: Duration::from_secs(600) },
+        max_attempts,
+        disable_after,
+    }
+}
+
+/// A local HTTP receiver that records every request and answers with the
+/// scripted statuses, then 200.
+#[derive(Clone, Default)]
+struct Receiver {
+    statuses: Arc<Mutex<VecDeque<u16>>>,
+    received: Arc<Mutex<Vec<(HeaderMap, Bytes)>>>,
+}
+
+impl Receiver {
+    async fn start(statuses: &[u16]) -> (Self, String) {
+        let receiver = Receiver { statuses: Arc::new(Mutex::new(statuses.iter().copied().collect())), ..Receiver::default() };
+        let app = Router::new().route("/hook", post(receive)).with_state(receiver.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (receiver, url)
+    }
+
+    fn received(&self) -> Vec<(HeaderMap, Bytes)> {
+        self.received.lock().unwrap().clone()
+    }
+}
+
+async fn receive(State(receiver): State<Receiver>, headers: HeaderMap, body: Bytes) -> StatusCode {
+    receiver.received.lock().unwrap().push((headers, body));
+    let status = receiver.statuses.lock().unwrap().pop_front().unwrap_or(200);
+    StatusCode::from_u16(status).unwrap()
+}
+
+/// A webhook at `url` with one queued `TodoCreated` delivery.
+async fn subscribe(repo: &InMemoryWebhookRepository, url: &str) -> (Webhook, OutboxMessage) {
+    let webhook = repo
+        .create(CreateWebhookRequest { url: url.to_string(), events: Vec::new(), secret: SECRET.to_string() })
+        .await
+        .unwrap();
+    let now = Utc::now();
+    let todo = Todo { id: Uuid::new_v4(), title: "Buy milk".to_string(), done: false, description: None, completed_at: None, due_at: None, priority: Default::default(), created_at: now, updated_at: now, version: 1, list_id: None, tags: Vec::new(), owner_id: None, tenant_id: "default".to_string() };
+    let change = NewTodoEvent::created(&todo);
+    let message = OutboxMessage::queue(&change, TodoDomainEvents::created(&change)).remove(0);
+    assert_eq!(repo.enqueue(&message).await.unwrap(), 1);
+    (webhook, message)
+}
+
+/// A sender that may reach the local receivers.
+fn sender() -> HttpWebhookSender {
+    HttpWebhookSender::new(Duration::from_secs(5), TargetPolicy { allow_private: true }).unwrap()
+}
+
+#[tokio::test]
+async fn test_delivers_signed_events() {
+    RequestContext::for_tenant(DEFAULT_TENANT).scope(async {
+        let (receiver, url) = Receiver::start(&[204]).await;
+        let repo = InMemoryWebhookRepository::new();
+        let (webhook, message) = subscribe(&repo, &url).await;
+        let sender = sender();
+
+        let report = DeliverWebhooksUseCase::new(&repo, &sender).execute(10, policy(Duration::ZERO, 3, 5)).await.unwrap();
+        assert_eq!(report, DispatchReport { fetched: 1, succeeded: 1, failed: 0 });
+
+        let received = receiver.received();
+        assert_eq!(received.len(), 1);
+        let (headers, body) = &received[0];
+        let signature = headers["x-signature"].to_str().unwrap();
+        assert!(verify(SECRET, signature, body, Utc::now().timestamp(), 300));
+        assert!(!verify("another-secret-value", signature, body, Utc::now().timestamp(), 300));
+        assert_eq!(headers["x-webhook-id"], message.event_id.to_string().as_str());
+        assert_eq!(headers["x-webhook-event"], "TodoCreated");
+        assert_eq!(headers["content-type"], "application/json");
+
+        let payload: serde_json::Value = serde_json::from_slice(body).unwrap();
+        assert_eq!(payload, serde_json::to_value(&message).unwrap());
+
+        let log = repo.find_deliveries(webhook.id, PaginationQuery::default()).await.unwrap();
+        assert_eq!(log.data[0].status, DeliveryStatus::Succeeded);
+        assert_eq!(log.data[0].response_status, Some(204));
+        assert_eq!(log.data[0].attempts, 1);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_failure_schedules_a_retry_with_backoff() {
+    RequestContext::for_tenant(DEFAULT_TENANT).scope(async {
+        let (_receiver, url) = Receiver::start(&[500]).await;
+        let repo = InMemoryWebhookRepository::new();
+        let (webhook, _) = subscribe(&repo, &url).await;
+        let sender = sender();
+        let use_case = DeliverWebhooksUseCase::new(&repo, &sender);
+        let policy = policy(Duration::from_secs(60), 3, 10);
+
+        assert_eq!(use_case.execute(10, policy).await.unwrap(), DispatchReport { fetched: 1, succeeded: 0, failed: 1 });
+        // The retry is a minute away, so nothing is due yet.
+        assert_eq!(use_case.execute(10, policy).await.unwrap().fetched, 0);
+
+        let log = repo.find_deliveries(webhook.id, PaginationQuery::default()).await.unwrap();
+        let delivery = &log.data[0];
+        assert_eq!(delivery.status, DeliveryStatus::Pending);
+        assert_eq!(delivery.attempts, 1);
+        assert_eq!(delivery.response_status, Some(500));
+        assert_eq!(delivery.last_error.as_deref(), Some("receiver answered 500"));
+        assert!(delivery.next_attempt_at.unwrap() > Utc::now() + chrono::Duration::seconds(50));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_gives_up_after_max_attempts() {
+    RequestContext::for_tenant(DEFAULT_TENANT).scope(async {
+        let (receiver, url) = Receiver::start(&[500, 503, 500, 200]).await;
+        let repo = InMemoryWebhookRepository::new();
+        let (webhook, _) = subscribe(&repo, &url).await;
+        let sender = sender();
+        let use_case = DeliverWebhooksUseCase::new(&repo, &sender);
+        let policy = policy(Duration::ZERO, 3, 10);
+
+        for _ in 0..3 {
+            assert_eq!(use_case.execute(10, policy).await.unwrap().failed, 1);
+        }
+        assert_eq!(use_case.execute(10, policy).await.unwrap().fetched, 0);
+
+        let log = repo.find_deliveries(webhook.id, PaginationQuery::default()).await.unwrap();
+        assert_eq!(log.data[0].status, DeliveryStatus::Failed);
+        assert_eq!(log.data[0].attempts, 3);
+        assert!(log.data[0].next_attempt_at.is_none());
+        assert_eq!(receiver.received().len(), 3);
+        // Every attempt carries the same event id, for the receiver to deduplicate on.
+        let ids: Vec<_> = receiver.received().iter().map(|(headers, _)| headers["x-webhook-id"].clone()).collect();
+        assert!(ids.windows(2).all(|pair| pair[0] == pair[1]));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_disables_webhook_after_repeated_failures() {
+    RequestContext::for_tenant(DEFAULT_TENANT).scope(async {
+        let (receiver, url) = Receiver::start(&[500, 500, 500]).await;
+        let repo = InMemoryWebhookRepository::new();
+        let (webhook, _) = subscribe(&repo, &url).await;
+        let sender = sender();
+        let use_case = DeliverWebhooksUseCase::new(&repo, &sender);
+        let policy = policy(Duration::ZERO, 10, 2);
+
+        assert_eq!(use_case.execute(10, policy).await.unwrap().failed, 1);
+        assert!(repo.find_by_id(webhook.id).await.unwrap().unwrap().active);
+        assert_eq!(use_case.execute(10, policy).await.unwrap().failed, 1);
+
+        let disabled = repo.find_by_id(webhook.id).await.unwrap().unwrap();
+        assert!(!disabled.active);
+        assert_eq!(disabled.consecutive_failures, 2);
+        assert_eq!(use_case.execute(10, policy).await.unwrap().fetched, 0);
+        assert_eq!(receiver.received().len(), 2);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_unreachable_receiver_is_a_failure() {
+    RequestContext::for_tenant(DEFAULT_TENANT).scope(async {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        drop(listener);
+        let repo = InMemoryWebhookRepository::new();
+        let (webhook, _) = subscribe(&repo, &url).await;
+        let sender = sender();
+
+        let report = DeliverWebhooksUseCase::new(&repo, &sender).execute(10, policy(Duration::ZERO, 1, 5)).await.unwrap();
+        assert_eq!(report, DispatchReport { fetched: 1, succeeded: 0, failed: 1 });
+
+        let log = repo.find_deliveries(webhook.id, PaginationQuery::default()).await.unwrap();
+        assert_eq!(log.data[0].status, DeliveryStatus::Failed);
+        assert_eq!(log.data[0].response_status, None);
+        assert!(log.data[0].last_error.is_some());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_private_receivers_are_refused_when_sending() {
+    RequestContext::for_tenant(DEFAULT_TENANT).scope(async {
+        let (receiver, url) = Receiver::start(&[]).await;
+        let port = url.split(':').nth(2).unwrap().split('/').next().unwrap().to_string();
+        let sender = HttpWebhookSender::new(Duration::from_secs(5), TargetPolicy::default()).unwrap();
+
+        // The address in the URL, and the one a name resolves to, are both checked.
+        for url in [url.clone(), format!("http://localhost:{port}/hook")] {
+            let repo = InMemoryWebhookRepository::new();
+            let (webhook, _) = subscribe(&repo, &url).await;
+
+            let report = DeliverWebhooksUseCase::new(&repo, &sender).execute(10, policy(Duration::ZERO, 1, 5)).await.unwrap();
+            assert_eq!(report, DispatchReport { fetched: 1, succeeded: 0, failed: 1 });
+            let log = repo.find_deliveries(webhook.id, PaginationQuery::default()).await.unwrap();
+            assert!(log.data[0].last_error.as_deref().unwrap().contains("webhooks may reach"), "{:?}", log.data[0].last_error);
+        }
+        assert!(receiver.received().is_empty());
+    })
+    .await;
+}
@@ -0,0 +1,330 @@
+use std::io::Write;
+
+use axum_api::config::{CliArgs, Config, ConfigError, DatabaseBackend, PublisherKind};
+
+fn env(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+}
+
+fn write_config(contents: &str) -> tempfile::NamedTempFile {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(contents.as_bytes()).unwrap();
+    file
+}
+
+#[test]
+fn test_defaults() {
+    let config = Config::default();
+    assert_eq!(config.server.bind_address, "127.0.0.1:3000");
+    assert_eq!(config.database.max_connections, 10);
+    assert!(config.database.run_migrations);
+    assert_eq!(config.log.level, "info");
+    assert!(config.features.swagger_ui);
+}
+
+#[test]
+fn test_file_fills_missing_fields_with_defaults() {
+    let file = write_config(
+        r#"
+        [server]
+        bind_address = "0.0.0.0:8080"
+
+        [database]
+        url = "postgres://localhost/todos"
+        max_connections = 20
+        "#,
+    );
+
+    let config = Config::from_file(file.path()).unwrap();
+    assert_eq!(config.server.bind_address, "0.0.0.0:8080");
+    assert_eq!(config.database.max_connections, 20);
+    assert_eq!(config.database.min_connections, 0);
+    assert_eq!(config.log.level, "info");
+}
+
+#[test]
+fn test_file_rejects_unknown_fields() {
+    let file = write_config("[server]\nport = 3000\n");
+    assert!(matches!(Config::from_file(file.path()), Err(ConfigError::Parse { .. })));
+}
+
+#[test]
+fn test_precedence_file_then_env_then_cli() {
+    let file = write_config(
+        r#"
+        [server]
+        bind_address = "0.0.0.0:1000"
+
+        [database]
+        url = "postgres://file/todos"
+        max_connections = 5
+        "#,
+    );
+
+    let mut config = Config::from_file(file.path()).unwrap();
+    config
+        .apply_env(env(&[
+            ("APP__SERVER__BIND_ADDRESS", "0.0.0.0:2000"),
+            ("APP__DATABASE__MAX_CONNECTIONS", "7"),
+            ("UNRELATED", "ignored"),
+        ]))
+        .unwrap();
+    config.apply_cli(&CliArgs {
+        bind_address: Some("0.0.0.0:3000".to_string()),
+        ..Default::default()
+    });
+
+    assert_eq!(config.server.bind_address, "0.0.0.0:3000");
+    assert_eq!(config.database.max_connections, 7);
+    assert_eq!(config.database.url.as_deref(), Some("postgres://file/todos"));
+}
+
+#[test]
+fn test_prefixed_database_url_wins_over_plain() {
+    let mut config = Config::default();
+    config
+        .apply_env(env(&[
+            ("APP__DATABASE__URL", "postgres://prefixed/db"),
+            ("DATABASE_URL", "postgres://plain/db"),
+        ]))
+        .unwrap();
+    assert_eq!(config.database.url.as_deref(), Some("postgres://prefixed/db"));
+}
+
+#[test]
+fn test_env_errors() {
+    let mut config = Config::default();
+    assert!(matches!(
+        config.apply_env(env(&[("APP__SERVER__PORT", "1")])),
+        Err(ConfigError::UnknownKey(key)) if key == "server.port"
+    ));
+    assert!(matches!(
+        config.apply_env(env(&[("APP__FEATURES__SWAGGER_UI", "maybe")])),
+        Err(ConfigError::InvalidValue { .. })
+    ));
+}
+
+#[test]
+fn test_validation_reports_every_problem() {
+    let mut config = Config::default();
+    config.server.bind_address = "not-an-address".to_string();
+    config.database.max_connections = 1;
+    config.database.min_connections = 2;
+
+    match config.validate() {
+        Err(ConfigError::Invalid(errors)) => {
+            assert_eq!(errors.len(), 3);
+            assert!(errors[0].contains("server.bind_address"));
+            assert!(errors[1].contains("database.url"));
+            assert!(errors[2].contains("min_connections"));
+        }
+        other => panic!("expected validation errors, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_short_cursor_secret_is_rejected() {
+    let mut config = Config::default();
+    config.database.url = Some("memory://".to_string());
+    config.set("pagination.cursor_secret", "short").unwrap();
+
+    match config.validate() {
+        Err(ConfigError::Invalid(errors)) => assert!(errors[0].contains("pagination.cursor_secret")),
+        other => panic!("expected validation errors, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_trash_retention() {
+    let mut config = Config::default();
+    config.database.url = Some("memory://".to_string());
+    assert_eq!(config.trash.retention(), Some(chrono::Duration::days(30)));
+
+    config.set("trash.retention_days", "0").unwrap();
+    assert_eq!(config.trash.retention(), None);
+
+    config.set("trash.purge_interval_secs", "0").unwrap();
+    match config.validate() {
+        Err(ConfigError::Invalid(errors)) => assert!(errors[0].contains("trash.purge_interval_secs")),
+        other => panic!("expected validation errors, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_outbox_settings() {
+    let mut config = Config::default();
+    config.database.url = Some("memory://".to_string());
+    assert_eq!(config.outbox.publisher, PublisherKind::Stdout);
+
+    config.set("outbox.publisher", "file").unwrap();
+    config.set("outbox.file_path", "/tmp/events.jsonl").unwrap();
+    assert_eq!(config.outbox.publisher, PublisherKind::File);
+    assert!(matches!(config.set("outbox.publisher", "kafka"), Err(ConfigError::InvalidValue { .. })));
+
+    config.set("outbox.retry_base_ms", "2000").unwrap();
+    config.set("outbox.retry_max_secs", "1").unwrap();
+    config.set("outbox.batch_size", "0").unwrap();
+    match config.validate() {
+        Err(ConfigError::Invalid(errors)) => {
+            assert_eq!(errors.len(), 2, "got {errors:?}");
+            assert!(errors[0].contains("outbox.batch_size"));
+            assert!(errors[1].contains("outbox.retry_max_secs"));
+        }
+        other => panic!("expected validation errors, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_webhook_settings() {
+    let mut config = Config::default();
+    config.database.url = Some("memory://".to_string());
+    assert!(config.webhooks.enabled);
+
+    config.set("webhooks.enabled", "false").unwrap();
+    config.set("webhooks.max_attempts", "5").unwrap();
+    config.set("webhooks.retry_base_ms", "250").unwrap();
+    config.set("webhooks.disable_after_failures", "3").unwrap();
+    assert!(!config.webhooks.enabled);
+    let policy = config.webhooks.delivery_policy();
+    assert_eq!(policy.max_attempts, 5);
+    assert_eq!(policy.disable_after, 3);
+    assert_eq!(policy.retry.base, std::time::Duration::from_millis(250));
+    assert!(!config.webhooks.target_policy().allow_private);
+    config.set("webhooks.allow_private_targets", "true").unwrap();
+    assert!(config.webhooks.target_policy().allow_private);
+    assert!(config.validate().is_ok());
+
+    config.set("webhooks.max_attempts", "0").unwrap();
+    config.set("webhooks.timeout_secs", "0").unwrap();
+    match config.validate() {
+        Err(ConfigError::Invalid(errors)) => {
+            assert_eq!(errors.len(), 2, "got {errors:?}");
+            assert!(errors[0].contains("webhooks.timeout_secs"));
+            assert!(errors[1].contains("webhooks.max_attempts"));
+        }
+        other => panic!("expected validation errors, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_change_feed_settings() {
+    let mut config = Config::default();
+    config.database.url = Some("memory://".to_string());
+    assert_eq!(config.changes.replay_buffer, 1000);
+
+    config.set("changes.replay_buffer", "0").unwrap();
+    config.set("changes.keep_alive_secs", "30").unwrap();
+    assert_eq!(config.changes.keep_alive(), std::time::Duration::from_secs(30));
+    assert!(config.validate().is_ok());
+
+    config.set("changes.replay_buffer", "100001").unwrap();
+    config.set("changes.poll_interval_ms", "0").unwrap();
+    match config.validate() {
+        Err(ConfigError::Invalid(errors)) => {
+            assert_eq!(errors.len(), 2, "got {errors:?}");
+            assert!(errors[0].contains("changes.replay_buffer"));
+            assert!(errors[1].contains("changes.poll_interval_ms"));
+        }
+        other => panic!("expected validation errors, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_websocket_settings() {
+    let mut config = Config::default();
+    config.database.url = Some("memory://".to_string());
+    config.set("websocket.ping_interval_secs", "10").unwrap();
+    config.set("websocket.max_subscriptions", "5").unwrap();
+    assert_eq!(config.websocket.ping_interval(), std::time::Duration::from_secs(10));
+    assert_eq!(config.websocket.max_subscriptions, 5);
+    assert!(config.validate().is_ok());
+
+    config.set("websocket.idle_timeout_secs", "10").unwrap();
+    config.set("websocket.max_message_bytes", "100").unwrap();
+    match config.validate() {
+        Err(ConfigError::Invalid(errors)) => {
+            assert_eq!(errors.len(), 2, "got {errors:?}");
+            assert!(errors[0].contains("websocket.idle_timeout_secs"));
+            assert!(errors[1].contains("websocket.max_message_bytes"));
+        }
+        other => panic!("expected validation errors, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_auth_settings() {
+    let mut config = Config::default();
+    config.database.url = Some("memory://".to_string());
+    assert!(config.auth.jwt_secret.is_none());
+    assert_eq!(config.auth.access_token_ttl(), std::time::Duration::from_secs(900));
+    assert_eq!(config.auth.refresh_token_ttl(), chrono::Duration::days(30));
+
+    config.set("auth.jwt_secret", "an-access-token-signing-secret-of-enough-bytes").unwrap();
+    config.set("auth.access_token_ttl_secs", "60").unwrap();
+    config.set("auth.refresh_token_ttl_days", "7").unwrap();
+    assert_eq!(config.auth.access_token_ttl(), std::time::Duration::from_secs(60));
+    assert_eq!(config.auth.refresh_token_ttl(), chrono::Duration::days(7));
+    assert!(config.validate().is_ok());
+
+    config.set("auth.jwt_secret", "short").unwrap();
+    config.set("auth.access_token_ttl_secs", "0").unwrap();
+    config.set("auth.refresh_token_ttl_days", "0").unwrap();
+    match config.validate() {
+        Err(ConfigError::Invalid(errors)) => {
+            assert_eq!(errors.len(), 3, "got {errors:?}");
+            assert!(errors[0].contains("auth.jwt_secret"));
+            assert!(errors[1].contains("auth.access_token_ttl_secs"));
+            assert!(errors[2].contains("auth.refresh_token_ttl_days"));
+        }
+        other => panic!("expected validation errors, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_tenancy_settings() {
+    let mut config = Config::default();
+    config.database.url = Some("postgres://localhost/todos".to_string());
+    assert!(!config.tenancy.enabled);
+
+    config.set("tenancy.enabled", "true").unwrap();
+    config.set("tenancy.base_domain", "todos.example.com").unwrap();
+    assert!(config.validate().is_ok());
+
+    config.database.url = Some("sqlite://todos.db".to_string());
+    config.set("tenancy.base_domain", "https://todos.example.com").unwrap();
+    match config.validate() {
+        Err(ConfigError::Invalid(errors)) => {
+            let expected = if cfg!(feature = "sqlite") { 2 } else { 3 };
+            assert_eq!(errors.len(), expected, "got {errors:?}");
+            assert!(errors[expected - 2].contains("tenancy.enabled"));
+            assert!(errors[expected - 1].contains("tenancy.base_domain"));
+        }
+        other => panic!("expected validation errors, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_valid_config() {
+    let mut config = Config::default();
+    config.database.url = Some("postgres://localhost/todos".to_string());
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn test_backend_is_selected_by_url_scheme() {
+    let mut config = Config::default();
+
+    config.database.url = Some("memory://".to_string());
+    assert_eq!(config.database.backend(), Some(DatabaseBackend::Memory));
+    assert!(config.validate().is_ok());
+
+    config.database.url = Some("postgresql://localhost/todos".to_string());
+    assert_eq!(config.database.backend(), Some(DatabaseBackend::Postgres));
+
+    config.database.url = Some("sqlite://todos.db".to_string());
+    assert_eq!(config.database.backend(), Some(DatabaseBackend::Sqlite));
+    assert_eq!(config.validate().is_ok(), cfg!(feature = "sqlite"));
+
+    config.database.url = Some("mysql://localhost/todos".to_string());
+    assert!(config.validate().is_err());
+}
@@ -0,0 +1,51 @@
+use axum_api::doc::{ApiDoc, PUBLIC_PATHS};
+use utoipa::OpenApi;
+
+#[test]
+fn test_every_resource_operation_documents_problem_responses() {
+    let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+    assert!(doc["components"]["schemas"]["ProblemDetails"].is_object());
+
+    for (path, item) in doc["paths"].as_object().unwrap() {
+        if !path.starts_with("/todos") && !path.starts_with("/webhooks") {
+            continue;
+        }
+        for (method, operation) in item.as_object().unwrap() {
+            let responses = operation["responses"].as_object().unwrap();
+            let problem = &responses["500"]["content"]["application/problem+json"]["schema"]["$ref"];
+            assert_eq!(problem, "#/components/schemas/ProblemDetails", "{method} {path}");
+        }
+    }
+}
+
+#[test]
+fn test_patch_documents_both_patch_formats() {
+    let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+    let content = &doc["paths"]["/todos/{id}"]["patch"]["requestBody"]["content"];
+    assert!(content["application/merge-patch+json"].is_object());
+    assert_eq!(
+        content["application/json-patch+json"]["schema"]["items"]["$ref"],
+        "#/components/schemas/JsonPatchOperation"
+    );
+}
+
+#[test]
+fn test_operations_outside_auth_require_a_bearer_token_or_api_key() {
+    let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+    assert_eq!(doc["components"]["securitySchemes"]["bearer"]["scheme"], "bearer");
+    assert_eq!(doc["components"]["securitySchemes"]["api_key"]["name"], "Authorization");
+
+    for (path, item) in doc["paths"].as_object().unwrap() {
+        for (method, operation) in item.as_object().unwrap() {
+            let secured = operation["security"][0]["bearer"].is_array();
+            assert_eq!(secured, !PUBLIC_PATHS.contains(&path.as_str()), "{method} {path}");
+            if secured {
+                assert!(operation["security"][1]["api_key"].is_array(), "{method} {path}");
+                assert!(operation["responses"]["401"].is_object(), "{method} {path}");
+                if method != "get" {
+                    assert!(operation["responses"]["403"].is_object(), "{method} {path}");
+                }
+            }
+        }
+    }
+}
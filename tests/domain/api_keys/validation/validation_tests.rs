@@ -0,0 +1,38 @@
+use axum_api::{
+    domain::api_keys::{validation::NAME_MAX_LENGTH, ApiKeyScope, CreateApiKeyRequest},
+    domain::todos::validation::FieldError,
+    error::ApiError,
+};
+use chrono::{DateTime, Duration, Utc};
+
+fn codes(result: Result<(), ApiError>) -> Vec<(String, String)> {
+    match result {
+        Err(ApiError::Validation(errors)) => errors.into_iter().map(|error: FieldError| (error.field, error.code)).collect(),
+        other => panic!("expected validation error, got {other:?}"),
+    }
+}
+
+fn request(name: &str, expires_at: Option<DateTime<Utc>>) -> CreateApiKeyRequest {
+    CreateApiKeyRequest { name: name.to_string(), scope: ApiKeyScope::ReadOnly, expires_at }
+}
+
+#[test]
+fn test_valid_request() {
+    let now = Utc::now();
+    assert!(request("ci", None).validate(now).is_ok());
+    assert!(request(&"k".repeat(NAME_MAX_LENGTH), Some(now + Duration::days(1))).validate(now).is_ok());
+}
+
+#[test]
+fn test_invalid_names() {
+    let now = Utc::now();
+    assert_eq!(codes(request("  ", None).validate(now)), vec![("name".into(), "blank".into())]);
+    assert_eq!(codes(request(&"k".repeat(NAME_MAX_LENGTH + 1), None).validate(now)), vec![("name".into(), "too_long".into())]);
+    assert_eq!(codes(request("c\ni", None).validate(now)), vec![("name".into(), "control_characters".into())]);
+}
+
+#[test]
+fn test_expiry_must_be_in_the_future() {
+    let now = Utc::now();
+    assert_eq!(codes(request("ci", Some(now)).validate(now)), vec![("expires_at".into(), "in_past".into())]);
+}
@@ -0,0 +1,32 @@
+use axum_api::domain::api_keys::{hash_api_key, ApiKeyScope, ApiKeySecret, API_KEY_PREFIX};
+
+#[test]
+fn test_scopes_include_the_ones_below_them() {
+    assert!(ApiKeyScope::Admin.includes(ApiKeyScope::ReadWrite));
+    assert!(ApiKeyScope::ReadWrite.includes(ApiKeyScope::ReadOnly));
+    assert!(ApiKeyScope::ReadOnly.includes(ApiKeyScope::ReadOnly));
+    assert!(!ApiKeyScope::ReadOnly.includes(ApiKeyScope::ReadWrite));
+    assert!(!ApiKeyScope::ReadWrite.includes(ApiKeyScope::Admin));
+}
+
+#[test]
+fn test_scope_round_trips_through_strings() {
+    for scope in [ApiKeyScope::ReadOnly, ApiKeyScope::ReadWrite, ApiKeyScope::Admin] {
+        assert_eq!(ApiKeyScope::try_from(scope.as_str().to_string()).unwrap(), scope);
+        assert_eq!(serde_json::to_value(scope).unwrap(), scope.as_str());
+    }
+    assert!(ApiKeyScope::try_from("root".to_string()).is_err());
+    assert_eq!(ApiKeyScope::default(), ApiKeyScope::ReadOnly);
+}
+
+#[test]
+fn test_generated_keys_carry_their_prefix_and_hash() {
+    let secret = ApiKeySecret::generate();
+    assert!(secret.prefix.starts_with(API_KEY_PREFIX));
+    assert_eq!(secret.prefix.len(), API_KEY_PREFIX.len() + 8);
+    let rest = secret.key.strip_prefix(&format!("{}_", secret.prefix)).unwrap();
+    assert_eq!(rest.len(), 43);
+    assert_eq!(secret.hash, hash_api_key(&secret.key));
+    assert_eq!(secret.hash.len(), 64);
+    assert_ne!(ApiKeySecret::generate().key, secret.key);
+}
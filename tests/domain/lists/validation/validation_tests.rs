@@ -0,0 +1,59 @@
+use axum_api::{
+    domain::lists::{
+        CreateTodoListRequest, UpdateTodoListRequest,
+        validation::{DESCRIPTION_MAX_LENGTH, NAME_MAX_LENGTH},
+    },
+    domain::todos::validation::FieldError,
+    error::ApiError,
+};
+
+fn codes(result: Result<(), ApiError>) -> Vec<(String, String)> {
+    match result {
+        Err(ApiError::Validation(errors)) => errors.into_iter().map(|error: FieldError| (error.field, error.code)).collect(),
+        other => panic!("expected validation error, got {other:?}"),
+    }
+}
+
+fn create(name: &str) -> CreateTodoListRequest {
+    CreateTodoListRequest { name: name.to_string(), description: None, color: None }
+}
+
+fn code(field: &str, code: &str) -> (String, String) {
+    (field.to_string(), code.to_string())
+}
+
+#[test]
+fn test_valid_list() {
+    assert!(create("Groceries").validate().is_ok());
+    let full = CreateTodoListRequest {
+        description: Some("d".repeat(DESCRIPTION_MAX_LENGTH)),
+        color: Some("#1E90ff".to_string()),
+        ..create(&"é".repeat(NAME_MAX_LENGTH))
+    };
+    assert!(full.validate().is_ok());
+}
+
+#[test]
+fn test_invalid_names() {
+    assert_eq!(codes(create("  ").validate()), vec![code("name", "blank")]);
+    assert_eq!(codes(create(&"n".repeat(NAME_MAX_LENGTH + 1)).validate()), vec![code("name", "too_long")]);
+    assert_eq!(codes(create("Groceries\n").validate()), vec![code("name", "control_characters")]);
+}
+
+#[test]
+fn test_invalid_colors_and_descriptions() {
+    for color in ["1e90ff", "#1e90f", "#1e90ffa", "#gggggg", "blue"] {
+        let request = CreateTodoListRequest { color: Some(color.to_string()), ..create("Groceries") };
+        assert_eq!(codes(request.validate()), vec![code("color", "invalid")], "{color}");
+    }
+    let request = CreateTodoListRequest { description: Some("d".repeat(DESCRIPTION_MAX_LENGTH + 1)), ..create("Groceries") };
+    assert_eq!(codes(request.validate()), vec![code("description", "too_long")]);
+}
+
+#[test]
+fn test_update_needs_a_valid_field() {
+    assert_eq!(codes(UpdateTodoListRequest::default().validate()), vec![code("body", "empty_update")]);
+    let blank = UpdateTodoListRequest { name: Some(String::new()), color: Some("red".to_string()), ..Default::default() };
+    assert_eq!(codes(blank.validate()), vec![code("name", "blank"), code("color", "invalid")]);
+    assert!(UpdateTodoListRequest { description: Some(String::new()), ..Default::default() }.validate().is_ok());
+}
@@ -0,0 +1,54 @@
+mod todos {
+    mod entities {
+        mod todo_tests;
+    }
+    mod value_objects {
+        mod value_objects_tests;
+        mod patch_tests;
+        mod search_tests;
+        mod history_tests;
+        mod outbox_tests;
+    }
+    mod validation {
+        mod validation_tests;
+    }
+}
+
+mod lists {
+    mod validation {
+        mod validation_tests;
+    }
+}
+
+mod tags {
+    mod validation {
+        mod validation_tests;
+    }
+}
+
+mod webhooks {
+    mod value_objects {
+        mod signature_tests;
+    }
+    mod validation {
+        mod validation_tests;
+    }
+}
+
+mod users {
+    mod value_objects {
+        mod refresh_token_tests;
+    }
+    mod validation {
+        mod validation_tests;
+    }
+}
+
+mod api_keys {
+    mod value_objects {
+        mod api_key_tests;
+    }
+    mod validation {
+        mod validation_tests;
+    }
+}
@@ -0,0 +1,41 @@
+use axum_api::{
+    domain::tags::{CreateTagRequest, RenameTagRequest, validation::NAME_MAX_LENGTH},
+    domain::todos::validation::FieldError,
+    error::ApiError,
+};
+
+fn codes(result: Result<(), ApiError>) -> Vec<(String, String)> {
+    match result {
+        Err(ApiError::Validation(errors)) => errors.into_iter().map(|error: FieldError| (error.field, error.code)).collect(),
+        other => panic!("expected validation error, got {other:?}"),
+    }
+}
+
+fn create(name: &str) -> CreateTagRequest {
+    CreateTagRequest { name: name.to_string() }
+}
+
+fn code(field: &str, code: &str) -> (String, String) {
+    (field.to_string(), code.to_string())
+}
+
+#[test]
+fn test_valid_tag() {
+    assert!(create("work").validate().is_ok());
+    assert!(create("needs review").validate().is_ok());
+    assert!(create(&"é".repeat(NAME_MAX_LENGTH)).validate().is_ok());
+}
+
+#[test]
+fn test_invalid_names() {
+    assert_eq!(codes(create("  ").validate()), vec![code("name", "blank")]);
+    assert_eq!(codes(create(&"n".repeat(NAME_MAX_LENGTH + 1)).validate()), vec![code("name", "too_long")]);
+    assert_eq!(codes(create(" work").validate()), vec![code("name", "untrimmed")]);
+    assert_eq!(codes(create("wo\trk").validate()), vec![code("name", "control_characters")]);
+}
+
+#[test]
+fn test_rename_is_validated() {
+    assert!(RenameTagRequest { name: "office".to_string() }.validate().is_ok());
+    assert_eq!(codes(RenameTagRequest { name: String::new() }.validate()), vec![code("name", "blank")]);
+}
@@ -10,13 +10,22 @@ fn test_todo_creation() {
         id,
         title: "Test Todo".to_string(),
         done: false,
+        description: None,
+        completed_at: None,
+        due_at: None,
+        priority: Default::default(),
         created_at: now,
         updated_at: now,
+        version: 1,
+        list_id: None,
+        tags: Vec::new(),
+        owner_id: None,
+        tenant_id: "default".to_string(),
     };
 
     assert_eq!(todo.id, id);
     assert_eq!(todo.title, "Test Todo");
-    assert_eq!(todo.done, false);
+    assert!(!todo.done);
     assert_eq!(todo.created_at, now);
     assert_eq!(todo.updated_at, now);
 }
@@ -29,8 +38,17 @@ fn test_todo_serialization() {
         id,
         title: "Test Todo".to_string(),
         done: false,
+        description: None,
+        completed_at: None,
+        due_at: None,
+        priority: Default::default(),
         created_at: now,
         updated_at: now,
+        version: 1,
+        list_id: None,
+        tags: Vec::new(),
+        owner_id: None,
+        tenant_id: "default".to_string(),
     };
 
     let json = serde_json::to_string(&todo).unwrap();
@@ -0,0 +1,160 @@
+use axum_api::{
+    domain::todos::{
+        CreateTodoRequest, UpdateTodoRequest, PaginationQuery, SearchTodosQuery, GrantAccessRequest, TodoRole,
+        validation::{FieldError, DESCRIPTION_MAX_LENGTH, TITLE_MAX_LENGTH},
+    },
+    error::ApiError,
+};
+
+fn errors(result: Result<(), ApiError>) -> Vec<FieldError> {
+    match result {
+        Err(ApiError::Validation(errors)) => errors,
+        other => panic!("expected validation error, got {other:?}"),
+    }
+}
+
+fn create(title: &str) -> CreateTodoRequest {
+    CreateTodoRequest { title: title.to_string(), done: None, list_id: None, ..Default::default() }
+}
+
+#[test]
+fn test_valid_title() {
+    assert!(create("Buy milk").validate().is_ok());
+    assert!(create(&"é".repeat(TITLE_MAX_LENGTH)).validate().is_ok());
+}
+
+#[test]
+fn test_blank_title() {
+    for title in ["", "   ", "\u{3000}"] {
+        let errors = errors(create(title).validate());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "title");
+        assert_eq!(errors[0].code, "blank");
+    }
+}
+
+#[test]
+fn test_title_too_long() {
+    let errors = errors(create(&"a".repeat(TITLE_MAX_LENGTH + 1)).validate());
+    assert_eq!(errors[0].code, "too_long");
+}
+
+#[test]
+fn test_title_with_surrounding_whitespace() {
+    for title in [" Buy milk", "Buy milk ", "\u{3000}Buy milk"] {
+        let errors = errors(create(title).validate());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, "surrounding_whitespace");
+    }
+    assert!(create("Buy  milk").validate().is_ok());
+
+    let update = UpdateTodoRequest { title: Some("Buy milk ".to_string()), ..Default::default() };
+    assert_eq!(errors(update.validate())[0].code, "surrounding_whitespace");
+}
+
+#[test]
+fn test_title_with_control_characters() {
+    let errors = errors(create("line\nbreak").validate());
+    assert_eq!(errors[0].code, "control_characters");
+}
+
+#[test]
+fn test_update_must_set_something() {
+    let errors = errors(UpdateTodoRequest { title: None, done: None, ..Default::default() }.validate());
+    assert_eq!(errors[0].code, "empty_update");
+
+    assert!(UpdateTodoRequest { title: None, done: Some(false), ..Default::default() }.validate().is_ok());
+}
+
+#[test]
+fn test_update_title_is_validated() {
+    let errors = errors(
+        UpdateTodoRequest { title: Some("\t".to_string()), done: None, ..Default::default() }.validate(),
+    );
+    let codes: Vec<&str> = errors.iter().map(|e| e.code.as_str()).collect();
+    assert_eq!(codes, vec!["blank", "control_characters"]);
+}
+
+#[test]
+fn test_description_is_validated() {
+    let described = |description: &str| CreateTodoRequest { description: Some(description.to_string()), ..create("Buy milk") };
+
+    assert!(described("- [ ] oat\n- [x] whole\r\n\tcold").validate().is_ok());
+    assert!(described(&"é".repeat(DESCRIPTION_MAX_LENGTH)).validate().is_ok());
+    assert_eq!(errors(described(&"a".repeat(DESCRIPTION_MAX_LENGTH + 1)).validate())[0].code, "too_long");
+    assert_eq!(errors(described("bell\u{7}").validate())[0].code, "control_characters");
+
+    let update = |description: Option<Option<String>>| UpdateTodoRequest { description, ..Default::default() };
+    assert!(update(Some(None)).validate().is_ok());
+    assert_eq!(errors(update(Some(Some("\u{0}".to_string()))).validate())[0].field, "description");
+}
+
+#[test]
+fn test_sort_fields_are_whitelisted() {
+    let query = |sort: &str| PaginationQuery { sort: Some(sort.to_string()), ..Default::default() };
+
+    assert!(query("-updated_at,title").validate().is_ok());
+
+    let errors = errors(query("title,title;DROP TABLE todos,-title").validate());
+    let codes: Vec<&str> = errors.iter().map(|e| e.code.as_str()).collect();
+    assert_eq!(codes, vec!["unknown_sort_field", "duplicate_sort_field"]);
+    assert!(errors.iter().all(|e| e.field == "sort"));
+}
+
+#[test]
+fn test_list_filters_are_validated() {
+    let now = chrono::Utc::now();
+    let errors = errors(
+        PaginationQuery { created_after: Some(now), created_before: Some(now), ..Default::default() }.validate(),
+    );
+    assert_eq!(errors[0].field, "created_before");
+    assert_eq!(errors[0].code, "invalid_range");
+
+    let cursor_sorted = PaginationQuery {
+        cursor: Some(String::new()),
+        sort: Some("title".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(errors_of(cursor_sorted), vec!["unsupported_with_cursor"]);
+
+    let cursor_default = PaginationQuery {
+        cursor: Some(String::new()),
+        sort: Some("-created_at".to_string()),
+        ..Default::default()
+    };
+    assert!(cursor_default.validate().is_ok());
+
+    assert!(PaginationQuery { overdue: true, due_today: true, ..Default::default() }.validate().is_ok());
+    let done_overdue = PaginationQuery { overdue: true, done: Some(true), ..Default::default() };
+    assert_eq!(errors_of(done_overdue), vec!["conflicting_filter"]);
+
+    let tags = |count: usize| PaginationQuery { tag: (0..count).map(|i| format!("tag{i}")).collect(), ..Default::default() };
+    assert!(tags(PaginationQuery::MAX_TAGS).validate().is_ok());
+    assert_eq!(errors_of(tags(PaginationQuery::MAX_TAGS + 1)), vec!["too_many"]);
+}
+
+fn errors_of(query: PaginationQuery) -> Vec<String> {
+    errors(query.validate()).into_iter().map(|e| e.code).collect()
+}
+
+#[test]
+fn test_search_query_needs_a_word() {
+    let search = |q: &str| SearchTodosQuery { q: q.to_string(), page: 1, limit: 10 };
+
+    assert!(search("milk").validate().is_ok());
+    assert_eq!(errors(search(" \"\" * -- ").validate())[0].code, "blank");
+    assert_eq!(errors(search(&"a ".repeat(200)).validate())[0].code, "too_long");
+}
+
+#[test]
+fn test_grant_access_request() {
+    let grant = |email: &str, role| GrantAccessRequest { email: email.to_string(), role };
+    assert!(grant("bob@example.com", TodoRole::Viewer).validate().is_ok());
+    assert!(grant("bob@example.com", TodoRole::Editor).validate().is_ok());
+
+    let codes: Vec<(String, String)> = errors(grant(" ", TodoRole::Owner).validate())
+        .into_iter()
+        .map(|error| (error.field, error.code))
+        .collect();
+    assert_eq!(codes, vec![("email".into(), "blank".into()), ("role".into(), "not_grantable".into())]);
+}
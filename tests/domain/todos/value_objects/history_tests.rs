@@ -0,0 +1,46 @@
+use axum_api::domain::todos::{diff, FieldChange, NewTodoEvent, Todo, TodoEventKind};
+use axum_api::request_context::RequestContext;
+use chrono::Utc;
+use serde_json::json;
+use uuid::Uuid;
+
+fn todo(title: &str, done: bool, version: i64) -> Todo {
+    Todo { id: Uuid::nil(), title: title.to_string(), done, description: None, completed_at: None, due_at: None, priority: Default::default(), created_at: Utc::now(), updated_at: Utc::now(), version, list_id: None, tags: Vec::new(), owner_id: None, tenant_id: "default".to_string() }
+}
+
+#[test]
+fn test_diff_skips_bookkeeping_fields() {
+    let before = todo("Buy milk", false, 1);
+    let after = Todo {
+        updated_at: Utc::now() + chrono::Duration::seconds(1),
+        completed_at: Some(Utc::now()),
+        ..todo("Buy milk", true, 2)
+    };
+
+    assert_eq!(
+        diff(Some(&before), Some(&after)),
+        vec![FieldChange { field: "done".to_string(), from: json!(false), to: json!(true) }]
+    );
+    assert!(diff(Some(&before), Some(&before)).is_empty());
+}
+
+#[test]
+fn test_diff_against_nothing_lists_every_field() {
+    let created = todo("Buy milk", false, 1);
+    let fields: Vec<String> = diff(None, Some(&created)).into_iter().map(|change| change.field).collect();
+    assert_eq!(fields, vec!["done", "priority", "tags", "title"]);
+}
+
+#[tokio::test]
+async fn test_events_carry_the_request_context() {
+    let context = RequestContext { request_id: Some("abc".to_string()), ..RequestContext::default() };
+    let event = context.scope(async { NewTodoEvent::deleted(&todo("Buy milk", false, 3), 4) }).await;
+
+    assert_eq!(event.kind, TodoEventKind::Deleted);
+    assert_eq!(event.version, 4);
+    assert_eq!(event.request_id.as_deref(), Some("abc"));
+    assert!(event.changes.is_empty());
+    assert!(event.after.is_none());
+
+    assert_eq!(NewTodoEvent::created(&todo("Buy milk", false, 1)).request_id, None);
+}
@@ -0,0 +1,11 @@
+use axum_api::domain::todos::DomainEvent;
+use uuid::Uuid;
+
+#[test]
+fn test_events_serialize_with_type_and_data() {
+    let event = DomainEvent::TodoDeleted { todo_id: Uuid::nil(), version: 2 };
+    assert_eq!(
+        serde_json::to_value(&event).unwrap(),
+        serde_json::json!({ "type": "TodoDeleted", "data": { "todo_id": Uuid::nil(), "version": 2 } })
+    );
+}
@@ -0,0 +1,128 @@
+use axum_api::{
+    domain::todos::{Todo, TodoPatch, TodoPriority, ReplaceTodoRequest},
+    error::ApiError,
+};
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+fn todo() -> Todo {
+    Todo {
+        id: Uuid::new_v4(),
+        title: "Buy milk".to_string(),
+        done: false,
+        description: None,
+        completed_at: None,
+        due_at: None,
+        priority: Default::default(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        version: 1,
+        list_id: None,
+        tags: Vec::new(),
+        owner_id: None,
+        tenant_id: "default".to_string(),
+    }
+}
+
+fn error_codes(result: Result<ReplaceTodoRequest, ApiError>) -> Vec<String> {
+    match result {
+        Err(ApiError::Validation(errors)) => errors.into_iter().map(|e| e.code).collect(),
+        other => panic!("expected validation error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_parse_selects_format_by_media_type() {
+    let merge = TodoPatch::parse("application/merge-patch+json; charset=utf-8", br#"{"done":true}"#);
+    assert!(matches!(merge, Ok(TodoPatch::Merge(_))));
+
+    let json = TodoPatch::parse("application/json-patch+json", br#"[{"op":"remove","path":"/x"}]"#);
+    assert!(matches!(json, Ok(TodoPatch::Json(_))));
+
+    assert!(matches!(TodoPatch::parse("application/json", b"{}"), Err(ApiError::UnsupportedMediaType(_))));
+    assert!(matches!(TodoPatch::parse("application/merge-patch+json", b"{"), Err(ApiError::BadRequest(_))));
+}
+
+#[test]
+fn test_merge_patch_keeps_absent_fields() {
+    let patch = TodoPatch::Merge(json!({ "done": true }));
+    let result = patch.apply(&todo()).unwrap();
+    assert_eq!(
+        result,
+        ReplaceTodoRequest {
+            title: "Buy milk".to_string(),
+            done: true,
+            description: None,
+            due_at: None,
+            priority: TodoPriority::Medium,
+            list_id: None,
+        }
+    );
+}
+
+#[test]
+fn test_merge_patch_sets_and_clears_optional_fields() {
+    let mut current = todo();
+    current.description = Some("Semi-skimmed".to_string());
+
+    let patch = TodoPatch::Merge(json!({
+        "description": null,
+        "due_at": "2030-01-02T09:00:00Z",
+        "priority": "high"
+    }));
+    let result = patch.apply(&current).unwrap();
+    assert_eq!(result.description, None);
+    assert_eq!(result.due_at, Some(Utc.with_ymd_and_hms(2030, 1, 2, 9, 0, 0).unwrap()));
+    assert_eq!(result.priority, TodoPriority::High);
+}
+
+#[test]
+fn test_merge_patch_moves_between_lists() {
+    let mut current = todo();
+    current.list_id = Some(Uuid::new_v4());
+    let target = Uuid::new_v4();
+
+    let moved = TodoPatch::Merge(json!({ "list_id": target })).apply(&current).unwrap();
+    assert_eq!(moved.list_id, Some(target));
+    assert_eq!(TodoPatch::Merge(json!({})).apply(&current).unwrap().list_id, current.list_id);
+    assert_eq!(TodoPatch::Merge(json!({ "list_id": null })).apply(&current).unwrap().list_id, None);
+}
+
+#[test]
+fn test_patch_rejects_invalid_optional_fields() {
+    let patch = TodoPatch::Merge(json!({ "description": 1, "due_at": "tomorrow", "priority": "urgent", "list_id": 7 }));
+    assert_eq!(error_codes(patch.apply(&todo())), vec!["invalid_type", "invalid_type", "invalid_type", "invalid_type"]);
+
+    let removed = TodoPatch::Merge(json!({ "priority": null }));
+    assert_eq!(error_codes(removed.apply(&todo())), vec!["required"]);
+}
+
+#[test]
+fn test_merge_patch_null_removes_required_field() {
+    let patch = TodoPatch::Merge(json!({ "title": null }));
+    assert_eq!(error_codes(patch.apply(&todo())), vec!["required"]);
+}
+
+#[test]
+fn test_patch_rejects_wrong_types_and_unknown_fields() {
+    let patch = TodoPatch::Merge(json!({ "done": "yes", "colour": 1 }));
+    assert_eq!(error_codes(patch.apply(&todo())), vec!["invalid_type", "unknown_field"]);
+}
+
+#[test]
+fn test_json_patch_operations() {
+    let patch: TodoPatch = TodoPatch::Json(
+        serde_json::from_value(json!([
+            { "op": "test", "path": "/title", "value": "Buy milk" },
+            { "op": "replace", "path": "/done", "value": true }
+        ]))
+        .unwrap(),
+    );
+    assert!(patch.apply(&todo()).unwrap().done);
+
+    let failing = TodoPatch::Json(
+        serde_json::from_value(json!([{ "op": "test", "path": "/title", "value": "other" }])).unwrap(),
+    );
+    assert_eq!(error_codes(failing.apply(&todo())), vec!["patch_failed"]);
+}
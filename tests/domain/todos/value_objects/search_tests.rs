@@ -0,0 +1,82 @@
+use axum_api::domain::todos::{SearchTerm, SearchTodosQuery, Todo, TodoSearch};
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+fn parse(q: &str) -> TodoSearch {
+    TodoSearch::parse(&SearchTodosQuery { q: q.to_string(), page: 1, limit: 10 })
+}
+
+fn word(text: &str, prefix: bool) -> SearchTerm {
+    SearchTerm::Word { text: text.to_string(), prefix }
+}
+
+#[test]
+fn test_parse_words_phrases_and_prefixes() {
+    let search = parse(r#"Milk "fresh  BREAD" groc* e-mail "solo""#);
+    assert_eq!(
+        search.terms,
+        vec![
+            word("milk", false),
+            SearchTerm::Phrase(vec!["fresh".to_string(), "bread".to_string()]),
+            word("groc", true),
+            SearchTerm::Phrase(vec!["e".to_string(), "mail".to_string()]),
+            word("solo", false),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_drops_operators_and_punctuation() {
+    assert_eq!(parse("milk & !bread:*").terms, vec![word("milk", false), word("bread", true)]);
+    assert!(parse(r#" "" * -- "#).terms.is_empty());
+}
+
+#[test]
+fn test_score_requires_every_term_and_highlights() {
+    let search = parse(r#""fresh milk" groc*"#);
+
+    let (rank, snippet) = search.score("Buy fresh Milk at the grocery").unwrap();
+    assert_eq!(snippet, "Buy <mark>fresh</mark> <mark>Milk</mark> at the <mark>grocery</mark>");
+    assert!((rank - 0.5).abs() < f32::EPSILON);
+
+    assert!(search.score("Buy milk fresh at the grocery").is_none());
+    assert!(search.score("Fresh milk").is_none());
+}
+
+#[test]
+fn test_score_escapes_the_title() {
+    let (_, snippet) = parse("milk").score("<b>Milk</b> & \"bread\" 'n' <script>").unwrap();
+    assert_eq!(snippet, "&lt;b&gt;<mark>Milk</mark>&lt;/b&gt; &amp; &quot;bread&quot; &#39;n&#39; &lt;script&gt;");
+}
+
+#[test]
+fn test_rank_orders_by_relevance_then_newest() {
+    let todo = |title: &str, age: i64| Todo {
+        id: Uuid::new_v4(),
+        title: title.to_string(),
+        done: false,
+        description: None,
+        completed_at: None,
+        due_at: None,
+        priority: Default::default(),
+        created_at: Utc::now() - Duration::minutes(age),
+        updated_at: Utc::now(),
+        version: 1,
+        list_id: None,
+        tags: Vec::new(),
+        owner_id: None,
+        tenant_id: "default".to_string(),
+    };
+    let search = parse("milk");
+
+    let page = search.rank(vec![
+        todo("Buy milk and bread", 2),
+        todo("Milk", 3),
+        todo("Bread", 0),
+        todo("Oat milk please thanks", 1),
+    ]);
+
+    let titles: Vec<&str> = page.data.iter().map(|hit| hit.todo.title.as_str()).collect();
+    assert_eq!(titles, vec!["Milk", "Oat milk please thanks", "Buy milk and bread"]);
+    assert_eq!(page.pagination.total, 3);
+}
@@ -1,12 +1,15 @@
 use axum_api::domain::todos::value_objects::{
     CreateTodoRequest, UpdateTodoRequest, PaginationQuery, 
-    PaginatedResponse, PaginationMeta
+    PaginatedResponse, PaginationMeta, SortField, SortKey, TagMatch, TodoRole
 };
 
 #[test]
 fn test_create_todo_request() {
     let request = CreateTodoRequest {
         title: "Test Todo".to_string(),
+        done: None,
+        list_id: None,
+        ..Default::default()
     };
 
     assert_eq!(request.title, "Test Todo");
@@ -17,6 +20,7 @@ fn test_update_todo_request() {
     let request = UpdateTodoRequest {
         title: Some("Updated Todo".to_string()),
         done: Some(true),
+        ..Default::default()
     };
 
     assert_eq!(request.title, Some("Updated Todo".to_string()));
@@ -28,6 +32,7 @@ fn test_update_todo_request_partial() {
     let request = UpdateTodoRequest {
         title: Some("Updated Todo".to_string()),
         done: None,
+        ..Default::default()
     };
 
     assert_eq!(request.title, Some("Updated Todo".to_string()));
@@ -39,8 +44,13 @@ fn test_pagination_query_defaults() {
     let query = PaginationQuery {
         page: 0,
         limit: 0,
+        ..Default::default()
     };
 
+    // Out-of-range values are clamped
+    assert_eq!(query.page(), 1);
+    assert_eq!(query.limit(), 1);
+
     // Test default values
     assert_eq!(axum_api::domain::todos::value_objects::default_page(), 1);
     assert_eq!(axum_api::domain::todos::value_objects::default_limit(), 10);
@@ -89,3 +99,41 @@ fn test_paginated_response() {
     assert_eq!(response.data.len(), 2);
     assert_eq!(response.pagination.total, 2);
 }
+
+#[test]
+fn test_sort_keys_parse_direction_and_default() {
+    let query = PaginationQuery { sort: Some("-updated_at, title".to_string()), ..Default::default() };
+    assert_eq!(
+        query.sort_keys(),
+        vec![
+            SortKey { field: SortField::UpdatedAt, descending: true },
+            SortKey { field: SortField::Title, descending: false },
+        ]
+    );
+
+    assert_eq!(PaginationQuery::default().sort_keys(), SortKey::DEFAULT);
+}
+
+#[test]
+fn test_repeated_tags_count_once() {
+    let query = PaginationQuery {
+        tag: vec!["work".to_string(), "home".to_string(), "work".to_string()],
+        tag_match: TagMatch::All,
+        ..Default::default()
+    };
+    let filter = query.filter();
+    assert_eq!(filter.tags, vec!["home", "work"]);
+    assert_eq!(filter.tag_match, TagMatch::All);
+}
+
+#[test]
+fn test_todo_roles_are_ordered() {
+    assert!(TodoRole::Owner.includes(TodoRole::Editor));
+    assert!(TodoRole::Editor.includes(TodoRole::Viewer));
+    assert!(!TodoRole::Viewer.includes(TodoRole::Editor));
+    for role in [TodoRole::Viewer, TodoRole::Editor, TodoRole::Owner] {
+        assert_eq!(TodoRole::try_from(role.as_str().to_string()).unwrap(), role);
+        assert_eq!(serde_json::to_value(role).unwrap(), role.as_str());
+    }
+    assert!(TodoRole::try_from("admin".to_string()).is_err());
+}
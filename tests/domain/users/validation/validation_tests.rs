@@ -0,0 +1,71 @@
+use axum_api::{
+    domain::todos::validation::FieldError,
+    domain::users::{
+        RegisterRequest,
+        validation::{normalize_email, EMAIL_MAX_LENGTH, PASSWORD_MAX_LENGTH, PASSWORD_MIN_LENGTH},
+    },
+    error::ApiError,
+};
+
+fn codes(result: Result<(), ApiError>) -> Vec<(String, String)> {
+    match result {
+        Err(ApiError::Validation(errors)) => errors.into_iter().map(|error: FieldError| (error.field, error.code)).collect(),
+        other => panic!("expected validation error, got {other:?}"),
+    }
+}
+
+fn register(email: &str, password: &str) -> RegisterRequest {
+    RegisterRequest { email: email.to_string(), password: password.to_string() }
+}
+
+#[test]
+fn test_normalize_email() {
+    assert_eq!(normalize_email("  Ada@Example.COM "), "ada@example.com");
+}
+
+#[test]
+fn test_valid_registration() {
+    assert!(register("ada@example.com", "correct horse").validate().is_ok());
+    assert!(register(" Ada@Example.com ", &"p".repeat(PASSWORD_MAX_LENGTH)).validate().is_ok());
+}
+
+#[test]
+fn test_invalid_emails() {
+    let long = format!("{}@example.com", "a".repeat(EMAIL_MAX_LENGTH));
+    for (email, code) in [
+        ("", "invalid"),
+        ("ada", "invalid"),
+        ("@example.com", "invalid"),
+        ("ada@localhost", "invalid"),
+        ("ada@.example.com", "invalid"),
+        ("ada@example.com.", "invalid"),
+        ("ada@b@example.com", "invalid"),
+        ("a da@example.com", "invalid"),
+        (long.as_str(), "too_long"),
+    ] {
+        assert_eq!(
+            codes(register(email, "correct horse").validate()),
+            vec![("email".to_string(), code.to_string())],
+            "{email}"
+        );
+    }
+}
+
+#[test]
+fn test_password_length() {
+    let short = "p".repeat(PASSWORD_MIN_LENGTH - 1);
+    let long = "p".repeat(PASSWORD_MAX_LENGTH + 1);
+    for password in [short, long] {
+        assert_eq!(
+            codes(register("ada@example.com", &password).validate()),
+            vec![("password".to_string(), "invalid_length".to_string())]
+        );
+    }
+    // Counted in characters, not bytes.
+    assert!(register("ada@example.com", &"é".repeat(PASSWORD_MIN_LENGTH)).validate().is_ok());
+}
+
+#[test]
+fn test_all_errors_are_reported() {
+    assert_eq!(codes(register("ada", "short").validate()).len(), 2);
+}
@@ -0,0 +1,40 @@
+use axum_api::domain::users::{hash_refresh_token, RefreshRotation, RefreshSecret, StoredRefreshToken};
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+fn stored() -> StoredRefreshToken {
+    StoredRefreshToken {
+        user_id: Uuid::new_v4(),
+        family_id: Uuid::new_v4(),
+        expires_at: Utc::now() + Duration::days(1),
+        replaced_at: None,
+        revoked_at: None,
+    }
+}
+
+#[test]
+fn test_generated_secrets_are_random_and_hashed() {
+    let first = RefreshSecret::generate();
+    let second = RefreshSecret::generate();
+    assert_ne!(first.secret, second.secret);
+    assert_eq!(first.hash, hash_refresh_token(&first.secret));
+    assert_ne!(first.hash, first.secret);
+    assert_eq!(first.hash.len(), 64);
+}
+
+#[test]
+fn test_rotation() {
+    let now = Utc::now();
+    let token = stored();
+    assert_eq!(token.rotation(now), RefreshRotation::Rotated { user_id: token.user_id });
+
+    let expired = StoredRefreshToken { expires_at: now, ..stored() };
+    assert_eq!(expired.rotation(now), RefreshRotation::Invalid);
+
+    let replaced = StoredRefreshToken { replaced_at: Some(now), ..stored() };
+    assert_eq!(replaced.rotation(now), RefreshRotation::Reused);
+
+    // A revoked family stays revoked; reuse is only reported once.
+    let revoked = StoredRefreshToken { replaced_at: Some(now), revoked_at: Some(now), ..stored() };
+    assert_eq!(revoked.rotation(now), RefreshRotation::Invalid);
+}
@@ -0,0 +1,110 @@
+use axum_api::{
+    domain::todos::validation::FieldError,
+    domain::webhooks::{
+        CreateWebhookRequest, TargetPolicy, UpdateWebhookRequest,
+        validation::{SECRET_MIN_LENGTH, URL_MAX_LENGTH},
+    },
+    error::ApiError,
+};
+
+const PUBLIC: TargetPolicy = TargetPolicy { allow_private: false };
+
+fn errors(result: Result<(), ApiError>) -> Vec<FieldError> {
+    match result {
+        Err(ApiError::Validation(errors)) => errors,
+        other => panic!("expected validation error, got {other:?}"),
+    }
+}
+
+fn codes(result: Result<(), ApiError>) -> Vec<(String, String)> {
+    errors(result).into_iter().map(|error| (error.field, error.code)).collect()
+}
+
+fn create(url: &str, events: &[&str], secret: &str) -> CreateWebhookRequest {
+    CreateWebhookRequest {
+        url: url.to_string(),
+        events: events.iter().map(|event| event.to_string()).collect(),
+        secret: secret.to_string(),
+    }
+}
+
+#[test]
+fn test_valid_webhook() {
+    assert!(create("https://example.com/hooks", &[], "0123456789abcdef").validate(PUBLIC).is_ok());
+    assert!(create("http://93.184.216.34:8080", &["TodoCreated", "TodoDeleted"], &"s".repeat(256)).validate(PUBLIC).is_ok());
+}
+
+#[test]
+fn test_private_targets() {
+    let private = [
+        "http://localhost:8080",
+        "http://api.LOCALHOST./hooks",
+        "http://127.0.0.1/hooks",
+        "http://10.1.2.3/hooks",
+        "http://172.16.0.1/hooks",
+        "http://192.168.1.1/hooks",
+        "http://169.254.169.254/latest/meta-data",
+        "http://100.64.0.1/hooks",
+        "http://0.0.0.0/hooks",
+        "http://[::1]/hooks",
+        "http://[::]/hooks",
+        "http://[fd00::1]/hooks",
+        "http://[fe80::1]/hooks",
+        "http://[::ffff:127.0.0.1]/hooks",
+        // Decimal and hex forms are normalized before the check.
+        "http://2130706433/hooks",
+        "http://0x7f.1/hooks",
+    ];
+    for url in private {
+        assert_eq!(
+            codes(create(url, &[], "0123456789abcdef").validate(PUBLIC)),
+            vec![("url".to_string(), "private_address".to_string())],
+            "{url}"
+        );
+        assert!(create(url, &[], "0123456789abcdef").validate(TargetPolicy { allow_private: true }).is_ok());
+    }
+    assert_eq!(
+        codes(UpdateWebhookRequest { url: Some("http://127.0.0.1".to_string()), ..Default::default() }.validate(PUBLIC)),
+        vec![("url".to_string(), "private_address".to_string())]
+    );
+}
+
+#[test]
+fn test_invalid_urls() {
+    for url in ["", "example.com/hooks", "not a url"] {
+        assert_eq!(codes(create(url, &[], "0123456789abcdef").validate(PUBLIC)), vec![("url".to_string(), "invalid".to_string())]);
+    }
+    for url in ["ftp://example.com", "mailto:ops@example.com"] {
+        assert_eq!(
+            codes(create(url, &[], "0123456789abcdef").validate(PUBLIC)),
+            vec![("url".to_string(), "unsupported_scheme".to_string())]
+        );
+    }
+    let long = format!("https://example.com/{}", "a".repeat(URL_MAX_LENGTH));
+    assert_eq!(codes(create(&long, &[], "0123456789abcdef").validate(PUBLIC)), vec![("url".to_string(), "too_long".to_string())]);
+}
+
+#[test]
+fn test_unknown_event_and_short_secret() {
+    let short = "s".repeat(SECRET_MIN_LENGTH - 1);
+    assert_eq!(
+        codes(create("https://example.com", &["TodoCreated", "TodoArchived"], &short).validate(PUBLIC)),
+        vec![
+            ("events".to_string(), "unknown_event".to_string()),
+            ("secret".to_string(), "invalid_length".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_update_checks_present_fields_only() {
+    assert!(UpdateWebhookRequest { active: Some(true), ..Default::default() }.validate(PUBLIC).is_ok());
+    assert_eq!(
+        codes(UpdateWebhookRequest::default().validate(PUBLIC)),
+        vec![("body".to_string(), "empty_update".to_string())]
+    );
+    assert_eq!(
+        codes(UpdateWebhookRequest { secret: Some("short".to_string()), ..Default::default() }.validate(PUBLIC)),
+        vec![("secret".to_string(), "invalid_length".to_string())]
+    );
+}
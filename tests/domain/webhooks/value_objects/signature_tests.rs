@@ -0,0 +1,42 @@
+use axum_api::domain::webhooks::{sign, verify};
+
+const SECRET: &str = "0123456789abcdef";
+const BODY: &[u8] = br#"{"type":"TodoCreated"}"#;
+
+#[test]
+fn test_sign_formats_timestamp_and_hex_mac() {
+    let signature = sign(SECRET, 1_700_000_000, BODY);
+    let (timestamp, mac) = signature.split_once(',').unwrap();
+    assert_eq!(timestamp, "t=1700000000");
+    let mac = mac.strip_prefix("v1=").unwrap();
+    assert_eq!(mac.len(), 64);
+    assert!(mac.chars().all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()));
+    assert_eq!(sign(SECRET, 1_700_000_000, BODY), signature);
+}
+
+#[test]
+fn test_verify_accepts_own_signature() {
+    let signature = sign(SECRET, 1_700_000_000, BODY);
+    assert!(verify(SECRET, &signature, BODY, 1_700_000_000, 300));
+    assert!(verify(SECRET, &signature, BODY, 1_700_000_300, 300));
+}
+
+#[test]
+fn test_verify_rejects_tampering() {
+    let signature = sign(SECRET, 1_700_000_000, BODY);
+    assert!(!verify("fedcba9876543210", &signature, BODY, 1_700_000_000, 300));
+    assert!(!verify(SECRET, &signature, br#"{"type":"TodoDeleted"}"#, 1_700_000_000, 300));
+
+    // The timestamp is covered by the MAC, so it cannot be refreshed.
+    let replayed = signature.replace("t=1700000000", "t=1700000600");
+    assert!(!verify(SECRET, &replayed, BODY, 1_700_000_600, 300));
+}
+
+#[test]
+fn test_verify_rejects_stale_and_malformed_headers() {
+    let signature = sign(SECRET, 1_700_000_000, BODY);
+    assert!(!verify(SECRET, &signature, BODY, 1_700_000_301, 300));
+    for header in ["", "t=1700000000", "v1=00", "t=abc,v1=00", "t=1700000000,v1=zz"] {
+        assert!(!verify(SECRET, header, BODY, 1_700_000_000, 300), "{header}");
+    }
+}
@@ -0,0 +1,61 @@
+use axum::{http::StatusCode, response::IntoResponse};
+use axum_api::{domain::todos::validation::FieldError, error::ApiError};
+
+#[test]
+fn test_status_and_code_mapping() {
+    let cases = [
+        (ApiError::NotFound, StatusCode::NOT_FOUND, "not_found"),
+        (ApiError::Validation(vec![]), StatusCode::UNPROCESSABLE_ENTITY, "validation_failed"),
+        (ApiError::Unauthorized("who?".into()), StatusCode::UNAUTHORIZED, "unauthorized"),
+        (ApiError::Forbidden("read-only".into()), StatusCode::FORBIDDEN, "forbidden"),
+        (ApiError::PayloadTooLarge("big".into()), StatusCode::PAYLOAD_TOO_LARGE, "payload_too_large"),
+        (ApiError::Conflict("dup".into()), StatusCode::CONFLICT, "conflict"),
+        (ApiError::FailedDependency, StatusCode::FAILED_DEPENDENCY, "failed_dependency"),
+        (ApiError::ConstraintViolation("check".into()), StatusCode::UNPROCESSABLE_ENTITY, "constraint_violation"),
+        (ApiError::ServiceUnavailable("down".into()), StatusCode::SERVICE_UNAVAILABLE, "service_unavailable"),
+        (ApiError::DatabaseError("boom".into()), StatusCode::INTERNAL_SERVER_ERROR, "database_error"),
+        (ApiError::Anyhow(anyhow::anyhow!("boom")), StatusCode::INTERNAL_SERVER_ERROR, "internal_error"),
+    ];
+
+    for (error, status, code) in cases {
+        assert_eq!(error.status(), status);
+        assert_eq!(error.code(), code);
+    }
+}
+
+#[test]
+fn test_problem_hides_server_side_cause() {
+    let problem = ApiError::DatabaseError("relation \"todos\" does not exist".into()).to_problem();
+    assert_eq!(problem.problem_type, "/problems/database-error");
+    assert_eq!(problem.status, 500);
+    assert!(!problem.detail.contains("todos"));
+    assert!(problem.errors.is_none());
+}
+
+#[test]
+fn test_validation_problem_carries_field_errors() {
+    let error = ApiError::Validation(vec![FieldError::new("title", "blank", "title must not be empty")]);
+    let problem = error.to_problem();
+    assert_eq!(problem.errors.unwrap()[0].field, "title");
+}
+
+#[test]
+fn test_sqlx_pool_errors_are_unavailable() {
+    assert!(matches!(ApiError::from(sqlx::Error::PoolTimedOut), ApiError::ServiceUnavailable(_)));
+    assert!(matches!(ApiError::from(sqlx::Error::PoolClosed), ApiError::ServiceUnavailable(_)));
+}
+
+#[test]
+fn test_into_response_sets_problem_content_type() {
+    let response = ApiError::NotFound.into_response();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.headers()["content-type"], "application/problem+json");
+}
+
+#[test]
+fn test_unauthorized_challenges_for_a_bearer_token() {
+    let response = ApiError::Unauthorized("The access token has expired.".into()).into_response();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(response.headers()["www-authenticate"], "Bearer");
+    assert_eq!(ApiError::Unauthorized("The access token has expired.".into()).to_problem().detail, "The access token has expired.");
+}
@@ -0,0 +1,191 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum_api::{
+    application::todos::TodoDomainEvents,
+    config::ChangesConfig,
+    domain::todos::{validation::DESCRIPTION_MAX_LENGTH, CreateTodoRequest, Todo, TodoChange, TodoChangeKind, UpdateTodoRequest, traits::{TodoCreator, TodoDeleter, TodoTrash, TodoUpdater}},
+    infrastructure::{
+        change_feed::{self, ChangeFeed, ChangeSource},
+        database::{repositories::{InMemoryTodoRepository, PostgresTodoRepository}, MIGRATOR},
+    },
+    request_context::{RequestContext, DEFAULT_TENANT},
+};
+use chrono::Utc;
+use sqlx::{postgres::PgPoolOptions, Executor};
+use tokio::sync::broadcast::Receiver;
+use uuid::Uuid;
+
+fn change(seq: i64) -> TodoChange {
+    let now = Utc::now();
+    let todo = Todo { id: Uuid::new_v4(), title: format!("todo {seq}"), done: false, description: None, completed_at: None, due_at: None, priority: Default::default(), created_at: now, updated_at: now, version: 1, list_id: None, tags: Vec::new(), owner_id: None, tenant_id: "default".to_string() };
+    TodoChange { seq, kind: TodoChangeKind::Created, todo }
+}
+
+fn seqs(changes: &[TodoChange]) -> Vec<i64> {
+    changes.iter().map(|change| change.seq).collect()
+}
+
+#[tokio::test]
+async fn test_subscribers_get_live_changes() {
+    let feed = ChangeFeed::new(10);
+    feed.publish(change(1));
+    let mut subscription = feed.subscribe(None);
+    assert!(subscription.replay.is_empty());
+    assert!(!subscription.missed);
+
+    feed.publish(change(2));
+    assert_eq!(subscription.receiver.recv().await.unwrap().seq, 2);
+}
+
+#[tokio::test]
+async fn test_resuming_replays_buffered_changes() {
+    let feed = ChangeFeed::new(3);
+    for seq in 1..=5 {
+        feed.publish(change(seq));
+    }
+
+    let subscription = feed.subscribe(Some(3));
+    assert_eq!(seqs(&subscription.replay), vec![4, 5]);
+    assert!(!subscription.missed);
+    assert!(feed.subscribe(Some(5)).replay.is_empty());
+
+    // 1 was evicted, so whatever followed it may be lost.
+    let subscription = feed.subscribe(Some(1));
+    assert!(subscription.replay.is_empty());
+    assert!(subscription.missed);
+
+    feed.clear();
+    assert!(feed.subscribe(Some(5)).missed);
+}
+
+async fn next_change_of(receiver: &mut Receiver<TodoChange>, id: Uuid) -> TodoChange {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let change = receiver.recv().await.unwrap();
+            if change.todo.id == id {
+                return change;
+            }
+        }
+    })
+    .await
+    .expect("no change notification arrived")
+}
+
+#[tokio::test]
+async fn test_audit_log_source_publishes_changes() {
+    RequestContext::for_tenant(DEFAULT_TENANT).scope(async {
+        let repo = Arc::new(InMemoryTodoRepository::new());
+        repo.create(CreateTodoRequest { title: "before startup".to_string(), done: None, list_id: None, ..Default::default() }, TodoDomainEvents::created).await.unwrap();
+        let feed = Arc::new(ChangeFeed::new(100));
+        let config = ChangesConfig { poll_interval_ms: 10, ..ChangesConfig::default() };
+        let mut receiver = feed.subscribe(None).receiver;
+        let task = change_feed::spawn(ChangeSource::AuditLog, repo.clone(), feed.clone(), &config);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let todo = repo.create(CreateTodoRequest { title: "Buy milk".to_string(), done: None, list_id: None, ..Default::default() }, TodoDomainEvents::created).await.unwrap();
+        repo.update(todo.id, UpdateTodoRequest { title: None, done: Some(true), ..Default::default() }, None, TodoDomainEvents::updated).await.unwrap();
+        repo.delete(todo.id, None, TodoDomainEvents::deleted).await.unwrap();
+        repo.restore(todo.id, TodoDomainEvents::restored).await.unwrap();
+
+        let mut kinds = Vec::new();
+        for _ in 0..4 {
+            kinds.push(next_change_of(&mut receiver, todo.id).await.kind);
+        }
+        assert_eq!(kinds, vec![TodoChangeKind::Created, TodoChangeKind::Updated, TodoChangeKind::Deleted, TodoChangeKind::Updated]);
+        task.abort();
+    })
+    .await;
+}
+
+/// Runs against `TEST_DATABASE_URL` when set, in a schema of its own. Other
+/// tests notify the same channel, so only this test's todos are checked.
+#[tokio::test]
+async fn test_postgres_notifications_reach_the_feed() {
+    RequestContext::for_tenant(DEFAULT_TENANT).scope(async {
+        let Ok(url) = std::env::var("TEST_DATABASE_URL") else { return };
+        let schema = format!("test_{}", Uuid::new_v4().simple());
+        let admin = PgPoolOptions::new().max_connections(1).connect(&url).await.unwrap();
+        admin.execute(format!("CREATE SCHEMA {schema}").as_str()).await.unwrap();
+        let pool = PgPoolOptions::new()
+            .max_connections(2)
+            .after_connect(move |conn, _| {
+                let schema = schema.clone();
+                Box::pin(async move {
+                    conn.execute(format!("SET search_path TO {schema}, public").as_str()).await?;
+                    Ok(())
+                })
+            })
+            .connect(&url)
+            .await
+            .unwrap();
+        MIGRATOR.run(&pool).await.unwrap();
+
+        let repo = Arc::new(PostgresTodoRepository::new(pool.clone()));
+        let feed = Arc::new(ChangeFeed::new(100));
+        let mut receiver = feed.subscribe(None).receiver;
+        let task = change_feed::spawn(ChangeSource::Notify(pool), repo.clone(), feed.clone(), &ChangesConfig::default());
+
+        // Wait until the listener is up.
+        let probe = repo.create(CreateTodoRequest { title: "probe".to_string(), done: None, list_id: None, ..Default::default() }, TodoDomainEvents::created).await.unwrap();
+        let probed = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                repo.update(probe.id, UpdateTodoRequest { title: None, done: None, ..Default::default() }, None, TodoDomainEvents::updated).await.unwrap();
+                if let Ok(Ok(change)) = tokio::time::timeout(Duration::from_millis(100), receiver.recv()).await
+                    && change.todo.id == probe.id
+                {
+                    break;
+                }
+            }
+        })
+        .await;
+        assert!(probed.is_ok(), "the listener never started");
+
+        // Notifications only name the todo, so each step is awaited before the
+        // next changes it again.
+        let description = "📝".repeat(DESCRIPTION_MAX_LENGTH);
+        let request = CreateTodoRequest { title: "Buy milk".to_string(), description: Some(description.clone()), ..Default::default() };
+        let todo = repo.create(request, TodoDomainEvents::created).await.unwrap();
+        let created = next_change_of(&mut receiver, todo.id).await;
+        assert_eq!(created.kind, TodoChangeKind::Created);
+        assert_eq!(created.todo.title, "Buy milk");
+        assert_eq!(created.todo.description, Some(description));
+        assert_eq!(created.todo.created_at, todo.created_at);
+
+        repo.update(todo.id, UpdateTodoRequest { title: None, done: Some(true), ..Default::default() }, None, TodoDomainEvents::updated).await.unwrap();
+        let completed = next_change_of(&mut receiver, todo.id).await;
+        assert_eq!(completed.kind, TodoChangeKind::Updated);
+        assert!(completed.todo.done);
+        assert!(completed.seq > created.seq);
+
+        repo.delete(todo.id, None, TodoDomainEvents::deleted).await.unwrap();
+        let deleted = next_change_of(&mut receiver, todo.id).await;
+        assert_eq!(deleted.kind, TodoChangeKind::Deleted);
+        assert_eq!(deleted.todo.version, 3);
+
+        repo.restore(todo.id, TodoDomainEvents::restored).await.unwrap();
+        assert_eq!(next_change_of(&mut receiver, todo.id).await.kind, TodoChangeKind::Updated);
+
+        // Should the todo be purged before the listener loads it, it is read
+        // from the audit log.
+        repo.delete(todo.id, None, TodoDomainEvents::deleted).await.unwrap();
+        repo.purge(todo.id).await.unwrap();
+        let marker = repo.create(CreateTodoRequest { title: "marker".to_string(), done: None, list_id: None, ..Default::default() }, TodoDomainEvents::created).await.unwrap();
+        let purged = next_change_of(&mut receiver, todo.id).await;
+        assert_eq!((purged.kind, purged.todo.version), (TodoChangeKind::Deleted, 5));
+        // The purge sends nothing, so the marker comes next.
+        let next = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let change = receiver.recv().await.unwrap();
+                if change.todo.id == todo.id || change.todo.id == marker.id {
+                    return change;
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(next.todo.id, marker.id);
+        task.abort();
+    })
+    .await;
+}
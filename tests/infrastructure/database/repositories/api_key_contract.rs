@@ -0,0 +1,112 @@
+//! Behaviour every `ApiKeyRepository` backend must share, opted into with
+//! `api_key_repository_contract!(factory)` like the todo contract. Backends
+//! with foreign keys must have seeded the fixture users.
+
+use axum_api::{
+    domain::api_keys::{traits::ApiKeyRepository, ApiKeyScope, ApiKeySecret, NewApiKey},
+    domain::todos::PaginationQuery,
+    error::ApiError,
+};
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use super::fixtures::{ALICE, BOB};
+
+pub fn new_key(user_id: Uuid, scope: ApiKeyScope, expires_at: Option<DateTime<Utc>>) -> (ApiKeySecret, NewApiKey) {
+    let secret = ApiKeySecret::generate();
+    let key = NewApiKey {
+        user_id,
+        name: "ci".to_string(),
+        scope,
+        expires_at,
+        prefix: secret.prefix.clone(),
+        key_hash: secret.hash.clone(),
+    };
+    (secret, key)
+}
+
+pub async fn create_then_find<R: ApiKeyRepository>(repo: &R) {
+    let expires_at = Utc::now() + Duration::days(30);
+    let (secret, key) = new_key(ALICE, ApiKeyScope::ReadWrite, Some(expires_at));
+    let created = repo.create(key).await.unwrap();
+    assert_eq!(created.user_id, ALICE);
+    assert_eq!(created.prefix, secret.prefix);
+    assert_eq!(created.scope, ApiKeyScope::ReadWrite);
+    assert_eq!(created.expires_at.unwrap().timestamp_micros(), expires_at.timestamp_micros());
+    assert!(created.last_used_at.is_none() && created.revoked_at.is_none());
+
+    let found = repo.find_by_id(ALICE, created.id).await.unwrap().unwrap();
+    assert_eq!(found.name, "ci");
+    assert!(repo.find_by_id(BOB, created.id).await.unwrap().is_none());
+    assert!(repo.find_by_id(ALICE, Uuid::new_v4()).await.unwrap().is_none());
+}
+
+pub async fn lists_are_per_user_and_oldest_first<R: ApiKeyRepository>(repo: &R) {
+    let first = repo.create(new_key(ALICE, ApiKeyScope::ReadOnly, None).1).await.unwrap();
+    repo.create(new_key(BOB, ApiKeyScope::Admin, None).1).await.unwrap();
+    let second = repo.create(new_key(ALICE, ApiKeyScope::Admin, None).1).await.unwrap();
+
+    let page = repo.find_all_paginated(ALICE, PaginationQuery::default()).await.unwrap();
+    assert_eq!(page.data.iter().map(|key| key.id).collect::<Vec<_>>(), vec![first.id, second.id]);
+    assert_eq!(page.pagination.total, 2);
+    let page = repo.find_all_paginated(ALICE, PaginationQuery { page: 2, limit: 1, ..Default::default() }).await.unwrap();
+    assert_eq!(page.data[0].id, second.id);
+}
+
+pub async fn authenticate_records_use<R: ApiKeyRepository>(repo: &R) {
+    let (secret, key) = new_key(ALICE, ApiKeyScope::ReadOnly, None);
+    let created = repo.create(key).await.unwrap();
+
+    let now = Utc::now();
+    let authenticated = repo.authenticate(&secret.hash, now).await.unwrap().unwrap();
+    assert_eq!(authenticated.id, created.id);
+    assert_eq!(authenticated.last_used_at.unwrap().timestamp_micros(), now.timestamp_micros());
+    let found = repo.find_by_id(ALICE, created.id).await.unwrap().unwrap();
+    assert!(found.last_used_at.is_some());
+
+    assert!(repo.authenticate(&ApiKeySecret::generate().hash, now).await.unwrap().is_none());
+}
+
+pub async fn expired_and_revoked_keys_do_not_authenticate<R: ApiKeyRepository>(repo: &R) {
+    let now = Utc::now();
+    let (expiring, key) = new_key(ALICE, ApiKeyScope::ReadOnly, Some(now + Duration::hours(1)));
+    repo.create(key).await.unwrap();
+    assert!(repo.authenticate(&expiring.hash, now).await.unwrap().is_some());
+    assert!(repo.authenticate(&expiring.hash, now + Duration::hours(1)).await.unwrap().is_none());
+
+    let (revoked, key) = new_key(ALICE, ApiKeyScope::ReadOnly, None);
+    let created = repo.create(key).await.unwrap();
+    assert!(matches!(repo.revoke(BOB, created.id, now).await, Err(ApiError::NotFound)));
+    let first = repo.revoke(ALICE, created.id, now).await.unwrap();
+    assert_eq!(first.revoked_at.unwrap().timestamp_micros(), now.timestamp_micros());
+    // Revoking again keeps the original time.
+    let again = repo.revoke(ALICE, created.id, now + Duration::minutes(5)).await.unwrap();
+    assert_eq!(again.revoked_at, first.revoked_at);
+    assert!(repo.authenticate(&revoked.hash, now).await.unwrap().is_none());
+
+    assert!(matches!(repo.revoke(ALICE, Uuid::new_v4(), now).await, Err(ApiError::NotFound)));
+}
+
+#[macro_export]
+macro_rules! api_key_repository_contract {
+    ($factory:expr) => {
+        $crate::api_key_repository_contract!(@cases $factory;
+            create_then_find,
+            lists_are_per_user_and_oldest_first,
+            authenticate_records_use,
+            expired_and_revoked_keys_do_not_authenticate,
+        );
+    };
+    (@cases $factory:expr; $($case:ident),* $(,)?) => {
+        $(
+            #[tokio::test]
+            async fn $case() {
+                if let Some(repo) = $factory.await {
+                    ::axum_api::request_context::RequestContext::for_tenant(::axum_api::request_context::DEFAULT_TENANT)
+                        .scope($crate::database::repositories::api_key_contract::$case(&repo))
+                        .await;
+                }
+            }
+        )*
+    };
+}
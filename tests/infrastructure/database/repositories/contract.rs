@@ -0,0 +1,850 @@
+//! Behaviour every `TodoRepository` backend must share. Backends opt in with
+//! `todo_repository_contract!(factory)`, where `factory` is an async
+//! expression yielding `Option<impl TodoRepository>`; `None` skips the suite
+//! (e.g. when no Postgres is configured). Backends with foreign keys must
+//! have seeded the fixture users.
+
+use axum_api::{
+    application::todos::TodoDomainEvents,
+    domain::todos::{
+        Todo, CreateTodoRequest, UpdateTodoRequest, PaginationQuery, KeysetQuery, KeysetDirection, KeysetPosition,
+        TodoFilter, SearchTodosQuery, TodoSearch, BatchOperation, BatchMode, BatchOutcome, TodoEventKind,
+        FieldChange, DomainEvent, TodoRole, TodoPriority, traits::TodoRepository,
+        validation::{DESCRIPTION_MAX_LENGTH, TITLE_MAX_LENGTH},
+    },
+    error::ApiError,
+    request_context::RequestContext,
+};
+use chrono::{DateTime, Duration, NaiveTime, TimeZone, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+use super::fixtures::{ALICE, BOB};
+
+pub fn create_request(title: &str, done: Option<bool>) -> CreateTodoRequest {
+    CreateTodoRequest { title: title.to_string(), done, list_id: None, ..Default::default() }
+}
+
+pub async fn create_then_find<R: TodoRepository>(repo: &R) {
+    let todo = repo.create(create_request("Write tests", None), TodoDomainEvents::created).await.unwrap();
+    assert!(!todo.done);
+
+    let found = repo.find_by_id(todo.id).await.unwrap().unwrap();
+    assert_eq!(found.id, todo.id);
+    assert_eq!(found.title, "Write tests");
+}
+
+pub async fn find_missing_is_none<R: TodoRepository>(repo: &R) {
+    assert!(repo.find_by_id(Uuid::new_v4()).await.unwrap().is_none());
+}
+
+fn ids(todos: &[Todo]) -> Vec<Uuid> {
+    todos.iter().map(|t| t.id).collect()
+}
+
+pub async fn filter_by_done_is_newest_first<R: TodoRepository>(repo: &R) {
+    let first = repo.create(create_request("first", Some(true)), TodoDomainEvents::created).await.unwrap();
+    repo.create(create_request("open", None), TodoDomainEvents::created).await.unwrap();
+    let second = repo.create(create_request("second", Some(true)), TodoDomainEvents::created).await.unwrap();
+
+    let page = repo
+        .find_all_paginated(PaginationQuery { done: Some(true), ..Default::default() })
+        .await
+        .unwrap();
+    assert_eq!(ids(&page.data), vec![second.id, first.id]);
+    assert_eq!(page.pagination.total, 2);
+}
+
+pub async fn filters_combine<R: TodoRepository>(repo: &R) {
+    let milk = repo.create(create_request("Buy MILK", None), TodoDomainEvents::created).await.unwrap();
+    let percent = repo.create(create_request("100% done", Some(true)), TodoDomainEvents::created).await.unwrap();
+    let bread = repo.create(create_request("buy bread", Some(true)), TodoDomainEvents::created).await.unwrap();
+
+    let find = |pagination: PaginationQuery| async move {
+        ids(&repo.find_all_paginated(pagination).await.unwrap().data)
+    };
+
+    let title = |title: &str| Some(title.to_string());
+    assert_eq!(find(PaginationQuery { title: title("buy"), ..Default::default() }).await, vec![bread.id, milk.id]);
+    assert_eq!(find(PaginationQuery { title: title("%"), ..Default::default() }).await, vec![percent.id]);
+    assert_eq!(find(PaginationQuery { title: title("_"), ..Default::default() }).await, Vec::<Uuid>::new());
+    assert_eq!(
+        find(PaginationQuery { title: title("buy"), done: Some(true), ..Default::default() }).await,
+        vec![bread.id]
+    );
+    assert_eq!(
+        find(PaginationQuery {
+            created_after: Some(milk.created_at),
+            created_before: Some(bread.created_at),
+            ..Default::default()
+        })
+        .await,
+        vec![percent.id]
+    );
+
+    let touched = repo
+        .update(milk.id, UpdateTodoRequest { title: None, done: Some(true), ..Default::default() }, None, TodoDomainEvents::updated)
+        .await
+        .unwrap();
+    assert_eq!(
+        find(PaginationQuery { updated_since: Some(touched.updated_at), ..Default::default() }).await,
+        vec![milk.id]
+    );
+}
+
+pub async fn sort_by_several_fields<R: TodoRepository>(repo: &R) {
+    let b_open = repo.create(create_request("b", None), TodoDomainEvents::created).await.unwrap();
+    let a_done = repo.create(create_request("a", Some(true)), TodoDomainEvents::created).await.unwrap();
+    let c_done = repo.create(create_request("c", Some(true)), TodoDomainEvents::created).await.unwrap();
+    let a_open = repo.create(create_request("a", None), TodoDomainEvents::created).await.unwrap();
+
+    let sorted = |sort: &str| {
+        let pagination = PaginationQuery { sort: Some(sort.to_string()), ..Default::default() };
+        async move { ids(&repo.find_all_paginated(pagination).await.unwrap().data) }
+    };
+
+    assert_eq!(sorted("created_at").await, vec![b_open.id, a_done.id, c_done.id, a_open.id]);
+    assert_eq!(sorted("-done,title").await, vec![a_done.id, c_done.id, a_open.id, b_open.id]);
+    // Equal titles fall back to `id DESC`, not insertion order.
+    let (high, low) = if a_done.id > a_open.id { (a_done.id, a_open.id) } else { (a_open.id, a_done.id) };
+    assert_eq!(sorted("title").await, vec![high, low, b_open.id, c_done.id]);
+}
+
+pub async fn pagination_caps_limit<R: TodoRepository>(repo: &R) {
+    for i in 0..105 {
+        repo.create(create_request(&format!("todo {i}"), None), TodoDomainEvents::created).await.unwrap();
+    }
+
+    let page = repo.find_all_paginated(PaginationQuery { page: 1, limit: 500, ..Default::default() }).await.unwrap();
+    assert_eq!(page.data.len(), 100);
+    assert_eq!(page.pagination.limit, 100);
+    assert_eq!(page.pagination.total, 105);
+    assert_eq!(page.pagination.total_pages, 2);
+    assert!(page.pagination.has_next);
+    assert!(!page.pagination.has_prev);
+    assert_eq!(page.data[0].title, "todo 104");
+
+    let last = repo.find_all_paginated(PaginationQuery { page: 2, limit: 100, ..Default::default() }).await.unwrap();
+    assert_eq!(last.data.len(), 5);
+    assert!(!last.pagination.has_next);
+    assert!(last.pagination.has_prev);
+}
+
+pub async fn keyset_walks_both_ways<R: TodoRepository>(repo: &R) {
+    for i in 0..5 {
+        repo.create(create_request(&format!("todo {i}"), None), TodoDomainEvents::created).await.unwrap();
+    }
+    let all: Vec<Uuid> = repo
+        .find_all_paginated(PaginationQuery { page: 1, limit: 100, ..Default::default() })
+        .await
+        .unwrap()
+        .data
+        .iter()
+        .map(|t| t.id)
+        .collect();
+    let query = |start| KeysetQuery { start, limit: 2, include_total: false, filter: TodoFilter::default() };
+
+    let first = repo.find_keyset(KeysetQuery { include_total: true, ..query(None) }).await.unwrap();
+    assert_eq!(ids(&first.data), all[0..2]);
+    assert!(first.has_more);
+    assert_eq!(first.total, Some(5));
+
+    let after = |todo| Some((KeysetDirection::After, KeysetPosition::from(todo)));
+    let second = repo.find_keyset(query(after(&first.data[1]))).await.unwrap();
+    assert_eq!(ids(&second.data), all[2..4]);
+    assert!(second.has_more);
+    assert_eq!(second.total, None);
+
+    let last = repo.find_keyset(query(after(&second.data[1]))).await.unwrap();
+    assert_eq!(ids(&last.data), all[4..5]);
+    assert!(!last.has_more);
+
+    let back = repo
+        .find_keyset(query(Some((KeysetDirection::Before, KeysetPosition::from(&last.data[0])))))
+        .await
+        .unwrap();
+    assert_eq!(ids(&back.data), all[2..4]);
+    assert!(back.has_more);
+
+    let done = repo.create(create_request("done", Some(true)), TodoDomainEvents::created).await.unwrap();
+    let filtered = repo
+        .find_keyset(KeysetQuery {
+            include_total: true,
+            filter: TodoFilter { done: Some(true), ..Default::default() },
+            ..query(None)
+        })
+        .await
+        .unwrap();
+    assert_eq!(ids(&filtered.data), vec![done.id]);
+    assert!(!filtered.has_more);
+    assert_eq!(filtered.total, Some(1));
+}
+
+pub async fn search_matches_words_phrases_and_prefixes<R: TodoRepository>(repo: &R) {
+    let fresh = repo.create(create_request("Buy fresh milk", None), TodoDomainEvents::created).await.unwrap();
+    let oat = repo.create(create_request("Oat milk for the office", None), TodoDomainEvents::created).await.unwrap();
+    let groceries = repo.create(create_request("Groceries list", None), TodoDomainEvents::created).await.unwrap();
+    repo.create(create_request("Walk the dog", None), TodoDomainEvents::created).await.unwrap();
+    let markup = repo.create(create_request("<img src=x onerror=alert(1)> & tea", None), TodoDomainEvents::created).await.unwrap();
+
+    let search = |q: &str| {
+        let search = TodoSearch::parse(&SearchTodosQuery { q: q.to_string(), page: 1, limit: 10 });
+        async move { repo.search(search).await.unwrap() }
+    };
+    let sorted_ids = |mut ids: Vec<Uuid>| {
+        ids.sort();
+        ids
+    };
+
+    let milk = search("MILK").await;
+    assert_eq!(milk.pagination.total, 2);
+    let hit_ids: Vec<Uuid> = milk.data.iter().map(|hit| hit.todo.id).collect();
+    assert_eq!(sorted_ids(hit_ids), sorted_ids(vec![fresh.id, oat.id]));
+    let snippet = &milk.data.iter().find(|hit| hit.todo.id == fresh.id).unwrap().snippet;
+    assert_eq!(snippet, "Buy fresh <mark>milk</mark>");
+
+    let phrase = search("\"fresh milk\"").await;
+    assert_eq!(ids(&phrase.data.into_iter().map(|hit| hit.todo).collect::<Vec<_>>()), vec![fresh.id]);
+
+    assert!(search("\"milk fresh\"").await.data.is_empty());
+    assert!(search("milk bread").await.data.is_empty());
+
+    // Titles are escaped, so the marks are the only markup in a snippet.
+    let tea = search("tea").await;
+    assert_eq!(tea.data[0].todo.id, markup.id);
+    assert_eq!(tea.data[0].snippet, "&lt;img src=x onerror=alert(1)&gt; &amp; <mark>tea</mark>");
+
+    let prefix = search("groc*").await;
+    assert_eq!(prefix.data.len(), 1);
+    assert_eq!(prefix.data[0].todo.id, groceries.id);
+
+    let paged = repo
+        .search(TodoSearch::parse(&SearchTodosQuery { q: "milk".to_string(), page: 2, limit: 1 }))
+        .await
+        .unwrap();
+    assert_eq!(paged.data.len(), 1);
+    assert!(paged.pagination.has_prev);
+    assert!(!paged.pagination.has_next);
+}
+
+pub async fn update_is_partial<R: TodoRepository>(repo: &R) {
+    let todo = repo.create(create_request("before", None), TodoDomainEvents::created).await.unwrap();
+
+    let updated = repo
+        .update(todo.id, UpdateTodoRequest { title: None, done: Some(true), ..Default::default() }, None, TodoDomainEvents::updated)
+        .await
+        .unwrap();
+    assert_eq!(updated.title, "before");
+    assert!(updated.done);
+    assert!(updated.updated_at >= todo.updated_at);
+    assert_eq!(updated.version, todo.version + 1);
+}
+
+pub async fn details_round_trip_and_completion_is_stamped<R: TodoRepository>(repo: &R) {
+    let due_at = Utc.with_ymd_and_hms(2030, 1, 2, 9, 30, 0).unwrap();
+    let todo = repo
+        .create(CreateTodoRequest {
+            description: Some("Semi-skimmed, **two** pints".to_string()),
+            due_at: Some(due_at),
+            priority: Some(TodoPriority::High),
+            ..create_request("Buy milk", None)
+        }, TodoDomainEvents::created)
+        .await
+        .unwrap();
+    let found = repo.find_by_id(todo.id).await.unwrap().unwrap();
+    assert_eq!(found.description.as_deref(), Some("Semi-skimmed, **two** pints"));
+    assert_eq!((found.due_at, found.priority, found.completed_at), (Some(due_at), TodoPriority::High, None));
+    assert_eq!(repo.create(create_request("Buy bread", None), TodoDomainEvents::created).await.unwrap().priority, TodoPriority::Medium);
+
+    let done = repo.update(todo.id, UpdateTodoRequest { done: Some(true), ..Default::default() }, None, TodoDomainEvents::updated).await.unwrap();
+    let completed_at = done.completed_at.expect("completing stamps completed_at");
+    assert!(completed_at >= todo.updated_at);
+    let renamed = repo
+        .update(todo.id, UpdateTodoRequest { title: Some("Buy oat milk".to_string()), done: Some(true), ..Default::default() }, None, TodoDomainEvents::updated)
+        .await
+        .unwrap();
+    assert_eq!(renamed.completed_at, Some(completed_at));
+    assert_eq!(renamed.description, found.description);
+
+    let cleared = repo
+        .update(
+            todo.id,
+            UpdateTodoRequest { done: Some(false), description: Some(None), due_at: Some(None), ..Default::default() },
+            None,
+            TodoDomainEvents::updated,
+        )
+        .await
+        .unwrap();
+    assert_eq!((cleared.completed_at, cleared.description, cleared.due_at), (None, None, None));
+    assert_eq!(cleared.priority, TodoPriority::High);
+
+    let created_done = repo.create(create_request("Call mum", Some(true)), TodoDomainEvents::created).await.unwrap();
+    assert!(created_done.completed_at.is_some());
+
+    let results = repo
+        .apply_batch(
+            vec![
+                BatchOperation::Create {
+                    title: "Pay rent".to_string(),
+                    done: None,
+                    description: Some("Before the 5th".to_string()),
+                    due_at: Some(due_at),
+                    priority: Some(TodoPriority::Low),
+                    list_id: None,
+                },
+                BatchOperation::Update {
+                    id: created_done.id,
+                    title: None,
+                    done: Some(false),
+                    description: None,
+                    due_at: Some(Some(due_at)),
+                    priority: Some(TodoPriority::High),
+                    list_id: None,
+                    expected_version: None,
+                },
+            ],
+            BatchMode::Atomic,
+            TodoDomainEvents::written,
+        )
+        .await
+        .unwrap();
+    assert!(matches!(
+        &results[0],
+        Ok(BatchOutcome::Created(todo)) if todo.priority == TodoPriority::Low && todo.due_at == Some(due_at)
+    ), "got {results:?}");
+    assert!(matches!(
+        &results[1],
+        Ok(BatchOutcome::Updated(todo))
+            if todo.completed_at.is_none() && todo.due_at == Some(due_at) && todo.priority == TodoPriority::High
+    ), "got {results:?}");
+}
+
+/// The longest description, in four-byte characters, is well over the 8000
+/// bytes a Postgres notification may carry; announcing the todo's changes
+/// must not make them fail.
+pub async fn longest_todo_round_trips<R: TodoRepository>(repo: &R) {
+    let description = "📝".repeat(DESCRIPTION_MAX_LENGTH);
+    let todo = repo
+        .create(
+            CreateTodoRequest { description: Some(description.clone()), ..create_request(&"a".repeat(TITLE_MAX_LENGTH), None) },
+            TodoDomainEvents::created,
+        )
+        .await
+        .unwrap();
+    assert_eq!(repo.find_by_id(todo.id).await.unwrap().unwrap().description, Some(description));
+
+    let done = repo.update(todo.id, UpdateTodoRequest { done: Some(true), ..Default::default() }, None, TodoDomainEvents::updated).await.unwrap();
+    assert!(done.done);
+    repo.delete(todo.id, None, TodoDomainEvents::deleted).await.unwrap();
+    assert_eq!(repo.restore(todo.id, TodoDomainEvents::restored).await.unwrap().version, 4);
+}
+
+pub async fn due_filters_bound_due_at<R: TodoRepository>(repo: &R) {
+    let now = Utc::now();
+    let today = now.date_naive().and_time(NaiveTime::MIN).and_utc();
+    let due = |title: &str, due_at: Option<DateTime<Utc>>, done: bool| {
+        repo.create(CreateTodoRequest { due_at, ..create_request(title, Some(done)) }, TodoDomainEvents::created)
+    };
+    let yesterday = due("yesterday", Some(today - Duration::hours(1)), false).await.unwrap();
+    let done_yesterday = due("done yesterday", Some(today - Duration::hours(2)), true).await.unwrap();
+    let tonight = due("tonight", Some(today + Duration::hours(23)), false).await.unwrap();
+    let next_week = due("next week", Some(today + Duration::days(7)), false).await.unwrap();
+    due("whenever", None, false).await.unwrap();
+
+    let find = |pagination: PaginationQuery| async move {
+        let mut found = ids(&repo.find_all_paginated(pagination).await.unwrap().data);
+        found.sort();
+        found
+    };
+    let sorted = |mut ids: Vec<Uuid>| {
+        ids.sort();
+        ids
+    };
+
+    let mut overdue = vec![yesterday.id];
+    if tonight.due_at.is_some_and(|at| at < now) {
+        overdue.push(tonight.id);
+    }
+    assert_eq!(find(PaginationQuery { overdue: true, ..Default::default() }).await, sorted(overdue));
+    assert_eq!(find(PaginationQuery { due_today: true, ..Default::default() }).await, vec![tonight.id]);
+
+    let filter = TodoFilter { due_after: Some(today), ..Default::default() };
+    let keyset = KeysetQuery { start: None, limit: 10, include_total: true, filter };
+    let page = repo.find_keyset(keyset).await.unwrap();
+    assert_eq!((sorted(ids(&page.data)), page.total), (sorted(vec![tonight.id, next_week.id]), Some(2)));
+    let filter = TodoFilter { due_before: Some(today), ..Default::default() };
+    let page = repo.find_keyset(KeysetQuery { start: None, limit: 10, include_total: false, filter }).await.unwrap();
+    assert_eq!(sorted(ids(&page.data)), sorted(vec![yesterday.id, done_yesterday.id]));
+}
+
+pub async fn update_missing_is_not_found<R: TodoRepository>(repo: &R) {
+    let result = repo
+        .update(Uuid::new_v4(), UpdateTodoRequest { title: Some("x".to_string()), done: None, ..Default::default() }, None, TodoDomainEvents::updated)
+        .await;
+    assert!(matches!(result, Err(ApiError::NotFound)), "got {result:?}");
+}
+
+pub async fn delete_missing_is_not_found<R: TodoRepository>(repo: &R) {
+    let todo = repo.create(create_request("gone", None), TodoDomainEvents::created).await.unwrap();
+
+    repo.delete(todo.id, None, TodoDomainEvents::deleted).await.unwrap();
+    assert!(repo.find_by_id(todo.id).await.unwrap().is_none());
+    assert!(matches!(repo.delete(todo.id, None, TodoDomainEvents::deleted).await, Err(ApiError::NotFound)));
+}
+
+pub async fn update_checks_expected_version<R: TodoRepository>(repo: &R) {
+    let todo = repo.create(create_request("versioned", None), TodoDomainEvents::created).await.unwrap();
+    assert_eq!(todo.version, 1);
+
+    let updated = repo
+        .update(todo.id, UpdateTodoRequest { title: None, done: Some(true), ..Default::default() }, Some(1), TodoDomainEvents::updated)
+        .await
+        .unwrap();
+    assert_eq!(updated.version, 2);
+
+    let stale = repo
+        .update(todo.id, UpdateTodoRequest { title: Some("lost".to_string()), done: None, ..Default::default() }, Some(1), TodoDomainEvents::updated)
+        .await;
+    assert!(matches!(stale, Err(ApiError::PreconditionFailed)), "got {stale:?}");
+
+    let current = repo.find_by_id(todo.id).await.unwrap().unwrap();
+    assert_eq!(current.title, "versioned");
+    assert_eq!(current.version, 2);
+
+    let missing = repo
+        .update(Uuid::new_v4(), UpdateTodoRequest { title: Some("x".to_string()), done: None, ..Default::default() }, Some(1), TodoDomainEvents::updated)
+        .await;
+    assert!(matches!(missing, Err(ApiError::NotFound)), "got {missing:?}");
+}
+
+pub async fn delete_checks_expected_version<R: TodoRepository>(repo: &R) {
+    let todo = repo.create(create_request("versioned", None), TodoDomainEvents::created).await.unwrap();
+
+    assert!(matches!(repo.delete(todo.id, Some(2), TodoDomainEvents::deleted).await, Err(ApiError::PreconditionFailed)));
+    assert!(repo.find_by_id(todo.id).await.unwrap().is_some());
+
+    repo.delete(todo.id, Some(1), TodoDomainEvents::deleted).await.unwrap();
+    assert!(matches!(repo.delete(todo.id, Some(1), TodoDomainEvents::deleted).await, Err(ApiError::NotFound)));
+}
+
+pub async fn delete_moves_to_trash_and_restore_brings_back<R: TodoRepository>(repo: &R) {
+    let todo = repo.create(create_request("Buy milk", None), TodoDomainEvents::created).await.unwrap();
+    let kept = repo.create(create_request("Buy bread", None), TodoDomainEvents::created).await.unwrap();
+    repo.delete(todo.id, Some(1), TodoDomainEvents::deleted).await.unwrap();
+
+    assert!(repo.find_by_id(todo.id).await.unwrap().is_none());
+    let live = repo.find_all_paginated(PaginationQuery::default()).await.unwrap();
+    assert_eq!(ids(&live.data), vec![kept.id]);
+    let keyset = repo
+        .find_keyset(KeysetQuery { start: None, limit: 10, include_total: true, filter: TodoFilter::default() })
+        .await
+        .unwrap();
+    assert_eq!(keyset.total, Some(1));
+    let query = SearchTodosQuery { q: "milk".to_string(), page: 1, limit: 10 };
+    assert_eq!(repo.search(TodoSearch::parse(&query)).await.unwrap().pagination.total, 0);
+
+    let update = repo.update(todo.id, UpdateTodoRequest { title: None, done: Some(true), ..Default::default() }, None, TodoDomainEvents::updated).await;
+    assert!(matches!(update, Err(ApiError::NotFound)), "got {update:?}");
+
+    let trash = repo.find_trashed(PaginationQuery::default()).await.unwrap();
+    assert_eq!(trash.pagination.total, 1);
+    assert_eq!(trash.data[0].todo.id, todo.id);
+    assert_eq!(trash.data[0].todo.version, 2);
+
+    let restored = repo.restore(todo.id, TodoDomainEvents::restored).await.unwrap();
+    assert_eq!(restored.title, "Buy milk");
+    assert_eq!(restored.version, 3);
+    assert!(repo.find_by_id(todo.id).await.unwrap().is_some());
+    assert_eq!(repo.find_trashed(PaginationQuery::default()).await.unwrap().pagination.total, 0);
+    assert!(matches!(repo.restore(todo.id, TodoDomainEvents::restored).await, Err(ApiError::NotFound)));
+}
+
+pub async fn purge_only_removes_trashed<R: TodoRepository>(repo: &R) {
+    let live = repo.create(create_request("live", None), TodoDomainEvents::created).await.unwrap();
+    let first = repo.create(create_request("first", None), TodoDomainEvents::created).await.unwrap();
+    let second = repo.create(create_request("second", None), TodoDomainEvents::created).await.unwrap();
+
+    assert!(matches!(repo.purge(live.id).await, Err(ApiError::NotFound)));
+
+    repo.delete(first.id, None, TodoDomainEvents::deleted).await.unwrap();
+    repo.purge(first.id).await.unwrap();
+    assert!(matches!(repo.restore(first.id, TodoDomainEvents::restored).await, Err(ApiError::NotFound)));
+
+    repo.delete(second.id, None, TodoDomainEvents::deleted).await.unwrap();
+    let past = chrono::Utc::now() - chrono::Duration::minutes(1);
+    assert_eq!(repo.purge_trashed_before(past).await.unwrap(), 0);
+    let future = chrono::Utc::now() + chrono::Duration::minutes(1);
+    assert_eq!(repo.purge_trashed_before(future).await.unwrap(), 1);
+
+    assert_eq!(repo.find_trashed(PaginationQuery::default()).await.unwrap().pagination.total, 0);
+    assert!(repo.find_by_id(live.id).await.unwrap().is_some());
+}
+
+fn create_operation(title: &str) -> BatchOperation {
+    BatchOperation::Create { title: title.to_string(), done: None, description: None, due_at: None, priority: None, list_id: None }
+}
+
+pub async fn batch_atomic_commits_in_order<R: TodoRepository>(repo: &R) {
+    let kept = repo.create(create_request("kept", None), TodoDomainEvents::created).await.unwrap();
+    let dropped = repo.create(create_request("dropped", None), TodoDomainEvents::created).await.unwrap();
+
+    let results = repo
+        .apply_batch(
+            vec![
+                create_operation("first"),
+                BatchOperation::Update { id: kept.id, title: None, done: Some(true), description: None, due_at: None, priority: None, list_id: None, expected_version: Some(1) },
+                create_operation("second"),
+                BatchOperation::Delete { id: dropped.id, expected_version: None },
+            ],
+            BatchMode::Atomic,
+            TodoDomainEvents::written,
+        )
+        .await
+        .unwrap();
+
+    assert!(matches!(&results[0], Ok(BatchOutcome::Created(todo)) if todo.title == "first"), "got {results:?}");
+    assert!(matches!(&results[1], Ok(BatchOutcome::Updated(todo)) if todo.done && todo.version == 2));
+    assert!(matches!(&results[2], Ok(BatchOutcome::Created(todo)) if todo.title == "second"));
+    assert!(matches!(results[3], Ok(BatchOutcome::Deleted)));
+
+    assert!(repo.find_by_id(dropped.id).await.unwrap().is_none());
+    let all = repo.find_all_paginated(PaginationQuery::default()).await.unwrap();
+    assert_eq!(all.pagination.total, 3);
+}
+
+pub async fn batch_atomic_rolls_back_on_failure<R: TodoRepository>(repo: &R) {
+    let todo = repo.create(create_request("untouched", None), TodoDomainEvents::created).await.unwrap();
+
+    let results = repo
+        .apply_batch(
+            vec![
+                create_operation("rolled back"),
+                BatchOperation::Update { id: todo.id, title: None, done: Some(true), description: None, due_at: None, priority: None, list_id: None, expected_version: None },
+                BatchOperation::Delete { id: Uuid::new_v4(), expected_version: None },
+                BatchOperation::Delete { id: todo.id, expected_version: None },
+            ],
+            BatchMode::Atomic,
+            TodoDomainEvents::written,
+        )
+        .await
+        .unwrap();
+
+    assert!(matches!(results[0], Err(ApiError::FailedDependency)), "got {results:?}");
+    assert!(matches!(results[1], Err(ApiError::FailedDependency)));
+    assert!(matches!(results[2], Err(ApiError::NotFound)));
+    assert!(matches!(results[3], Err(ApiError::FailedDependency)));
+
+    let current = repo.find_by_id(todo.id).await.unwrap().unwrap();
+    assert!(!current.done);
+    assert_eq!(current.version, 1);
+    let all = repo.find_all_paginated(PaginationQuery::default()).await.unwrap();
+    assert_eq!(all.pagination.total, 1);
+}
+
+pub async fn batch_best_effort_keeps_successes<R: TodoRepository>(repo: &R) {
+    let todo = repo.create(create_request("versioned", None), TodoDomainEvents::created).await.unwrap();
+
+    let results = repo
+        .apply_batch(
+            vec![
+                create_operation("kept"),
+                BatchOperation::Update { id: Uuid::new_v4(), title: None, done: Some(true), description: None, due_at: None, priority: None, list_id: None, expected_version: None },
+                BatchOperation::Delete { id: todo.id, expected_version: Some(5) },
+                BatchOperation::Update { id: todo.id, title: None, done: Some(true), description: None, due_at: None, priority: None, list_id: None, expected_version: Some(1) },
+            ],
+            BatchMode::BestEffort,
+            TodoDomainEvents::written,
+        )
+        .await
+        .unwrap();
+
+    let Ok(BatchOutcome::Created(created)) = &results[0] else { panic!("got {results:?}") };
+    assert!(repo.find_by_id(created.id).await.unwrap().is_some());
+    assert!(matches!(results[1], Err(ApiError::NotFound)));
+    assert!(matches!(results[2], Err(ApiError::PreconditionFailed)));
+    assert!(matches!(&results[3], Ok(BatchOutcome::Updated(todo)) if todo.done));
+}
+
+pub async fn history_records_every_change<R: TodoRepository>(repo: &R) {
+    let context = RequestContext { request_id: Some("req-1".to_string()), actor: Some("alice".to_string()), ..RequestContext::current() };
+    let todo = context.scope(repo.create(create_request("Buy milk", None), TodoDomainEvents::created)).await.unwrap();
+    let other = repo.create(create_request("unrelated", None), TodoDomainEvents::created).await.unwrap();
+    repo.update(todo.id, UpdateTodoRequest { title: Some("Buy oat milk".to_string()), done: None, ..Default::default() }, None, TodoDomainEvents::updated)
+        .await
+        .unwrap();
+    repo.update(todo.id, UpdateTodoRequest { title: None, done: None, ..Default::default() }, None, TodoDomainEvents::updated).await.unwrap();
+    repo.delete(todo.id, None, TodoDomainEvents::deleted).await.unwrap();
+    repo.restore(todo.id, TodoDomainEvents::restored).await.unwrap();
+    repo.delete(todo.id, None, TodoDomainEvents::deleted).await.unwrap();
+    repo.purge(todo.id).await.unwrap();
+
+    let history = repo.find_history(todo.id, PaginationQuery::default()).await.unwrap();
+    let kinds: Vec<TodoEventKind> = history.data.iter().map(|event| event.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            TodoEventKind::Created,
+            TodoEventKind::Updated,
+            TodoEventKind::Updated,
+            TodoEventKind::Deleted,
+            TodoEventKind::Restored,
+            TodoEventKind::Deleted,
+            TodoEventKind::Purged,
+        ]
+    );
+    assert_eq!(history.pagination.total, 7);
+    let versions: Vec<i64> = history.data.iter().map(|event| event.version).collect();
+    assert_eq!(versions, vec![1, 2, 3, 4, 5, 6, 6]);
+    assert!(history.data.windows(2).all(|pair| pair[0].id < pair[1].id));
+
+    let created = &history.data[0];
+    assert_eq!(created.actor.as_deref(), Some("alice"));
+    assert_eq!(created.request_id.as_deref(), Some("req-1"));
+    assert!(created.before.is_none());
+    assert_eq!(created.after.as_ref().map(|todo| todo.title.as_str()), Some("Buy milk"));
+    assert_eq!(created.changes.len(), 4);
+
+    let renamed = &history.data[1];
+    assert_eq!(renamed.actor, None);
+    assert_eq!(renamed.before.as_ref().map(|todo| todo.version), Some(1));
+    assert_eq!(
+        renamed.changes,
+        vec![FieldChange { field: "title".to_string(), from: json!("Buy milk"), to: json!("Buy oat milk") }]
+    );
+    assert!(history.data[2].changes.is_empty());
+
+    let deleted = &history.data[3];
+    assert!(deleted.after.is_none());
+    assert_eq!(deleted.before.as_ref().map(|todo| todo.version), Some(3));
+
+    let page = repo.find_history(todo.id, PaginationQuery { page: 2, limit: 5, ..Default::default() }).await.unwrap();
+    assert_eq!(page.data.len(), 2);
+    assert_eq!(page.data[0].kind, TodoEventKind::Deleted);
+
+    assert_eq!(repo.find_history(other.id, PaginationQuery::default()).await.unwrap().pagination.total, 1);
+    assert_eq!(repo.find_history(Uuid::new_v4(), PaginationQuery::default()).await.unwrap().pagination.total, 0);
+}
+
+pub async fn history_rolls_back_with_batch<R: TodoRepository>(repo: &R) {
+    let todo = repo.create(create_request("untouched", None), TodoDomainEvents::created).await.unwrap();
+
+    repo.apply_batch(
+        vec![
+            BatchOperation::Update { id: todo.id, title: None, done: Some(true), description: None, due_at: None, priority: None, list_id: None, expected_version: None },
+            BatchOperation::Delete { id: Uuid::new_v4(), expected_version: None },
+        ],
+        BatchMode::Atomic,
+        TodoDomainEvents::written,
+    )
+    .await
+    .unwrap();
+    let history = repo.find_history(todo.id, PaginationQuery::default()).await.unwrap();
+    assert_eq!(history.pagination.total, 1);
+    assert_eq!(repo.pending_messages(10, chrono::Utc::now()).await.unwrap().len(), 1);
+
+    repo.apply_batch(
+        vec![BatchOperation::Update { id: todo.id, title: None, done: Some(true), description: None, due_at: None, priority: None, list_id: None, expected_version: None }],
+        BatchMode::Atomic,
+        TodoDomainEvents::written,
+    )
+    .await
+    .unwrap();
+    let history = repo.find_history(todo.id, PaginationQuery::default()).await.unwrap();
+    assert_eq!(history.data[1].kind, TodoEventKind::Updated);
+    assert_eq!(history.data[1].changes[0].field, "done");
+}
+
+fn event_types(messages: &[axum_api::domain::todos::OutboxMessage]) -> Vec<&'static str> {
+    messages.iter().map(|message| message.event.event_type()).collect()
+}
+
+pub async fn outbox_queues_domain_events_in_order<R: TodoRepository>(repo: &R) {
+    let todo = repo.create(create_request("Buy milk", None), TodoDomainEvents::created).await.unwrap();
+    repo.update(todo.id, UpdateTodoRequest { title: None, done: Some(true), ..Default::default() }, None, TodoDomainEvents::updated).await.unwrap();
+    repo.delete(todo.id, None, TodoDomainEvents::deleted).await.unwrap();
+    repo.restore(todo.id, TodoDomainEvents::restored).await.unwrap();
+    repo.delete(todo.id, None, TodoDomainEvents::deleted).await.unwrap();
+    repo.purge(todo.id).await.unwrap();
+    let other = repo.create(create_request("Buy bread", None), TodoDomainEvents::created).await.unwrap();
+
+    let now = chrono::Utc::now();
+    let pending = repo.pending_messages(100, now).await.unwrap();
+    assert_eq!(
+        event_types(&pending),
+        vec!["TodoCreated", "TodoUpdated", "TodoCompleted", "TodoDeleted", "TodoUpdated", "TodoDeleted", "TodoCreated"]
+    );
+    assert!(pending.windows(2).all(|pair| pair[0].id < pair[1].id));
+    assert!(matches!(&pending[1].event, DomainEvent::TodoUpdated { todo: updated, changes } if updated.done && changes.len() == 1));
+    assert!(matches!(pending[3].event, DomainEvent::TodoDeleted { todo_id, version: 3 } if todo_id == todo.id));
+    assert_eq!(pending[6].todo_id, other.id);
+    assert_eq!(repo.pending_messages(2, now).await.unwrap().len(), 2);
+
+    // A failed message holds back the later messages of the same todo only.
+    repo.mark_failed(pending[0].id, "broker down", now + chrono::Duration::minutes(1)).await.unwrap();
+    let held = repo.pending_messages(100, now).await.unwrap();
+    assert_eq!(held.iter().map(|message| message.todo_id).collect::<Vec<_>>(), vec![other.id]);
+
+    let later = now + chrono::Duration::minutes(2);
+    let retried = repo.pending_messages(100, later).await.unwrap();
+    assert_eq!(retried.len(), 7);
+    assert_eq!(retried[0].attempts, 1);
+    assert_eq!(retried[0].event_id, pending[0].event_id);
+
+    for message in &retried[..6] {
+        repo.mark_published(message.id).await.unwrap();
+    }
+    assert_eq!(event_types(&repo.pending_messages(100, later).await.unwrap()), vec!["TodoCreated"]);
+}
+
+pub async fn audit_log_can_be_tailed<R: TodoRepository>(repo: &R) {
+    let start = repo.last_event_id().await.unwrap();
+    let first = repo.create(create_request("first", None), TodoDomainEvents::created).await.unwrap();
+    let second = repo.create(create_request("second", None), TodoDomainEvents::created).await.unwrap();
+    repo.delete(first.id, None, TodoDomainEvents::deleted).await.unwrap();
+
+    let events = repo.events_since(start, 10).await.unwrap();
+    let seen: Vec<(Uuid, TodoEventKind)> = events.iter().map(|event| (event.todo_id, event.kind)).collect();
+    assert_eq!(
+        seen,
+        vec![(first.id, TodoEventKind::Created), (second.id, TodoEventKind::Created), (first.id, TodoEventKind::Deleted)]
+    );
+    assert_eq!(repo.last_event_id().await.unwrap(), events[2].id);
+
+    let rest = repo.events_since(events[0].id, 1).await.unwrap();
+    assert_eq!(rest.len(), 1);
+    assert_eq!(rest[0].id, events[1].id);
+    assert!(repo.events_since(events[2].id, 10).await.unwrap().is_empty());
+}
+
+pub async fn queries_are_scoped_to_the_signed_in_user<R: TodoRepository>(repo: &R) {
+    let (ada, bob) = (RequestContext::for_user(Uuid::new_v4()), RequestContext::for_user(Uuid::new_v4()));
+    let ada_id = ada.user_id;
+    let todo = ada.clone().scope(repo.create(create_request("Ada's milk", None), TodoDomainEvents::created)).await.unwrap();
+    assert_eq!(todo.owner_id, ada_id);
+    let trashed = ada.clone().scope(repo.create(create_request("Ada's bread", None), TodoDomainEvents::created)).await.unwrap();
+    ada.clone().scope(repo.delete(trashed.id, None, TodoDomainEvents::deleted)).await.unwrap();
+
+    bob.clone()
+        .scope(async {
+            assert!(repo.find_by_id(todo.id).await.unwrap().is_none());
+            assert_eq!(repo.find_all_paginated(PaginationQuery::default()).await.unwrap().pagination.total, 0);
+            let search = TodoSearch::parse(&SearchTodosQuery { q: "milk".to_string(), page: 1, limit: 10 });
+            assert!(repo.search(search).await.unwrap().data.is_empty());
+            let update = UpdateTodoRequest { title: Some("Bob's milk".to_string()), done: None, ..Default::default() };
+            assert!(matches!(repo.update(todo.id, update, None, TodoDomainEvents::updated).await, Err(ApiError::NotFound)));
+            assert!(matches!(repo.delete(todo.id, None, TodoDomainEvents::deleted).await, Err(ApiError::NotFound)));
+            assert!(repo.find_trashed(PaginationQuery::default()).await.unwrap().data.is_empty());
+            assert!(matches!(repo.restore(trashed.id, TodoDomainEvents::restored).await, Err(ApiError::NotFound)));
+            assert!(matches!(repo.purge(trashed.id).await, Err(ApiError::NotFound)));
+            assert!(repo.find_history(todo.id, PaginationQuery::default()).await.unwrap().data.is_empty());
+        })
+        .await;
+
+    ada.scope(async {
+        let page = repo.find_all_paginated(PaginationQuery::default()).await.unwrap();
+        assert_eq!(ids(&page.data), vec![todo.id]);
+        assert_eq!(repo.find_history(todo.id, PaginationQuery::default()).await.unwrap().data.len(), 1);
+        repo.restore(trashed.id, TodoDomainEvents::restored).await.unwrap();
+    })
+    .await;
+
+    // Background work runs unscoped and sees everyone's todos.
+    assert!(repo.find_by_id(todo.id).await.unwrap().is_some());
+}
+
+pub async fn shared_todos_reach_their_grantees<R: TodoRepository>(repo: &R) {
+    let (alice, bob) = (RequestContext::for_user(ALICE), RequestContext::for_user(BOB));
+    let todo = alice.clone().scope(repo.create(create_request("Team milk", None), TodoDomainEvents::created)).await.unwrap();
+    assert_eq!(repo.role_of(todo.id, ALICE).await.unwrap(), Some(TodoRole::Owner));
+    assert_eq!(repo.role_of(todo.id, BOB).await.unwrap(), None);
+    assert_eq!(repo.role_of(Uuid::new_v4(), ALICE).await.unwrap(), None);
+
+    let share = repo.grant(todo.id, BOB, TodoRole::Viewer).await.unwrap();
+    assert_eq!((share.todo_id, share.user_id, share.role), (todo.id, BOB, TodoRole::Viewer));
+    // Granting again changes the role instead of adding a second share.
+    repo.grant(todo.id, BOB, TodoRole::Editor).await.unwrap();
+    let shares = repo.find_shares(todo.id).await.unwrap();
+    assert_eq!(shares.iter().map(|share| (share.user_id, share.role)).collect::<Vec<_>>(), vec![(BOB, TodoRole::Editor)]);
+    assert_eq!(repo.role_of(todo.id, BOB).await.unwrap(), Some(TodoRole::Editor));
+
+    bob.clone()
+        .scope(async {
+            assert!(repo.find_by_id(todo.id).await.unwrap().is_some());
+            assert_eq!(ids(&repo.find_all_paginated(PaginationQuery::default()).await.unwrap().data), vec![todo.id]);
+            let search = TodoSearch::parse(&SearchTodosQuery { q: "milk".to_string(), page: 1, limit: 10 });
+            assert_eq!(repo.search(search).await.unwrap().data.len(), 1);
+            let update = UpdateTodoRequest { title: Some("Team oat milk".to_string()), done: None, ..Default::default() };
+            let updated = repo.update(todo.id, update, None, TodoDomainEvents::updated).await.unwrap();
+            // The todo and its events stay the owner's.
+            assert_eq!(updated.owner_id, Some(ALICE));
+            assert_eq!(repo.find_history(todo.id, PaginationQuery::default()).await.unwrap().data.len(), 2);
+        })
+        .await;
+
+    alice.clone().scope(repo.delete(todo.id, None, TodoDomainEvents::deleted)).await.unwrap();
+    assert_eq!(repo.role_of(todo.id, BOB).await.unwrap(), Some(TodoRole::Editor));
+    assert!(bob.clone().scope(repo.find_trashed(PaginationQuery::default())).await.unwrap().data.is_empty());
+    alice.clone().scope(repo.restore(todo.id, TodoDomainEvents::restored)).await.unwrap();
+
+    repo.revoke(todo.id, BOB).await.unwrap();
+    assert!(matches!(repo.revoke(todo.id, BOB).await, Err(ApiError::NotFound)));
+    assert!(bob.clone().scope(repo.find_by_id(todo.id)).await.unwrap().is_none());
+
+    // Purging takes the shares with it.
+    repo.grant(todo.id, BOB, TodoRole::Viewer).await.unwrap();
+    alice
+        .scope(async {
+            repo.delete(todo.id, None, TodoDomainEvents::deleted).await.unwrap();
+            repo.purge(todo.id).await.unwrap();
+        })
+        .await;
+    assert!(repo.find_shares(todo.id).await.unwrap().is_empty());
+    assert_eq!(repo.role_of(todo.id, BOB).await.unwrap(), None);
+}
+
+#[macro_export]
+macro_rules! todo_repository_contract {
+    ($factory:expr) => {
+        $crate::todo_repository_contract!(@cases $factory;
+            create_then_find,
+            find_missing_is_none,
+            filter_by_done_is_newest_first,
+            filters_combine,
+            sort_by_several_fields,
+            search_matches_words_phrases_and_prefixes,
+            pagination_caps_limit,
+            keyset_walks_both_ways,
+            update_is_partial,
+            details_round_trip_and_completion_is_stamped,
+            longest_todo_round_trips,
+            due_filters_bound_due_at,
+            update_missing_is_not_found,
+            delete_missing_is_not_found,
+            update_checks_expected_version,
+            delete_checks_expected_version,
+            delete_moves_to_trash_and_restore_brings_back,
+            purge_only_removes_trashed,
+            batch_atomic_commits_in_order,
+            batch_atomic_rolls_back_on_failure,
+            batch_best_effort_keeps_successes,
+            history_records_every_change,
+            history_rolls_back_with_batch,
+            outbox_queues_domain_events_in_order,
+            audit_log_can_be_tailed,
+            queries_are_scoped_to_the_signed_in_user,
+            shared_todos_reach_their_grantees,
+        );
+    };
+    (@cases $factory:expr; $($case:ident),* $(,)?) => {
+        $(
+            #[tokio::test]
+            async fn $case() {
+                if let Some(repo) = $factory.await {
+                    ::axum_api::request_context::RequestContext::for_tenant(::axum_api::request_context::DEFAULT_TENANT)
+                        .scope($crate::database::repositories::contract::$case(&repo))
+                        .await;
+                }
+            }
+        )*
+    };
+}
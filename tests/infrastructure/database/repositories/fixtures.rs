@@ -0,0 +1,95 @@
+//! Accounts for contracts whose tables reference `users`, and the Postgres
+//! schema the Postgres suites run in. Backends with foreign keys seed the
+//! accounts before handing out a repository.
+
+use axum_api::infrastructure::database::MIGRATOR;
+use sqlx::{postgres::PgPoolOptions, Executor, PgPool};
+use uuid::Uuid;
+
+pub const ALICE: Uuid = Uuid::from_u128(0xa11ce);
+pub const BOB: Uuid = Uuid::from_u128(0xb0b);
+
+const USERS: [(Uuid, &str); 2] = [(ALICE, "alice@example.com"), (BOB, "bob@example.com")];
+
+pub async fn seed_postgres_users(pool: &PgPool) {
+    for (id, email) in USERS {
+        sqlx::query("INSERT INTO users (id, email, password_hash) VALUES ($1, $2, 'hash')")
+            .bind(id)
+            .bind(email)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+}
+
+/// Role the repository connects as. The tests' own user is a superuser,
+/// whom row-level security does not apply to.
+const APP_ROLE: &str = "axum_api_test_app";
+
+/// Runs against `TEST_DATABASE_URL` when set, skipping otherwise. Each test
+/// gets its own schema so tests can run in parallel on one database. Returns
+/// a pool of the schema's owner and one of `APP_ROLE`.
+pub async fn postgres_pools() -> Option<(PgPool, PgPool)> {
+    let url = std::env::var("TEST_DATABASE_URL").ok()?;
+    let schema = format!("test_{}", Uuid::new_v4().simple());
+
+    let admin = PgPoolOptions::new().max_connections(1).connect(&url).await.unwrap();
+    admin.execute(format!("CREATE SCHEMA {schema}").as_str()).await.unwrap();
+    admin
+        .execute(
+            format!(
+                "DO $$ BEGIN CREATE ROLE {APP_ROLE} NOLOGIN NOSUPERUSER NOBYPASSRLS; \
+                 EXCEPTION WHEN duplicate_object OR unique_violation THEN NULL; END $$"
+            )
+            .as_str(),
+        )
+        .await
+        .unwrap();
+
+    let connect = |role: Option<&'static str>| {
+        let schema = schema.clone();
+        PgPoolOptions::new()
+            .max_connections(2)
+            .after_connect(move |conn, _| {
+                let schema = schema.clone();
+                Box::pin(async move {
+                    if let Some(role) = role {
+                        conn.execute(format!("SET ROLE {role}").as_str()).await?;
+                    }
+                    conn.execute(format!("SET search_path TO {schema}, public").as_str()).await?;
+                    Ok(())
+                })
+            })
+            .connect(&url)
+    };
+
+    let owner = connect(None).await.unwrap();
+    MIGRATOR.run(&owner).await.unwrap();
+    seed_postgres_users(&owner).await;
+    owner
+        .execute(
+            format!(
+                "GRANT USAGE ON SCHEMA {schema} TO {APP_ROLE}; \
+                 GRANT SELECT, INSERT, UPDATE, DELETE ON ALL TABLES IN SCHEMA {schema} TO {APP_ROLE}; \
+                 GRANT USAGE, SELECT ON ALL SEQUENCES IN SCHEMA {schema} TO {APP_ROLE}"
+            )
+            .as_str(),
+        )
+        .await
+        .unwrap();
+
+    let app = connect(Some(APP_ROLE)).await.unwrap();
+    Some((owner, app))
+}
+
+#[cfg(feature = "sqlite")]
+pub async fn seed_sqlite_users(pool: &sqlx::SqlitePool) {
+    for (id, email) in USERS {
+        sqlx::query("INSERT INTO users (id, email, password_hash, created_at, updated_at) VALUES (?1, ?2, 'hash', '', '')")
+            .bind(id)
+            .bind(email)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+}
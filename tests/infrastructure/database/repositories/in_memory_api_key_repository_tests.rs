@@ -0,0 +1,7 @@
+use axum_api::infrastructure::database::repositories::InMemoryApiKeyRepository;
+
+async fn repository() -> Option<InMemoryApiKeyRepository> {
+    Some(InMemoryApiKeyRepository::new())
+}
+
+crate::api_key_repository_contract!(repository());
@@ -0,0 +1,8 @@
+use axum_api::infrastructure::database::repositories::{InMemoryTagRepository, InMemoryTodoRepository};
+
+async fn repositories() -> Option<(InMemoryTagRepository, InMemoryTodoRepository)> {
+    let todos = InMemoryTodoRepository::new();
+    Some((todos.tags(), todos))
+}
+
+crate::tag_repository_contract!(repositories());
@@ -0,0 +1,8 @@
+use axum_api::infrastructure::database::repositories::{InMemoryTodoListRepository, InMemoryTodoRepository};
+
+async fn repositories() -> Option<(InMemoryTodoListRepository, InMemoryTodoRepository)> {
+    let todos = InMemoryTodoRepository::new();
+    Some((todos.lists(), todos))
+}
+
+crate::todo_list_repository_contract!(repositories());
@@ -0,0 +1,7 @@
+use axum_api::infrastructure::database::repositories::InMemoryTodoRepository;
+
+async fn repository() -> Option<InMemoryTodoRepository> {
+    Some(InMemoryTodoRepository::new())
+}
+
+crate::todo_repository_contract!(repository());
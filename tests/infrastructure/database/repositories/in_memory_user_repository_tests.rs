@@ -0,0 +1,7 @@
+use axum_api::infrastructure::database::repositories::InMemoryUserRepository;
+
+async fn repository() -> Option<InMemoryUserRepository> {
+    Some(InMemoryUserRepository::new())
+}
+
+crate::user_repository_contract!(repository());
@@ -0,0 +1,7 @@
+use axum_api::infrastructure::database::repositories::InMemoryWebhookRepository;
+
+async fn repository() -> Option<InMemoryWebhookRepository> {
+    Some(InMemoryWebhookRepository::new())
+}
+
+crate::webhook_repository_contract!(repository());
@@ -0,0 +1,182 @@
+//! Behaviour every `TodoListRepository` backend must share, together with
+//! the `TodoRepository` holding its todos. Backends opt in with
+//! `todo_list_repository_contract!(factory)`, where `factory` yields
+//! `Option<(lists, todos)>` over the same database.
+
+use axum_api::{
+    application::todos::TodoDomainEvents,
+    domain::lists::{traits::TodoListRepository, CreateTodoListRequest, UpdateTodoListRequest},
+    domain::todos::{
+        traits::TodoRepository, BatchMode, BatchOperation, BatchOutcome, CreateTodoRequest, KeysetQuery, PaginationQuery,
+        Todo, TodoFilter, UpdateTodoRequest,
+    },
+    error::ApiError,
+    request_context::RequestContext,
+};
+use uuid::Uuid;
+
+use super::fixtures::{ALICE, BOB};
+
+pub fn create_request(name: &str) -> CreateTodoListRequest {
+    CreateTodoListRequest { name: name.to_string(), description: None, color: None }
+}
+
+fn todo_in(list_id: Option<Uuid>, title: &str) -> CreateTodoRequest {
+    CreateTodoRequest { title: title.to_string(), done: None, list_id, ..Default::default() }
+}
+
+fn ids(todos: &[Todo]) -> Vec<Uuid> {
+    todos.iter().map(|t| t.id).collect()
+}
+
+pub async fn crud_round_trip<L: TodoListRepository, T: TodoRepository>(lists: &L, _todos: &T) {
+    let first = lists
+        .create(CreateTodoListRequest { color: Some("#ff8800".to_string()), ..create_request("Groceries") })
+        .await
+        .unwrap();
+    let second = lists.create(create_request("Chores")).await.unwrap();
+    assert!(!first.archived);
+
+    let found = lists.find_by_id(first.id).await.unwrap().unwrap();
+    assert_eq!(found.name, "Groceries");
+    assert_eq!(found.color.as_deref(), Some("#ff8800"));
+
+    let page = lists.find_all_paginated(false, PaginationQuery::default()).await.unwrap();
+    assert_eq!(page.data.iter().map(|list| list.id).collect::<Vec<_>>(), vec![first.id, second.id]);
+    assert_eq!(page.pagination.total, 2);
+
+    let updated = lists
+        .update(first.id, UpdateTodoListRequest { description: Some("Weekly shop".to_string()), ..Default::default() })
+        .await
+        .unwrap();
+    assert_eq!(updated.description.as_deref(), Some("Weekly shop"));
+    assert_eq!(updated.name, "Groceries");
+    assert_eq!(updated.color.as_deref(), Some("#ff8800"));
+
+    lists.delete(first.id).await.unwrap();
+    assert!(lists.find_by_id(first.id).await.unwrap().is_none());
+    assert!(matches!(lists.delete(first.id).await, Err(ApiError::NotFound)));
+    assert!(matches!(lists.update(first.id, UpdateTodoListRequest::default()).await, Err(ApiError::NotFound)));
+    assert!(matches!(lists.set_archived(first.id, true).await, Err(ApiError::NotFound)));
+}
+
+pub async fn archived_lists_hide_their_todos<L: TodoListRepository, T: TodoRepository>(lists: &L, todos: &T) {
+    let list = lists.create(create_request("Someday")).await.unwrap();
+    let listed = todos.create(todo_in(Some(list.id), "Learn the cello"), TodoDomainEvents::created).await.unwrap();
+    assert_eq!(listed.list_id, Some(list.id));
+    let loose = todos.create(todo_in(None, "Buy milk"), TodoDomainEvents::created).await.unwrap();
+
+    let archived = lists.set_archived(list.id, true).await.unwrap();
+    assert!(archived.archived);
+    assert!(lists.find_all_paginated(false, PaginationQuery::default()).await.unwrap().data.is_empty());
+    assert_eq!(lists.find_all_paginated(true, PaginationQuery::default()).await.unwrap().pagination.total, 1);
+
+    let page = todos.find_all_paginated(PaginationQuery::default()).await.unwrap();
+    assert_eq!(ids(&page.data), vec![loose.id]);
+    assert_eq!(page.pagination.total, 1);
+    let keyset = KeysetQuery { start: None, limit: 10, include_total: true, filter: TodoFilter::default() };
+    let first = todos.find_keyset(keyset.clone()).await.unwrap();
+    assert_eq!((ids(&first.data), first.total), (vec![loose.id], Some(1)));
+
+    // Asking for the list by id still reaches its todos, and so does the id.
+    let in_list = PaginationQuery { list_id: Some(list.id), ..Default::default() };
+    assert_eq!(ids(&todos.find_all_paginated(in_list).await.unwrap().data), vec![listed.id]);
+    let filter = TodoFilter { list_id: Some(list.id), ..Default::default() };
+    assert_eq!(ids(&todos.find_keyset(KeysetQuery { filter, ..keyset }).await.unwrap().data), vec![listed.id]);
+    assert!(todos.find_by_id(listed.id).await.unwrap().is_some());
+
+    lists.set_archived(list.id, false).await.unwrap();
+    assert_eq!(todos.find_all_paginated(PaginationQuery::default()).await.unwrap().pagination.total, 2);
+}
+
+pub async fn delete_detaches_todos<L: TodoListRepository, T: TodoRepository>(lists: &L, todos: &T) {
+    let list = lists.create(create_request("Groceries")).await.unwrap();
+    let live = todos.create(todo_in(Some(list.id), "Buy milk"), TodoDomainEvents::created).await.unwrap();
+    let trashed = todos.create(todo_in(Some(list.id), "Buy bread"), TodoDomainEvents::created).await.unwrap();
+    todos.delete(trashed.id, None, TodoDomainEvents::deleted).await.unwrap();
+
+    lists.delete(list.id).await.unwrap();
+
+    assert_eq!(todos.find_by_id(live.id).await.unwrap().unwrap().list_id, None);
+    let restored = todos.restore(trashed.id, TodoDomainEvents::restored).await.unwrap();
+    assert_eq!(restored.list_id, None);
+}
+
+pub async fn updates_move_todos_between_lists<L: TodoListRepository, T: TodoRepository>(lists: &L, todos: &T) {
+    let groceries = lists.create(create_request("Groceries")).await.unwrap();
+    let errands = lists.create(create_request("Errands")).await.unwrap();
+    let todo = todos.create(todo_in(Some(groceries.id), "Buy milk"), TodoDomainEvents::created).await.unwrap();
+
+    let move_to = |list_id| UpdateTodoRequest { list_id: Some(list_id), ..Default::default() };
+    let moved = todos.update(todo.id, move_to(Some(errands.id)), Some(1), TodoDomainEvents::updated).await.unwrap();
+    assert_eq!((moved.list_id, moved.version), (Some(errands.id), 2));
+    let in_list = |list_id| PaginationQuery { list_id: Some(list_id), ..Default::default() };
+    assert_eq!(ids(&todos.find_all_paginated(in_list(errands.id)).await.unwrap().data), vec![todo.id]);
+    assert_eq!(todos.find_all_paginated(in_list(groceries.id)).await.unwrap().pagination.total, 0);
+
+    // Leaving `list_id` out keeps the todo where it is.
+    let kept = todos.update(todo.id, UpdateTodoRequest { done: Some(true), ..Default::default() }, None, TodoDomainEvents::updated).await.unwrap();
+    assert_eq!(kept.list_id, Some(errands.id));
+
+    let operation = BatchOperation::Update {
+        id: todo.id,
+        title: None,
+        done: None,
+        description: None,
+        due_at: None,
+        priority: None,
+        list_id: Some(None),
+        expected_version: Some(3),
+    };
+    let applied = todos.apply_batch(vec![operation], BatchMode::Atomic, TodoDomainEvents::written).await.unwrap();
+    assert!(matches!(&applied[0], Ok(BatchOutcome::Updated(todo)) if todo.list_id.is_none()));
+
+    let history = todos.find_history(todo.id, PaginationQuery::default()).await.unwrap();
+    assert_eq!(history.data[1].changes.iter().map(|change| change.field.as_str()).collect::<Vec<_>>(), vec!["list_id"]);
+}
+
+pub async fn lists_belong_to_their_owner<L: TodoListRepository, T: TodoRepository>(lists: &L, _todos: &T) {
+    let (alice, bob) = (RequestContext::for_user(ALICE), RequestContext::for_user(BOB));
+    let list = alice.clone().scope(lists.create(create_request("Alice's"))).await.unwrap();
+    assert_eq!(list.owner_id, Some(ALICE));
+
+    bob.scope(async {
+        assert!(lists.find_by_id(list.id).await.unwrap().is_none());
+        assert_eq!(lists.find_all_paginated(false, PaginationQuery::default()).await.unwrap().pagination.total, 0);
+        let update = UpdateTodoListRequest { name: Some("Bob's".to_string()), ..Default::default() };
+        assert!(matches!(lists.update(list.id, update).await, Err(ApiError::NotFound)));
+        assert!(matches!(lists.set_archived(list.id, true).await, Err(ApiError::NotFound)));
+        assert!(matches!(lists.delete(list.id).await, Err(ApiError::NotFound)));
+    })
+    .await;
+
+    let page = alice.scope(lists.find_all_paginated(false, PaginationQuery::default())).await.unwrap();
+    assert_eq!(page.data.iter().map(|list| list.id).collect::<Vec<_>>(), vec![list.id]);
+    // Background work runs unscoped and sees everyone's lists.
+    assert!(lists.find_by_id(list.id).await.unwrap().is_some());
+}
+
+#[macro_export]
+macro_rules! todo_list_repository_contract {
+    ($factory:expr) => {
+        $crate::todo_list_repository_contract!(@cases $factory;
+            crud_round_trip,
+            archived_lists_hide_their_todos,
+            delete_detaches_todos,
+            updates_move_todos_between_lists,
+            lists_belong_to_their_owner,
+        );
+    };
+    (@cases $factory:expr; $($case:ident),* $(,)?) => {
+        $(
+            #[tokio::test]
+            async fn $case() {
+                if let Some((lists, todos)) = $factory.await {
+                    ::axum_api::request_context::RequestContext::for_tenant(::axum_api::request_context::DEFAULT_TENANT)
+                        .scope($crate::database::repositories::list_contract::$case(&lists, &todos))
+                        .await;
+                }
+            }
+        )*
+    };
+}
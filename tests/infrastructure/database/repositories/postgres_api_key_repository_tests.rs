@@ -0,0 +1,41 @@
+use axum_api::{
+    domain::api_keys::{traits::ApiKeyRepository, ApiKeyScope},
+    error::ApiError,
+    infrastructure::database::repositories::PostgresApiKeyRepository,
+    request_context::RequestContext,
+};
+use chrono::Utc;
+
+use super::api_key_contract::new_key;
+use super::fixtures::{postgres_pools, ALICE};
+
+async fn repository() -> Option<PostgresApiKeyRepository> {
+    let (_, app) = postgres_pools().await?;
+    Some(PostgresApiKeyRepository::new(app))
+}
+
+crate::api_key_repository_contract!(repository());
+
+#[tokio::test]
+async fn keys_only_authenticate_in_their_tenant() {
+    let Some((_, app)) = postgres_pools().await else { return };
+    let keys = PostgresApiKeyRepository::new(app);
+    let (acme, globex) = (RequestContext::for_tenant("acme"), RequestContext::for_tenant("globex"));
+
+    let (secret, key) = new_key(ALICE, ApiKeyScope::ReadWrite, None);
+    let created = acme.clone().scope(keys.create(key)).await.unwrap();
+    assert_eq!(created.tenant_id, "acme");
+
+    globex
+        .scope(async {
+            assert!(keys.authenticate(&secret.hash, Utc::now()).await.unwrap().is_none());
+            assert!(keys.find_by_id(ALICE, created.id).await.unwrap().is_none());
+            assert_eq!(keys.find_all_paginated(ALICE, Default::default()).await.unwrap().pagination.total, 0);
+            assert!(matches!(keys.revoke(ALICE, created.id, Utc::now()).await, Err(ApiError::NotFound)));
+        })
+        .await;
+    assert_eq!(acme.scope(keys.authenticate(&secret.hash, Utc::now())).await.unwrap().unwrap().id, created.id);
+
+    // A lookup outside any tenant is refused rather than run across all.
+    assert!(matches!(keys.authenticate(&secret.hash, Utc::now()).await, Err(ApiError::Anyhow(_))));
+}
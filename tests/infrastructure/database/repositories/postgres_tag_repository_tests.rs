@@ -0,0 +1,35 @@
+use axum_api::{
+    domain::tags::traits::TagStore,
+    infrastructure::database::repositories::{PostgresTagRepository, PostgresTodoRepository},
+    request_context::RequestContext,
+};
+
+use super::tag_contract::create_request;
+use super::fixtures::{postgres_pools, ALICE};
+
+async fn repositories() -> Option<(PostgresTagRepository, PostgresTodoRepository)> {
+    let (_, app) = postgres_pools().await?;
+    Some((PostgresTagRepository::new(app.clone()), PostgresTodoRepository::new(app)))
+}
+
+crate::tag_repository_contract!(repositories());
+
+#[tokio::test]
+async fn tenants_cannot_reach_each_others_tags() {
+    let Some((_, app)) = postgres_pools().await else { return };
+    let tags = PostgresTagRepository::new(app);
+    let in_tenant = |tenant: &str| RequestContext { tenant_id: Some(tenant.to_string()), ..RequestContext::for_user(ALICE) };
+
+    let tag = in_tenant("acme").scope(tags.create(create_request("work"))).await.unwrap();
+    assert_eq!(tag.tenant_id, "acme");
+
+    in_tenant("globex")
+        .scope(async {
+            assert!(tags.find_by_id(tag.id).await.unwrap().is_none());
+            assert_eq!(tags.find_all_paginated(Default::default()).await.unwrap().pagination.total, 0);
+            // The same user may reuse the name in another tenant.
+            tags.create(create_request("work")).await.unwrap();
+        })
+        .await;
+    assert!(in_tenant("acme").scope(tags.find_by_id(tag.id)).await.unwrap().is_some());
+}
@@ -0,0 +1,33 @@
+use axum_api::{
+    domain::lists::traits::TodoListStore,
+    infrastructure::database::repositories::{PostgresTodoListRepository, PostgresTodoRepository},
+    request_context::RequestContext,
+};
+
+use super::list_contract::create_request;
+use super::fixtures::{postgres_pools, ALICE};
+
+async fn repositories() -> Option<(PostgresTodoListRepository, PostgresTodoRepository)> {
+    let (_, app) = postgres_pools().await?;
+    Some((PostgresTodoListRepository::new(app.clone()), PostgresTodoRepository::new(app)))
+}
+
+crate::todo_list_repository_contract!(repositories());
+
+#[tokio::test]
+async fn tenants_cannot_reach_each_others_lists() {
+    let Some((_, app)) = postgres_pools().await else { return };
+    let lists = PostgresTodoListRepository::new(app);
+    let in_tenant = |tenant: &str| RequestContext { tenant_id: Some(tenant.to_string()), ..RequestContext::for_user(ALICE) };
+
+    let list = in_tenant("acme").scope(lists.create(create_request("Acme's"))).await.unwrap();
+    assert_eq!(list.tenant_id, "acme");
+
+    in_tenant("globex")
+        .scope(async {
+            assert!(lists.find_by_id(list.id).await.unwrap().is_none());
+            assert_eq!(lists.find_all_paginated(false, Default::default()).await.unwrap().pagination.total, 0);
+        })
+        .await;
+    assert!(in_tenant("acme").scope(lists.find_by_id(list.id)).await.unwrap().is_some());
+}
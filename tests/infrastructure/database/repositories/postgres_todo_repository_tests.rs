@@ -0,0 +1,101 @@
+use axum_api::{
+    application::todos::TodoDomainEvents,
+    domain::todos::{
+        traits::{TodoCreator, TodoDeleter, TodoFinder, TodoHistory, TodoPaginator, TodoRoles, TodoSearcher, TodoSharing, TodoUpdater},
+        PaginationQuery, SearchTodosQuery, TodoRole, TodoSearch, UpdateTodoRequest,
+    },
+    error::ApiError,
+    infrastructure::database::repositories::PostgresTodoRepository,
+    request_context::RequestContext,
+};
+use sqlx::Executor;
+use uuid::Uuid;
+
+use super::contract::create_request;
+use super::fixtures::{postgres_pools as pools, ALICE, BOB};
+
+async fn repository() -> Option<PostgresTodoRepository> {
+    let (_, app) = pools().await?;
+    Some(PostgresTodoRepository::new(app))
+}
+
+crate::todo_repository_contract!(repository());
+
+fn in_tenant(tenant: &str, user: Uuid) -> RequestContext {
+    RequestContext { tenant_id: Some(tenant.to_string()), ..RequestContext::for_user(user) }
+}
+
+#[tokio::test]
+async fn tenants_cannot_reach_each_others_todos() {
+    let Some((_, app)) = pools().await else { return };
+    let repo = PostgresTodoRepository::new(app);
+    let (acme, globex) = (in_tenant("acme", ALICE), in_tenant("globex", ALICE));
+
+    let todo = acme.clone().scope(repo.create(create_request("Quarterly report", None), TodoDomainEvents::created)).await.unwrap();
+    assert_eq!(todo.tenant_id, "acme");
+    acme.clone().scope(repo.grant(todo.id, BOB, TodoRole::Editor)).await.unwrap();
+
+    // The same user, signed in to another tenant
+    globex
+        .clone()
+        .scope(async {
+            assert!(repo.find_by_id(todo.id).await.unwrap().is_none());
+            assert_eq!(repo.find_all_paginated(PaginationQuery::default()).await.unwrap().pagination.total, 0);
+            let search = TodoSearch::parse(&SearchTodosQuery { q: "report".to_string(), page: 1, limit: 10 });
+            assert!(repo.search(search).await.unwrap().data.is_empty());
+            assert!(repo.find_history(todo.id, PaginationQuery::default()).await.unwrap().data.is_empty());
+            assert!(repo.events_since(0, 100).await.unwrap().is_empty());
+            assert_eq!(repo.role_of(todo.id, ALICE).await.unwrap(), None);
+            assert!(repo.find_shares(todo.id).await.unwrap().is_empty());
+
+            let update = UpdateTodoRequest { title: Some("Stolen".to_string()), done: None, ..Default::default() };
+            assert!(matches!(repo.update(todo.id, update, None, TodoDomainEvents::updated).await, Err(ApiError::NotFound)));
+            assert!(matches!(repo.delete(todo.id, None, TodoDomainEvents::deleted).await, Err(ApiError::NotFound)));
+        })
+        .await;
+    assert_eq!(in_tenant("globex", BOB).scope(repo.role_of(todo.id, BOB)).await.unwrap(), None);
+
+    // Its own tenant, and background tasks across all tenants, still see it
+    assert_eq!(acme.scope(repo.find_by_id(todo.id)).await.unwrap().unwrap().title, "Quarterly report");
+    let history = RequestContext::all_tenants().scope(repo.find_history(todo.id, PaginationQuery::default())).await;
+    assert_eq!(history.unwrap().data.len(), 1);
+
+    // A context naming neither is a bug, not a licence to see everything
+    assert!(matches!(repo.find_by_id(todo.id).await, Err(ApiError::Anyhow(_))));
+}
+
+#[tokio::test]
+async fn row_level_security_holds_without_a_where_clause() {
+    let Some((owner, app)) = pools().await else { return };
+    let repo = PostgresTodoRepository::new(app.clone());
+    in_tenant("acme", ALICE).scope(repo.create(create_request("Quarterly report", None), TodoDomainEvents::created)).await.unwrap();
+    in_tenant("globex", ALICE).scope(repo.create(create_request("Board minutes", None), TodoDomainEvents::created)).await.unwrap();
+
+    let mut conn = app.acquire().await.unwrap();
+    let count = |sql: &'static str| sqlx::query_scalar::<_, i64>(sql);
+
+    // A fresh session names no tenant and sees nothing
+    conn.execute("RESET app.tenant_id; RESET app.all_tenants").await.unwrap();
+    assert_eq!(count("SELECT COUNT(*) FROM todos").fetch_one(&mut *conn).await.unwrap(), 0);
+    assert_eq!(count("SELECT COUNT(*) FROM outbox").fetch_one(&mut *conn).await.unwrap(), 0);
+
+    conn.execute("SELECT set_config('app.tenant_id', 'globex', false)").await.unwrap();
+    assert_eq!(count("SELECT COUNT(*) FROM todos").fetch_one(&mut *conn).await.unwrap(), 1);
+    assert_eq!(count("SELECT COUNT(*) FROM todo_events").fetch_one(&mut *conn).await.unwrap(), 1);
+    assert_eq!(count("SELECT COUNT(*) FROM outbox").fetch_one(&mut *conn).await.unwrap(), 1);
+    let renamed = conn.execute("UPDATE todos SET title = 'Renamed'").await.unwrap();
+    assert_eq!(renamed.rows_affected(), 1);
+
+    let smuggled = sqlx::query("INSERT INTO todos (id, title, tenant_id) VALUES ($1, 'Smuggled', 'acme')")
+        .bind(Uuid::new_v4())
+        .execute(&mut *conn)
+        .await;
+    assert!(smuggled.unwrap_err().to_string().contains("row-level security"));
+
+    // The superuser owning the schema is exempt and sees both tenants intact
+    let titles: Vec<String> = sqlx::query_scalar("SELECT title FROM todos ORDER BY tenant_id")
+        .fetch_all(&owner)
+        .await
+        .unwrap();
+    assert_eq!(titles, vec!["Quarterly report", "Renamed"]);
+}
@@ -0,0 +1,50 @@
+use axum_api::{
+    domain::users::{traits::{RefreshTokens, UserStore}, NewRefreshToken, RefreshRotation},
+    infrastructure::database::repositories::PostgresUserRepository,
+    request_context::RequestContext,
+};
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use super::fixtures::postgres_pools;
+
+/// Runs against `TEST_DATABASE_URL` when set, skipping otherwise, as the
+/// application role so row-level security applies.
+async fn repository() -> Option<PostgresUserRepository> {
+    let (_, app) = postgres_pools().await?;
+    Some(PostgresUserRepository::new(app))
+}
+
+crate::user_repository_contract!(repository());
+
+#[tokio::test]
+async fn accounts_and_sessions_stay_in_their_tenant() {
+    let Some(users) = repository().await else { return };
+    let (acme, globex) = (RequestContext::for_tenant("acme"), RequestContext::for_tenant("globex"));
+
+    let ada = acme.clone().scope(users.create_user("ada@example.com", "hash")).await.unwrap();
+    let token = NewRefreshToken {
+        user_id: ada.id,
+        family_id: Uuid::new_v4(),
+        token_hash: "a".repeat(64),
+        expires_at: Utc::now() + Duration::days(1),
+    };
+    acme.clone().scope(users.store_refresh_token(token)).await.unwrap();
+
+    let now = Utc::now();
+    globex
+        .clone()
+        .scope(async {
+            assert!(users.find_user(ada.id).await.unwrap().is_none());
+            assert!(users.find_credentials("ada@example.com").await.unwrap().is_none());
+            let rotation = users.rotate_refresh_token(&"a".repeat(64), &"b".repeat(64), now + Duration::days(1), now).await;
+            assert_eq!(rotation.unwrap(), RefreshRotation::Invalid);
+        })
+        .await;
+
+    // The same email registers separately in another tenant.
+    let other = globex.scope(users.create_user("ada@example.com", "hash")).await.unwrap();
+    assert_ne!(other.id, ada.id);
+    let rotation = acme.scope(users.rotate_refresh_token(&"a".repeat(64), &"b".repeat(64), now + Duration::days(1), now)).await;
+    assert_eq!(rotation.unwrap(), RefreshRotation::Rotated { user_id: ada.id });
+}
@@ -0,0 +1,57 @@
+use axum_api::{
+    application::todos::TodoDomainEvents,
+    domain::todos::{NewTodoEvent, OutboxMessage, Todo},
+    domain::webhooks::{traits::{WebhookDeliveries, WebhookStore}, UpdateWebhookRequest},
+    error::ApiError,
+    infrastructure::database::repositories::PostgresWebhookRepository,
+    request_context::RequestContext,
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::fixtures::{postgres_pools, ALICE};
+use super::webhook_contract::create_request;
+
+async fn repository() -> Option<PostgresWebhookRepository> {
+    let (_, app) = postgres_pools().await?;
+    Some(PostgresWebhookRepository::new(app))
+}
+
+crate::webhook_repository_contract!(repository());
+
+/// A `TodoCreated` message for one of `ALICE`'s todos in `tenant`.
+fn created_in(tenant: &str) -> OutboxMessage {
+    let now = Utc::now();
+    let todo = Todo { id: Uuid::new_v4(), title: "Buy milk".to_string(), done: false, description: None, completed_at: None, due_at: None, priority: Default::default(), created_at: now, updated_at: now, version: 1, list_id: None, tags: Vec::new(), owner_id: Some(ALICE), tenant_id: tenant.to_string() };
+    let change = NewTodoEvent::created(&todo);
+    OutboxMessage::queue(&change, TodoDomainEvents::created(&change)).remove(0)
+}
+
+#[tokio::test]
+async fn tenants_cannot_reach_each_others_webhooks() {
+    let Some((_, app)) = postgres_pools().await else { return };
+    let webhooks = PostgresWebhookRepository::new(app);
+    let in_tenant = |tenant: &str| RequestContext { tenant_id: Some(tenant.to_string()), ..RequestContext::for_user(ALICE) };
+
+    let webhook = in_tenant("acme").scope(webhooks.create(create_request("https://example.com/hooks", &[]))).await.unwrap();
+    assert_eq!(webhook.tenant_id, "acme");
+
+    in_tenant("globex")
+        .scope(async {
+            assert!(webhooks.find_by_id(webhook.id).await.unwrap().is_none());
+            assert_eq!(webhooks.find_all_paginated(Default::default()).await.unwrap().pagination.total, 0);
+            let update = UpdateWebhookRequest { active: Some(false), ..Default::default() };
+            assert!(matches!(webhooks.update(webhook.id, update).await, Err(ApiError::NotFound)));
+            assert!(matches!(webhooks.delete(webhook.id).await, Err(ApiError::NotFound)));
+        })
+        .await;
+
+    // The relay serves every tenant, but events only reach their own.
+    let relay = RequestContext::all_tenants();
+    assert_eq!(relay.clone().scope(webhooks.enqueue(&created_in("globex"))).await.unwrap(), 0);
+    assert_eq!(relay.clone().scope(webhooks.enqueue(&created_in("acme"))).await.unwrap(), 1);
+    assert_eq!(relay.scope(webhooks.due_deliveries(10, Utc::now())).await.unwrap().len(), 1);
+
+    // Without a tenant, or the explicit all-tenants context, nothing runs.
+    assert!(matches!(webhooks.find_by_id(webhook.id).await, Err(ApiError::Anyhow(_))));
+}
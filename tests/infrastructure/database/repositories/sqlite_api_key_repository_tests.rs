@@ -0,0 +1,18 @@
+use axum_api::infrastructure::database::{repositories::SqliteApiKeyRepository, SQLITE_MIGRATOR};
+use sqlx::sqlite::SqlitePoolOptions;
+
+use super::fixtures::seed_sqlite_users;
+
+async fn repository() -> Option<SqliteApiKeyRepository> {
+    // A single connection, since every `sqlite::memory:` connection is its own database.
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    SQLITE_MIGRATOR.run(&pool).await.unwrap();
+    seed_sqlite_users(&pool).await;
+    Some(SqliteApiKeyRepository::new(pool))
+}
+
+crate::api_key_repository_contract!(repository());
@@ -0,0 +1,21 @@
+use axum_api::infrastructure::database::{
+    repositories::{SqliteTodoListRepository, SqliteTodoRepository},
+    SQLITE_MIGRATOR,
+};
+use sqlx::sqlite::SqlitePoolOptions;
+
+use super::fixtures::seed_sqlite_users;
+
+async fn repositories() -> Option<(SqliteTodoListRepository, SqliteTodoRepository)> {
+    // A single connection, since every `sqlite::memory:` connection is its own database.
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    SQLITE_MIGRATOR.run(&pool).await.unwrap();
+    seed_sqlite_users(&pool).await;
+    Some((SqliteTodoListRepository::new(pool.clone()), SqliteTodoRepository::new(pool)))
+}
+
+crate::todo_list_repository_contract!(repositories());
@@ -0,0 +1,15 @@
+use axum_api::infrastructure::database::{repositories::SqliteUserRepository, SQLITE_MIGRATOR};
+use sqlx::sqlite::SqlitePoolOptions;
+
+async fn repository() -> Option<SqliteUserRepository> {
+    // A single connection, since every `sqlite::memory:` connection is its own database.
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    SQLITE_MIGRATOR.run(&pool).await.unwrap();
+    Some(SqliteUserRepository::new(pool))
+}
+
+crate::user_repository_contract!(repository());
@@ -0,0 +1,216 @@
+//! Behaviour every `TagRepository` backend must share, together with the
+//! `TodoRepository` the tags are attached through. Backends opt in with
+//! `tag_repository_contract!(factory)`, where `factory` yields
+//! `Option<(tags, todos)>` over the same database.
+
+use axum_api::{
+    application::todos::TodoDomainEvents,
+    domain::tags::{traits::TagRepository, CreateTagRequest, RenameTagRequest, Tag, TagRef},
+    domain::todos::{
+        traits::TodoRepository, CreateTodoRequest, KeysetQuery, PaginationQuery, TagMatch, Todo, TodoEventKind,
+        TodoFilter,
+    },
+    error::ApiError,
+    request_context::RequestContext,
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::fixtures::{ALICE, BOB};
+
+pub fn create_request(name: &str) -> CreateTagRequest {
+    CreateTagRequest { name: name.to_string() }
+}
+
+fn rename_request(name: &str) -> RenameTagRequest {
+    RenameTagRequest { name: name.to_string() }
+}
+
+fn todo_request(title: &str) -> CreateTodoRequest {
+    CreateTodoRequest { title: title.to_string(), ..Default::default() }
+}
+
+fn ids(todos: &[Todo]) -> Vec<Uuid> {
+    todos.iter().map(|t| t.id).collect()
+}
+
+fn names(tags: &[TagRef]) -> Vec<&str> {
+    tags.iter().map(|tag| tag.name.as_str()).collect()
+}
+
+fn tagged(tags: &[&str], tag_match: TagMatch) -> PaginationQuery {
+    PaginationQuery { tag: tags.iter().map(|tag| tag.to_string()).collect(), tag_match, ..Default::default() }
+}
+
+pub async fn crud_round_trip<G: TagRepository, T: TodoRepository>(tags: &G, _todos: &T) {
+    let work = tags.create(create_request("work")).await.unwrap();
+    let home = tags.create(create_request("home")).await.unwrap();
+    assert_eq!(work.name, "work");
+
+    let found = tags.find_by_id(work.id).await.unwrap().unwrap();
+    assert_eq!(found.name, "work");
+
+    let page = tags.find_all_paginated(PaginationQuery::default()).await.unwrap();
+    assert_eq!(page.data.iter().map(|tag| tag.id).collect::<Vec<_>>(), vec![home.id, work.id]);
+    assert_eq!(page.pagination.total, 2);
+
+    assert!(matches!(tags.create(create_request("work")).await, Err(ApiError::Conflict(_))));
+    assert!(matches!(tags.rename(home.id, rename_request("work"), TodoDomainEvents::updated).await, Err(ApiError::Conflict(_))));
+    let renamed = tags.rename(work.id, rename_request("office"), TodoDomainEvents::updated).await.unwrap();
+    assert_eq!((renamed.id, renamed.name.as_str()), (work.id, "office"));
+    // The old name is free again.
+    tags.create(create_request("work")).await.unwrap();
+
+    tags.delete(home.id, TodoDomainEvents::updated).await.unwrap();
+    assert!(tags.find_by_id(home.id).await.unwrap().is_none());
+    assert!(matches!(tags.delete(home.id, TodoDomainEvents::updated).await, Err(ApiError::NotFound)));
+    assert!(matches!(tags.rename(home.id, rename_request("house"), TodoDomainEvents::updated).await, Err(ApiError::NotFound)));
+}
+
+pub async fn tags_belong_to_their_owner<G: TagRepository, T: TodoRepository>(tags: &G, _todos: &T) {
+    let (alice, bob) = (RequestContext::for_user(ALICE), RequestContext::for_user(BOB));
+    let tag = alice.clone().scope(tags.create(create_request("work"))).await.unwrap();
+    assert_eq!(tag.owner_id, Some(ALICE));
+
+    bob.scope(async {
+        assert!(tags.find_by_id(tag.id).await.unwrap().is_none());
+        assert_eq!(tags.find_all_paginated(PaginationQuery::default()).await.unwrap().pagination.total, 0);
+        assert!(matches!(tags.rename(tag.id, rename_request("Bob's"), TodoDomainEvents::updated).await, Err(ApiError::NotFound)));
+        assert!(matches!(tags.delete(tag.id, TodoDomainEvents::updated).await, Err(ApiError::NotFound)));
+        // Names are only unique per owner.
+        let own = tags.create(create_request("work")).await.unwrap();
+        assert_eq!(own.owner_id, Some(BOB));
+    })
+    .await;
+
+    let page = alice.scope(tags.find_all_paginated(PaginationQuery::default())).await.unwrap();
+    assert_eq!(page.data.iter().map(|tag| tag.id).collect::<Vec<_>>(), vec![tag.id]);
+}
+
+pub async fn attaching_bumps_the_version<G: TagRepository, T: TodoRepository>(tags: &G, todos: &T) {
+    let work = tags.create(create_request("work")).await.unwrap();
+    let home = tags.create(create_request("home")).await.unwrap();
+    let todo = todos.create(todo_request("Buy milk"), TodoDomainEvents::created).await.unwrap();
+    assert!(todo.tags.is_empty());
+
+    let tagged = todos.attach_tag(todo.id, work.id, Some(1), TodoDomainEvents::updated).await.unwrap();
+    assert_eq!((tagged.version, tagged.tags.clone()), (2, vec![TagRef::from(&work)]));
+    let tagged = todos.attach_tag(todo.id, home.id, None, TodoDomainEvents::updated).await.unwrap();
+    assert_eq!(names(&tagged.tags), vec!["home", "work"]);
+    assert_eq!(tagged.version, 3);
+
+    // Attaching a tag the todo already has changes nothing.
+    let again = todos.attach_tag(todo.id, work.id, Some(3), TodoDomainEvents::updated).await.unwrap();
+    assert_eq!(again.version, 3);
+    assert!(matches!(todos.attach_tag(todo.id, work.id, Some(2), TodoDomainEvents::updated).await, Err(ApiError::PreconditionFailed)));
+    assert!(matches!(todos.detach_tag(todo.id, work.id, Some(2), TodoDomainEvents::updated).await, Err(ApiError::PreconditionFailed)));
+
+    let untagged = todos.detach_tag(todo.id, work.id, Some(3), TodoDomainEvents::updated).await.unwrap();
+    assert_eq!((untagged.version, names(&untagged.tags)), (4, vec!["home"]));
+    assert!(matches!(todos.detach_tag(todo.id, work.id, None, TodoDomainEvents::updated).await, Err(ApiError::NotFound)));
+    assert!(matches!(todos.attach_tag(Uuid::new_v4(), work.id, None, TodoDomainEvents::updated).await, Err(ApiError::NotFound)));
+
+    let found = todos.find_by_id(todo.id).await.unwrap().unwrap();
+    assert_eq!((found.version, found.tags), (4, vec![TagRef::from(&home)]));
+
+    let history = todos.find_history(todo.id, PaginationQuery::default()).await.unwrap();
+    let versions: Vec<i64> = history.data.iter().map(|event| event.version).collect();
+    assert_eq!(versions, vec![1, 2, 3, 4]);
+    let detached = &history.data[3];
+    assert_eq!(detached.kind, TodoEventKind::Updated);
+    assert_eq!(detached.changes.iter().map(|change| change.field.as_str()).collect::<Vec<_>>(), vec!["tags"]);
+}
+
+pub async fn listings_embed_and_filter_by_tags<G: TagRepository, T: TodoRepository>(tags: &G, todos: &T) {
+    let work = tags.create(create_request("work")).await.unwrap();
+    let urgent = tags.create(create_request("urgent")).await.unwrap();
+    let both = todos.create(todo_request("Ship the release"), TodoDomainEvents::created).await.unwrap();
+    let only_work = todos.create(todo_request("Review the PR"), TodoDomainEvents::created).await.unwrap();
+    let untagged = todos.create(todo_request("Buy milk"), TodoDomainEvents::created).await.unwrap();
+    todos.attach_tag(both.id, work.id, None, TodoDomainEvents::updated).await.unwrap();
+    todos.attach_tag(both.id, urgent.id, None, TodoDomainEvents::updated).await.unwrap();
+    todos.attach_tag(only_work.id, work.id, None, TodoDomainEvents::updated).await.unwrap();
+
+    let page = todos.find_all_paginated(PaginationQuery::default()).await.unwrap();
+    assert_eq!(ids(&page.data), vec![untagged.id, only_work.id, both.id]);
+    assert_eq!(names(&page.data[2].tags), vec!["urgent", "work"]);
+    assert!(page.data[0].tags.is_empty());
+
+    let any = todos.find_all_paginated(tagged(&["work", "home"], TagMatch::Any)).await.unwrap();
+    assert_eq!((ids(&any.data), any.pagination.total), (vec![only_work.id, both.id], 2));
+    let all = todos.find_all_paginated(tagged(&["work", "urgent"], TagMatch::All)).await.unwrap();
+    assert_eq!((ids(&all.data), all.pagination.total), (vec![both.id], 1));
+    // Repeating a tag does not raise the bar for `all`.
+    let repeated = todos.find_all_paginated(tagged(&["work", "work"], TagMatch::All)).await.unwrap();
+    assert_eq!(ids(&repeated.data), vec![only_work.id, both.id]);
+    let none = todos.find_all_paginated(tagged(&["work", "home"], TagMatch::All)).await.unwrap();
+    assert_eq!(none.pagination.total, 0);
+
+    let filter = TodoFilter { tags: vec!["urgent".to_string()], ..Default::default() };
+    let keyset = KeysetQuery { start: None, limit: 10, include_total: true, filter };
+    let page = todos.find_keyset(keyset).await.unwrap();
+    assert_eq!((ids(&page.data), page.total), (vec![both.id], Some(1)));
+    assert_eq!(names(&page.data[0].tags), vec!["urgent", "work"]);
+}
+
+pub async fn renaming_and_deleting_reach_tagged_todos<G: TagRepository, T: TodoRepository>(tags: &G, todos: &T) {
+    let work = tags.create(create_request("work")).await.unwrap();
+    let live = todos.create(todo_request("Ship the release"), TodoDomainEvents::created).await.unwrap();
+    let trashed = todos.create(todo_request("Review the PR"), TodoDomainEvents::created).await.unwrap();
+    todos.attach_tag(live.id, work.id, None, TodoDomainEvents::updated).await.unwrap();
+    todos.attach_tag(trashed.id, work.id, None, TodoDomainEvents::updated).await.unwrap();
+    todos.delete(trashed.id, None, TodoDomainEvents::deleted).await.unwrap();
+
+    let renamed: Tag = tags.rename(work.id, rename_request("office"), TodoDomainEvents::updated).await.unwrap();
+    let found = todos.find_by_id(live.id).await.unwrap().unwrap();
+    // Renaming changes the todo, so it gets a new version, and ETag.
+    assert_eq!((found.version, found.tags), (3, vec![TagRef::from(&renamed)]));
+    assert_eq!(todos.find_all_paginated(tagged(&["office"], TagMatch::Any)).await.unwrap().pagination.total, 1);
+    assert_eq!(todos.find_all_paginated(tagged(&["work"], TagMatch::Any)).await.unwrap().pagination.total, 0);
+
+    tags.delete(work.id, TodoDomainEvents::updated).await.unwrap();
+    let found = todos.find_by_id(live.id).await.unwrap().unwrap();
+    assert_eq!((found.version, found.tags), (4, Vec::new()));
+    let restored = todos.restore(trashed.id, TodoDomainEvents::restored).await.unwrap();
+    assert!(restored.tags.is_empty());
+
+    // Both changes are recorded and announced like any other update.
+    let history = todos.find_history(live.id, PaginationQuery::default()).await.unwrap();
+    let retagged: Vec<(TodoEventKind, i64)> = history.data[2..].iter().map(|event| (event.kind, event.version)).collect();
+    assert_eq!(retagged, vec![(TodoEventKind::Updated, 3), (TodoEventKind::Updated, 4)]);
+    assert!(history.data[2..].iter().all(|event| event.changes.iter().map(|change| change.field.as_str()).eq(["tags"])));
+    let pending = todos.pending_messages(100, Utc::now()).await.unwrap();
+    let announced: Vec<&str> = pending
+        .iter()
+        .filter(|message| message.todo_id == live.id)
+        .map(|message| message.event.event_type())
+        .collect();
+    assert_eq!(announced, vec!["TodoCreated", "TodoUpdated", "TodoUpdated", "TodoUpdated"]);
+    // The trashed todo was left alone: trashing made it 3, restoring 4.
+    assert_eq!(restored.version, 4);
+}
+
+#[macro_export]
+macro_rules! tag_repository_contract {
+    ($factory:expr) => {
+        $crate::tag_repository_contract!(@cases $factory;
+            crud_round_trip,
+            tags_belong_to_their_owner,
+            attaching_bumps_the_version,
+            listings_embed_and_filter_by_tags,
+            renaming_and_deleting_reach_tagged_todos,
+        );
+    };
+    (@cases $factory:expr; $($case:ident),* $(,)?) => {
+        $(
+            #[tokio::test]
+            async fn $case() {
+                if let Some((tags, todos)) = $factory.await {
+                    ::axum_api::request_context::RequestContext::for_tenant(::axum_api::request_context::DEFAULT_TENANT)
+                        .scope($crate::database::repositories::tag_contract::$case(&tags, &todos))
+                        .await;
+                }
+            }
+        )*
+    };
+}
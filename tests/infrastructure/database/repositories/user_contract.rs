@@ -0,0 +1,97 @@
+//! Behaviour every `UserRepository` backend must share, opted into with
+//! `user_repository_contract!(factory)` like the todo contract.
+
+use axum_api::{
+    domain::users::{traits::UserRepository, NewRefreshToken, RefreshRotation},
+    error::ApiError,
+};
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+fn token(user_id: Uuid, family_id: Uuid, hash: &str) -> NewRefreshToken {
+    NewRefreshToken { user_id, family_id, token_hash: hash.repeat(64), expires_at: Utc::now() + Duration::days(1) }
+}
+
+pub async fn create_then_find<R: UserRepository>(repo: &R) {
+    let user = repo.create_user("ada@example.com", "$argon2id$hash").await.unwrap();
+    assert_eq!(user.email, "ada@example.com");
+
+    assert_eq!(repo.find_user(user.id).await.unwrap().unwrap().email, "ada@example.com");
+    assert!(repo.find_user(Uuid::new_v4()).await.unwrap().is_none());
+
+    let credentials = repo.find_credentials("ada@example.com").await.unwrap().unwrap();
+    assert_eq!(credentials.user.id, user.id);
+    assert_eq!(credentials.password_hash, "$argon2id$hash");
+    assert!(repo.find_credentials("grace@example.com").await.unwrap().is_none());
+
+    let duplicate = repo.create_user("ada@example.com", "$argon2id$other").await;
+    assert!(matches!(duplicate, Err(ApiError::Conflict(_))), "got {duplicate:?}");
+}
+
+pub async fn refresh_tokens_rotate_once<R: UserRepository>(repo: &R) {
+    let user = repo.create_user("ada@example.com", "hash").await.unwrap();
+    let family = Uuid::new_v4();
+    repo.store_refresh_token(token(user.id, family, "a")).await.unwrap();
+
+    let now = Utc::now();
+    let expires_at = now + Duration::days(1);
+    let (a, b, c) = ("a".repeat(64), "b".repeat(64), "c".repeat(64));
+    assert_eq!(repo.rotate_refresh_token(&a, &b, expires_at, now).await.unwrap(), RefreshRotation::Rotated { user_id: user.id });
+    assert_eq!(repo.rotate_refresh_token(&b, &c, expires_at, now).await.unwrap(), RefreshRotation::Rotated { user_id: user.id });
+
+    // Replaying `a` revokes the family, so its newest token `c` dies too.
+    assert_eq!(repo.rotate_refresh_token(&a, &"d".repeat(64), expires_at, now).await.unwrap(), RefreshRotation::Reused);
+    assert_eq!(repo.rotate_refresh_token(&c, &"e".repeat(64), expires_at, now).await.unwrap(), RefreshRotation::Invalid);
+    assert_eq!(repo.rotate_refresh_token(&"f".repeat(64), &"g".repeat(64), expires_at, now).await.unwrap(), RefreshRotation::Invalid);
+}
+
+pub async fn expired_tokens_do_not_rotate<R: UserRepository>(repo: &R) {
+    let user = repo.create_user("ada@example.com", "hash").await.unwrap();
+    repo.store_refresh_token(token(user.id, Uuid::new_v4(), "a")).await.unwrap();
+
+    let later = Utc::now() + Duration::days(2);
+    let rotation = repo.rotate_refresh_token(&"a".repeat(64), &"b".repeat(64), later + Duration::days(1), later).await.unwrap();
+    assert_eq!(rotation, RefreshRotation::Invalid);
+}
+
+pub async fn revoking_a_family_spares_the_others<R: UserRepository>(repo: &R) {
+    let user = repo.create_user("ada@example.com", "hash").await.unwrap();
+    repo.store_refresh_token(token(user.id, Uuid::new_v4(), "a")).await.unwrap();
+    repo.store_refresh_token(token(user.id, Uuid::new_v4(), "b")).await.unwrap();
+
+    let now = Utc::now();
+    repo.revoke_refresh_family(&"a".repeat(64), now).await.unwrap();
+    // Unknown tokens are ignored.
+    repo.revoke_refresh_family(&"z".repeat(64), now).await.unwrap();
+
+    let expires_at = now + Duration::days(1);
+    assert_eq!(repo.rotate_refresh_token(&"a".repeat(64), &"c".repeat(64), expires_at, now).await.unwrap(), RefreshRotation::Invalid);
+    assert_eq!(
+        repo.rotate_refresh_token(&"b".repeat(64), &"d".repeat(64), expires_at, now).await.unwrap(),
+        RefreshRotation::Rotated { user_id: user.id }
+    );
+}
+
+#[macro_export]
+macro_rules! user_repository_contract {
+    ($factory:expr) => {
+        $crate::user_repository_contract!(@cases $factory;
+            create_then_find,
+            refresh_tokens_rotate_once,
+            expired_tokens_do_not_rotate,
+            revoking_a_family_spares_the_others,
+        );
+    };
+    (@cases $factory:expr; $($case:ident),* $(,)?) => {
+        $(
+            #[tokio::test]
+            async fn $case() {
+                if let Some(repo) = $factory.await {
+                    ::axum_api::request_context::RequestContext::for_tenant(::axum_api::request_context::DEFAULT_TENANT)
+                        .scope($crate::database::repositories::user_contract::$case(&repo))
+                        .await;
+                }
+            }
+        )*
+    };
+}
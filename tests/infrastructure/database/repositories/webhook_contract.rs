@@ -0,0 +1,204 @@
+//! Behaviour every `WebhookRepository` backend must share, opted into with
+//! `webhook_repository_contract!(factory)` like the todo contract.
+
+use axum_api::{
+    application::todos::TodoDomainEvents,
+    domain::todos::{NewTodoEvent, OutboxMessage, PaginationQuery, Todo},
+    domain::webhooks::{
+        traits::WebhookRepository, CreateWebhookRequest, DeliveryOutcome, DeliveryStatus, UpdateWebhookRequest,
+    },
+    error::ApiError,
+    request_context::RequestContext,
+};
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+pub fn create_request(url: &str, events: &[&str]) -> CreateWebhookRequest {
+    CreateWebhookRequest {
+        url: url.to_string(),
+        events: events.iter().map(|event| event.to_string()).collect(),
+        secret: "0123456789abcdef".to_string(),
+    }
+}
+
+/// `TodoCreated`, `TodoUpdated` and `TodoCompleted` for a todo that was
+/// created and then completed.
+fn messages() -> Vec<OutboxMessage> {
+    let now = Utc::now();
+    let todo = Todo { id: Uuid::new_v4(), title: "Buy milk".to_string(), done: false, description: None, completed_at: None, due_at: None, priority: Default::default(), created_at: now, updated_at: now, version: 1, list_id: None, tags: Vec::new(), owner_id: None, tenant_id: "default".to_string() };
+    let done = Todo { done: true, version: 2, ..todo.clone() };
+
+    let created = NewTodoEvent::created(&todo);
+    let completed = NewTodoEvent::updated(&todo, &done);
+    let mut messages = OutboxMessage::queue(&created, TodoDomainEvents::created(&created));
+    messages.extend(OutboxMessage::queue(&completed, TodoDomainEvents::updated(&completed)));
+    messages
+}
+
+fn failure(retry_at: Option<chrono::DateTime<Utc>>) -> DeliveryOutcome {
+    DeliveryOutcome::Failed { response_status: Some(500), error: "receiver answered 500".to_string(), retry_at }
+}
+
+pub async fn crud_round_trip<R: WebhookRepository>(repo: &R) {
+    let first = repo.create(create_request("https://example.com/a", &[])).await.unwrap();
+    let second = repo.create(create_request("https://example.com/b", &["TodoCompleted"])).await.unwrap();
+    assert!(first.active);
+    assert_eq!(first.consecutive_failures, 0);
+
+    let found = repo.find_by_id(second.id).await.unwrap().unwrap();
+    assert_eq!(found.events, vec!["TodoCompleted"]);
+    assert_eq!(found.secret, "0123456789abcdef");
+
+    let page = repo.find_all_paginated(PaginationQuery::default()).await.unwrap();
+    assert_eq!(page.data.iter().map(|webhook| webhook.id).collect::<Vec<_>>(), vec![first.id, second.id]);
+    assert_eq!(page.pagination.total, 2);
+
+    let updated = repo
+        .update(first.id, UpdateWebhookRequest { events: Some(vec!["TodoDeleted".to_string()]), ..Default::default() })
+        .await
+        .unwrap();
+    assert_eq!(updated.events, vec!["TodoDeleted"]);
+    assert_eq!(updated.url, "https://example.com/a");
+
+    repo.delete(first.id).await.unwrap();
+    assert!(repo.find_by_id(first.id).await.unwrap().is_none());
+    assert!(matches!(repo.delete(first.id).await, Err(ApiError::NotFound)));
+    assert!(matches!(repo.update(first.id, UpdateWebhookRequest::default()).await, Err(ApiError::NotFound)));
+}
+
+pub async fn enqueue_follows_subscriptions_once<R: WebhookRepository>(repo: &R) {
+    let all = repo.create(create_request("https://example.com/all", &[])).await.unwrap();
+    let completed = repo.create(create_request("https://example.com/done", &["TodoCompleted"])).await.unwrap();
+    let paused = repo.create(create_request("https://example.com/paused", &[])).await.unwrap();
+    repo.update(paused.id, UpdateWebhookRequest { active: Some(false), ..Default::default() }).await.unwrap();
+
+    let messages = messages();
+    assert_eq!(repo.enqueue(&messages[0]).await.unwrap(), 1);
+    assert_eq!(repo.enqueue(&messages[2]).await.unwrap(), 2);
+    // The outbox redelivered an event.
+    assert_eq!(repo.enqueue(&messages[0]).await.unwrap(), 0);
+
+    let log = repo.find_deliveries(all.id, PaginationQuery::default()).await.unwrap();
+    assert_eq!(log.data.iter().map(|delivery| delivery.event_type.as_str()).collect::<Vec<_>>(), vec!["TodoCompleted", "TodoCreated"]);
+    assert_eq!(log.data[1].event_id, messages[0].event_id);
+    assert_eq!(log.data[1].payload["type"], "TodoCreated");
+    assert_eq!(log.data[1].payload["event_id"], messages[0].event_id.to_string());
+    assert_eq!(log.data[1].status, DeliveryStatus::Pending);
+
+    let log = repo.find_deliveries(completed.id, PaginationQuery::default()).await.unwrap();
+    assert_eq!(log.pagination.total, 1);
+    assert_eq!(repo.find_deliveries(paused.id, PaginationQuery::default()).await.unwrap().pagination.total, 0);
+
+    let due = repo.due_deliveries(10, Utc::now()).await.unwrap();
+    assert_eq!(due.len(), 3);
+    assert_eq!(repo.due_deliveries(2, Utc::now()).await.unwrap().len(), 2);
+}
+
+pub async fn attempts_retry_settle_and_disable<R: WebhookRepository>(repo: &R) {
+    let webhook = repo.create(create_request("https://example.com/hook", &[])).await.unwrap();
+    let messages = messages();
+    for message in &messages {
+        repo.enqueue(message).await.unwrap();
+    }
+    let now = Utc::now();
+    let due = repo.due_deliveries(10, now).await.unwrap();
+    assert_eq!(due.len(), 3);
+
+    // A retryable failure stays pending until `retry_at`.
+    let retry_at = now + Duration::minutes(1);
+    repo.record_attempt(due[0].id, failure(Some(retry_at)), 2).await.unwrap();
+    let due_now = repo.due_deliveries(10, now).await.unwrap();
+    assert_eq!(due_now.iter().map(|delivery| delivery.id).collect::<Vec<_>>(), vec![due[1].id, due[2].id]);
+    let retried = repo.due_deliveries(10, retry_at).await.unwrap();
+    let retried = retried.iter().find(|delivery| delivery.id == due[0].id).unwrap();
+    assert_eq!(retried.attempts, 1);
+    assert_eq!(retried.response_status, Some(500));
+    assert_eq!(retried.last_error.as_deref(), Some("receiver answered 500"));
+
+    // A success settles the delivery and resets the failure count.
+    repo.record_attempt(due[1].id, DeliveryOutcome::Succeeded { response_status: 204 }, 2).await.unwrap();
+    assert_eq!(repo.find_by_id(webhook.id).await.unwrap().unwrap().consecutive_failures, 0);
+
+    // Giving up settles the delivery as failed.
+    repo.record_attempt(due[2].id, failure(None), 2).await.unwrap();
+    let log = repo.find_deliveries(webhook.id, PaginationQuery::default()).await.unwrap();
+    let status = |id: i64| log.data.iter().find(|delivery| delivery.id == id).unwrap().clone();
+    assert_eq!(status(due[1].id).status, DeliveryStatus::Succeeded);
+    assert!(status(due[1].id).delivered_at.is_some());
+    assert!(status(due[1].id).next_attempt_at.is_none());
+    assert_eq!(status(due[2].id).status, DeliveryStatus::Failed);
+    assert!(status(due[2].id).next_attempt_at.is_none());
+
+    // The second failure in a row disables the webhook and holds back its
+    // deliveries.
+    repo.record_attempt(due[0].id, failure(Some(retry_at)), 2).await.unwrap();
+    let disabled = repo.find_by_id(webhook.id).await.unwrap().unwrap();
+    assert!(!disabled.active);
+    assert_eq!(disabled.consecutive_failures, 2);
+    assert!(repo.due_deliveries(10, retry_at).await.unwrap().is_empty());
+
+    // Re-enabling resets the count and resumes delivery.
+    let enabled = repo.update(webhook.id, UpdateWebhookRequest { active: Some(true), ..Default::default() }).await.unwrap();
+    assert!(enabled.active);
+    assert_eq!(enabled.consecutive_failures, 0);
+    assert_eq!(repo.due_deliveries(10, retry_at).await.unwrap().len(), 1);
+}
+
+pub async fn delete_drops_deliveries<R: WebhookRepository>(repo: &R) {
+    let webhook = repo.create(create_request("https://example.com/hook", &[])).await.unwrap();
+    repo.enqueue(&messages()[0]).await.unwrap();
+    let due = repo.due_deliveries(10, Utc::now()).await.unwrap();
+
+    repo.delete(webhook.id).await.unwrap();
+    assert!(repo.due_deliveries(10, Utc::now()).await.unwrap().is_empty());
+    assert_eq!(repo.find_deliveries(webhook.id, PaginationQuery::default()).await.unwrap().pagination.total, 0);
+    // An attempt that was in flight during the delete is dropped.
+    repo.record_attempt(due[0].id, DeliveryOutcome::Succeeded { response_status: 200 }, 3).await.unwrap();
+}
+
+pub async fn webhooks_belong_to_their_owner<R: WebhookRepository>(repo: &R) {
+    let (ada, bob) = (RequestContext::for_user(Uuid::new_v4()), RequestContext::for_user(Uuid::new_v4()));
+    let webhook = ada.clone().scope(repo.create(create_request("https://example.com/ada", &[]))).await.unwrap();
+    assert_eq!(webhook.owner_id, ada.user_id);
+
+    bob.scope(async {
+        assert!(repo.find_by_id(webhook.id).await.unwrap().is_none());
+        assert_eq!(repo.find_all_paginated(PaginationQuery::default()).await.unwrap().pagination.total, 0);
+        assert!(matches!(repo.update(webhook.id, UpdateWebhookRequest::default()).await, Err(ApiError::NotFound)));
+        assert!(matches!(repo.delete(webhook.id).await, Err(ApiError::NotFound)));
+    })
+    .await;
+
+    // Only events about the owner's todos are delivered.
+    let mut foreign = messages().remove(0);
+    foreign.owner_id = Some(Uuid::new_v4());
+    assert_eq!(repo.enqueue(&foreign).await.unwrap(), 0);
+    let mut own = messages().remove(0);
+    own.owner_id = ada.user_id;
+    assert_eq!(repo.enqueue(&own).await.unwrap(), 1);
+}
+
+#[macro_export]
+macro_rules! webhook_repository_contract {
+    ($factory:expr) => {
+        $crate::webhook_repository_contract!(@cases $factory;
+            crud_round_trip,
+            enqueue_follows_subscriptions_once,
+            attempts_retry_settle_and_disable,
+            delete_drops_deliveries,
+            webhooks_belong_to_their_owner,
+        );
+    };
+    (@cases $factory:expr; $($case:ident),* $(,)?) => {
+        $(
+            #[tokio::test]
+            async fn $case() {
+                if let Some(repo) = $factory.await {
+                    ::axum_api::request_context::RequestContext::for_tenant(::axum_api::request_context::DEFAULT_TENANT)
+                        .scope($crate::database::repositories::webhook_contract::$case(&repo))
+                        .await;
+                }
+            }
+        )*
+    };
+}
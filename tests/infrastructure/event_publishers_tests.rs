@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use axum_api::{
+    application::todos::TodoDomainEvents,
+    config::{OutboxConfig, PublisherKind},
+    domain::todos::{NewTodoEvent, OutboxMessage, PaginationQuery, Todo, traits::EventPublisher},
+    domain::webhooks::{CreateWebhookRequest, traits::{WebhookDeliveries, WebhookRepository, WebhookStore}},
+    infrastructure::{database::repositories::InMemoryWebhookRepository, event_publishers::{self, FilePublisher}},
+    request_context::{RequestContext, DEFAULT_TENANT},
+};
+use chrono::Utc;
+use serde_json::Value;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_file_publisher_appends_json_lines() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("events.jsonl");
+    let todo = Todo { id: Uuid::new_v4(), title: "Buy milk".to_string(), done: false, description: None, completed_at: None, due_at: None, priority: Default::default(), created_at: Utc::now(), updated_at: Utc::now(), version: 1, list_id: None, tags: Vec::new(), owner_id: None, tenant_id: "default".to_string() };
+    let change = NewTodoEvent::created(&todo);
+    let message = OutboxMessage::queue(&change, TodoDomainEvents::created(&change)).remove(0);
+
+    let publisher = FilePublisher::open(&path).await.unwrap();
+    publisher.publish(&message).await.unwrap();
+    publisher.publish(&message).await.unwrap();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<Value> = contents.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["type"], "TodoCreated");
+    assert_eq!(lines[0]["event_id"], message.event_id.to_string());
+    assert_eq!(lines[0]["todo_id"], todo.id.to_string());
+    assert_eq!(lines[0]["data"]["todo"]["title"], "Buy milk");
+    assert!(lines[0].get("attempts").is_none());
+}
+
+#[tokio::test]
+async fn test_from_config_fans_out_to_file_and_webhooks() {
+    RequestContext::for_tenant(DEFAULT_TENANT).scope(async {
+        let dir = tempfile::tempdir().unwrap();
+        let config = OutboxConfig { publisher: PublisherKind::File, file_path: dir.path().join("events.jsonl"), ..OutboxConfig::default() };
+        let webhooks = Arc::new(InMemoryWebhookRepository::new());
+        let webhook = webhooks
+            .create(CreateWebhookRequest { url: "https://example.com".to_string(), events: Vec::new(), secret: "0123456789abcdef".to_string() })
+            .await
+            .unwrap();
+        let todo = Todo { id: Uuid::new_v4(), title: "Buy milk".to_string(), done: false, description: None, completed_at: None, due_at: None, priority: Default::default(), created_at: Utc::now(), updated_at: Utc::now(), version: 1, list_id: None, tags: Vec::new(), owner_id: None, tenant_id: "default".to_string() };
+        let change = NewTodoEvent::created(&todo);
+        let message = OutboxMessage::queue(&change, TodoDomainEvents::created(&change)).remove(0);
+
+        let publisher = event_publishers::from_config(&config, Some(webhooks.clone() as Arc<dyn WebhookRepository>)).await.unwrap().unwrap();
+        publisher.publish(&message).await.unwrap();
+
+        assert_eq!(std::fs::read_to_string(&config.file_path).unwrap().lines().count(), 1);
+        let log = webhooks.find_deliveries(webhook.id, PaginationQuery::default()).await.unwrap();
+        assert_eq!(log.data[0].event_id, message.event_id);
+
+        let none = OutboxConfig { publisher: PublisherKind::None, ..OutboxConfig::default() };
+        assert!(event_publishers::from_config(&none, None).await.unwrap().is_none());
+    })
+    .await;
+}
@@ -0,0 +1,45 @@
+mod change_feed_tests;
+mod event_publishers_tests;
+
+mod database {
+    pub mod repositories {
+        #[macro_use]
+        pub mod api_key_contract;
+        #[macro_use]
+        pub mod contract;
+        #[macro_use]
+        pub mod webhook_contract;
+        pub mod fixtures;
+        #[macro_use]
+        pub mod list_contract;
+        #[macro_use]
+        pub mod tag_contract;
+        #[macro_use]
+        pub mod user_contract;
+
+        mod in_memory_api_key_repository_tests;
+        mod in_memory_tag_repository_tests;
+        mod in_memory_todo_list_repository_tests;
+        mod in_memory_todo_repository_tests;
+        mod in_memory_user_repository_tests;
+        mod in_memory_webhook_repository_tests;
+        mod postgres_api_key_repository_tests;
+        mod postgres_tag_repository_tests;
+        mod postgres_todo_list_repository_tests;
+        mod postgres_todo_repository_tests;
+        mod postgres_user_repository_tests;
+        mod postgres_webhook_repository_tests;
+        #[cfg(feature = "sqlite")]
+        mod sqlite_api_key_repository_tests;
+        #[cfg(feature = "sqlite")]
+        mod sqlite_tag_repository_tests;
+        #[cfg(feature = "sqlite")]
+        mod sqlite_todo_list_repository_tests;
+        #[cfg(feature = "sqlite")]
+        mod sqlite_todo_repository_tests;
+        #[cfg(feature = "sqlite")]
+        mod sqlite_user_repository_tests;
+        #[cfg(feature = "sqlite")]
+        mod sqlite_webhook_repository_tests;
+    }
+}